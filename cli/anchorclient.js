@@ -8,11 +8,29 @@ const {
   Keypair,
   LAMPORTS_PER_SOL,
   SystemProgram,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_SLOT_HASHES_PUBKEY,
 } = require("@solana/web3.js");
 const anchor = require("@coral-xyz/anchor");
 const BN = require("bn.js");
 const nacl = require("tweetnacl");
 const bs58 = require("bs58");
+const { createHash } = require("crypto");
+
+// `Challenge` is zero-copy, so its status is stored as the `ChallengeStatus`
+// discriminant; these are the variant names in declaration order.
+const CHALLENGE_STATUSES = ["active", "finalized", "expired", "insufficientParticipants", "scheduled", "inconclusive"];
+
+/** Decodes a challenge's fixed id buffer into its string id. */
+function decodeChallengeId(account) {
+  return Buffer.from(account.challengeId.slice(0, account.challengeIdLen)).toString();
+}
+
+/** Returns a challenge's status in the `{ active: {} }` form Anchor uses for enums. */
+function decodeChallengeStatus(account) {
+  return { [CHALLENGE_STATUSES[account.status]]: {} };
+}
 
 /**
  * A client for interacting with the Poloc Anchor smart contract.
@@ -140,6 +158,38 @@ class AnchorClient {
     return bs58.encode(signature);
   }
 
+  /**
+   * Bytes a prover's beacon signs when answering a witness probe; mirrors
+   * `beacon_message` in the program.
+   * @param {object} challenge - The fetched challenge account.
+   * @param {PublicKey} challengePda
+   * @param {PublicKey} challenger - The probing witness.
+   * @returns {Buffer}
+   */
+  _beaconMessage(challenge, challengePda, challenger) {
+    return Buffer.concat([
+      Buffer.from("poloc:beacon:v1"),
+      this.program.programId.toBuffer(),
+      challengePda.toBuffer(),
+      Buffer.from(challenge.measurementNonce),
+      challenger.toBuffer(),
+    ]);
+  }
+
+  /**
+   * Signs a beacon echo for a witness as the challenge's prover.
+   * @param {object} params
+   * @param {string} params.challengeId
+   * @param {string} params.challenger - The probing witness's address.
+   * @returns {Promise<string>} The signature as a base58 string.
+   */
+  async signBeaconEcho({ challengeId, challenger }) {
+    const challengePda = this._findChallengePda(challengeId);
+    const challenge = await this.program.account.challenge.fetch(challengePda);
+    const message = this._beaconMessage(challenge, challengePda, new PublicKey(challenger));
+    return bs58.encode(nacl.sign.detached(message, this.wallet.payer.secretKey));
+  }
+
   /**
    * Gets the current balance of the connected wallet in SOL.
    * @returns {Promise<number>} The balance in SOL.
@@ -158,9 +208,15 @@ class AnchorClient {
    * @param {{lat: number, lon: number}} params.location - The claimed location.
    * @param {number} params.duration - The duration of the challenge in seconds.
    * @param {number} params.rewardPool - The reward pool in lamports.
+   * @param {string} [params.deviceId] - Prover device identifier; defaults to the wallet address.
+   * @param {string} [params.priorChallengeId] - A recent passed challenge here, to lower the quorum.
+   * @param {{period: number, offset: number, length: number}} [params.voteSchedule] - Repeating voting rounds, in seconds; omit to vote after the deadline.
+   * @param {string} [params.context] - Business object to bind the proof to (e.g. an order ID); stored as its SHA-256.
+   * @param {number} [params.minReputation] - Lowest challenger reputation score allowed to stake; newcomers have 0.
+   * @param {number} [params.thresholdMeters] - R* acceptance threshold in meters, within the config's bounds; defaults to the config's.
    * @returns {Promise<string>} The transaction signature.
    */
-  async initializeChallenge({ challengeId, location, duration, rewardPool }) {
+  async initializeChallenge({ challengeId, location, duration, rewardPool, deviceId, priorChallengeId, voteSchedule, context, minReputation, thresholdMeters }) {
     try {
       console.log(`🚀 Initializing challenge: ${challengeId}`);
       console.log(`   Location: ${location.lat}, ${location.lon}`);
@@ -170,18 +226,52 @@ class AnchorClient {
       const challengePda = this._findChallengePda(challengeId);
       console.log(`📍 Challenge PDA: ${challengePda.toString()}`);
 
+      const deviceHash = createHash("sha256")
+        .update(deviceId ?? this.wallet.publicKey.toString())
+        .digest();
+
       // Use the correct method name from your IDL
       const tx = await this.program.methods
-        .initializeChallenge(
+        .initializeChallenge({
           challengeId,
-          Math.round(location.lat * 1e6), // Convert to micro-degrees
-          Math.round(location.lon * 1e6),
-          new BN(duration),
-          new BN(rewardPool)
-        )
+          claimedLat: Math.round(location.lat * 1e6), // Convert to micro-degrees
+          claimedLon: Math.round(location.lon * 1e6),
+          duration: new BN(duration),
+          rewardPool: new BN(rewardPool),
+          tranches: 1,
+          private: false,
+          locationSalt: new Array(32).fill(0),
+          minEquipmentClass: 0,
+          deviceHash: [...deviceHash],
+          startTime: new BN(0),
+          locationCommitment: new Array(32).fill(0),
+          underwriteWitnesses: false,
+          encryptedVotes: false,
+          requiredCredential: PublicKey.default,
+          requiredSchema: PublicKey.default,
+          alternateLocations: [],
+          arbiter: PublicKey.default,
+          voteSchedule: voteSchedule ?? { period: 0, offset: 0, length: 0 },
+          context: context ? [...createHash("sha256").update(context).digest()] : new Array(32).fill(0),
+          minReputation: minReputation ?? 0,
+          thresholdMeters: thresholdMeters ?? 0,
+          rttAttested: false,
+        })
         .accounts({
           challenge: challengePda,
+          vault: this._findVaultPda(challengeId),
+          config: this._findConfigPda(),
+          waldoProfile: this._findWaldoProfilePda(this.wallet.publicKey),
+          deviceLock: this._findDeviceLockPda(deviceHash),
+          insurancePool: this._findInsurancePoolPda(),
           waldo: this.wallet.publicKey,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+          priorResult: priorChallengeId ? this._findChallengePda(priorChallengeId) : null,
+          feeWaivers: null,
+          waiverAttestation: null,
+          expiryFeed: null,
+          challengeRegistry: null,
+          registryPage: null,
           systemProgram: SystemProgram.programId,
         })
         .rpc();
@@ -215,11 +305,19 @@ class AnchorClient {
       console.log(`📍 Challenge PDA: ${challengePda.toString()}`);
       console.log(`📍 Stake PDA: ${stakePda.toString()}`);
 
+      const challengeAccount = await this.program.account.challenge.fetch(challengePda);
+
       const tx = await this.program.methods
         .stake(challengeId, new BN(amount))
         .accounts({
           challenge: challengePda,
+          vault: this._findVaultPda(challengeId),
+          config: this._findConfigPda(),
+          regionPolicy: this._findRegionPolicyPda(challengeAccount.claimedLat, challengeAccount.claimedLon),
           stakeAccount: stakePda,
+          collusionFlag: this._findCollusionFlagPda(this.wallet.publicKey),
+          challengerProfile: this._findChallengerProfilePda(this.wallet.publicKey),
+          credential: null,
           challenger: this.wallet.publicKey,
           systemProgram: SystemProgram.programId,
         })
@@ -236,6 +334,28 @@ class AnchorClient {
     }
   }
 
+  /**
+   * Creates this wallet's empty vote account for a challenge ahead of the
+   * voting window, so `submitVote` doesn't have to pay for it under congestion.
+   * @param {string} challengeId
+   * @returns {Promise<string>} The transaction signature.
+   */
+  async prepareVoteAccount(challengeId) {
+    const tx = await this.program.methods
+      .prepareVoteAccount()
+      .accounts({
+        challenge: this._findChallengePda(challengeId),
+        stakeAccount: this._findStakePda(challengeId, this.wallet.publicKey),
+        voteAccount: this._findVotePda(challengeId, this.wallet.publicKey),
+        challenger: this.wallet.publicKey,
+        operator: this.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    console.log(`✅ Vote account prepared for challenge '${challengeId}'. Tx: ${tx}`);
+    return tx;
+  }
+
   /**
    * Submits a vote for a challenge.
    * @param {object} params
@@ -243,9 +363,13 @@ class AnchorClient {
    * @param {boolean} params.isValid - The voter's decision.
    * @param {number} params.uncertainty - The voter's calculated uncertainty in meters.
    * @param {number} params.minRtt - The voter's measured minimum RTT in microseconds.
+   * @param {number} [params.candidate] - Candidate location measured, for any-of challenges.
+   * @param {number} [params.confidence] - Confidence in the vote, 0 to 1; a wrong vote is slashed more the higher it is.
+   * @param {string} [params.beaconSignature] - The prover's base58 beacon echo signature; required when valid.
+   * @param {number} [params.measuredAt] - Unix time (seconds) the RTT was measured; defaults to now.
    * @returns {Promise<string>} The transaction signature.
    */
-  async submitVote({ challengeId, isValid, uncertainty, minRtt }) {
+  async submitVote({ challengeId, isValid, uncertainty, minRtt, candidate = 0, confidence = 1, beaconSignature, measuredAt }) {
     try {
       console.log(`🗳️  Submitting vote for challenge: ${challengeId}`);
       console.log(`   Valid: ${isValid}, Uncertainty: ${uncertainty}m, Min RTT: ${minRtt}μs`);
@@ -254,21 +378,43 @@ class AnchorClient {
       const stakePda = this._findStakePda(challengeId, this.wallet.publicKey);
       const votePda = this._findVotePda(challengeId, this.wallet.publicKey);
 
+      // Valid votes must follow the prover's signed beacon echo.
+      const preInstructions = [];
+      if (isValid) {
+        if (!beaconSignature) {
+          throw new Error("A valid vote needs the prover's beacon signature");
+        }
+        const challenge = await this.program.account.challenge.fetch(challengePda);
+        preInstructions.push(
+          Ed25519Program.createInstructionWithPublicKey({
+            publicKey: challenge.waldo.toBytes(),
+            message: this._beaconMessage(challenge, challengePda, this.wallet.publicKey),
+            signature: bs58.decode(beaconSignature),
+          })
+        );
+      }
+
       const tx = await this.program.methods
-        .submitVote(
+        .submitVote({
           challengeId,
-          this.wallet.publicKey.toString(), // Using wallet pubkey as challenger_id
+          challengerId: this.wallet.publicKey.toString(), // Using wallet pubkey as challenger_id
           isValid,
           uncertainty,
-          minRtt
-        )
+          minRtt,
+          candidate,
+          confidence: Math.round(Math.min(Math.max(confidence, 0), 1) * 10000),
+          measuredAt: new BN(measuredAt ?? Math.floor(Date.now() / 1000)),
+        })
         .accounts({
           challenge: challengePda,
           stakeAccount: stakePda,
           voteAccount: votePda,
           challenger: this.wallet.publicKey,
+          operator: this.wallet.publicKey,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
           systemProgram: SystemProgram.programId,
         })
+        .preInstructions(preInstructions)
         .rpc();
 
       console.log(`✅ Vote submitted for challenge '${challengeId}'. Tx: ${tx}`);
@@ -283,22 +429,47 @@ class AnchorClient {
   }
 
   /**
-   * Finalizes a challenge by submitting the off-chain calculated result.
+   * Computes R* on-chain from the votes a challenge holds, once its voting window has closed.
+   * @param {string} challengeId - The ID of the challenge.
+   * @returns {Promise<string>} The transaction signature.
+   */
+  async computeRStar(challengeId) {
+    return this.program.methods
+      .computeRStar(challengeId)
+      .accounts({ challenge: this._findChallengePda(challengeId) })
+      .rpc();
+  }
+
+  /**
+   * Finalizes a challenge. R* is computed on-chain from the votes first;
+   * `rStar` is no longer trusted and is only logged.
    * @param {string} challengeId - The ID of the challenge to finalize.
-   * @param {number} rStar - The final R* uncertainty value calculated off-chain.
+   * @param {number} rStar - The off-chain R* estimate, for comparison in the logs.
+   * @param {string} [previousChallengeId] - The prover's most recent passed challenge, for the travel consistency check.
    * @returns {Promise<string>} The transaction signature.
    */
-  async finalizeChallenge(challengeId, rStar) {
+  async finalizeChallenge(challengeId, rStar, previousChallengeId) {
     try {
-      console.log(`🏁 Finalizing challenge: ${challengeId} with R*: ${rStar}`);
+      console.log(`🏁 Finalizing challenge: ${challengeId} (off-chain R*: ${rStar})`);
 
       const challengePda = this._findChallengePda(challengeId);
+      await this.computeRStar(challengeId);
+      const challengeAccount = await this.program.account.challenge.fetch(challengePda);
+      console.log(`📐 On-chain R*: ${challengeAccount.rStar}`);
 
       const tx = await this.program.methods
-        .finalizeChallenge(challengeId, rStar)
+        .finalizeChallenge(challengeId, rStar ?? 0)
         .accounts({
           challenge: challengePda,
+          config: this._findConfigPda(),
+          regionPolicy: this._findRegionPolicyPda(challengeAccount.claimedLat, challengeAccount.claimedLon),
+          regionStats: this._findRegionStatsPda(challengeAccount.claimedLat, challengeAccount.claimedLon),
+          waldoProfile: this._findWaldoProfilePda(challengeAccount.waldo),
           authority: this.wallet.publicKey,
+          oracleSet: this._findOracleSetPda(),
+          previousChallenge: previousChallengeId ? this._findChallengePda(previousChallengeId) : null,
+          challengeRegistry: null,
+          registryPage: null,
         })
         .rpc();
 
@@ -324,18 +495,18 @@ class AnchorClient {
       const challengeAccount = await this.program.account.challenge.fetch(challengePda);
       
       return {
-        challengeId: challengeAccount.challengeId,
+        challengeId: decodeChallengeId(challengeAccount),
         waldo: challengeAccount.waldo.toString(),
         participantCount: challengeAccount.participantCount,
         voteCount: challengeAccount.voteCount,
         validVoteCount: challengeAccount.validVoteCount,
-        status: challengeAccount.status,
+        status: decodeChallengeStatus(challengeAccount),
         deadline: challengeAccount.deadline.toNumber(),
         startTime: challengeAccount.startTime.toNumber(),
         rStar: challengeAccount.rStar,
         rStarThreshold: challengeAccount.rStarThreshold,
         rewardPool: challengeAccount.rewardPool.toNumber(),
-        rewardsDistributed: challengeAccount.rewardsDistributed,
+        rewardsDistributed: challengeAccount.rewardsDistributed !== 0,
         claimedLocation: {
           lat: challengeAccount.claimedLat / 1e6,
           lon: challengeAccount.claimedLon / 1e6,
@@ -358,13 +529,22 @@ class AnchorClient {
 
       const challengePda = this._findChallengePda(challengeId);
       const votePda = this._findVotePda(challengeId, this.wallet.publicKey);
+      const configPda = this._findConfigPda();
+      const config = await this.program.account.config.fetch(configPda);
+      const escrowed = config.claimEscrowPeriod.gtn(0);
       
       const tx = await this.program.methods
         .claimReward(challengeId)
         .accounts({
           challenge: challengePda,
+          vault: this._findVaultPda(challengeId),
           vote: votePda,
           winner: this.wallet.publicKey,
+          config: configPda,
+          claimEscrow: escrowed ? this._findClaimEscrowPda(challengeId, this.wallet.publicKey) : null,
+          payoutLedger: this._findPayoutLedgerPda(this.wallet.publicKey),
+          challengerProfile: this._findChallengerProfilePda(this.wallet.publicKey),
+          insurancePool: this._findInsurancePoolPda(),
           systemProgram: SystemProgram.programId,
         })
         .rpc();
@@ -380,6 +560,70 @@ class AnchorClient {
     }
   }
 
+  /**
+   * Takes back this wallet's stake from an inconclusive or failed challenge.
+   * @param {string} challengeId - The ID of the challenge.
+   * @returns {Promise<string>} The transaction signature.
+   */
+  async returnStake(challengeId) {
+    try {
+      console.log(`↩️ Returning stake for inconclusive challenge: ${challengeId}`);
+
+      const tx = await this.program.methods
+        .returnStake(challengeId, this.wallet.publicKey)
+        .accounts({
+          challenge: this._findChallengePda(challengeId),
+          vault: this._findVaultPda(challengeId),
+          stakeAccount: this._findStakePda(challengeId, this.wallet.publicKey),
+          challenger: this.wallet.publicKey,
+          voteAccount: this._findVotePda(challengeId, this.wallet.publicKey),
+          challengerProfile: this._findChallengerProfilePda(this.wallet.publicKey),
+        })
+        .rpc();
+
+      console.log(`✅ Stake returned for challenge '${challengeId}'. Tx: ${tx}`);
+      return tx;
+    } catch (error) {
+      console.error("❌ Failed to return stake:", error);
+      if (error.logs) {
+        console.error("Transaction logs:", error.logs);
+      }
+      throw error;
+    }
+  }
+
+  /**
+   * Withdraws this wallet's unslashed stake from an ended challenge.
+   * @param {string} challengeId - The ID of the challenge.
+   * @returns {Promise<string>} The transaction signature.
+   */
+  async unstake(challengeId) {
+    try {
+      console.log(`↩️ Unstaking from challenge: ${challengeId}`);
+
+      const tx = await this.program.methods
+        .unstake(challengeId)
+        .accounts({
+          challenge: this._findChallengePda(challengeId),
+          vault: this._findVaultPda(challengeId),
+          stakeAccount: this._findStakePda(challengeId, this.wallet.publicKey),
+          voteAccount: this._findVotePda(challengeId, this.wallet.publicKey),
+          challenger: this.wallet.publicKey,
+          challengerProfile: this._findChallengerProfilePda(this.wallet.publicKey),
+        })
+        .rpc();
+
+      console.log(`✅ Unstaked from challenge '${challengeId}'. Tx: ${tx}`);
+      return tx;
+    } catch (error) {
+      console.error("❌ Failed to unstake:", error);
+      if (error.logs) {
+        console.error("Transaction logs:", error.logs);
+      }
+      throw error;
+    }
+  }
+
   /**
    * Refunds a failed challenge to the creator.
    * @param {string} challengeId - The ID of the challenge.
@@ -390,13 +634,20 @@ class AnchorClient {
       console.log(`💸 Refunding failed challenge: ${challengeId}`);
 
       const challengePda = this._findChallengePda(challengeId);
+      const { funder } = await this.program.account.challenge.fetch(challengePda);
       
       const tx = await this.program.methods
         .refundFailedChallenge(challengeId)
         .accounts({
           challenge: challengePda,
+          vault: this._findVaultPda(challengeId),
           waldo: this.wallet.publicKey,
           authority: this.wallet.publicKey,
+          waldoProfile: this._findWaldoProfilePda(this.wallet.publicKey),
+          // Program-funded pools go back to the funding vault.
+          funder: funder.equals(PublicKey.default) ? null : funder,
+          challengeRegistry: null,
+          registryPage: null,
         })
         .rpc();
 
@@ -421,9 +672,9 @@ class AnchorClient {
 
       // Filter for only active challenges and map to a clean JS object
       return allChallenges
-        .filter((c) => c.account.status.active !== undefined)
+        .filter((c) => c.account.status === CHALLENGE_STATUSES.indexOf("active"))
         .map((c) => ({
-          id: c.account.challengeId,
+          id: decodeChallengeId(c.account),
           publicKey: c.publicKey.toString(),
           waldo: c.account.waldo.toString(),
           claimedLocation: {
@@ -452,7 +703,7 @@ class AnchorClient {
       const allChallenges = await this.program.account.challenge.all();
       
       return allChallenges.map((c) => ({
-        id: c.account.challengeId,
+        id: decodeChallengeId(c.account),
         publicKey: c.publicKey.toString(),
         waldo: c.account.waldo.toString(),
         claimedLocation: {
@@ -465,10 +716,10 @@ class AnchorClient {
         participantCount: c.account.participantCount,
         voteCount: c.account.voteCount,
         validVoteCount: c.account.validVoteCount,
-        status: c.account.status,
+        status: decodeChallengeStatus(c.account),
         rStar: c.account.rStar,
         rStarThreshold: c.account.rStarThreshold,
-        rewardsDistributed: c.account.rewardsDistributed,
+        rewardsDistributed: c.account.rewardsDistributed !== 0,
       }));
     } catch (error) {
       console.error("❌ Failed to get all challenges:", error);
@@ -478,6 +729,99 @@ class AnchorClient {
 
   // --- Private Helper Methods for Finding PDAs ---
 
+  _findConfigPda() {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("config")],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  _findOracleSetPda() {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("oracle_set")],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  _findInsurancePoolPda() {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("insurance")],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  _findWaldoProfilePda(userPublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("waldo"), userPublicKey.toBuffer()],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  _findChallengerProfilePda(userPublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("challenger"), userPublicKey.toBuffer()],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  _findCollusionFlagPda(userPublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("collusion"), userPublicKey.toBuffer()],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  _findRegionPolicyPda(claimedLat, claimedLon) {
+    return this._findCellPda("region", claimedLat, claimedLon);
+  }
+
+  _findRegionStatsPda(claimedLat, claimedLon) {
+    return this._findCellPda("region_stats", claimedLat, claimedLon);
+  }
+
+  _findCellPda(seed, claimedLat, claimedLon) {
+    // Cells are 1x1 degree: floor(micro-degrees / 1e6), as i16 LE
+    const cellLat = Buffer.alloc(2);
+    const cellLon = Buffer.alloc(2);
+    cellLat.writeInt16LE(Math.floor(claimedLat / 1e6));
+    cellLon.writeInt16LE(Math.floor(claimedLon / 1e6));
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from(seed), cellLat, cellLon],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  _findDeviceLockPda(deviceHash) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("device"), deviceHash],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  _findClaimEscrowPda(challengeId, userPublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("claim_escrow"), Buffer.from(challengeId), userPublicKey.toBuffer()],
+      this.program.programId
+    );
+    return pda;
+  }
+
+  _findPayoutLedgerPda(userPublicKey) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("payout_ledger"), userPublicKey.toBuffer()],
+      this.program.programId
+    );
+    return pda;
+  }
+
   _findChallengePda(challengeId) {
     const [pda] = PublicKey.findProgramAddressSync(
       [Buffer.from("challenge"), Buffer.from(challengeId)],
@@ -486,6 +830,14 @@ class AnchorClient {
     return pda;
   }
 
+  _findVaultPda(challengeId) {
+    const [pda] = PublicKey.findProgramAddressSync(
+      [Buffer.from("vault"), Buffer.from(challengeId)],
+      this.program.programId
+    );
+    return pda;
+  }
+
   _findStakePda(challengeId, userPublicKey) {
     // Based on your IDL: seeds = ["stake", challenge_id, challenger]
     const [pda] = PublicKey.findProgramAddressSync(
@@ -191,6 +191,7 @@ class Challenger {
       const pingData = {
         challengeId,
         challengerId: this.challengerId,
+        challenger: this.anchorClient.wallet?.publicKey.toString(),
         sequence: sequence + round * this.PING_COUNT,
         timestamp: Number(startTime / 1000000n), // Convert to milliseconds
         nonce,
@@ -285,6 +286,8 @@ class Challenger {
       };
     }
 
+    // Any signed echo will do: they all carry this challenge's nonce.
+    const signed = successful.find((m) => m.responseData.beaconSignature);
     const rtts = successful.map((m) => m.rtt);
     const minRTT = Math.min(...rtts);
     const avgRTT = rtts.reduce((a, b) => a + b, 0) / rtts.length;
@@ -303,6 +306,7 @@ class Challenger {
       avgRTT: Math.round(avgRTT * 100) / 100,
       medianRTT: Math.round(medianRTT * 100) / 100,
       stdRTT: Math.round(stdRTT * 100) / 100,
+      beaconSignature: signed ? signed.responseData.beaconSignature : null,
     };
   }
 
@@ -374,6 +378,7 @@ class Challenger {
         measurements: {
           minRTT: measurements.minRTT,
           avgRTT: measurements.avgRTT,
+          beaconSignature: measurements.beaconSignature,
           successfulPings: measurements.successful.length,
           totalPings:
             measurements.successful.length + measurements.failed.length,
@@ -407,6 +412,7 @@ class Challenger {
         isValid: vote.isValid,
         uncertainty: vote.uncertainty,
         minRTT: vote.measurements.minRTT,
+        beaconSignature: vote.measurements.beaconSignature,
       });
 
       console.log("✅ Vote submitted successfully!");
@@ -23,6 +23,7 @@ class ChallengeCoordinator extends EventEmitter {
       minChallengers: 3,
       maxChallengers: 20,
       votingWindow: 60, // 1 minute after challenge ends
+      settlementBuffer: 30, // seconds after voting before finalize; Config::min_settlement_buffer
       rStarThreshold: 1000, // meters
     };
 
@@ -292,6 +293,8 @@ class ChallengeCoordinator extends EventEmitter {
         isValid,
         uncertainty,
         minRTT: participant.pingData.minRTT,
+        confidence,
+        beaconSignature: participant.pingData.beaconSignature,
       });
 
       // Store vote locally
@@ -539,10 +542,10 @@ class ChallengeCoordinator extends EventEmitter {
       this.transitionToPhase(challengeId, "voting");
     }, challenge.duration * 1000);
 
-    // Schedule voting -> finalization (voting window after deadline)
+    // Schedule voting -> finalization (voting window and settlement buffer after deadline)
     setTimeout(() => {
       this.finalizeChallenge(challengeId).catch(console.error);
-    }, (challenge.duration + this.config.votingWindow) * 1000);
+    }, (challenge.duration + this.config.votingWindow + this.config.settlementBuffer) * 1000);
   }
 
   /**
@@ -17,6 +17,7 @@ class Waldo {
     this.server = null;
     this.location = null;
     this.challenges = new Map(); // challengeId -> challenge data
+    this.beaconSignatures = new Map(); // "challengeId:challenger" -> signed beacon echo
     this.port = 8888; // Default Waldo listening port
     this.isRunning = false;
     this.responseStats = {
@@ -68,6 +69,26 @@ class Waldo {
     });
   }
 
+  /**
+   * Signed beacon echo a witness needs for a valid on-chain vote. Signing
+   * reads the challenge nonce from chain, so the first pings go out without
+   * one and later pings pick up the cached signature.
+   * @param {string} challengeId
+   * @param {string} challenger - The witness's wallet address.
+   * @returns {string|null}
+   */
+  beaconSignature(challengeId, challenger) {
+    const key = `${challengeId}:${challenger}`;
+    if (!this.beaconSignatures.has(key)) {
+      this.beaconSignatures.set(key, null);
+      this.anchorClient
+        .signBeaconEcho({ challengeId, challenger })
+        .then((signature) => this.beaconSignatures.set(key, signature))
+        .catch(() => this.beaconSignatures.delete(key));
+    }
+    return this.beaconSignatures.get(key);
+  }
+
   /**
    * Handle incoming ping from challenger
    * @param {Buffer} message - Ping message
@@ -78,7 +99,7 @@ class Waldo {
 
     try {
       const pingData = JSON.parse(message.toString());
-      const { challengeId, challengerId, sequence, timestamp, nonce } =
+      const { challengeId, challengerId, challenger, sequence, timestamp, nonce } =
         pingData;
 
       this.responseStats.totalPings++;
@@ -92,6 +113,7 @@ class Waldo {
         waldoLocation: this.location,
         timestamp: timestamp, // Echo original timestamp
         responseTime: Number(receiveTime / 1000000n), // Convert to milliseconds
+        beaconSignature: challenger ? this.beaconSignature(challengeId, challenger) : null,
       };
 
       // Send immediate response
@@ -0,0 +1,1042 @@
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::hash::Hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::message::{
+    v0, AddressLookupTableAccount, CompileError, VersionedMessage,
+};
+use anchor_lang::solana_program::pubkey;
+use anchor_lang::solana_program::sysvar::slot_hashes;
+use anchor_lang::system_program;
+use anchor_lang::{InstructionData, ToAccountMetas};
+use poloc::constants::ASSOCIATED_TOKEN_PROGRAM_ID;
+use poloc::state::{CallbackAccount, Challenge};
+use poloc::{CloneChallengeArgs, InitializeChallengeArgs, OpenFlashChallengeArgs, SubmitVoteArgs, SubmitVoteByKeyArgs};
+
+use crate::intent::{ed25519_verify_instruction, BeaconEcho, ClaimIntent, ClaimProof, RttAttestation, StakeIntent};
+use crate::pda;
+
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    pubkey!("ComputeBudget111111111111111111111111111111");
+
+// ComputeBudgetInstruction discriminants (see solana-compute-budget-interface).
+const SET_COMPUTE_UNIT_LIMIT: u8 = 2;
+const SET_COMPUTE_UNIT_PRICE: u8 = 3;
+
+/// Token accounts for `claim_reward_swapped`, all owned by the winner.
+#[derive(Clone, Copy, Debug)]
+pub struct SwapTokenAccounts {
+    /// Wrapped SOL account the reward is paid into before the swap.
+    pub wsol_account: Pubkey,
+    /// Receives the swapped tokens.
+    pub destination: Pubkey,
+    pub token_program: Pubkey,
+}
+
+/// Composes multi-instruction poloc transactions.
+///
+/// Steps are appended in call order. Compute-budget instructions are always
+/// emitted first regardless of when `priority_fee` / `compute_unit_limit` were
+/// called, since the runtime only honours them at the front of a transaction.
+///
+/// ```ignore
+/// let message = ChallengeFlowBuilder::new(waldo)
+///     .priority_fee(5_000)
+///     .initialize_challenge(args)
+///     .stake(&challenge_id, region, waldo, 1_000_000)
+///     .build_v0_message(recent_blockhash)?;
+/// ```
+#[derive(Clone, Default)]
+pub struct ChallengeFlowBuilder {
+    payer: Pubkey,
+    compute_unit_price: Option<u64>,
+    compute_unit_limit: Option<u32>,
+    lookup_tables: Vec<AddressLookupTableAccount>,
+    registry_page: Option<u32>,
+    waldo: Option<Pubkey>,
+    steps: Vec<Instruction>,
+}
+
+impl ChallengeFlowBuilder {
+    pub fn new(payer: Pubkey) -> Self {
+        Self {
+            payer,
+            ..Self::default()
+        }
+    }
+
+    /// Priority fee in micro-lamports per compute unit.
+    pub fn priority_fee(mut self, micro_lamports: u64) -> Self {
+        self.compute_unit_price = Some(micro_lamports);
+        self
+    }
+
+    pub fn compute_unit_limit(mut self, units: u32) -> Self {
+        self.compute_unit_limit = Some(units);
+        self
+    }
+
+    /// Address lookup table used when compiling a v0 message.
+    pub fn lookup_table(mut self, table: AddressLookupTableAccount) -> Self {
+        self.lookup_tables.push(table);
+        self
+    }
+
+    /// Passes the challenge registry and `page`, the page listing the
+    /// challenge, to the stakes, votes, claims and unstakes added after this,
+    /// so they keep the registry's TVL current and close the entry of a
+    /// challenge they settle.
+    pub fn registry_page(mut self, page: u32) -> Self {
+        self.registry_page = Some(page);
+        self
+    }
+
+    /// Passes the profile of `waldo`, the challenge's creator, to the stakes,
+    /// votes, claims and unstakes added after this, so one that finds the
+    /// challenge overdue settles it rather than failing with
+    /// `ChallengeExpired`. Stakes also record the settle in their region's
+    /// stats.
+    pub fn challenge_creator(mut self, waldo: Pubkey) -> Self {
+        self.waldo = Some(waldo);
+        self
+    }
+
+    /// Escape hatch for instructions the builder has no helper for.
+    pub fn instruction(mut self, ix: Instruction) -> Self {
+        self.steps.push(ix);
+        self
+    }
+
+    pub fn initialize_challenge(self, args: InitializeChallengeArgs) -> Self {
+        self.initialize_challenge_with_prior(args, None)
+    }
+
+    /// Same as `initialize_challenge`, offering the payer's recent passed
+    /// `prior_id` challenge at the same location to lower the quorum.
+    pub fn initialize_challenge_with_prior(self, args: InitializeChallengeArgs, prior_id: Option<&str>) -> Self {
+        self.initialize_challenge_inner(args, prior_id, None, false, None)
+    }
+
+    /// Same as `initialize_challenge`, also listing the challenge's deadline
+    /// in the expiry feed. The feed must already be initialized.
+    pub fn initialize_listed_challenge(self, args: InitializeChallengeArgs) -> Self {
+        self.initialize_challenge_inner(args, None, None, true, None)
+    }
+
+    /// Same as `initialize_challenge`, also listing the challenge in the
+    /// challenge registry. `page` is the registry's `current_page`.
+    pub fn initialize_registered_challenge(self, args: InitializeChallengeArgs, page: u32) -> Self {
+        self.initialize_challenge_inner(args, None, None, false, Some(page))
+    }
+
+    /// Same as `initialize_challenge` for a creator on the fee waiver list.
+    /// Pass their attestation when they qualify by credential, not by key.
+    pub fn initialize_waived_challenge(self, args: InitializeChallengeArgs, attestation: Option<Pubkey>) -> Self {
+        self.initialize_challenge_inner(args, None, Some(attestation), false, None)
+    }
+
+    fn initialize_challenge_inner(
+        self,
+        args: InitializeChallengeArgs,
+        prior_id: Option<&str>,
+        waiver: Option<Option<Pubkey>>,
+        listed: bool,
+        registry_page: Option<u32>,
+    ) -> Self {
+        let waldo = self.payer;
+        let accounts = poloc::accounts::InitializeChallenge {
+            challenge: pda::challenge(&args.challenge_id).0,
+            vault: pda::vault(&args.challenge_id).0,
+            config: pda::config().0,
+            waldo_profile: pda::waldo_profile(&waldo).0,
+            device_lock: pda::device_lock(&args.device_hash).0,
+            insurance_pool: pda::insurance_pool().0,
+            waldo,
+            slot_hashes: slot_hashes::ID,
+            prior_result: prior_id.map(|id| pda::challenge(id).0),
+            fee_waivers: waiver.map(|_| pda::fee_waivers().0),
+            waiver_attestation: waiver.flatten(),
+            expiry_feed: listed.then(|| pda::expiry_feed().0),
+            challenge_registry: registry_page.map(|_| pda::challenge_registry().0),
+            registry_page: registry_page.map(|page| pda::registry_page(page).0),
+            system_program: system_program::ID,
+        };
+        self.program_ix(accounts, poloc::instruction::InitializeChallenge { args })
+    }
+
+    /// Moves out the payer's under-subscribed challenge's deadline and adds
+    /// `additional_reward` lamports to its pool. `device_hash` is the one the
+    /// challenge was created with.
+    pub fn extend_challenge(
+        self,
+        challenge_id: &str,
+        region: (i16, i16),
+        device_hash: &[u8; 32],
+        additional_duration: u64,
+        additional_reward: u64,
+    ) -> Self {
+        let accounts = poloc::accounts::ExtendChallenge {
+            challenge: pda::challenge(challenge_id).0,
+            vault: pda::vault(challenge_id).0,
+            config: pda::config().0,
+            region_policy: pda::region_policy(region.0, region.1).0,
+            device_lock: pda::device_lock(device_hash).0,
+            waldo: self.payer,
+            system_program: system_program::ID,
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::ExtendChallenge {
+                challenge_id: challenge_id.to_string(),
+                additional_duration,
+                additional_reward,
+            },
+        )
+    }
+
+    /// Sweeps a passed challenge's unclaimed rewards once its claim period is
+    /// over, closing it. Anyone may send this.
+    pub fn sweep_unclaimed(self, challenge_id: &str, waldo: Pubkey) -> Self {
+        let accounts = poloc::accounts::SweepUnclaimed {
+            challenge: pda::challenge(challenge_id).0,
+            vault: pda::vault(challenge_id).0,
+            waldo,
+            insurance_pool: pda::insurance_pool().0,
+        };
+        self.program_ix(accounts, poloc::instruction::SweepUnclaimed { challenge_id: challenge_id.to_string() })
+    }
+
+    /// Clears the expiry feed entry at `index`, which must name
+    /// `challenge_id`, once that challenge is no longer open. Anyone may send this.
+    pub fn consume_expiry_entry(self, challenge_id: &str, index: u8) -> Self {
+        let accounts = poloc::accounts::ConsumeExpiryEntry {
+            expiry_feed: pda::expiry_feed().0,
+            challenge: pda::challenge(challenge_id).0,
+            keeper: self.payer,
+        };
+        self.program_ix(accounts, poloc::instruction::ConsumeExpiryEntry { index })
+    }
+
+    /// Closes `challenger`'s settled vote, refunding its rent to them.
+    /// Anyone may send this.
+    pub fn close_vote(self, challenge_id: &str, challenger: Pubkey) -> Self {
+        let accounts = poloc::accounts::CloseVote {
+            challenge: pda::challenge(challenge_id).0,
+            vote: pda::vote(challenge_id, &challenger).0,
+            challenger,
+        };
+        self.program_ix(accounts, poloc::instruction::CloseVote { challenge_id: challenge_id.to_string() })
+    }
+
+    /// Closes `challenger`'s slashed or orphaned stake, refunding its rent to
+    /// them. Anyone may send this.
+    pub fn close_stake(self, challenge_id: &str, challenger: Pubkey) -> Self {
+        let accounts = poloc::accounts::CloseStake {
+            challenge: pda::challenge(challenge_id).0,
+            stake_account: pda::stake(challenge_id, &challenger).0,
+            challenger,
+        };
+        self.program_ix(accounts, poloc::instruction::CloseStake { challenge_id: challenge_id.to_string() })
+    }
+
+    /// Claims the payer's share of a rejected challenge's forfeited waldo bond,
+    /// for having voted invalid.
+    pub fn claim_bond_share(self, challenge_id: &str) -> Self {
+        let accounts = poloc::accounts::ClaimBondShare {
+            challenge: pda::challenge(challenge_id).0,
+            vault: pda::vault(challenge_id).0,
+            vote: pda::vote(challenge_id, &self.payer).0,
+            challenger: self.payer,
+        };
+        self.program_ix(accounts, poloc::instruction::ClaimBondShare { challenge_id: challenge_id.to_string() })
+    }
+
+    /// Logs a `ChallengeReported` summary of a settled challenge.
+    pub fn emit_challenge_report(self, challenge_id: &str) -> Self {
+        let accounts = poloc::accounts::EmitChallengeReport { challenge: pda::challenge(challenge_id).0 };
+        self.program_ix(accounts, poloc::instruction::EmitChallengeReport { challenge_id: challenge_id.to_string() })
+    }
+
+    /// Creates the challenge registry and its first page. The payer must be
+    /// the config admin.
+    pub fn initialize_challenge_registry(self) -> Self {
+        let accounts = poloc::accounts::InitializeChallengeRegistry {
+            challenge_registry: pda::challenge_registry().0,
+            registry_page: pda::registry_page(0).0,
+            config: pda::config().0,
+            admin: self.payer,
+            system_program: system_program::ID,
+        };
+        self.program_ix(accounts, poloc::instruction::InitializeChallengeRegistry {})
+    }
+
+    /// Opens the page after `current_page`, which must be full. Anyone may
+    /// send this; the payer funds the new page.
+    pub fn open_registry_page(self, current_page: u32) -> Self {
+        let accounts = poloc::accounts::OpenRegistryPage {
+            challenge_registry: pda::challenge_registry().0,
+            current_page: pda::registry_page(current_page).0,
+            next_page: pda::registry_page(current_page + 1).0,
+            payer: self.payer,
+            system_program: system_program::ID,
+        };
+        self.program_ix(accounts, poloc::instruction::OpenRegistryPage {})
+    }
+
+    /// Marks the registry entry at (`page`, `slot`), which must name
+    /// `challenge_id`, closed once that challenge has settled. Anyone may send this.
+    pub fn delist_challenge(self, challenge_id: &str, page: u32, slot: u8) -> Self {
+        let accounts = poloc::accounts::DelistChallenge {
+            challenge_registry: pda::challenge_registry().0,
+            registry_page: pda::registry_page(page).0,
+            challenge: pda::challenge(challenge_id).0,
+        };
+        self.program_ix(accounts, poloc::instruction::DelistChallenge { page, slot })
+    }
+
+    /// Claims the reward for `challenger`'s vote to the payer. Pass the
+    /// original key's signed proof, or `None` when the payer is the stake's
+    /// delegated operator.
+    pub fn claim_reward_with_proof(
+        mut self,
+        challenge_id: &str,
+        challenger: Pubkey,
+        proof: Option<(&ClaimProof, &[u8; 64])>,
+        escrowed: bool,
+    ) -> Self {
+        if let Some((proof, signature)) = proof {
+            self.steps.push(ed25519_verify_instruction(&challenger, signature, &proof.message()));
+        }
+        let claimant = self.payer;
+        let accounts = poloc::accounts::ClaimRewardWithProof {
+            challenge: pda::challenge(challenge_id).0,
+            vault: pda::vault(challenge_id).0,
+            vote: pda::vote(challenge_id, &challenger).0,
+            stake_account: proof.is_none().then(|| pda::stake(challenge_id, &challenger).0),
+            claimant,
+            config: pda::config().0,
+            claim_escrow: escrowed.then(|| pda::claim_escrow(challenge_id, &claimant).0),
+            payout_ledger: pda::payout_ledger(&claimant).0,
+            challenger_profile: pda::challenger_profile(&challenger).0,
+            insurance_pool: pda::insurance_pool().0,
+            instructions: anchor_lang::solana_program::sysvar::instructions::ID,
+            challenge_registry: self.registry_page.map(|_| pda::challenge_registry().0),
+            registry_page: self.registry_page.map(|page| pda::registry_page(page).0),
+            waldo_profile: self.waldo.map(|waldo| pda::waldo_profile(&waldo).0),
+            system_program: system_program::ID,
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::ClaimRewardWithProof { challenge_id: challenge_id.to_string(), challenger },
+        )
+    }
+
+    /// Requires `threshold` of `approvers` to release the payer's reward on
+    /// `challenge_id`, paid to `recipient`. Must be sent before voting.
+    pub fn set_claim_policy(
+        self,
+        challenge_id: &str,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+        recipient: Pubkey,
+    ) -> Self {
+        let challenger = self.payer;
+        let accounts = poloc::accounts::SetClaimPolicy {
+            challenge: pda::challenge(challenge_id).0,
+            stake_account: pda::stake(challenge_id, &challenger).0,
+            vote_account: pda::vote(challenge_id, &challenger).0,
+            challenger,
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::SetClaimPolicy { challenge_id: challenge_id.to_string(), approvers, threshold, recipient },
+        )
+    }
+
+    /// Signs off on `challenger`'s policy-gated claim as the payer.
+    pub fn approve_claim(self, challenge_id: &str, challenger: Pubkey) -> Self {
+        let accounts = poloc::accounts::ApproveClaim {
+            challenge: pda::challenge(challenge_id).0,
+            stake_account: pda::stake(challenge_id, &challenger).0,
+            approver: self.payer,
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::ApproveClaim { challenge_id: challenge_id.to_string(), challenger_pubkey: challenger },
+        )
+    }
+
+    /// Pays `challenger`'s approved claim to the policy's `recipient`.
+    pub fn execute_claim(self, challenge_id: &str, challenger: Pubkey, recipient: Pubkey, escrowed: bool) -> Self {
+        let accounts = poloc::accounts::ExecuteClaim {
+            challenge: pda::challenge(challenge_id).0,
+            vault: pda::vault(challenge_id).0,
+            stake_account: pda::stake(challenge_id, &challenger).0,
+            vote: pda::vote(challenge_id, &challenger).0,
+            recipient,
+            config: pda::config().0,
+            claim_escrow: escrowed.then(|| pda::claim_escrow(challenge_id, &challenger).0),
+            payout_ledger: pda::payout_ledger(&challenger).0,
+            challenger_profile: pda::challenger_profile(&challenger).0,
+            insurance_pool: pda::insurance_pool().0,
+            payer: self.payer,
+            system_program: system_program::ID,
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::ExecuteClaim { challenge_id: challenge_id.to_string(), challenger_pubkey: challenger },
+        )
+    }
+
+    /// Re-opens the payer's settled `source_id` challenge under `args.challenge_id`.
+    pub fn clone_challenge(self, source_id: &str, args: CloneChallengeArgs) -> Self {
+        let waldo = self.payer;
+        let accounts = poloc::accounts::CloneChallenge {
+            source: pda::challenge(source_id).0,
+            challenge: pda::challenge(&args.challenge_id).0,
+            vault: pda::vault(&args.challenge_id).0,
+            config: pda::config().0,
+            waldo_profile: pda::waldo_profile(&waldo).0,
+            device_lock: pda::device_lock(&args.device_hash).0,
+            insurance_pool: pda::insurance_pool().0,
+            waldo,
+            slot_hashes: slot_hashes::ID,
+            system_program: system_program::ID,
+        };
+        self.program_ix(accounts, poloc::instruction::CloneChallenge { args })
+    }
+
+    /// Withdraws the payer's challenge before anyone has staked in it.
+    /// `funder` is the program PDA that paid the pool, if any.
+    pub fn cancel_challenge(self, challenge_id: &str, funder: Option<Pubkey>) -> Self {
+        let waldo = self.payer;
+        let accounts = poloc::accounts::CancelChallenge {
+            challenge: pda::challenge(challenge_id).0,
+            vault: pda::vault(challenge_id).0,
+            waldo,
+            waldo_profile: pda::waldo_profile(&waldo).0,
+            funder,
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::CancelChallenge {
+                challenge_id: challenge_id.to_string(),
+            },
+        )
+    }
+
+    /// `region` is the challenge's cell, `RegionPolicy::cell(lat, lon)`.
+    pub fn stake(self, challenge_id: &str, region: (i16, i16), challenger: Pubkey, amount: u64) -> Self {
+        self.stake_ix(challenge_id, region, challenger, amount, None)
+    }
+
+    /// Same as `stake`, for challenges that require a credential;
+    /// `attestation` is the challenger's `pda::attestation` account.
+    pub fn stake_with_credential(
+        self,
+        challenge_id: &str,
+        region: (i16, i16),
+        challenger: Pubkey,
+        amount: u64,
+        attestation: Pubkey,
+    ) -> Self {
+        self.stake_ix(challenge_id, region, challenger, amount, Some(attestation))
+    }
+
+    /// Same as `stake`, without sending the challenge id; the program reads
+    /// it from the challenge account. No credential.
+    pub fn stake_by_key(self, challenge_id: &str, region: (i16, i16), challenger: Pubkey, amount: u64) -> Self {
+        let accounts = stake_accounts(challenge_id, region, challenger, None, self.registry_page, self.waldo);
+        self.program_ix(accounts, poloc::instruction::StakeByKey { amount })
+    }
+
+    fn stake_ix(
+        self,
+        challenge_id: &str,
+        region: (i16, i16),
+        challenger: Pubkey,
+        amount: u64,
+        credential: Option<Pubkey>,
+    ) -> Self {
+        let accounts = stake_accounts(challenge_id, region, challenger, credential, self.registry_page, self.waldo);
+        self.program_ix(
+            accounts,
+            poloc::instruction::Stake {
+                _challenge_id: challenge_id.to_string(),
+                amount,
+            },
+        )
+    }
+
+    /// Stakes by locking part of the challenger's `pda::standing_stake`
+    /// instead of depositing; no credential.
+    pub fn stake_from_standing(self, challenge_id: &str, region: (i16, i16), challenger: Pubkey, amount: u64) -> Self {
+        let accounts = poloc::accounts::StakeFromStanding {
+            challenge: pda::challenge(challenge_id).0,
+            stake_account: pda::stake(challenge_id, &challenger).0,
+            standing_stake: pda::standing_stake(&challenger).0,
+            config: pda::config().0,
+            region_policy: pda::region_policy(region.0, region.1).0,
+            collusion_flag: pda::collusion_flag(&challenger).0,
+            challenger_profile: pda::challenger_profile(&challenger).0,
+            credential: None,
+            challenger,
+            system_program: system_program::ID,
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::StakeFromStanding {
+                challenge_id: challenge_id.to_string(),
+                amount,
+            },
+        )
+    }
+
+    /// Stakes `amount` of a token challenge's reward mint from the
+    /// challenger's associated token account; no credential.
+    pub fn stake_tokens(
+        self,
+        challenge_id: &str,
+        region: (i16, i16),
+        challenger: Pubkey,
+        mint: Pubkey,
+        token_program: Pubkey,
+        amount: u64,
+    ) -> Self {
+        let accounts = poloc::accounts::StakeTokens {
+            challenge: pda::challenge(challenge_id).0,
+            stake_account: pda::stake(challenge_id, &challenger).0,
+            config: pda::config().0,
+            region_policy: pda::region_policy(region.0, region.1).0,
+            region_stats: pda::region_stats(region.0, region.1).0,
+            collusion_flag: pda::collusion_flag(&challenger).0,
+            challenger_profile: pda::challenger_profile(&challenger).0,
+            credential: None,
+            reward_mint: mint,
+            reward_vault: pda::reward_vault(challenge_id, &mint, &token_program).0,
+            challenger_token_account: Pubkey::find_program_address(
+                &[challenger.as_ref(), token_program.as_ref(), mint.as_ref()],
+                &ASSOCIATED_TOKEN_PROGRAM_ID,
+            ).0,
+            challenger,
+            token_program,
+            waldo_profile: self.waldo.map(|waldo| pda::waldo_profile(&waldo).0),
+            challenge_registry: self.registry_page.map(|_| pda::challenge_registry().0),
+            registry_page: self.registry_page.map(|page| pda::registry_page(page).0),
+            system_program: system_program::ID,
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::StakeTokens {
+                challenge_id: challenge_id.to_string(),
+                amount,
+            },
+        )
+    }
+
+    /// Relays a witness's signed stake intent, with the payer as relayer.
+    /// Adds the Ed25519 verification instruction right before the stake.
+    pub fn stake_with_intent(
+        mut self,
+        region: (i16, i16),
+        challenger: Pubkey,
+        intent: &StakeIntent,
+        signature: &[u8; 64],
+    ) -> Self {
+        self.steps.push(ed25519_verify_instruction(&challenger, signature, &intent.message()));
+        let challenge_id = intent.challenge_id.as_str();
+        let accounts = poloc::accounts::StakeWithIntent {
+            challenge: pda::challenge(challenge_id).0,
+            vault: pda::vault(challenge_id).0,
+            config: pda::config().0,
+            region_policy: pda::region_policy(region.0, region.1).0,
+            stake_account: pda::stake(challenge_id, &challenger).0,
+            collusion_flag: pda::collusion_flag(&challenger).0,
+            challenger_profile: pda::challenger_profile(&challenger).0,
+            intent_escrow: pda::intent_escrow(&challenger).0,
+            credential: None,
+            challenger,
+            relayer: self.payer,
+            instructions: anchor_lang::solana_program::sysvar::instructions::ID,
+            system_program: system_program::ID,
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::StakeWithIntent {
+                challenge_id: challenge_id.to_string(),
+                amount: intent.amount,
+                expiry: intent.expiry,
+            },
+        )
+    }
+
+    /// Without a beacon echo only invalid votes are accepted; see
+    /// `submit_measured_vote`.
+    pub fn submit_vote(self, challenger: Pubkey, args: SubmitVoteArgs) -> Self {
+        self.submit_delegated_vote(challenger, challenger, args)
+    }
+
+    /// Votes with the prover's signed beacon echo, as valid votes require.
+    /// Adds the Ed25519 verification instruction right before the vote.
+    pub fn submit_measured_vote(
+        mut self,
+        operator: Pubkey,
+        waldo: Pubkey,
+        echo: &BeaconEcho,
+        signature: &[u8; 64],
+        args: SubmitVoteArgs,
+    ) -> Self {
+        self.steps.push(ed25519_verify_instruction(&waldo, signature, &echo.message()));
+        self.submit_delegated_vote(echo.challenger, operator, args)
+    }
+
+    /// `submit_measured_vote` for `rtt_attested` challenges: the witness's
+    /// signed timing goes between the beacon echo and the vote.
+    pub fn submit_attested_vote(
+        mut self,
+        waldo: Pubkey,
+        echo: &BeaconEcho,
+        echo_signature: &[u8; 64],
+        timing: &RttAttestation,
+        timing_signature: &[u8; 64],
+        args: SubmitVoteArgs,
+    ) -> Self {
+        self.steps.push(ed25519_verify_instruction(&waldo, echo_signature, &echo.message()));
+        self.steps.push(ed25519_verify_instruction(&timing.witness, timing_signature, &timing.message()));
+        self.submit_delegated_vote(echo.challenger, timing.witness, args)
+    }
+
+    /// Votes for `challenger`'s stake from the operator it delegated to.
+    pub fn submit_delegated_vote(self, challenger: Pubkey, operator: Pubkey, args: SubmitVoteArgs) -> Self {
+        let accounts = poloc::accounts::SubmitVote {
+            challenge: pda::challenge(&args.challenge_id).0,
+            stake_account: pda::stake(&args.challenge_id, &challenger).0,
+            vote_account: pda::vote(&args.challenge_id, &challenger).0,
+            challenger,
+            operator,
+            instructions: anchor_lang::solana_program::sysvar::instructions::ID,
+            waldo_profile: self.waldo.map(|waldo| pda::waldo_profile(&waldo).0),
+            challenge_registry: self.registry_page.map(|_| pda::challenge_registry().0),
+            registry_page: self.registry_page.map(|page| pda::registry_page(page).0),
+            system_program: system_program::ID,
+        };
+        self.program_ix(accounts, poloc::instruction::SubmitVote { args })
+    }
+
+    /// Creates `challenger`'s empty vote account ahead of the voting window,
+    /// paid by `operator`, so the later vote doesn't have to.
+    pub fn prepare_vote_account(self, challenge_id: &str, challenger: Pubkey, operator: Pubkey) -> Self {
+        let accounts = poloc::accounts::PrepareVoteAccount {
+            challenge: pda::challenge(challenge_id).0,
+            stake_account: pda::stake(challenge_id, &challenger).0,
+            vote_account: pda::vote(challenge_id, &challenger).0,
+            challenger,
+            operator,
+            system_program: system_program::ID,
+        };
+        self.program_ix(accounts, poloc::instruction::PrepareVoteAccount {})
+    }
+
+    /// Same as `submit_vote`, without sending strings; the program reads the
+    /// challenge id from the challenge account.
+    pub fn submit_vote_by_key(self, challenge_id: &str, challenger: Pubkey, args: SubmitVoteByKeyArgs) -> Self {
+        let accounts = poloc::accounts::SubmitVote {
+            challenge: pda::challenge(challenge_id).0,
+            stake_account: pda::stake(challenge_id, &challenger).0,
+            vote_account: pda::vote(challenge_id, &challenger).0,
+            challenger,
+            operator: challenger,
+            instructions: anchor_lang::solana_program::sysvar::instructions::ID,
+            waldo_profile: self.waldo.map(|waldo| pda::waldo_profile(&waldo).0),
+            challenge_registry: self.registry_page.map(|_| pda::challenge_registry().0),
+            registry_page: self.registry_page.map(|page| pda::registry_page(page).0),
+            system_program: system_program::ID,
+        };
+        self.program_ix(accounts, poloc::instruction::SubmitVoteByKey { args })
+    }
+
+    /// Starts tracking settlement stats for `region`, paid by the payer.
+    pub fn initialize_region_stats(self, region: (i16, i16)) -> Self {
+        let accounts = poloc::accounts::InitializeRegionStats {
+            region_stats: pda::region_stats(region.0, region.1).0,
+            payer: self.payer,
+            system_program: system_program::ID,
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::InitializeRegionStats { cell_lat: region.0, cell_lon: region.1 },
+        )
+    }
+
+    /// Computes R* from the challenge's vote roster.
+    pub fn compute_r_star(self, challenge_id: &str) -> Self {
+        let accounts = poloc::accounts::ComputeRStar {
+            challenge: pda::challenge(challenge_id).0,
+        };
+        self.program_ix(accounts, poloc::instruction::ComputeRStar { challenge_id: challenge_id.to_string() })
+    }
+
+    /// `previous` is the prover's most recent passed challenge, if any, for
+    /// the on-chain travel consistency check.
+    pub fn finalize_challenge(
+        self,
+        challenge_id: &str,
+        region: (i16, i16),
+        authority: Pubkey,
+        previous: Option<&str>,
+    ) -> Self {
+        self.finalize_challenge_with_callback(challenge_id, region, authority, previous, None)
+    }
+
+    /// Same as `finalize_challenge`; pass the fetched challenge when it has a
+    /// registered result callback so its accounts are appended, or when it's
+    /// listed in the challenge registry so its entry is marked closed.
+    pub fn finalize_challenge_with_callback(
+        mut self,
+        challenge_id: &str,
+        region: (i16, i16),
+        authority: Pubkey,
+        previous: Option<&str>,
+        challenge: Option<&Challenge>,
+    ) -> Self {
+        let accounts = poloc::accounts::FinalizeChallenge {
+            challenge: pda::challenge(challenge_id).0,
+            config: pda::config().0,
+            region_policy: pda::region_policy(region.0, region.1).0,
+            region_stats: pda::region_stats(region.0, region.1).0,
+            waldo_profile: pda::waldo_profile(&authority).0,
+            authority,
+            oracle_set: pda::oracle_set().0,
+            previous_challenge: previous.map(|id| pda::challenge(id).0),
+            challenge_registry: challenge.filter(|c| c.listed != 0).map(|_| pda::challenge_registry().0),
+            registry_page: challenge.filter(|c| c.listed != 0).map(|c| pda::registry_page(c.registry_page).0),
+        };
+        let mut metas = accounts.to_account_metas(None);
+        if let Some(challenge) = challenge.filter(|c| c.callback_program != Pubkey::default()) {
+            metas.push(AccountMeta::new_readonly(challenge.callback_program, false));
+            metas.push(AccountMeta::new_readonly(pda::callback_authority(challenge_id).0, false));
+            let registered = &challenge.callback_accounts[..challenge.callback_account_count as usize];
+            metas.extend(registered.iter().map(|r| {
+                if r.is_writable != 0 {
+                    AccountMeta::new(r.pubkey, false)
+                } else {
+                    AccountMeta::new_readonly(r.pubkey, false)
+                }
+            }));
+        }
+        self.steps.push(Instruction {
+            program_id: poloc::ID,
+            accounts: metas,
+            data: poloc::instruction::FinalizeChallenge {
+                challenge_id: challenge_id.to_string(),
+                r_star: 0,
+            }
+            .data(),
+        });
+        self
+    }
+
+    /// Has `program` invoked with the result when `challenge_id` is
+    /// finalized; signed by the creator.
+    pub fn register_callback(
+        self,
+        challenge_id: &str,
+        waldo: Pubkey,
+        program: Pubkey,
+        accounts: Vec<CallbackAccount>,
+    ) -> Self {
+        let ix_accounts = poloc::accounts::RegisterCallback {
+            challenge: pda::challenge(challenge_id).0,
+            waldo,
+        };
+        self.program_ix(
+            ix_accounts,
+            poloc::instruction::RegisterCallback {
+                challenge_id: challenge_id.to_string(),
+                program,
+                accounts,
+            },
+        )
+    }
+
+    /// Opens a flash challenge for the payer, who escrows every witness's reward.
+    pub fn open_flash_challenge(self, args: OpenFlashChallengeArgs) -> Self {
+        let accounts = poloc::accounts::OpenFlashChallenge {
+            flash: pda::flash_challenge(&self.payer, args.flash_id).0,
+            waldo: self.payer,
+            slot_hashes: slot_hashes::ID,
+            system_program: system_program::ID,
+        };
+        self.program_ix(accounts, poloc::instruction::OpenFlashChallenge { args })
+    }
+
+    /// `witness`'s flash vote, behind the beacon echo `waldo` signed over the
+    /// flash challenge's `measurement_nonce`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn submit_flash_vote(
+        mut self,
+        waldo: Pubkey,
+        flash_id: u64,
+        witness: Pubkey,
+        nonce: &[u8; 32],
+        signature: &[u8; 64],
+        uncertainty: u32,
+        min_rtt: u32,
+    ) -> Self {
+        let flash = pda::flash_challenge(&waldo, flash_id).0;
+        let message = poloc::instructions::vote::beacon_message(&flash, nonce, &witness);
+        self.steps.push(ed25519_verify_instruction(&waldo, signature, &message));
+        let accounts = poloc::accounts::SubmitFlashVote {
+            flash,
+            challenger_profile: pda::challenger_profile(&witness).0,
+            witness,
+            instructions: anchor_lang::solana_program::sysvar::instructions::ID,
+        };
+        self.program_ix(accounts, poloc::instruction::SubmitFlashVote { uncertainty, min_rtt })
+    }
+
+    /// Settles a flash challenge; `voters` are its witnesses in vote order.
+    pub fn finalize_flash_challenge(mut self, waldo: Pubkey, flash_id: u64, voters: &[Pubkey]) -> Self {
+        let accounts = poloc::accounts::FinalizeFlashChallenge {
+            flash: pda::flash_challenge(&waldo, flash_id).0,
+            waldo,
+        };
+        let mut metas = accounts.to_account_metas(None);
+        metas.extend(voters.iter().map(|voter| AccountMeta::new(*voter, false)));
+        self.steps.push(Instruction {
+            program_id: poloc::ID,
+            accounts: metas,
+            data: poloc::instruction::FinalizeFlashChallenge {}.data(),
+        });
+        self
+    }
+
+    /// `escrowed` must match whether the config sets a claim escrow period.
+    pub fn claim_reward(self, challenge_id: &str, winner: Pubkey, escrowed: bool) -> Self {
+        let accounts = claim_reward_accounts(challenge_id, winner, escrowed, self.registry_page, self.waldo);
+        self.program_ix(
+            accounts,
+            poloc::instruction::ClaimReward {
+                _challenge_id: challenge_id.to_string(),
+            },
+        )
+    }
+
+    /// Same as `claim_reward`, without sending the challenge id.
+    pub fn claim_reward_by_key(self, challenge_id: &str, winner: Pubkey, escrowed: bool) -> Self {
+        let accounts = claim_reward_accounts(challenge_id, winner, escrowed, self.registry_page, self.waldo);
+        self.program_ix(accounts, poloc::instruction::ClaimRewardByKey {})
+    }
+
+    /// Withdraws the challenger's unslashed stake once the challenge has ended.
+    pub fn unstake(self, challenge_id: &str, challenger: Pubkey) -> Self {
+        let accounts = poloc::accounts::Unstake {
+            challenge: pda::challenge(challenge_id).0,
+            vault: pda::vault(challenge_id).0,
+            stake_account: pda::stake(challenge_id, &challenger).0,
+            vote_account: pda::vote(challenge_id, &challenger).0,
+            challenger,
+            challenger_profile: pda::challenger_profile(&challenger).0,
+            challenge_registry: self.registry_page.map(|_| pda::challenge_registry().0),
+            registry_page: self.registry_page.map(|page| pda::registry_page(page).0),
+            waldo_profile: self.waldo.map(|waldo| pda::waldo_profile(&waldo).0),
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::Unstake {
+                challenge_id: challenge_id.to_string(),
+            },
+        )
+    }
+
+    /// Relays a winner's signed claim intent, with the payer as relayer.
+    /// Adds the Ed25519 verification instruction right before the claim.
+    pub fn claim_reward_with_intent(
+        mut self,
+        winner: Pubkey,
+        intent: &ClaimIntent,
+        signature: &[u8; 64],
+        escrowed: bool,
+    ) -> Self {
+        self.steps.push(ed25519_verify_instruction(&winner, signature, &intent.message()));
+        let challenge_id = intent.challenge_id.as_str();
+        let accounts = poloc::accounts::ClaimRewardWithIntent {
+            challenge: pda::challenge(challenge_id).0,
+            vault: pda::vault(challenge_id).0,
+            vote: pda::vote(challenge_id, &winner).0,
+            winner,
+            relayer: self.payer,
+            config: pda::config().0,
+            claim_escrow: escrowed.then(|| pda::claim_escrow(challenge_id, &winner).0),
+            payout_ledger: pda::payout_ledger(&winner).0,
+            challenger_profile: pda::challenger_profile(&winner).0,
+            insurance_pool: pda::insurance_pool().0,
+            instructions: anchor_lang::solana_program::sysvar::instructions::ID,
+            waldo_profile: self.waldo.map(|waldo| pda::waldo_profile(&waldo).0),
+            challenge_registry: self.registry_page.map(|_| pda::challenge_registry().0),
+            registry_page: self.registry_page.map(|page| pda::registry_page(page).0),
+            system_program: system_program::ID,
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::ClaimRewardWithIntent {
+                challenge_id: challenge_id.to_string(),
+            },
+        )
+    }
+
+    /// Claims `winner`'s reward into `wsol_account` and swaps it through
+    /// `route`, a Jupiter swap instruction quoted for the winner. Register the
+    /// route's lookup tables with `lookup_table`.
+    pub fn claim_reward_swapped(
+        mut self,
+        challenge_id: &str,
+        winner: Pubkey,
+        token_accounts: SwapTokenAccounts,
+        route: &Instruction,
+        min_amount_out: u64,
+    ) -> Self {
+        let accounts = poloc::accounts::ClaimRewardSwapped {
+            challenge: pda::challenge(challenge_id).0,
+            vault: pda::vault(challenge_id).0,
+            vote: pda::vote(challenge_id, &winner).0,
+            winner,
+            config: pda::config().0,
+            wsol_account: token_accounts.wsol_account,
+            destination: token_accounts.destination,
+            token_program: token_accounts.token_program,
+            jupiter_program: poloc::constants::JUPITER_PROGRAM_ID,
+            payout_ledger: pda::payout_ledger(&winner).0,
+            challenger_profile: pda::challenger_profile(&winner).0,
+            insurance_pool: pda::insurance_pool().0,
+            waldo_profile: self.waldo.map(|waldo| pda::waldo_profile(&waldo).0),
+            challenge_registry: self.registry_page.map(|_| pda::challenge_registry().0),
+            registry_page: self.registry_page.map(|page| pda::registry_page(page).0),
+            system_program: system_program::ID,
+        };
+        let mut metas = accounts.to_account_metas(None);
+        metas.extend(route.accounts.iter().cloned());
+        self.steps.push(Instruction {
+            program_id: poloc::ID,
+            accounts: metas,
+            data: poloc::instruction::ClaimRewardSwapped {
+                challenge_id: challenge_id.to_string(),
+                route_data: route.data.clone(),
+                min_amount_out,
+            }
+            .data(),
+        });
+        self
+    }
+
+    /// One-shot localnet/devnet setup: config, witness profiles and a sample
+    /// challenge created by the payer.
+    #[cfg(feature = "demo")]
+    pub fn bootstrap_demo(self, challenge_id: &str, witnesses: [Pubkey; 3]) -> Self {
+        let payer = self.payer;
+        let accounts = poloc::accounts::BootstrapDemo {
+            config: pda::config().0,
+            challenge: pda::challenge(challenge_id).0,
+            waldo_profile: pda::waldo_profile(&payer).0,
+            witness_profile_0: pda::challenger_profile(&witnesses[0]).0,
+            witness_profile_1: pda::challenger_profile(&witnesses[1]).0,
+            witness_profile_2: pda::challenger_profile(&witnesses[2]).0,
+            payer,
+            system_program: system_program::ID,
+        };
+        self.program_ix(
+            accounts,
+            poloc::instruction::BootstrapDemo {
+                challenge_id: challenge_id.to_string(),
+                witnesses,
+            },
+        )
+    }
+
+    /// All instructions in submission order, compute-budget first.
+    pub fn instructions(&self) -> Vec<Instruction> {
+        let mut ixs = Vec::with_capacity(self.steps.len() + 2);
+        if let Some(units) = self.compute_unit_limit {
+            let mut data = vec![SET_COMPUTE_UNIT_LIMIT];
+            data.extend_from_slice(&units.to_le_bytes());
+            ixs.push(Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, vec![]));
+        }
+        if let Some(price) = self.compute_unit_price {
+            let mut data = vec![SET_COMPUTE_UNIT_PRICE];
+            data.extend_from_slice(&price.to_le_bytes());
+            ixs.push(Instruction::new_with_bytes(COMPUTE_BUDGET_PROGRAM_ID, &data, vec![]));
+        }
+        ixs.extend(self.steps.iter().cloned());
+        ixs
+    }
+
+    /// Compiles a v0 message using any registered lookup tables. The caller
+    /// signs it with the payer plus every other signer referenced by the steps.
+    pub fn build_v0_message(&self, recent_blockhash: Hash) -> Result<VersionedMessage, CompileError> {
+        let message = v0::Message::try_compile(
+            &self.payer,
+            &self.instructions(),
+            &self.lookup_tables,
+            recent_blockhash,
+        )?;
+        Ok(VersionedMessage::V0(message))
+    }
+
+    fn program_ix(mut self, accounts: impl ToAccountMetas, data: impl InstructionData) -> Self {
+        self.steps.push(Instruction {
+            program_id: poloc::ID,
+            accounts: accounts.to_account_metas(None),
+            data: data.data(),
+        });
+        self
+    }
+}
+
+fn claim_reward_accounts(
+    challenge_id: &str,
+    winner: Pubkey,
+    escrowed: bool,
+    registry_page: Option<u32>,
+    waldo: Option<Pubkey>,
+) -> poloc::accounts::ClaimReward {
+    poloc::accounts::ClaimReward {
+        challenge: pda::challenge(challenge_id).0,
+        vault: pda::vault(challenge_id).0,
+        vote: pda::vote(challenge_id, &winner).0,
+        winner,
+        config: pda::config().0,
+        claim_escrow: escrowed.then(|| pda::claim_escrow(challenge_id, &winner).0),
+        payout_ledger: pda::payout_ledger(&winner).0,
+        challenger_profile: pda::challenger_profile(&winner).0,
+        insurance_pool: pda::insurance_pool().0,
+        challenge_registry: registry_page.map(|_| pda::challenge_registry().0),
+        registry_page: registry_page.map(|page| pda::registry_page(page).0),
+        waldo_profile: waldo.map(|waldo| pda::waldo_profile(&waldo).0),
+        system_program: system_program::ID,
+    }
+}
+
+fn stake_accounts(
+    challenge_id: &str,
+    region: (i16, i16),
+    challenger: Pubkey,
+    credential: Option<Pubkey>,
+    registry_page: Option<u32>,
+    waldo: Option<Pubkey>,
+) -> poloc::accounts::StakeCtx {
+    poloc::accounts::StakeCtx {
+        challenge: pda::challenge(challenge_id).0,
+        vault: pda::vault(challenge_id).0,
+        config: pda::config().0,
+        region_policy: pda::region_policy(region.0, region.1).0,
+        region_stats: pda::region_stats(region.0, region.1).0,
+        stake_account: pda::stake(challenge_id, &challenger).0,
+        collusion_flag: pda::collusion_flag(&challenger).0,
+        challenger_profile: pda::challenger_profile(&challenger).0,
+        credential,
+        challenger,
+        challenge_registry: registry_page.map(|_| pda::challenge_registry().0),
+        registry_page: registry_page.map(|page| pda::registry_page(page).0),
+        waldo_profile: waldo.map(|waldo| pda::waldo_profile(&waldo).0),
+        system_program: system_program::ID,
+    }
+}
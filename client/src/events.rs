@@ -0,0 +1,262 @@
+//! Decoder for poloc events with an explicit schema compatibility policy.
+//!
+//! Policy:
+//! - Events are append-only. Every on-chain addition bumps
+//!   `poloc::events::EVENT_SCHEMA_VERSION`.
+//! - Versions below `MIN_SCHEMA_VERSION` are rejected.
+//! - Versions up to `SUPPORTED_SCHEMA_VERSION` must decode exactly, with no
+//!   trailing bytes.
+//! - Newer versions decode the known prefix and set `has_unknown_fields`, so
+//!   an indexer keeps running across a program upgrade and can re-index once
+//!   the client is updated.
+
+use anchor_lang::{AnchorDeserialize, Discriminator};
+use base64::Engine;
+use poloc::events::*;
+
+pub const MIN_SCHEMA_VERSION: u8 = 1;
+pub const SUPPORTED_SCHEMA_VERSION: u8 = EVENT_SCHEMA_VERSION;
+
+const PROGRAM_DATA_PREFIX: &str = "Program data: ";
+
+/// Bytes appended in v2 to events that existed in v1 (the 32-byte
+/// `context`). Older events are zero-padded so they decode with it unset.
+const V2_APPENDED_LEN: usize = 32;
+
+pub enum PolocEvent {
+    ChallengeInitialized(ChallengeInitialized),
+    Staked(Staked),
+    VoteSubmitted(VoteSubmitted),
+    ChallengeFinalized(ChallengeFinalized),
+    RewardClaimed(RewardClaimed),
+    ChallengeRefunded(ChallengeRefunded),
+    ChallengeCancelled(ChallengeCancelled),
+    StakeSlashed(StakeSlashed),
+    ChallengeArchived(ChallengeArchived),
+    KeeperPenalized(KeeperPenalized),
+    TrancheFunded(TrancheFunded),
+    PayoutRootPosted(PayoutRootPosted),
+    PrivateChallengeInitialized(PrivateChallengeInitialized),
+    OraclePenalized(OraclePenalized),
+    ContributionReceived(ContributionReceived),
+    SponsorRefunded(SponsorRefunded),
+    ClaimEscrowSettled(ClaimEscrowSettled),
+    LocationRevealed(LocationRevealed),
+    ChallengeFreezeChanged(ChallengeFreezeChanged),
+    RewardSwapped(RewardSwapped),
+    AccountingDiscrepancy(AccountingDiscrepancy),
+    EncryptedVoteSubmitted(EncryptedVoteSubmitted),
+    DecryptionShareSubmitted(DecryptionShareSubmitted),
+    CandidateSelected(CandidateSelected),
+    CallbackRegistered(CallbackRegistered),
+    CampaignCreated(CampaignCreated),
+    CampaignChallengeReported(CampaignChallengeReported),
+    CampaignReconciled(CampaignReconciled),
+    EvidenceSubmitted(EvidenceSubmitted),
+    TierBadgeClaimed(TierBadgeClaimed),
+    FlashChallengeFinalized(FlashChallengeFinalized),
+    ReverificationDue(ReverificationDue),
+    StakeReturned(StakeReturned),
+    StandingStakeLocked(StandingStakeLocked),
+    StandingStakeReleased(StandingStakeReleased),
+    EmergencyWithdrawalProposed(EmergencyWithdrawalProposed),
+    EmergencyWithdrawalCancelled(EmergencyWithdrawalCancelled),
+    EmergencyWithdrawalExecuted(EmergencyWithdrawalExecuted),
+    Unstaked(Unstaked),
+    StakeReceiptMinted(StakeReceiptMinted),
+    StakeReceiptRedeemed(StakeReceiptRedeemed),
+    RStarComputed(RStarComputed),
+    StreakBonusPaid(StreakBonusPaid),
+    ChallengeExtended(ChallengeExtended),
+    TimingWindowMissed(TimingWindowMissed),
+    UnclaimedRewardsSwept(UnclaimedRewardsSwept),
+    WaldoBondClaimed(WaldoBondClaimed),
+    ChallengeReported(ChallengeReported),
+    VoteChained(VoteChained),
+}
+
+pub struct DecodedEvent {
+    pub event: PolocEvent,
+    pub schema_version: u8,
+    /// True when the event came from a newer schema and carried fields this
+    /// client does not know about.
+    pub has_unknown_fields: bool,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    UnknownDiscriminator,
+    UnsupportedSchemaVersion(u8),
+    Malformed,
+}
+
+/// Decodes one event payload (discriminator + borsh body).
+pub fn decode_event(data: &[u8]) -> Result<DecodedEvent, DecodeError> {
+    if data.len() < 9 {
+        return Err(DecodeError::Malformed);
+    }
+    let (discriminator, body) = data.split_at(8);
+    let schema_version = body[0];
+    if schema_version < MIN_SCHEMA_VERSION {
+        return Err(DecodeError::UnsupportedSchemaVersion(schema_version));
+    }
+
+    let padded;
+    let mut cursor = if schema_version < 2 && gained_context(discriminator) {
+        padded = [body, &[0u8; V2_APPENDED_LEN]].concat();
+        &padded[..]
+    } else {
+        body
+    };
+    let event = match discriminator {
+        d if d == ChallengeInitialized::DISCRIMINATOR => {
+            PolocEvent::ChallengeInitialized(read(&mut cursor)?)
+        }
+        d if d == Staked::DISCRIMINATOR => PolocEvent::Staked(read(&mut cursor)?),
+        d if d == VoteSubmitted::DISCRIMINATOR => PolocEvent::VoteSubmitted(read(&mut cursor)?),
+        d if d == ChallengeFinalized::DISCRIMINATOR => {
+            PolocEvent::ChallengeFinalized(read(&mut cursor)?)
+        }
+        d if d == RewardClaimed::DISCRIMINATOR => PolocEvent::RewardClaimed(read(&mut cursor)?),
+        d if d == ChallengeRefunded::DISCRIMINATOR => {
+            PolocEvent::ChallengeRefunded(read(&mut cursor)?)
+        }
+        d if d == ChallengeCancelled::DISCRIMINATOR => {
+            PolocEvent::ChallengeCancelled(read(&mut cursor)?)
+        }
+        d if d == StakeSlashed::DISCRIMINATOR => PolocEvent::StakeSlashed(read(&mut cursor)?),
+        d if d == ChallengeArchived::DISCRIMINATOR => {
+            PolocEvent::ChallengeArchived(read(&mut cursor)?)
+        }
+        d if d == KeeperPenalized::DISCRIMINATOR => {
+            PolocEvent::KeeperPenalized(read(&mut cursor)?)
+        }
+        d if d == TrancheFunded::DISCRIMINATOR => PolocEvent::TrancheFunded(read(&mut cursor)?),
+        d if d == PayoutRootPosted::DISCRIMINATOR => {
+            PolocEvent::PayoutRootPosted(read(&mut cursor)?)
+        }
+        d if d == PrivateChallengeInitialized::DISCRIMINATOR => {
+            PolocEvent::PrivateChallengeInitialized(read(&mut cursor)?)
+        }
+        d if d == OraclePenalized::DISCRIMINATOR => {
+            PolocEvent::OraclePenalized(read(&mut cursor)?)
+        }
+        d if d == ContributionReceived::DISCRIMINATOR => {
+            PolocEvent::ContributionReceived(read(&mut cursor)?)
+        }
+        d if d == SponsorRefunded::DISCRIMINATOR => {
+            PolocEvent::SponsorRefunded(read(&mut cursor)?)
+        }
+        d if d == ClaimEscrowSettled::DISCRIMINATOR => {
+            PolocEvent::ClaimEscrowSettled(read(&mut cursor)?)
+        }
+        d if d == LocationRevealed::DISCRIMINATOR => {
+            PolocEvent::LocationRevealed(read(&mut cursor)?)
+        }
+        d if d == ChallengeFreezeChanged::DISCRIMINATOR => {
+            PolocEvent::ChallengeFreezeChanged(read(&mut cursor)?)
+        }
+        d if d == RewardSwapped::DISCRIMINATOR => {
+            PolocEvent::RewardSwapped(read(&mut cursor)?)
+        }
+        d if d == AccountingDiscrepancy::DISCRIMINATOR => {
+            PolocEvent::AccountingDiscrepancy(read(&mut cursor)?)
+        }
+        d if d == EncryptedVoteSubmitted::DISCRIMINATOR => {
+            PolocEvent::EncryptedVoteSubmitted(read(&mut cursor)?)
+        }
+        d if d == DecryptionShareSubmitted::DISCRIMINATOR => {
+            PolocEvent::DecryptionShareSubmitted(read(&mut cursor)?)
+        }
+        d if d == CandidateSelected::DISCRIMINATOR => {
+            PolocEvent::CandidateSelected(read(&mut cursor)?)
+        }
+        d if d == CallbackRegistered::DISCRIMINATOR => {
+            PolocEvent::CallbackRegistered(read(&mut cursor)?)
+        }
+        d if d == CampaignCreated::DISCRIMINATOR => {
+            PolocEvent::CampaignCreated(read(&mut cursor)?)
+        }
+        d if d == CampaignChallengeReported::DISCRIMINATOR => {
+            PolocEvent::CampaignChallengeReported(read(&mut cursor)?)
+        }
+        d if d == CampaignReconciled::DISCRIMINATOR => {
+            PolocEvent::CampaignReconciled(read(&mut cursor)?)
+        }
+        d if d == EvidenceSubmitted::DISCRIMINATOR => {
+            PolocEvent::EvidenceSubmitted(read(&mut cursor)?)
+        }
+        d if d == TierBadgeClaimed::DISCRIMINATOR => {
+            PolocEvent::TierBadgeClaimed(read(&mut cursor)?)
+        }
+        d if d == FlashChallengeFinalized::DISCRIMINATOR => {
+            PolocEvent::FlashChallengeFinalized(read(&mut cursor)?)
+        }
+        d if d == ReverificationDue::DISCRIMINATOR => {
+            PolocEvent::ReverificationDue(read(&mut cursor)?)
+        }
+        d if d == StakeReturned::DISCRIMINATOR => PolocEvent::StakeReturned(read(&mut cursor)?),
+        d if d == StandingStakeLocked::DISCRIMINATOR => {
+            PolocEvent::StandingStakeLocked(read(&mut cursor)?)
+        }
+        d if d == StandingStakeReleased::DISCRIMINATOR => {
+            PolocEvent::StandingStakeReleased(read(&mut cursor)?)
+        }
+        d if d == EmergencyWithdrawalProposed::DISCRIMINATOR => {
+            PolocEvent::EmergencyWithdrawalProposed(read(&mut cursor)?)
+        }
+        d if d == EmergencyWithdrawalCancelled::DISCRIMINATOR => {
+            PolocEvent::EmergencyWithdrawalCancelled(read(&mut cursor)?)
+        }
+        d if d == EmergencyWithdrawalExecuted::DISCRIMINATOR => {
+            PolocEvent::EmergencyWithdrawalExecuted(read(&mut cursor)?)
+        }
+        d if d == Unstaked::DISCRIMINATOR => PolocEvent::Unstaked(read(&mut cursor)?),
+        d if d == StakeReceiptMinted::DISCRIMINATOR => PolocEvent::StakeReceiptMinted(read(&mut cursor)?),
+        d if d == StakeReceiptRedeemed::DISCRIMINATOR => PolocEvent::StakeReceiptRedeemed(read(&mut cursor)?),
+        d if d == RStarComputed::DISCRIMINATOR => PolocEvent::RStarComputed(read(&mut cursor)?),
+        d if d == StreakBonusPaid::DISCRIMINATOR => PolocEvent::StreakBonusPaid(read(&mut cursor)?),
+        d if d == ChallengeExtended::DISCRIMINATOR => PolocEvent::ChallengeExtended(read(&mut cursor)?),
+        d if d == TimingWindowMissed::DISCRIMINATOR => PolocEvent::TimingWindowMissed(read(&mut cursor)?),
+        d if d == UnclaimedRewardsSwept::DISCRIMINATOR => PolocEvent::UnclaimedRewardsSwept(read(&mut cursor)?),
+        d if d == WaldoBondClaimed::DISCRIMINATOR => PolocEvent::WaldoBondClaimed(read(&mut cursor)?),
+        d if d == ChallengeReported::DISCRIMINATOR => PolocEvent::ChallengeReported(read(&mut cursor)?),
+        d if d == VoteChained::DISCRIMINATOR => PolocEvent::VoteChained(read(&mut cursor)?),
+        _ => return Err(DecodeError::UnknownDiscriminator),
+    };
+
+    let has_unknown_fields = !cursor.is_empty();
+    if has_unknown_fields && schema_version <= SUPPORTED_SCHEMA_VERSION {
+        return Err(DecodeError::Malformed);
+    }
+
+    Ok(DecodedEvent {
+        event,
+        schema_version,
+        has_unknown_fields,
+    })
+}
+
+/// Decodes every `Program data:` line in a transaction's log messages.
+/// Lines that are not poloc events are reported as `UnknownDiscriminator`.
+pub fn decode_logs<S: AsRef<str>>(logs: &[S]) -> Vec<Result<DecodedEvent, DecodeError>> {
+    logs.iter()
+        .filter_map(|line| line.as_ref().strip_prefix(PROGRAM_DATA_PREFIX))
+        .map(|encoded| {
+            base64::engine::general_purpose::STANDARD
+                .decode(encoded.trim())
+                .map_err(|_| DecodeError::Malformed)
+                .and_then(|bytes| decode_event(&bytes))
+        })
+        .collect()
+}
+
+fn gained_context(discriminator: &[u8]) -> bool {
+    discriminator == ChallengeInitialized::DISCRIMINATOR
+        || discriminator == PrivateChallengeInitialized::DISCRIMINATOR
+        || discriminator == ChallengeFinalized::DISCRIMINATOR
+}
+
+fn read<T: AnchorDeserialize>(cursor: &mut &[u8]) -> Result<T, DecodeError> {
+    T::deserialize(cursor).map_err(|_| DecodeError::Malformed)
+}
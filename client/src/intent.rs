@@ -0,0 +1,152 @@
+//! Pre-signed stake and claim intents.
+//!
+//! A witness signs `StakeIntent::message()` with their wallet ahead of time
+//! (e.g. from a mobile app). A relayer later submits the signature through
+//! `ChallengeFlowBuilder::stake_with_intent`, paying fees and rent; the stake
+//! itself is drawn from the witness's intent escrow.
+//!
+//! `ClaimIntent` works the same way for `claim_reward_with_intent`, with the
+//! relayer fee deducted from the reward. `ClaimProof` is signed by the key
+//! that cast a vote, so `claim_reward_with_proof` can pay a new wallet after
+//! key rotation.
+//!
+//! `BeaconEcho` is the other way round: the prover's beacon signs it when a
+//! witness probes, and the witness submits it with a valid vote.
+//! `RttAttestation` is the witness's own signed timing of that echo, which
+//! `rtt_attested` challenges require as well.
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::instruction::Instruction;
+
+use crate::pda;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StakeIntent {
+    pub challenge_id: String,
+    pub amount: u64,
+    /// Unix timestamp after which the intent can no longer be used.
+    pub expiry: i64,
+}
+
+impl StakeIntent {
+    /// Bytes the witness signs.
+    pub fn message(&self) -> Vec<u8> {
+        poloc::instructions::stake_with_intent::stake_intent_message(
+            &pda::challenge(&self.challenge_id).0,
+            self.amount,
+            self.expiry,
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClaimIntent {
+    pub challenge_id: String,
+    /// Must equal the deployment's `Config::relayer_claim_fee`.
+    pub relayer_fee: u64,
+}
+
+impl ClaimIntent {
+    /// Bytes the winner signs.
+    pub fn message(&self) -> Vec<u8> {
+        poloc::instructions::claim_reward_with_intent::claim_intent_message(
+            &pda::challenge(&self.challenge_id).0,
+            self.relayer_fee,
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ClaimProof {
+    pub challenge_id: String,
+    /// The vote's `chain_index`, read from the account.
+    pub vote_index: u32,
+    /// Wallet the reward is paid to.
+    pub claimant: Pubkey,
+}
+
+impl ClaimProof {
+    /// Bytes the original voting key signs.
+    pub fn message(&self) -> Vec<u8> {
+        poloc::instructions::claim_reward_with_proof::claim_proof_message(
+            &pda::challenge(&self.challenge_id).0,
+            self.vote_index,
+            &self.claimant,
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct BeaconEcho {
+    pub challenge_id: String,
+    /// The challenge's `measurement_nonce`, read from the account.
+    pub nonce: [u8; 32],
+    pub challenger: Pubkey,
+}
+
+impl BeaconEcho {
+    /// Bytes the prover signs.
+    pub fn message(&self) -> Vec<u8> {
+        poloc::instructions::vote::beacon_message(
+            &pda::challenge(&self.challenge_id).0,
+            &self.nonce,
+            &self.challenger,
+        )
+    }
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RttAttestation {
+    pub challenge_id: String,
+    /// The challenge's `measurement_nonce`, read from the account.
+    pub nonce: [u8; 32],
+    /// Whoever took the measurement: the stake's operator.
+    pub witness: Pubkey,
+    pub min_rtt: u32,
+    pub measured_at: i64,
+}
+
+impl RttAttestation {
+    /// Bytes the witness signs.
+    pub fn message(&self) -> Vec<u8> {
+        poloc::instructions::vote::rtt_message(
+            &pda::challenge(&self.challenge_id).0,
+            &self.nonce,
+            &self.witness,
+            self.min_rtt,
+            self.measured_at,
+        )
+    }
+}
+
+const SIGNATURE_OFFSETS_START: u16 = 2;
+const SIGNATURE_OFFSETS_LEN: u16 = 14;
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+/// Ed25519 program instruction verifying one signature whose pubkey,
+/// signature and message are all embedded in the instruction data.
+pub fn ed25519_verify_instruction(signer: &Pubkey, signature: &[u8; 64], message: &[u8]) -> Instruction {
+    let pubkey_offset = SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN;
+    let signature_offset = pubkey_offset + 32;
+    let message_offset = signature_offset + 64;
+
+    let mut data = Vec::with_capacity(message_offset as usize + message.len());
+    data.extend_from_slice(&[1, 0]);
+    for field in [
+        signature_offset,
+        CURRENT_INSTRUCTION,
+        pubkey_offset,
+        CURRENT_INSTRUCTION,
+        message_offset,
+        message.len() as u16,
+        CURRENT_INSTRUCTION,
+    ] {
+        data.extend_from_slice(&field.to_le_bytes());
+    }
+    data.extend_from_slice(signer.as_ref());
+    data.extend_from_slice(signature);
+    data.extend_from_slice(message);
+
+    Instruction::new_with_bytes(ed25519_program::ID, &data, vec![])
+}
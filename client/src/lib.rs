@@ -0,0 +1,17 @@
+//! Off-chain helpers for building poloc transactions.
+//!
+//! Integrators should prefer `ChallengeFlowBuilder` over hand-assembling
+//! instructions: it derives every PDA the same way the program does and keeps
+//! compute-budget instructions in front of the program instructions.
+
+pub mod builder;
+pub mod events;
+pub mod intent;
+pub mod pda;
+pub mod replay;
+
+pub use builder::{ChallengeFlowBuilder, SwapTokenAccounts};
+pub use intent::{BeaconEcho, ClaimProof, RttAttestation, StakeIntent};
+/// Shared R* math, re-exported for off-chain oracles.
+pub use poloc_core;
+pub use poloc::{state::VoteSchedule, InitializeChallengeArgs, SubmitVoteArgs};
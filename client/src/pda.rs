@@ -0,0 +1,182 @@
+use anchor_lang::prelude::Pubkey;
+use poloc::constants::*;
+
+// Seeds come from `poloc::constants`, shared with the program's
+// `seeds = [...]` constraints.
+
+pub fn protocol_info() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PROTOCOL_INFO_SEED], &poloc::ID)
+}
+
+pub fn config() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CONFIG_SEED], &poloc::ID)
+}
+
+pub fn waldo_profile(waldo: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[WALDO_SEED, waldo.as_ref()], &poloc::ID)
+}
+
+pub fn emergency_withdrawal(challenge: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EMERGENCY_WITHDRAWAL_SEED, challenge.as_ref()], &poloc::ID)
+}
+
+pub fn evidence(challenge_id: &str, challenger: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EVIDENCE_SEED, challenge_id.as_bytes(), challenger.as_ref()], &poloc::ID)
+}
+
+pub fn insurance_pool() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INSURANCE_SEED], &poloc::ID)
+}
+
+/// A credential attestation for `holder`, owned by the config's
+/// `attestation_program`.
+pub fn attestation(program: &Pubkey, credential: &Pubkey, schema: &Pubkey, holder: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[b"attestation", credential.as_ref(), schema.as_ref(), holder.as_ref()],
+        program,
+    )
+}
+
+/// Authority PDA an allowlisted integrator program signs with to create
+/// challenges in its namespace.
+pub fn integrator_authority(integrator_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INTEGRATOR_AUTHORITY_SEED], integrator_program)
+}
+
+pub fn payout_ledger(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PAYOUT_LEDGER_SEED, wallet.as_ref()], &poloc::ID)
+}
+
+pub fn vote_committee() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VOTE_COMMITTEE_SEED], &poloc::ID)
+}
+
+pub fn receipt_mint(challenge_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[RECEIPT_MINT_SEED, challenge_id.as_bytes()], &poloc::ID)
+}
+
+pub fn oracle_set() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[ORACLE_SET_SEED], &poloc::ID)
+}
+
+pub fn fee_waivers() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FEE_WAIVER_SEED], &poloc::ID)
+}
+
+pub fn expiry_feed() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[EXPIRY_FEED_SEED], &poloc::ID)
+}
+
+pub fn challenge_registry() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_SEED], &poloc::ID)
+}
+
+pub fn registry_page(page: u32) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[REGISTRY_PAGE_SEED, &page.to_le_bytes()], &poloc::ID)
+}
+
+pub fn callback_authority(challenge_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CALLBACK_SEED, challenge_id.as_bytes()], &poloc::ID)
+}
+
+pub fn oracle_report(challenge_id: &str, oracle: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[ORACLE_REPORT_SEED, challenge_id.as_bytes(), oracle.as_ref()],
+        &poloc::ID,
+    )
+}
+
+/// Region cells come from `poloc::state::RegionPolicy::cell(lat, lon)`.
+pub fn region_policy(cell_lat: i16, cell_lon: i16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REGION_SEED, &cell_lat.to_le_bytes(), &cell_lon.to_le_bytes()],
+        &poloc::ID,
+    )
+}
+
+pub fn region_stats(cell_lat: i16, cell_lon: i16) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[REGION_STATS_SEED, &cell_lat.to_le_bytes(), &cell_lon.to_le_bytes()],
+        &poloc::ID,
+    )
+}
+
+pub fn device_lock(device_hash: &[u8; 32]) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[DEVICE_SEED, device_hash.as_ref()], &poloc::ID)
+}
+
+pub fn campaign(campaign_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CAMPAIGN_SEED, campaign_id.as_bytes()], &poloc::ID)
+}
+
+pub fn challenge(challenge_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CHALLENGE_SEED, challenge_id.as_bytes()], &poloc::ID)
+}
+
+pub fn payout(challenge_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[PAYOUT_SEED, challenge_id.as_bytes()], &poloc::ID)
+}
+
+pub fn claim_escrow(challenge_id: &str, beneficiary: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CLAIM_ESCROW_SEED, challenge_id.as_bytes(), beneficiary.as_ref()],
+        &poloc::ID,
+    )
+}
+
+pub fn contribution(challenge_id: &str, sponsor: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[CONTRIBUTION_SEED, challenge_id.as_bytes(), sponsor.as_ref()],
+        &poloc::ID,
+    )
+}
+
+pub fn collusion_flag(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[COLLUSION_SEED, wallet.as_ref()], &poloc::ID)
+}
+
+pub fn challenger_profile(wallet: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[CHALLENGER_SEED, wallet.as_ref()], &poloc::ID)
+}
+
+pub fn intent_escrow(witness: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[INTENT_ESCROW_SEED, witness.as_ref()], &poloc::ID)
+}
+
+pub fn stake(challenge_id: &str, challenger: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[STAKE_SEED, challenge_id.as_bytes(), challenger.as_ref()],
+        &poloc::ID,
+    )
+}
+
+pub fn standing_stake(witness: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[STANDING_STAKE_SEED, witness.as_ref()], &poloc::ID)
+}
+
+pub fn vault(challenge_id: &str) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[VAULT_SEED, challenge_id.as_bytes()], &poloc::ID)
+}
+
+pub fn vote(challenge_id: &str, challenger: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[VOTE_SEED, challenge_id.as_bytes(), challenger.as_ref()],
+        &poloc::ID,
+    )
+}
+
+pub fn tier_badge_mint() -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[TIER_BADGE_SEED], &poloc::ID)
+}
+
+pub fn flash_challenge(waldo: &Pubkey, flash_id: u64) -> (Pubkey, u8) {
+    Pubkey::find_program_address(&[FLASH_SEED, waldo.as_ref(), &flash_id.to_le_bytes()], &poloc::ID)
+}
+
+// Associated token account of the challenge PDA; holds a token challenge's pool.
+pub fn reward_vault(challenge_id: &str, mint: &Pubkey, token_program: &Pubkey) -> (Pubkey, u8) {
+    Pubkey::find_program_address(
+        &[challenge(challenge_id).0.as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    )
+}
@@ -0,0 +1,383 @@
+//! poloc-replay: deterministic replay of a challenge's history.
+//!
+//! Rebuilds a challenge's books from its transactions (instructions plus the
+//! events they logged) and diffs the result against the live account. Oracle
+//! finalizations are re-derived with `poloc_core`, so a disagreement between
+//! the logged R* and the submitted reports shows up as a transition mismatch
+//! rather than just a field diff.
+//!
+//! Transport is left to the caller through `ChainSource`, e.g. an RPC client
+//! walking `getSignaturesForAddress` and `getTransaction` for the challenge
+//! PDA, or fixtures captured from a disputed run.
+
+use std::collections::BTreeMap;
+
+use anchor_lang::prelude::Pubkey;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::{AccountDeserialize, AnchorDeserialize, Discriminator};
+use poloc::state::{Challenge, ChallengeStatus};
+use poloc_core::rstar::quantile_u32;
+
+use crate::events::{decode_logs, DecodeError, PolocEvent};
+use crate::pda;
+
+/// Oracle finalization settles on the median report.
+const MEDIAN_BPS: u16 = 5_000;
+
+/// One transaction touching the challenge, as fetched from the chain.
+#[derive(Clone, Debug)]
+pub struct ReplayTransaction {
+    pub signature: String,
+    pub slot: u64,
+    /// Failed transactions are skipped; they changed no state.
+    pub failed: bool,
+    /// Top-level and inner instructions with account keys resolved.
+    pub instructions: Vec<Instruction>,
+    pub logs: Vec<String>,
+}
+
+/// Where replay input comes from.
+pub trait ChainSource {
+    type Error;
+
+    /// Every transaction that touched `challenge`, oldest first.
+    fn challenge_transactions(&self, challenge: &Pubkey) -> Result<Vec<ReplayTransaction>, Self::Error>;
+
+    /// Raw account data, or `None` once the account is closed.
+    fn account_data(&self, address: &Pubkey) -> Result<Option<Vec<u8>>, Self::Error>;
+}
+
+/// Challenge fields as reconstructed from history.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct ReplayedChallenge {
+    pub initialized: bool,
+    /// Refunded or archived; the account should no longer exist.
+    pub closed: bool,
+    /// Set by the last `ChallengeFinalized`; `None` while still open.
+    pub status: Option<ChallengeStatus>,
+    pub r_star: u32,
+    pub reward_pool: u64,
+    pub total_staked: u64,
+    pub participant_count: u32,
+    pub vote_count: u32,
+    pub valid_vote_count: u32,
+    pub location_revealed: bool,
+    pub frozen: bool,
+    /// Per-winner amount of a posted payout root.
+    pub payout_amount: Option<u64>,
+    pub mismatches: Vec<TransitionMismatch>,
+}
+
+/// A logged outcome that the local recomputation disagrees with.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TransitionMismatch {
+    /// `finalize_with_oracles` logged an R* other than the median of the
+    /// reports it was given.
+    OracleMedian { signature: String, logged: u32, recomputed: u32 },
+    /// An event arrived for a challenge that was never initialized.
+    Uninitialized { signature: String },
+}
+
+/// A replayed field that differs from the live account.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FieldDiff {
+    pub field: &'static str,
+    pub replayed: String,
+    pub on_chain: String,
+}
+
+#[derive(Clone, Debug)]
+pub struct ReplayReport {
+    pub replayed: ReplayedChallenge,
+    pub diffs: Vec<FieldDiff>,
+}
+
+impl ReplayReport {
+    /// True when history and the live account agree.
+    pub fn is_consistent(&self) -> bool {
+        self.diffs.is_empty() && self.replayed.mismatches.is_empty()
+    }
+}
+
+#[derive(Debug)]
+pub enum ReplayError<E> {
+    Source(E),
+    Event { signature: String, error: DecodeError },
+    InvalidAccount,
+}
+
+/// Fetches a challenge's history and live account, replays and diffs them.
+pub fn replay_challenge<S: ChainSource>(
+    source: &S,
+    challenge_id: &str,
+) -> Result<ReplayReport, ReplayError<S::Error>> {
+    let address = pda::challenge(challenge_id).0;
+    let transactions = source.challenge_transactions(&address).map_err(ReplayError::Source)?;
+    let replayed = replay(&address, challenge_id, &transactions)
+        .map_err(|(signature, error)| ReplayError::Event { signature, error })?;
+
+    let on_chain = match source.account_data(&address).map_err(ReplayError::Source)? {
+        Some(data) => Some(
+            Challenge::try_deserialize(&mut data.as_slice()).map_err(|_| ReplayError::InvalidAccount)?,
+        ),
+        None => None,
+    };
+    let diffs = diff(&replayed, on_chain.as_ref());
+    Ok(ReplayReport { replayed, diffs })
+}
+
+/// Replays `transactions` in order. Fails with the offending signature if a
+/// poloc event cannot be decoded.
+pub fn replay(
+    challenge: &Pubkey,
+    challenge_id: &str,
+    transactions: &[ReplayTransaction],
+) -> Result<ReplayedChallenge, (String, DecodeError)> {
+    let mut state = ReplayedChallenge::default();
+    // Oracle report PDA -> submitted R*
+    let mut reports: BTreeMap<Pubkey, u32> = BTreeMap::new();
+
+    for tx in transactions.iter().filter(|tx| !tx.failed) {
+        let mut expected_median = None;
+
+        for ix in tx.instructions.iter().filter(|ix| ix.program_id == poloc::ID) {
+            if ix.data.len() < 8 {
+                continue;
+            }
+            let (discriminator, body) = ix.data.split_at(8);
+            if discriminator == poloc::instruction::SubmitOracleResult::DISCRIMINATOR {
+                let Ok(args) = poloc::instruction::SubmitOracleResult::try_from_slice(body) else {
+                    continue;
+                };
+                if args.challenge_id != challenge_id {
+                    continue;
+                }
+                for signer in ix.accounts.iter().filter(|meta| meta.is_signer) {
+                    let report = pda::oracle_report(challenge_id, &signer.pubkey).0;
+                    if ix.accounts.iter().any(|meta| meta.pubkey == report) {
+                        reports.insert(report, args.r_star);
+                    }
+                }
+            } else if discriminator == poloc::instruction::FinalizeWithOracles::DISCRIMINATOR {
+                let mut values: Vec<u32> = ix.accounts
+                    .iter()
+                    .filter_map(|meta| reports.get(&meta.pubkey).copied())
+                    .collect();
+                expected_median = quantile_u32(&mut values, MEDIAN_BPS);
+            }
+        }
+
+        for decoded in decode_logs(&tx.logs) {
+            let event = match decoded {
+                Ok(decoded) => decoded.event,
+                // Other programs' `Program data:` lines
+                Err(DecodeError::UnknownDiscriminator) => continue,
+                Err(error) => return Err((tx.signature.clone(), error)),
+            };
+            if event_challenge(&event) != Some(*challenge) {
+                continue;
+            }
+            if !state.initialized && !is_initialization(&event) {
+                state.mismatches.push(TransitionMismatch::Uninitialized {
+                    signature: tx.signature.clone(),
+                });
+            }
+            apply(&mut state, event, expected_median, &tx.signature);
+        }
+    }
+    Ok(state)
+}
+
+fn apply(
+    state: &mut ReplayedChallenge,
+    event: PolocEvent,
+    expected_median: Option<u32>,
+    signature: &str,
+) {
+    match event {
+        PolocEvent::ChallengeInitialized(e) => {
+            state.initialized = true;
+            state.reward_pool = e.reward_pool;
+        }
+        PolocEvent::PrivateChallengeInitialized(e) => {
+            state.initialized = true;
+            state.reward_pool = e.reward_pool;
+        }
+        PolocEvent::Staked(e) => {
+            state.participant_count += 1;
+            state.reward_pool = state.reward_pool.saturating_add(e.amount);
+            state.total_staked = state.total_staked.saturating_add(e.amount);
+        }
+        PolocEvent::VoteSubmitted(e) => {
+            state.vote_count += 1;
+            if e.is_valid {
+                state.valid_vote_count += 1;
+            }
+        }
+        PolocEvent::TrancheFunded(e) => {
+            state.reward_pool = state.reward_pool.saturating_add(e.amount);
+        }
+        PolocEvent::ContributionReceived(e) => state.reward_pool = e.reward_pool,
+        PolocEvent::ChallengeExtended(e) => state.reward_pool = e.reward_pool,
+        PolocEvent::SponsorRefunded(e) => {
+            state.reward_pool = state.reward_pool.saturating_sub(e.amount);
+        }
+        PolocEvent::StakeReturned(e) => {
+            state.reward_pool = state.reward_pool.saturating_sub(e.amount);
+        }
+        PolocEvent::Unstaked(e) => {
+            state.reward_pool = state.reward_pool.saturating_sub(e.amount);
+        }
+        // Standing stakes count as stake but stay out of the pool.
+        PolocEvent::StandingStakeLocked(e) => {
+            state.participant_count += 1;
+            state.total_staked = state.total_staked.saturating_add(e.amount);
+        }
+        PolocEvent::StandingStakeReleased(e) => {
+            state.reward_pool = state.reward_pool.saturating_add(e.forfeited);
+        }
+        // Anything past the pool came out of the bond, which isn't replayed.
+        PolocEvent::EmergencyWithdrawalExecuted(e) => {
+            state.reward_pool = state.reward_pool.saturating_sub(e.pool_debit);
+        }
+        PolocEvent::LocationRevealed(_) => state.location_revealed = true,
+        PolocEvent::ChallengeFreezeChanged(e) => state.frozen = e.frozen,
+        // Only the selected candidate's supporters stay valid.
+        PolocEvent::CandidateSelected(e) => state.valid_vote_count = e.valid_votes,
+        PolocEvent::ChallengeFinalized(e) => {
+            if let Some(recomputed) = expected_median {
+                if recomputed != e.r_star {
+                    state.mismatches.push(TransitionMismatch::OracleMedian {
+                        signature: signature.to_string(),
+                        logged: e.r_star,
+                        recomputed,
+                    });
+                }
+            }
+            state.status = Some(e.status);
+            state.r_star = e.r_star;
+        }
+        PolocEvent::PayoutRootPosted(e) => state.payout_amount = Some(e.amount),
+        PolocEvent::RewardClaimed(_) => {
+            // The logged amount is what the winner received, net of relayer
+            // fees and plus any bond top-up. The pool pays the share,
+            // recomputed the way `claimable_reward` does.
+            let share = match state.payout_amount {
+                Some(amount) => amount,
+                None if state.valid_vote_count > 0 => state.reward_pool / state.valid_vote_count as u64,
+                None => 0,
+            };
+            state.reward_pool = state.reward_pool.saturating_sub(share);
+        }
+        PolocEvent::UnclaimedRewardsSwept(_) => {
+            state.reward_pool = 0;
+            state.closed = true;
+        }
+        PolocEvent::ChallengeRefunded(_)
+        | PolocEvent::ChallengeCancelled(_)
+        | PolocEvent::ChallengeArchived(_) => state.closed = true,
+        _ => {}
+    }
+}
+
+fn is_initialization(event: &PolocEvent) -> bool {
+    matches!(
+        event,
+        PolocEvent::ChallengeInitialized(_) | PolocEvent::PrivateChallengeInitialized(_)
+    )
+}
+
+fn event_challenge(event: &PolocEvent) -> Option<Pubkey> {
+    Some(match event {
+        PolocEvent::ChallengeInitialized(e) => e.challenge,
+        PolocEvent::PrivateChallengeInitialized(e) => e.challenge,
+        PolocEvent::Staked(e) => e.challenge,
+        PolocEvent::VoteSubmitted(e) => e.challenge,
+        PolocEvent::ChallengeFinalized(e) => e.challenge,
+        PolocEvent::RewardClaimed(e) => e.challenge,
+        PolocEvent::ChallengeRefunded(e) => e.challenge,
+        PolocEvent::ChallengeCancelled(e) => e.challenge,
+        PolocEvent::StakeSlashed(e) => e.challenge,
+        PolocEvent::ChallengeArchived(e) => e.challenge,
+        PolocEvent::TrancheFunded(e) => e.challenge,
+        PolocEvent::PayoutRootPosted(e) => e.challenge,
+        PolocEvent::OraclePenalized(e) => e.challenge,
+        PolocEvent::ContributionReceived(e) => e.challenge,
+        PolocEvent::SponsorRefunded(e) => e.challenge,
+        PolocEvent::ClaimEscrowSettled(e) => e.challenge,
+        PolocEvent::LocationRevealed(e) => e.challenge,
+        PolocEvent::ChallengeFreezeChanged(e) => e.challenge,
+        PolocEvent::RewardSwapped(e) => e.challenge,
+        PolocEvent::AccountingDiscrepancy(e) => e.challenge,
+        PolocEvent::EncryptedVoteSubmitted(e) => e.challenge,
+        PolocEvent::DecryptionShareSubmitted(e) => e.challenge,
+        PolocEvent::CandidateSelected(e) => e.challenge,
+        PolocEvent::CallbackRegistered(e) => e.challenge,
+        PolocEvent::CampaignChallengeReported(e) => e.challenge,
+        PolocEvent::EvidenceSubmitted(e) => e.challenge,
+        PolocEvent::ReverificationDue(e) => e.challenge,
+        PolocEvent::StakeReturned(e) => e.challenge,
+        PolocEvent::StandingStakeLocked(e) => e.challenge,
+        PolocEvent::StandingStakeReleased(e) => e.challenge,
+        PolocEvent::EmergencyWithdrawalProposed(e) => e.challenge,
+        PolocEvent::EmergencyWithdrawalCancelled(e) => e.challenge,
+        PolocEvent::EmergencyWithdrawalExecuted(e) => e.challenge,
+        PolocEvent::Unstaked(e) => e.challenge,
+        PolocEvent::StakeReceiptMinted(e) => e.challenge,
+        PolocEvent::StakeReceiptRedeemed(e) => e.challenge,
+        PolocEvent::RStarComputed(e) => e.challenge,
+        PolocEvent::StreakBonusPaid(e) => e.challenge,
+        PolocEvent::ChallengeExtended(e) => e.challenge,
+        PolocEvent::TimingWindowMissed(e) => e.challenge,
+        PolocEvent::UnclaimedRewardsSwept(e) => e.challenge,
+        PolocEvent::WaldoBondClaimed(e) => e.challenge,
+        PolocEvent::ChallengeReported(e) => e.challenge,
+        PolocEvent::VoteChained(e) => e.challenge,
+        PolocEvent::KeeperPenalized(_)
+        | PolocEvent::CampaignCreated(_)
+        | PolocEvent::CampaignReconciled(_)
+        | PolocEvent::TierBadgeClaimed(_)
+        | PolocEvent::FlashChallengeFinalized(_) => return None,
+    })
+}
+
+/// Compares replayed fields with the live account (`None` if closed).
+pub fn diff(replayed: &ReplayedChallenge, on_chain: Option<&Challenge>) -> Vec<FieldDiff> {
+    let mut diffs = Vec::new();
+    let mut check = |field: &'static str, replayed: String, on_chain: String| {
+        if replayed != on_chain {
+            diffs.push(FieldDiff { field, replayed, on_chain });
+        }
+    };
+
+    let Some(account) = on_chain else {
+        check("closed", replayed.closed.to_string(), true.to_string());
+        return diffs;
+    };
+    check("closed", replayed.closed.to_string(), false.to_string());
+    // Open challenges move between Scheduled and Active without an event.
+    if let Some(status) = &replayed.status {
+        check("status", format!("{status:?}"), format!("{:?}", account.status));
+        check("r_star", replayed.r_star.to_string(), account.r_star.to_string());
+    }
+    check("reward_pool", replayed.reward_pool.to_string(), account.reward_pool.to_string());
+    check("total_staked", replayed.total_staked.to_string(), account.total_staked.to_string());
+    check(
+        "participant_count",
+        replayed.participant_count.to_string(),
+        account.participant_count.to_string(),
+    );
+    check("vote_count", replayed.vote_count.to_string(), account.vote_count.to_string());
+    check(
+        "valid_vote_count",
+        replayed.valid_vote_count.to_string(),
+        account.valid_vote_count.to_string(),
+    );
+    check(
+        "location_revealed",
+        replayed.location_revealed.to_string(),
+        account.location_revealed.to_string(),
+    );
+    check("frozen", replayed.frozen.to_string(), account.frozen.to_string());
+    diffs
+}
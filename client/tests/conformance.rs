@@ -0,0 +1,37 @@
+use anchor_lang::prelude::Pubkey;
+use poloc_client::pda;
+use poloc_core::conformance::{CHALLENGER, CHALLENGE_ID, PDA_VECTORS, PROGRAM_ID};
+
+#[test]
+fn program_id_matches() {
+    assert_eq!(poloc::ID.to_bytes(), PROGRAM_ID);
+}
+
+#[test]
+fn pda_vectors_match_derivation() {
+    let program_id = Pubkey::new_from_array(PROGRAM_ID);
+    for vector in PDA_VECTORS {
+        let (address, bump) = Pubkey::find_program_address(vector.seeds, &program_id);
+        assert_eq!(address.to_bytes(), vector.address, "{}", vector.name);
+        assert_eq!(bump, vector.bump, "{}", vector.name);
+    }
+}
+
+// The vectors spell their seeds out; make sure they are the ones the client
+// (and, through `poloc::constants`, the program) actually uses.
+#[test]
+fn pda_vectors_match_client_helpers() {
+    let challenger = Pubkey::new_from_array(CHALLENGER);
+    let derived = [
+        ("config", pda::config()),
+        ("challenge", pda::challenge(CHALLENGE_ID)),
+        ("vault", pda::vault(CHALLENGE_ID)),
+        ("stake", pda::stake(CHALLENGE_ID, &challenger)),
+        ("vote", pda::vote(CHALLENGE_ID, &challenger)),
+        ("claim_escrow", pda::claim_escrow(CHALLENGE_ID, &challenger)),
+    ];
+    for (name, (address, bump)) in derived {
+        let vector = PDA_VECTORS.iter().find(|v| v.name == name).unwrap();
+        assert_eq!((address.to_bytes(), bump), (vector.address, vector.bump), "{}", name);
+    }
+}
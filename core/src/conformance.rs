@@ -0,0 +1,177 @@
+//! Canonical inputs and expected outputs for cross-implementation checks.
+//!
+//! The JS SDK and any other client port should reproduce every vector here
+//! byte for byte. The Rust side is asserted by `core/tests/conformance.rs`
+//! (reward splits, R*) and `client/tests/conformance.rs` (PDAs, which need the
+//! curve check this crate doesn't carry). When a vector changes, the on-chain
+//! behavior it pins has changed too.
+
+/// The program id the PDA vectors are derived under.
+pub const PROGRAM_ID: [u8; 32] = [
+    3, 32, 153, 225, 24, 77, 185, 180, 74, 169, 240, 56, 58, 89, 202, 90, 123, 40, 163, 60, 61, 121,
+    234, 38, 49, 118, 92, 155, 54, 13, 167, 79,
+];
+
+/// Challenge id the PDA vectors use.
+pub const CHALLENGE_ID: &str = "conformance-1";
+
+/// Wallet the per-challenger PDA vectors use.
+pub const CHALLENGER: [u8; 32] = [7; 32];
+
+/// `find_program_address(seeds, PROGRAM_ID)`.
+pub struct PdaVector {
+    pub name: &'static str,
+    pub seeds: &'static [&'static [u8]],
+    pub address: [u8; 32],
+    pub bump: u8,
+}
+
+pub const PDA_VECTORS: &[PdaVector] = &[
+    PdaVector {
+        name: "config",
+        seeds: &[b"config"],
+        address: [
+            163, 70, 201, 105, 162, 101, 200, 193, 158, 14, 55, 100, 150, 175, 75, 169, 40, 160, 16, 68,
+            47, 141, 156, 106, 64, 78, 246, 234, 126, 145, 33, 60,
+        ],
+        bump: 253,
+    },
+    PdaVector {
+        name: "challenge",
+        seeds: &[b"challenge", CHALLENGE_ID.as_bytes()],
+        address: [
+            172, 152, 96, 120, 136, 81, 191, 39, 113, 112, 110, 16, 19, 142, 52, 248, 29, 223, 199, 1,
+            196, 95, 95, 118, 160, 49, 136, 49, 6, 173, 184, 49,
+        ],
+        bump: 254,
+    },
+    PdaVector {
+        name: "vault",
+        seeds: &[b"vault", CHALLENGE_ID.as_bytes()],
+        address: [
+            6, 183, 61, 137, 196, 192, 205, 194, 215, 22, 139, 40, 45, 119, 230, 185, 159, 212, 8, 153,
+            75, 196, 104, 44, 1, 91, 82, 227, 70, 4, 161, 101,
+        ],
+        bump: 255,
+    },
+    PdaVector {
+        name: "stake",
+        seeds: &[b"stake", CHALLENGE_ID.as_bytes(), &CHALLENGER],
+        address: [
+            31, 11, 96, 195, 141, 128, 151, 0, 4, 0, 198, 229, 217, 172, 91, 110, 130, 189, 2, 18, 127,
+            99, 18, 184, 159, 247, 114, 195, 56, 233, 180, 117,
+        ],
+        bump: 253,
+    },
+    PdaVector {
+        name: "vote",
+        seeds: &[b"vote", CHALLENGE_ID.as_bytes(), &CHALLENGER],
+        address: [
+            201, 74, 138, 55, 250, 252, 101, 153, 63, 91, 130, 131, 3, 211, 43, 123, 102, 0, 9, 116, 154,
+            10, 251, 51, 72, 34, 70, 224, 64, 23, 144, 189,
+        ],
+        bump: 255,
+    },
+    PdaVector {
+        name: "claim_escrow",
+        seeds: &[b"claim_escrow", CHALLENGE_ID.as_bytes(), &CHALLENGER],
+        address: [
+            116, 102, 89, 113, 186, 25, 59, 110, 0, 138, 161, 35, 223, 145, 157, 228, 245, 225, 60, 51,
+            32, 28, 5, 238, 63, 29, 172, 207, 159, 146, 214, 115,
+        ],
+        bump: 255,
+    },
+];
+
+/// Successive `rewards::claim_share` results as winners claim one after
+/// another, and what is left in the pool afterwards.
+pub struct RewardSplitVector {
+    pub reward_pool: u64,
+    pub valid_votes: u32,
+    pub claims: &'static [u64],
+    pub remaining: u64,
+}
+
+pub const REWARD_SPLIT_VECTORS: &[RewardSplitVector] = &[
+    RewardSplitVector {
+        reward_pool: 1_000_000_000,
+        valid_votes: 3,
+        claims: &[333_333_333, 222_222_222, 148_148_148],
+        remaining: 296_296_297,
+    },
+    RewardSplitVector {
+        reward_pool: 1_000_000_007,
+        valid_votes: 4,
+        claims: &[250_000_001, 187_500_001, 140_625_001, 105_468_751],
+        remaining: 316_406_253,
+    },
+    // A pool smaller than the vote count pays nothing.
+    RewardSplitVector {
+        reward_pool: 5,
+        valid_votes: 7,
+        claims: &[0, 0],
+        remaining: 5,
+    },
+];
+
+/// `rstar::estimate_r_star_fixed` over reports given as
+/// `(lat, lon, estimated distance in whole meters)`.
+pub struct RStarVector {
+    pub claimed: (i32, i32),
+    pub reports: &'static [(i32, i32, i32)],
+    pub angle_buckets: u32,
+    pub beta_bps: u16,
+    pub r_star: u32,
+}
+
+const MANHATTAN: (i32, i32) = (40_712_800, -74_006_000);
+
+const THREE_WITNESSES: &[(i32, i32, i32)] = &[
+    (40_730_600, -73_935_200, 6_500),
+    (40_650_100, -74_050_000, 7_200),
+    (40_760_000, -74_100_000, 9_000),
+];
+
+const FIVE_WITNESSES: &[(i32, i32, i32)] = &[
+    (40_730_600, -73_935_200, 6_500),
+    (40_731_000, -73_936_000, 5_900),
+    (40_650_100, -74_050_000, 7_200),
+    (40_700_000, -73_990_000, 1_500),
+    (40_690_000, -74_020_000, 3_000),
+];
+
+pub const R_STAR_VECTORS: &[RStarVector] = &[
+    RStarVector {
+        claimed: MANHATTAN,
+        reports: THREE_WITNESSES,
+        angle_buckets: 36,
+        beta_bps: 5_000,
+        r_star: 38_067_976,
+    },
+    RStarVector {
+        claimed: MANHATTAN,
+        reports: FIVE_WITNESSES,
+        angle_buckets: 36,
+        beta_bps: 5_000,
+        r_star: 38_067_976,
+    },
+    RStarVector {
+        claimed: MANHATTAN,
+        reports: FIVE_WITNESSES,
+        angle_buckets: 8,
+        beta_bps: 2_500,
+        r_star: 7_609_222,
+    },
+];
+
+/// Median of oracle-reported R* values, as `finalize_with_oracles` takes it:
+/// the upper median for an even count.
+pub struct OracleMedianVector {
+    pub reports: &'static [u32],
+    pub median: u32,
+}
+
+pub const ORACLE_MEDIAN_VECTORS: &[OracleMedianVector] = &[
+    OracleMedianVector { reports: &[1_200, 900, 1_500], median: 1_200 },
+    OracleMedianVector { reports: &[700, 650, 820, 2_000], median: 820 },
+];
@@ -0,0 +1,242 @@
+//! Q32.32 fixed-point arithmetic for distance, trilateration and statistics.
+//!
+//! The on-chain program uses this instead of `f64` so results never depend on
+//! soft-float codegen. Products are widened to `i128` before rescaling and
+//! square roots operate on the full-width product, which keeps haversine
+//! distances within a few millimetres of a double-precision reference.
+
+use core::ops::{Add, Div, Mul, Neg, Sub};
+
+pub const FRAC_BITS: u32 = 32;
+const ONE_RAW: i64 = 1 << FRAC_BITS;
+const HALF_ULP: i128 = 1 << (FRAC_BITS - 1);
+
+// π in Q0.64, used for micro-degree conversion without intermediate rounding.
+const PI_Q64: i128 = 57_952_155_664_616_982_739;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Fixed(i64);
+
+impl Fixed {
+    pub const ZERO: Fixed = Fixed(0);
+    pub const ONE: Fixed = Fixed(ONE_RAW);
+    pub const PI: Fixed = Fixed(13_493_037_705);
+    pub const HALF_PI: Fixed = Fixed(6_746_518_852);
+    pub const TWO_PI: Fixed = Fixed(26_986_075_409);
+
+    pub const fn from_raw(raw: i64) -> Self {
+        Fixed(raw)
+    }
+
+    pub const fn raw(self) -> i64 {
+        self.0
+    }
+
+    pub const fn from_int(value: i32) -> Self {
+        Fixed((value as i64) << FRAC_BITS)
+    }
+
+    /// Converts micro-degrees (the `claimed_lat/lon` encoding) to radians.
+    pub fn from_micro_degrees(value: i32) -> Self {
+        let q64 = value as i128 * PI_Q64 / 180_000_000;
+        Fixed(round_shift(q64, FRAC_BITS) as i64)
+    }
+
+    /// Rounds to the nearest integer (ties away from zero).
+    pub fn round(self) -> i64 {
+        if self.0 >= 0 {
+            (self.0 + (ONE_RAW >> 1)) >> FRAC_BITS
+        } else {
+            -((-self.0 + (ONE_RAW >> 1)) >> FRAC_BITS)
+        }
+    }
+
+    pub fn abs(self) -> Self {
+        Fixed(self.0.abs())
+    }
+
+    pub fn checked_add(self, rhs: Fixed) -> Option<Fixed> {
+        self.0.checked_add(rhs.0).map(Fixed)
+    }
+
+    pub fn checked_sub(self, rhs: Fixed) -> Option<Fixed> {
+        self.0.checked_sub(rhs.0).map(Fixed)
+    }
+
+    pub fn checked_mul(self, rhs: Fixed) -> Option<Fixed> {
+        let wide = round_shift(self.0 as i128 * rhs.0 as i128, FRAC_BITS);
+        i64::try_from(wide).ok().map(Fixed)
+    }
+
+    pub fn checked_div(self, rhs: Fixed) -> Option<Fixed> {
+        if rhs.0 == 0 {
+            return None;
+        }
+        let wide = ((self.0 as i128) << FRAC_BITS) / rhs.0 as i128;
+        i64::try_from(wide).ok().map(Fixed)
+    }
+
+    /// Square root; negative inputs return zero.
+    pub fn sqrt(self) -> Self {
+        if self.0 <= 0 {
+            return Fixed::ZERO;
+        }
+        Fixed(isqrt((self.0 as u128) << FRAC_BITS) as i64)
+    }
+
+    /// `sqrt(a² + b²)` computed on the full 64-fractional-bit sum, so tiny
+    /// components are not flushed to zero by squaring in Q32.32.
+    pub fn hypot(a: Fixed, b: Fixed) -> Self {
+        let a = a.0.unsigned_abs() as u128;
+        let b = b.0.unsigned_abs() as u128;
+        Fixed(isqrt(a * a + b * b) as i64)
+    }
+
+    pub fn sin(self) -> Self {
+        // Reduce to [-π, π), then fold into [-π/2, π/2].
+        let mut x = Fixed(self.0.rem_euclid(Self::TWO_PI.0));
+        if x > Self::PI {
+            x = x - Self::TWO_PI;
+        }
+        if x > Self::HALF_PI {
+            x = Self::PI - x;
+        } else if x < -Self::HALF_PI {
+            x = -Self::PI - x;
+        }
+
+        // Taylor series in Horner form through x¹⁵; truncation error on
+        // [-π/2, π/2] is ~2e-12, well below one ulp.
+        let x2 = x * x;
+        let mut acc = Fixed::ONE;
+        for denom in [210, 156, 110, 72, 42, 20, 6] {
+            acc = Fixed::ONE - x2 * acc / Fixed::from_int(denom);
+        }
+        x * acc
+    }
+
+    pub fn cos(self) -> Self {
+        (self + Self::HALF_PI).sin()
+    }
+
+    pub fn atan(self) -> Self {
+        if self.0 < 0 {
+            return -(-self).atan();
+        }
+        if self > Fixed::ONE {
+            return Self::HALF_PI - (Fixed::ONE / self).atan();
+        }
+
+        // atan(x) = atan(c) + atan((x - c) / (1 + x·c)) with c the nearest
+        // multiple of 1/8, leaving a residual of at most 1/16 for the series.
+        let k = ((self.0 + (ONE_RAW >> 4)) >> (FRAC_BITS - 3)) as usize;
+        let c = Fixed(k as i64 * (ONE_RAW >> 3));
+        let residual = (self - c) / (Fixed::ONE + self * c);
+        Fixed(ATAN_EIGHTHS[k]) + atan_series(residual)
+    }
+
+    pub fn atan2(y: Fixed, x: Fixed) -> Self {
+        if x.0 == 0 && y.0 == 0 {
+            return Fixed::ZERO;
+        }
+        // Always divide the smaller magnitude by the larger so the quotient
+        // stays within [-1, 1] and cannot overflow.
+        if y.abs() > x.abs() {
+            let quarter = if y.0 > 0 { Self::HALF_PI } else { -Self::HALF_PI };
+            return quarter - (x / y).atan();
+        }
+        let base = (y / x).atan();
+        if x.0 > 0 {
+            base
+        } else if y.0 >= 0 {
+            base + Self::PI
+        } else {
+            base - Self::PI
+        }
+    }
+
+    /// Lossy conversion for tests and off-chain display.
+    pub fn to_f64(self) -> f64 {
+        self.0 as f64 / ONE_RAW as f64
+    }
+
+    /// Lossy conversion for tests and off-chain callers.
+    pub fn from_f64(value: f64) -> Self {
+        Fixed(libm::round(value * ONE_RAW as f64) as i64)
+    }
+}
+
+// atan(k/8) for k = 0..=8, Q32.32.
+const ATAN_EIGHTHS: [i64; 9] = [
+    0, 534_100_635, 1_052_175_346, 1_540_908_296, 1_991_351_318,
+    2_399_165_791, 2_763_816_217, 3_087_351_340, 3_373_259_426,
+];
+
+/// atan(x) = x − x³/3 + x⁵/5 − … through x¹¹, for |x| ≤ 1/16.
+fn atan_series(x: Fixed) -> Fixed {
+    let x2 = x * x;
+    let mut acc = Fixed::ZERO;
+    for k in (0..6).rev() {
+        let term = Fixed::ONE / Fixed::from_int(2 * k + 1);
+        acc = term - x2 * acc;
+    }
+    x * acc
+}
+
+fn round_shift(value: i128, bits: u32) -> i128 {
+    let half = 1i128 << (bits - 1);
+    if value >= 0 {
+        (value + half) >> bits
+    } else {
+        -((-value + half) >> bits)
+    }
+}
+
+/// Floor integer square root.
+pub fn isqrt(n: u128) -> u128 {
+    if n < 2 {
+        return n;
+    }
+    let mut x = 1u128 << ((128 - n.leading_zeros()).div_ceil(2));
+    loop {
+        let y = (x + n / x) >> 1;
+        if y >= x {
+            return x;
+        }
+        x = y;
+    }
+}
+
+impl Add for Fixed {
+    type Output = Fixed;
+    fn add(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0 + rhs.0)
+    }
+}
+
+impl Sub for Fixed {
+    type Output = Fixed;
+    fn sub(self, rhs: Fixed) -> Fixed {
+        Fixed(self.0 - rhs.0)
+    }
+}
+
+impl Mul for Fixed {
+    type Output = Fixed;
+    fn mul(self, rhs: Fixed) -> Fixed {
+        Fixed(((self.0 as i128 * rhs.0 as i128 + HALF_ULP) >> FRAC_BITS) as i64)
+    }
+}
+
+impl Div for Fixed {
+    type Output = Fixed;
+    fn div(self, rhs: Fixed) -> Fixed {
+        Fixed((((self.0 as i128) << FRAC_BITS) / rhs.0 as i128) as i64)
+    }
+}
+
+impl Neg for Fixed {
+    type Output = Fixed;
+    fn neg(self) -> Fixed {
+        Fixed(-self.0)
+    }
+}
@@ -0,0 +1,141 @@
+use core::f64::consts::PI;
+
+use crate::fixed::{isqrt, Fixed};
+
+pub const EARTH_RADIUS_M: f64 = 6_371_000.0;
+const EARTH_RADIUS_M_INT: i32 = 6_371_000;
+pub const LIGHT_SPEED_M_PER_S: f64 = 299_792_458.0;
+
+/// A point in micro-degrees, the same encoding `Challenge.claimed_lat/lon` use.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Coordinate {
+    pub lat: i32,
+    pub lon: i32,
+}
+
+impl Coordinate {
+    pub fn new(lat: i32, lon: i32) -> Self {
+        Self { lat, lon }
+    }
+
+    pub fn lat_rad(&self) -> f64 {
+        micro_degrees_to_radians(self.lat)
+    }
+
+    pub fn lon_rad(&self) -> f64 {
+        micro_degrees_to_radians(self.lon)
+    }
+
+    pub fn lat_fixed(&self) -> Fixed {
+        Fixed::from_micro_degrees(self.lat)
+    }
+
+    pub fn lon_fixed(&self) -> Fixed {
+        Fixed::from_micro_degrees(self.lon)
+    }
+}
+
+pub fn micro_degrees_to_radians(value: i32) -> f64 {
+    (value as f64 / 1_000_000.0) * PI / 180.0
+}
+
+/// Great-circle (haversine) distance in meters.
+pub fn distance_m(a: Coordinate, b: Coordinate) -> f64 {
+    let (lat1, lon1) = (a.lat_rad(), a.lon_rad());
+    let (lat2, lon2) = (b.lat_rad(), b.lon_rad());
+
+    let delta_lat = lat2 - lat1;
+    let delta_lon = lon2 - lon1;
+
+    let sin_lat = libm::sin(delta_lat / 2.0);
+    let sin_lon = libm::sin(delta_lon / 2.0);
+    let h = sin_lat * sin_lat + libm::cos(lat1) * libm::cos(lat2) * sin_lon * sin_lon;
+
+    let c = 2.0 * libm::atan2(libm::sqrt(h), libm::sqrt(1.0 - h));
+    EARTH_RADIUS_M * c
+}
+
+/// Initial bearing from `a` to `b`, normalized to [0, 2π).
+pub fn bearing_rad(a: Coordinate, b: Coordinate) -> f64 {
+    let lat1 = a.lat_rad();
+    let lat2 = b.lat_rad();
+    let delta_lon = b.lon_rad() - a.lon_rad();
+
+    let y = libm::sin(delta_lon) * libm::cos(lat2);
+    let x = libm::cos(lat1) * libm::sin(lat2)
+        - libm::sin(lat1) * libm::cos(lat2) * libm::cos(delta_lon);
+
+    let bearing = libm::atan2(y, x);
+    if bearing < 0.0 {
+        bearing + 2.0 * PI
+    } else {
+        bearing
+    }
+}
+
+/// Equation (6): Ri = d̂ᵢ² − dᵢ² sin²(αᵢ) − dᵢ cos(αᵢ)
+pub fn ri(d_hat: f64, d: f64, alpha: f64) -> f64 {
+    let sin_alpha = libm::sin(alpha);
+    let cos_alpha = libm::cos(alpha);
+    d_hat * d_hat - d * d * sin_alpha * sin_alpha - d * cos_alpha
+}
+
+/// Speed-of-light upper bound on one-way distance for a round trip of
+/// `rtt_us` microseconds. Useful as a conservative delay→distance mapping when
+/// no calibrated envelope is available.
+pub fn max_distance_for_rtt_m(rtt_us: u32) -> f64 {
+    LIGHT_SPEED_M_PER_S * (rtt_us as f64 / 1_000_000.0) / 2.0
+}
+
+// --- Fixed-point equivalents used on-chain ---
+
+/// Haversine distance in meters, Q32.32.
+///
+/// Computes `sqrt(h)` directly as a hypotenuse instead of squaring into `h`
+/// first; in Q32.32 the latter would quantize sub-kilometre distances.
+pub fn distance_fixed(a: Coordinate, b: Coordinate) -> Fixed {
+    let two = Fixed::from_int(2);
+    let (lat1, lon1) = (a.lat_fixed(), a.lon_fixed());
+    let (lat2, lon2) = (b.lat_fixed(), b.lon_fixed());
+
+    let half_dlat = ((lat2 - lat1) / two).sin();
+    let half_dlon = ((lon2 - lon1) / two).sin();
+
+    let cos_product = lat1.cos().raw().max(0) as u128 * lat2.cos().raw().max(0) as u128;
+    let sqrt_cos = Fixed::from_raw(isqrt(cos_product) as i64);
+
+    let s = Fixed::hypot(half_dlat, sqrt_cos * half_dlon).min(Fixed::ONE);
+    let one_q64 = 1u128 << 64;
+    let s_sq = (s.raw() as u128) * (s.raw() as u128);
+    let t = Fixed::from_raw(isqrt(one_q64.saturating_sub(s_sq)) as i64);
+
+    let c = two * Fixed::atan2(s, t);
+    Fixed::from_int(EARTH_RADIUS_M_INT) * c
+}
+
+/// Initial bearing in radians, normalized to [0, 2π), Q32.32.
+pub fn bearing_fixed(a: Coordinate, b: Coordinate) -> Fixed {
+    let lat1 = a.lat_fixed();
+    let lat2 = b.lat_fixed();
+    let delta_lon = b.lon_fixed() - a.lon_fixed();
+
+    let y = delta_lon.sin() * lat2.cos();
+    let x = lat1.cos() * lat2.sin() - lat1.sin() * lat2.cos() * delta_lon.cos();
+
+    let bearing = Fixed::atan2(y, x);
+    if bearing < Fixed::ZERO {
+        bearing + Fixed::TWO_PI
+    } else {
+        bearing
+    }
+}
+
+/// Equation (6) in fixed point. Ri is quadratic in distance, so it is returned
+/// as an `i128` with 64 fractional bits rather than narrowed back to Q32.32.
+pub fn ri_q64(d_hat: Fixed, d: Fixed, alpha: Fixed) -> i128 {
+    let d_sin = d * alpha.sin();
+    let d_cos = d * alpha.cos();
+    d_hat.raw() as i128 * d_hat.raw() as i128
+        - d_sin.raw() as i128 * d_sin.raw() as i128
+        - ((d_cos.raw() as i128) << 32)
+}
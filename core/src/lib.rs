@@ -0,0 +1,26 @@
+//! Deterministic PoLoc math shared by the on-chain program and off-chain
+//! oracles.
+//!
+//! This is a port of `cli/geometry.js`. Everything here is `no_std` and goes
+//! through `libm` rather than platform intrinsics, so the BPF program and a
+//! native oracle produce bit-identical results for the same inputs.
+//!
+//! On-chain code should only call the Q32.32 functions (`fixed`,
+//! `*_fixed`); the `f64` versions are kept as the reference they are tested
+//! against.
+
+#![no_std]
+
+extern crate alloc;
+
+pub mod conformance;
+pub mod fixed;
+pub mod geometry;
+pub mod rewards;
+pub mod rstar;
+pub mod scoring;
+pub mod slashing;
+
+pub use fixed::Fixed;
+pub use geometry::Coordinate;
+pub use rstar::{estimate_r_star, estimate_r_star_fixed, Report};
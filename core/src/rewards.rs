@@ -0,0 +1,38 @@
+//! Reward pool splits.
+//!
+//! Winners claim one at a time. A claim takes the pool as it stands in
+//! proportion to the winner's stake among the stake not yet claimed, so the
+//! split follows stake and the last claim empties the pool.
+//!
+//! Votes cast before stakes were recorded on them carry no stake and fall
+//! back to `claim_share`: the pool divided by the valid votes, which shrinks
+//! as the pool drains and leaves the division's remainder behind.
+
+use alloc::vec::Vec;
+
+/// Lamports (or token base units) the next claim takes from `pool`. `None`
+/// when there are no valid votes to split it between.
+pub fn claim_share(pool: u64, valid_votes: u32) -> Option<u64> {
+    pool.checked_div(valid_votes as u64)
+}
+
+/// Up-front split of `pool` by `stakes`, for Merkle payouts where every
+/// share is fixed at settlement. A zero stake weighs one lamport, and
+/// rounding leaves the remainder unassigned.
+pub fn payout_shares(pool: u64, stakes: &[u64]) -> Vec<u64> {
+    let total: u128 = stakes.iter().map(|&s| s.max(1) as u128).sum();
+    stakes
+        .iter()
+        .map(|&s| (pool as u128 * s.max(1) as u128 / total) as u64)
+        .collect()
+}
+
+/// The next claim's take of `pool` for a winner with `stake`, out of the
+/// `unclaimed_stake` still owed a share (the winner's included). `None` when
+/// `stake` isn't covered by `unclaimed_stake`.
+pub fn stake_share(pool: u64, stake: u64, unclaimed_stake: u64) -> Option<u64> {
+    if stake > unclaimed_stake || unclaimed_stake == 0 {
+        return None;
+    }
+    Some((pool as u128 * stake as u128 / unclaimed_stake as u128) as u64)
+}
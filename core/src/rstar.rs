@@ -0,0 +1,139 @@
+use alloc::collections::BTreeMap;
+use alloc::vec::Vec;
+use core::f64::consts::PI;
+
+use crate::fixed::Fixed;
+use crate::geometry::{bearing_fixed, bearing_rad, distance_fixed, distance_m, ri, ri_q64, Coordinate};
+
+/// Default angular bucket width used by the JS coordinator: 10 degrees.
+pub const DEFAULT_ANGLE_BUCKETS: u32 = 36;
+
+/// Default β-quantile (median), in basis points.
+pub const DEFAULT_BETA_BPS: u16 = 5_000;
+
+/// One challenger's measurement as consumed by the estimator.
+#[derive(Clone, Copy, Debug)]
+pub struct Report {
+    pub location: Coordinate,
+    /// d̂ᵢ: distance estimated from the challenger's delay mapping, meters.
+    pub estimated_distance: Fixed,
+}
+
+/// Equation (2): β-th smallest value. `beta_bps` is in basis points so the
+/// index is computed without floats.
+///
+/// Returns `None` on empty input.
+pub fn quantile(values: &mut [f64], beta_bps: u16) -> Option<f64> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_by(f64::total_cmp);
+    let index = (beta_bps as usize * values.len()) / 10_000;
+    Some(values[index.min(values.len() - 1)])
+}
+
+/// Integer β-quantile over per-vote uncertainties (meters). This is the form
+/// usable from on-chain vote data, where challenger locations are unknown.
+pub fn quantile_u32(values: &mut [u32], beta_bps: u16) -> Option<u32> {
+    if values.is_empty() {
+        return None;
+    }
+    values.sort_unstable();
+    let index = (beta_bps as usize * values.len()) / 10_000;
+    Some(values[index.min(values.len() - 1)])
+}
+
+/// `quantile_u32` over `(value, weight)` pairs: the smallest value whose
+/// cumulative weight passes `beta_bps` of the total. Unit weights give the
+/// same result as `quantile_u32`. `None` on empty input or zero total weight.
+pub fn weighted_quantile_u32(values: &mut [(u32, u64)], beta_bps: u16) -> Option<u32> {
+    let total: u128 = values.iter().map(|&(_, w)| w as u128).sum();
+    if total == 0 {
+        return None;
+    }
+    values.sort_unstable();
+    let target = total * beta_bps as u128;
+    let mut cumulative = 0u128;
+    for &(value, weight) in values.iter() {
+        cumulative += weight as u128;
+        if cumulative * 10_000 > target {
+            return Some(value);
+        }
+    }
+    values.last().map(|&(value, _)| value)
+}
+
+/// Double-precision uncertainty estimate, mirroring `GeometryEngine.estimateUncertainty`:
+/// group reports by bearing bucket, take the β-quantile of Ri per bucket
+/// (Equation 2) and return the maximum across buckets (Equation 3).
+///
+/// This is the reference implementation; the program uses
+/// `estimate_r_star_fixed`. Returns `None` when there are no reports.
+pub fn estimate_r_star(
+    claimed: Coordinate,
+    reports: &[Report],
+    angle_buckets: u32,
+    beta_bps: u16,
+) -> Option<f64> {
+    if reports.is_empty() || angle_buckets == 0 {
+        return None;
+    }
+
+    let resolution = 2.0 * PI / angle_buckets as f64;
+    let mut groups: BTreeMap<u32, Vec<f64>> = BTreeMap::new();
+
+    for report in reports {
+        let alpha = bearing_rad(claimed, report.location);
+        let bucket = ((alpha / resolution) as u32).min(angle_buckets - 1);
+        let d = distance_m(claimed, report.location);
+        groups
+            .entry(bucket)
+            .or_default()
+            .push(ri(report.estimated_distance.to_f64(), d, alpha));
+    }
+
+    groups
+        .values_mut()
+        .filter_map(|values| quantile(values, beta_bps))
+        .reduce(f64::max)
+}
+
+/// Fixed-point twin of `estimate_r_star`, returning R* in whole meters
+/// (negative estimates clamp to zero, oversized ones to `u32::MAX`).
+pub fn estimate_r_star_fixed(
+    claimed: Coordinate,
+    reports: &[Report],
+    angle_buckets: u32,
+    beta_bps: u16,
+) -> Option<u32> {
+    if reports.is_empty() || angle_buckets == 0 {
+        return None;
+    }
+
+    let mut groups: BTreeMap<u32, Vec<i128>> = BTreeMap::new();
+
+    for report in reports {
+        let alpha = bearing_fixed(claimed, report.location);
+        let bucket = ((alpha.raw() as i128 * angle_buckets as i128) / Fixed::TWO_PI.raw() as i128) as u32;
+        let d = distance_fixed(claimed, report.location);
+        groups
+            .entry(bucket.min(angle_buckets - 1))
+            .or_default()
+            .push(ri_q64(report.estimated_distance, d, alpha));
+    }
+
+    let r_star_q64 = groups
+        .values_mut()
+        .filter_map(|values| {
+            if values.is_empty() {
+                return None;
+            }
+            values.sort_unstable();
+            let index = (beta_bps as usize * values.len()) / 10_000;
+            Some(values[index.min(values.len() - 1)])
+        })
+        .max()?;
+
+    let meters = r_star_q64 >> 64;
+    Some(meters.clamp(0, u32::MAX as i128) as u32)
+}
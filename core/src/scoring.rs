@@ -0,0 +1,36 @@
+//! Witness streak bonuses.
+//!
+//! A witness's streak is the number of rewarded votes they have claimed in a
+//! row. A slash, or any vote that settles without a reward, ends it. Every
+//! claim after the first in a streak earns a bonus on top of its pool share,
+//! `STREAK_STEP_BPS` more per vote, capped at `MAX_STREAK_BONUS_BPS` so a
+//! long history can't outweigh the pool itself. The program funds the bonus
+//! from its insurance pool.
+
+/// Bonus added per consecutive rewarded vote, in basis points of the share.
+pub const STREAK_STEP_BPS: u32 = 1_000;
+
+/// Largest bonus any streak earns, in basis points of the share.
+pub const MAX_STREAK_BONUS_BPS: u32 = 5_000;
+
+const BPS: u32 = 10_000;
+
+/// Bonus for the `streak`-th rewarded vote in a row, in basis points. The
+/// first vote of a streak (and an empty one) earns nothing.
+pub fn streak_bonus_bps(streak: u32) -> u32 {
+    streak
+        .saturating_sub(1)
+        .saturating_mul(STREAK_STEP_BPS)
+        .min(MAX_STREAK_BONUS_BPS)
+}
+
+/// What a claim is multiplied by at `streak`, in basis points; always in
+/// `[10_000, 10_000 + MAX_STREAK_BONUS_BPS]`.
+pub fn streak_multiplier_bps(streak: u32) -> u32 {
+    BPS + streak_bonus_bps(streak)
+}
+
+/// Lamports owed on top of `share` at `streak`, rounded down.
+pub fn streak_bonus(share: u64, streak: u32) -> u64 {
+    (share as u128 * streak_bonus_bps(streak) as u128 / BPS as u128) as u64
+}
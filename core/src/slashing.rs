@@ -0,0 +1,57 @@
+//! Confidence-weighted slashing.
+//!
+//! Witnesses report how sure they are of each vote, in basis points. A vote
+//! that contradicts the finalized outcome loses a share of its stake set by a
+//! quadratic (Brier) scoring rule: `MIN_SLASH + (1 - MIN_SLASH) · c²`. The
+//! penalty grows with the square of the confidence claimed, so a witness who
+//! overstates certainty pays more than one who honestly reports doubt, while
+//! the floor keeps a wrong vote from ever being free.
+//!
+//! When USD-denominated minimums are in use, each stake carries the USD rate
+//! it was posted at, and `rebase_penalty` re-prices the penalty at settlement
+//! so it is worth what it was when staked.
+
+use crate::fixed::{Fixed, FRAC_BITS};
+
+/// Full confidence, in basis points. Larger reports are clamped to it.
+pub const MAX_CONFIDENCE_BPS: u16 = 10_000;
+
+/// Share of the stake slashed at zero confidence, in basis points.
+pub const MIN_SLASH_BPS: u16 = 2_000;
+
+const BPS: i32 = 10_000;
+
+/// Double-precision reference for `slash_fraction_fixed`.
+pub fn slash_fraction(confidence_bps: u16) -> f64 {
+    let c = confidence_bps.min(MAX_CONFIDENCE_BPS) as f64 / BPS as f64;
+    let floor = MIN_SLASH_BPS as f64 / BPS as f64;
+    floor + (1.0 - floor) * c * c
+}
+
+/// Fraction of a dishonest vote's stake that is slashed, in `[MIN_SLASH, 1]`.
+pub fn slash_fraction_fixed(confidence_bps: u16) -> Fixed {
+    let bps = Fixed::from_int(BPS);
+    let c = Fixed::from_int(confidence_bps.min(MAX_CONFIDENCE_BPS) as i32) / bps;
+    let floor = Fixed::from_int(MIN_SLASH_BPS as i32) / bps;
+    (floor + (Fixed::ONE - floor) * c * c).min(Fixed::ONE)
+}
+
+/// Lamports slashed from `stake` for a dishonest vote at `confidence_bps`,
+/// rounded to the nearest lamport and never more than the stake.
+pub fn slash_amount(stake: u64, confidence_bps: u16) -> u64 {
+    let fraction = slash_fraction_fixed(confidence_bps).raw() as u128;
+    let half = 1u128 << (FRAC_BITS - 1);
+    ((stake as u128 * fraction + half) >> FRAC_BITS).min(stake as u128) as u64
+}
+
+/// Re-prices `penalty`, worked out on a stake posted at `staked_rate`, for
+/// settlement at `current_rate` (both USD per unit of the stake's asset), so
+/// it keeps the value it had when staked. Either rate unset leaves it as is.
+/// Never more than `cap`, the stake itself.
+pub fn rebase_penalty(penalty: u64, staked_rate: u64, current_rate: u64, cap: u64) -> u64 {
+    if staked_rate == 0 || current_rate == 0 {
+        return penalty.min(cap);
+    }
+    let rebased = penalty as u128 * staked_rate as u128 / current_rate as u128;
+    rebased.min(cap as u128) as u64
+}
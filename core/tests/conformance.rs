@@ -0,0 +1,54 @@
+use poloc_core::conformance::{ORACLE_MEDIAN_VECTORS, REWARD_SPLIT_VECTORS, R_STAR_VECTORS};
+use poloc_core::fixed::Fixed;
+use poloc_core::rewards::claim_share;
+use poloc_core::rstar::{estimate_r_star_fixed, quantile_u32, DEFAULT_BETA_BPS};
+use poloc_core::{Coordinate, Report};
+
+#[test]
+fn reward_splits_match() {
+    for vector in REWARD_SPLIT_VECTORS {
+        let mut pool = vector.reward_pool;
+        for &expected in vector.claims {
+            let share = claim_share(pool, vector.valid_votes).unwrap();
+            assert_eq!(share, expected, "pool {} over {}", vector.reward_pool, vector.valid_votes);
+            pool -= share;
+        }
+        assert_eq!(pool, vector.remaining);
+    }
+}
+
+#[test]
+fn no_valid_votes_has_no_share() {
+    assert_eq!(claim_share(1_000, 0), None);
+}
+
+#[test]
+fn r_star_estimates_match() {
+    for vector in R_STAR_VECTORS {
+        let reports: Vec<Report> = vector
+            .reports
+            .iter()
+            .map(|&(lat, lon, distance)| Report {
+                location: Coordinate::new(lat, lon),
+                estimated_distance: Fixed::from_int(distance),
+            })
+            .collect();
+        let claimed = Coordinate::new(vector.claimed.0, vector.claimed.1);
+        assert_eq!(
+            estimate_r_star_fixed(claimed, &reports, vector.angle_buckets, vector.beta_bps),
+            Some(vector.r_star),
+            "{} reports, {} buckets, beta {}",
+            reports.len(),
+            vector.angle_buckets,
+            vector.beta_bps
+        );
+    }
+}
+
+#[test]
+fn oracle_medians_match() {
+    for vector in ORACLE_MEDIAN_VECTORS {
+        let mut values = vector.reports.to_vec();
+        assert_eq!(quantile_u32(&mut values, DEFAULT_BETA_BPS), Some(vector.median));
+    }
+}
@@ -0,0 +1,119 @@
+use poloc_core::fixed::{isqrt, Fixed};
+use poloc_core::geometry::{bearing_fixed, bearing_rad, distance_fixed, distance_m, Coordinate};
+
+// Deterministic xorshift so failures reproduce without a seed dependency.
+struct Rng(u64);
+
+impl Rng {
+    fn next(&mut self) -> u64 {
+        self.0 ^= self.0 << 13;
+        self.0 ^= self.0 >> 7;
+        self.0 ^= self.0 << 17;
+        self.0
+    }
+
+    fn range(&mut self, lo: i64, hi: i64) -> i64 {
+        lo + (self.next() % (hi - lo + 1) as u64) as i64
+    }
+}
+
+fn within(actual: Fixed, expected: f64, tolerance: f64) -> bool {
+    (actual.to_f64() - expected).abs() <= tolerance
+}
+
+#[test]
+fn sin_cos_match_f64_over_several_turns() {
+    let mut rng = Rng(0x9E37_79B9_7F4A_7C15);
+    for _ in 0..20_000 {
+        let x = Fixed::from_raw(rng.range(-8 * Fixed::TWO_PI.raw(), 8 * Fixed::TWO_PI.raw()));
+        let reference = x.to_f64();
+        assert!(within(x.sin(), reference.sin(), 2e-9), "sin({})", reference);
+        assert!(within(x.cos(), reference.cos(), 2e-9), "cos({})", reference);
+    }
+}
+
+#[test]
+fn atan2_matches_f64_in_every_quadrant() {
+    let mut rng = Rng(0xD1B5_4A32_D192_ED03);
+    for _ in 0..20_000 {
+        let y = Fixed::from_raw(rng.range(-(1 << 40), 1 << 40));
+        let x = Fixed::from_raw(rng.range(-(1 << 40), 1 << 40));
+        let expected = y.to_f64().atan2(x.to_f64());
+        assert!(within(Fixed::atan2(y, x), expected, 2e-9), "atan2({:?}, {:?})", y, x);
+    }
+}
+
+#[test]
+fn atan2_handles_axes_and_tiny_denominators() {
+    let one = Fixed::ONE;
+    let tiny = Fixed::from_raw(1);
+    assert_eq!(Fixed::atan2(Fixed::ZERO, Fixed::ZERO), Fixed::ZERO);
+    assert!(within(Fixed::atan2(one, Fixed::ZERO), std::f64::consts::FRAC_PI_2, 1e-9));
+    assert!(within(Fixed::atan2(-one, Fixed::ZERO), -std::f64::consts::FRAC_PI_2, 1e-9));
+    assert!(within(Fixed::atan2(one, tiny), std::f64::consts::FRAC_PI_2, 1e-9));
+    assert!(within(Fixed::atan2(Fixed::ZERO, -one), std::f64::consts::PI, 1e-9));
+}
+
+#[test]
+fn sqrt_and_isqrt_are_exact_floors() {
+    for n in 0u128..10_000 {
+        let r = isqrt(n);
+        assert!(r * r <= n && (r + 1) * (r + 1) > n);
+    }
+    let mut rng = Rng(0x2545_F491_4F6C_DD1D);
+    for _ in 0..10_000 {
+        let x = Fixed::from_raw(rng.range(0, i64::MAX >> 1));
+        assert!(within(x.sqrt(), x.to_f64().sqrt(), 1e-9 * x.to_f64().sqrt().max(1.0)));
+    }
+}
+
+#[test]
+fn micro_degree_conversion_is_exact_to_one_ulp() {
+    for micro in [-180_000_000, -90_000_000, -1, 0, 1, 45_000_000, 90_000_000, 180_000_000] {
+        let expected = (micro as f64 / 1e6).to_radians();
+        assert!(within(Fixed::from_micro_degrees(micro), expected, 1.0 / (1u64 << 32) as f64));
+    }
+}
+
+#[test]
+fn haversine_error_stays_below_one_meter_at_continental_scale() {
+    let mut rng = Rng(0xA076_1D64_78BD_642F);
+    for _ in 0..20_000 {
+        let a = Coordinate::new(rng.range(-85_000_000, 85_000_000) as i32, rng.range(-180_000_000, 180_000_000) as i32);
+        // Up to ~45 degrees away in each axis: city blocks through continents.
+        let b = Coordinate::new(
+            (a.lat as i64 + rng.range(-45_000_000, 45_000_000)).clamp(-90_000_000, 90_000_000) as i32,
+            (a.lon as i64 + rng.range(-45_000_000, 45_000_000)).clamp(-180_000_000, 180_000_000) as i32,
+        );
+        let expected = distance_m(a, b);
+        let actual = distance_fixed(a, b);
+        assert!(within(actual, expected, 1.0), "{:?} -> {:?}: {} vs {}", a, b, actual.to_f64(), expected);
+    }
+}
+
+#[test]
+fn haversine_resolves_short_distances() {
+    let a = Coordinate::new(40_712_800, -74_006_000);
+    for offset in [1, 9, 90, 900] {
+        let b = Coordinate::new(a.lat + offset, a.lon);
+        assert!(within(distance_fixed(a, b), distance_m(a, b), 0.01));
+    }
+}
+
+#[test]
+fn bearing_matches_reference() {
+    let mut rng = Rng(0xE703_7ED1_A0B4_28DB);
+    for _ in 0..20_000 {
+        let a = Coordinate::new(rng.range(-80_000_000, 80_000_000) as i32, rng.range(-180_000_000, 180_000_000) as i32);
+        let b = Coordinate::new(rng.range(-80_000_000, 80_000_000) as i32, rng.range(-180_000_000, 180_000_000) as i32);
+        if a == b {
+            continue;
+        }
+        let expected = bearing_rad(a, b);
+        let actual = bearing_fixed(a, b).to_f64();
+        let diff = (actual - expected).abs();
+        // Bearings near 0 may land on opposite sides of the 2π wrap.
+        let wrapped = diff.min(2.0 * std::f64::consts::PI - diff);
+        assert!(wrapped < 1e-7, "{:?} -> {:?}: {} vs {}", a, b, actual, expected);
+    }
+}
@@ -0,0 +1,60 @@
+use poloc_core::rewards::stake_share;
+use poloc_core::rstar::{quantile_u32, weighted_quantile_u32, DEFAULT_BETA_BPS};
+
+#[test]
+fn stake_shares_follow_stake_and_empty_the_pool() {
+    let stakes = [1_000_000u64, 3_000_000, 6_000_000];
+    let mut pool = 1_000_000_007u64;
+    let mut unclaimed: u64 = stakes.iter().sum();
+    let mut claims = Vec::new();
+    for stake in stakes {
+        let share = stake_share(pool, stake, unclaimed).unwrap();
+        pool -= share;
+        unclaimed -= stake;
+        claims.push(share);
+    }
+    assert_eq!(claims, [100_000_000, 300_000_002, 600_000_005]);
+    assert_eq!(pool, 0);
+}
+
+#[test]
+fn many_small_stakes_split_what_one_large_stake_would() {
+    let pool = 900_000_000u64;
+    let whale = stake_share(pool, 9_000_000, 18_000_000).unwrap();
+    let mut remaining = pool - whale;
+    let mut unclaimed = 9_000_000u64;
+    let mut sybils = 0;
+    for _ in 0..9_000 {
+        let share = stake_share(remaining, 1_000, unclaimed).unwrap();
+        remaining -= share;
+        unclaimed -= 1_000;
+        sybils += share;
+    }
+    assert_eq!(whale, 450_000_000);
+    assert_eq!(sybils, 450_000_000);
+}
+
+#[test]
+fn stake_share_rejects_uncovered_stake() {
+    assert_eq!(stake_share(100, 5, 4), None);
+    assert_eq!(stake_share(100, 0, 0), None);
+}
+
+#[test]
+fn unit_weights_match_the_plain_quantile() {
+    let values = [900u32, 150, 4_000, 620, 620, 80];
+    for beta_bps in [0, 2_500, DEFAULT_BETA_BPS, 9_999] {
+        let mut plain = values;
+        let mut weighted: Vec<(u32, u64)> = values.iter().map(|&v| (v, 1)).collect();
+        assert_eq!(weighted_quantile_u32(&mut weighted, beta_bps), quantile_u32(&mut plain, beta_bps));
+    }
+}
+
+#[test]
+fn weighted_median_follows_stake_not_headcount() {
+    // Ten tiny stakes report 50 m; one stake larger than all of them reports 2 km.
+    let mut votes: Vec<(u32, u64)> = (0..10).map(|_| (50, 1_000_000)).collect();
+    votes.push((2_000, 20_000_000));
+    assert_eq!(weighted_quantile_u32(&mut votes, DEFAULT_BETA_BPS), Some(2_000));
+    assert_eq!(weighted_quantile_u32(&mut [(10, 0)], DEFAULT_BETA_BPS), None);
+}
@@ -0,0 +1,60 @@
+use poloc_core::scoring::{
+    streak_bonus, streak_bonus_bps, streak_multiplier_bps, MAX_STREAK_BONUS_BPS, STREAK_STEP_BPS,
+};
+
+const STREAKS: &[u32] = &[0, 1, 2, 3, 5, 6, 7, 100, 65_535, u32::MAX - 1, u32::MAX];
+
+#[test]
+fn first_vote_of_a_streak_earns_nothing() {
+    assert_eq!(streak_bonus_bps(0), 0);
+    assert_eq!(streak_bonus_bps(1), 0);
+    assert_eq!(streak_bonus(1_000_000_000, 1), 0);
+    assert_eq!(streak_multiplier_bps(1), 10_000);
+}
+
+#[test]
+fn each_vote_adds_a_step_until_the_cap() {
+    assert_eq!(streak_bonus_bps(2), STREAK_STEP_BPS);
+    assert_eq!(streak_bonus_bps(3), 2 * STREAK_STEP_BPS);
+    let capped_at = MAX_STREAK_BONUS_BPS / STREAK_STEP_BPS + 1;
+    assert_eq!(streak_bonus_bps(capped_at), MAX_STREAK_BONUS_BPS);
+    assert_eq!(streak_bonus_bps(capped_at + 1), MAX_STREAK_BONUS_BPS);
+}
+
+#[test]
+fn multiplier_never_decreases_and_stays_capped() {
+    let mut previous = streak_multiplier_bps(0);
+    for streak in 0..=100_000 {
+        let multiplier = streak_multiplier_bps(streak);
+        assert!(multiplier >= previous, "streak {}: {} < {}", streak, multiplier, previous);
+        previous = multiplier;
+    }
+    for &streak in STREAKS {
+        let multiplier = streak_multiplier_bps(streak);
+        assert!(multiplier <= 10_000 + MAX_STREAK_BONUS_BPS, "streak {}: {}", streak, multiplier);
+    }
+}
+
+#[test]
+fn bonus_is_bounded_by_the_cap_on_any_share() {
+    for &share in &[0, 1, 9_999, 10_000, 1_000_000_007, u64::MAX / 3, u64::MAX] {
+        for &streak in STREAKS {
+            let bonus = streak_bonus(share, streak);
+            let cap = (share as u128 * MAX_STREAK_BONUS_BPS as u128 / 10_000) as u64;
+            assert!(bonus <= cap, "share {} streak {}: {} > {}", share, streak, bonus, cap);
+            assert_eq!(bonus, (share as u128 * streak_bonus_bps(streak) as u128 / 10_000) as u64);
+        }
+    }
+}
+
+#[test]
+fn bonus_grows_with_the_share() {
+    for &streak in STREAKS {
+        let mut previous = 0;
+        for share in (0..20_000u64).map(|i| i * 7_919) {
+            let bonus = streak_bonus(share, streak);
+            assert!(bonus >= previous, "streak {} share {}: {} < {}", streak, share, bonus, previous);
+            previous = bonus;
+        }
+    }
+}
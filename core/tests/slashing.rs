@@ -0,0 +1,65 @@
+use poloc_core::slashing::{
+    rebase_penalty, slash_amount, slash_fraction, slash_fraction_fixed, MAX_CONFIDENCE_BPS, MIN_SLASH_BPS,
+};
+
+#[test]
+fn fixed_fraction_matches_f64_reference() {
+    for confidence in 0..=MAX_CONFIDENCE_BPS {
+        let fixed = slash_fraction_fixed(confidence).to_f64();
+        let reference = slash_fraction(confidence);
+        assert!((fixed - reference).abs() <= 1e-8, "confidence {}: {} vs {}", confidence, fixed, reference);
+    }
+}
+
+#[test]
+fn zero_confidence_slashes_the_floor() {
+    let stake = 1_000_000_000;
+    assert_eq!(slash_amount(stake, 0), stake * MIN_SLASH_BPS as u64 / 10_000);
+}
+
+#[test]
+fn full_confidence_slashes_everything() {
+    assert_eq!(slash_amount(1_000_000_000, MAX_CONFIDENCE_BPS), 1_000_000_000);
+    assert_eq!(slash_amount(u64::MAX, MAX_CONFIDENCE_BPS), u64::MAX);
+}
+
+#[test]
+fn higher_confidence_never_slashes_less() {
+    let stake = 123_456_789_012;
+    let mut previous = 0;
+    for confidence in 0..=MAX_CONFIDENCE_BPS {
+        let slashed = slash_amount(stake, confidence);
+        assert!(slashed >= previous, "confidence {} slashed {} < {}", confidence, slashed, previous);
+        assert!(slashed <= stake);
+        previous = slashed;
+    }
+}
+
+#[test]
+fn penalty_is_quadratic_in_confidence() {
+    // Halving confidence cuts the above-floor penalty to a quarter.
+    let stake = 10_000_000_000u64;
+    let floor = slash_amount(stake, 0);
+    let full = slash_amount(stake, MAX_CONFIDENCE_BPS) - floor;
+    let half = slash_amount(stake, MAX_CONFIDENCE_BPS / 2) - floor;
+    assert!((half as i128 - (full / 4) as i128).abs() <= 2, "{} vs {}", half, full / 4);
+}
+
+#[test]
+fn confidence_above_max_is_clamped() {
+    assert_eq!(slash_amount(5_000, u16::MAX), slash_amount(5_000, MAX_CONFIDENCE_BPS));
+}
+
+#[test]
+fn rebased_penalty_keeps_its_value_at_stake_time() {
+    let stake = 1_000_000_000;
+    let penalty = slash_amount(stake, 0);
+    // Staked at $150, settled at $200: fewer lamports for the same dollars.
+    assert_eq!(rebase_penalty(penalty, 150_000_000, 200_000_000, stake), penalty * 3 / 4);
+    // A price drop raises the lamports, never beyond the stake.
+    assert_eq!(rebase_penalty(penalty, 150_000_000, 100_000_000, stake), penalty * 3 / 2);
+    assert_eq!(rebase_penalty(stake, 150_000_000, 100_000_000, stake), stake);
+    // No snapshot, or no live rate: the penalty stands.
+    assert_eq!(rebase_penalty(penalty, 0, 200_000_000, stake), penalty);
+    assert_eq!(rebase_penalty(penalty, 150_000_000, 0, stake), penalty);
+}
@@ -0,0 +1,103 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+
+// Hand-rolled CPI into spl-account-compression. The published crate pins an
+// older anchor-lang, so we only mirror the three instructions we call.
+
+pub const SPL_ACCOUNT_COMPRESSION_ID: Pubkey = pubkey!("cmtDvXumGCrqC1Age74AVPhSRVXJMd8PJS91L8KbNCK");
+pub const SPL_NOOP_ID: Pubkey = pubkey!("noopb9bkMVfRPU8AsbpTUg8AQkHtKwMYZiFUjNRtMmV");
+
+// sha256("global:<ix_name>")[..8]
+const INIT_EMPTY_MERKLE_TREE: [u8; 8] = [191, 11, 119, 7, 180, 107, 220, 110];
+const APPEND: [u8; 8] = [149, 120, 18, 222, 236, 225, 88, 203];
+const VERIFY_LEAF: [u8; 8] = [124, 220, 22, 223, 104, 10, 250, 224];
+
+pub fn init_empty_merkle_tree<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    noop: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    max_depth: u32,
+    max_buffer_size: u32,
+) -> Result<()> {
+    let mut data = INIT_EMPTY_MERKLE_TREE.to_vec();
+    data.extend_from_slice(&max_depth.to_le_bytes());
+    data.extend_from_slice(&max_buffer_size.to_le_bytes());
+
+    let ix = Instruction {
+        program_id: SPL_ACCOUNT_COMPRESSION_ID,
+        accounts: vec![
+            AccountMeta::new(merkle_tree.key(), false),
+            AccountMeta::new_readonly(authority.key(), true),
+            AccountMeta::new_readonly(noop.key(), false),
+        ],
+        data,
+    };
+    invoke_signed(
+        &ix,
+        &[merkle_tree.clone(), authority.clone(), noop.clone(), compression_program.clone()],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+pub fn append_leaf<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    noop: &AccountInfo<'info>,
+    signer_seeds: &[&[&[u8]]],
+    leaf: [u8; 32],
+) -> Result<()> {
+    let mut data = APPEND.to_vec();
+    data.extend_from_slice(&leaf);
+
+    let ix = Instruction {
+        program_id: SPL_ACCOUNT_COMPRESSION_ID,
+        accounts: vec![
+            AccountMeta::new(merkle_tree.key(), false),
+            AccountMeta::new_readonly(authority.key(), true),
+            AccountMeta::new_readonly(noop.key(), false),
+        ],
+        data,
+    };
+    invoke_signed(
+        &ix,
+        &[merkle_tree.clone(), authority.clone(), noop.clone(), compression_program.clone()],
+        signer_seeds,
+    )?;
+    Ok(())
+}
+
+/// Fails (propagating the compression program's error) unless `leaf` sits at
+/// `index` under `root`. `proof` are the sibling nodes, leaf to root.
+pub fn verify_leaf<'info>(
+    compression_program: &AccountInfo<'info>,
+    merkle_tree: &AccountInfo<'info>,
+    proof: &[AccountInfo<'info>],
+    root: [u8; 32],
+    leaf: [u8; 32],
+    index: u32,
+) -> Result<()> {
+    let mut data = VERIFY_LEAF.to_vec();
+    data.extend_from_slice(&root);
+    data.extend_from_slice(&leaf);
+    data.extend_from_slice(&index.to_le_bytes());
+
+    let mut accounts = vec![AccountMeta::new_readonly(merkle_tree.key(), false)];
+    accounts.extend(proof.iter().map(|node| AccountMeta::new_readonly(node.key(), false)));
+
+    let mut infos = vec![merkle_tree.clone()];
+    infos.extend(proof.iter().cloned());
+    infos.push(compression_program.clone());
+
+    let ix = Instruction {
+        program_id: SPL_ACCOUNT_COMPRESSION_ID,
+        accounts,
+        data,
+    };
+    invoke(&ix, &infos)?;
+    Ok(())
+}
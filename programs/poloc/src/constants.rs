@@ -0,0 +1,176 @@
+//! Protocol constants. `#[constant]` items are exported in the IDL so clients
+//! can read them instead of mirroring them; live deployments also publish the
+//! window bounds and caps in the `ProtocolInfo` account.
+use anchor_lang::prelude::*;
+
+#[constant]
+pub const PROTOCOL_VERSION: &str = "0.3.0";
+
+// PDA seeds
+#[constant]
+pub const ARCHIVE_SEED: &[u8] = b"archive";
+#[constant]
+pub const CHALLENGE_SEED: &[u8] = b"challenge";
+// Signs result callbacks; holds nothing and has authority over nothing
+#[constant]
+pub const CALLBACK_SEED: &[u8] = b"callback";
+#[constant]
+pub const CAMPAIGN_SEED: &[u8] = b"campaign";
+#[constant]
+pub const CHALLENGER_SEED: &[u8] = b"challenger";
+#[constant]
+pub const CLAIM_ESCROW_SEED: &[u8] = b"claim_escrow";
+#[constant]
+pub const COLLUSION_SEED: &[u8] = b"collusion";
+#[constant]
+pub const CONFIG_SEED: &[u8] = b"config";
+#[constant]
+pub const CONTRIBUTION_SEED: &[u8] = b"contribution";
+#[constant]
+pub const DEVICE_SEED: &[u8] = b"device";
+#[constant]
+pub const EMERGENCY_WITHDRAWAL_SEED: &[u8] = b"emergency_withdrawal";
+#[constant]
+pub const EVIDENCE_SEED: &[u8] = b"evidence";
+#[constant]
+pub const EXPIRY_FEED_SEED: &[u8] = b"expiry_feed";
+#[constant]
+pub const FEE_WAIVER_SEED: &[u8] = b"fee_waivers";
+#[constant]
+pub const FLASH_SEED: &[u8] = b"flash";
+#[constant]
+pub const INSURANCE_SEED: &[u8] = b"insurance";
+// Derived under the integrator program's id, not ours
+#[constant]
+pub const INTEGRATOR_AUTHORITY_SEED: &[u8] = b"poloc_integrator";
+#[constant]
+pub const INTENT_ESCROW_SEED: &[u8] = b"intent_escrow";
+#[constant]
+pub const KEEPER_SEED: &[u8] = b"keeper";
+#[constant]
+pub const ORACLE_REPORT_SEED: &[u8] = b"oracle_report";
+#[constant]
+pub const ORACLE_SET_SEED: &[u8] = b"oracle_set";
+#[constant]
+pub const PAYOUT_SEED: &[u8] = b"payout";
+#[constant]
+pub const PAYOUT_LEDGER_SEED: &[u8] = b"payout_ledger";
+#[constant]
+pub const PROTOCOL_INFO_SEED: &[u8] = b"protocol_info";
+#[constant]
+pub const RECEIPT_MINT_SEED: &[u8] = b"receipt_mint";
+#[constant]
+pub const REGION_SEED: &[u8] = b"region";
+#[constant]
+pub const REGION_STATS_SEED: &[u8] = b"region_stats";
+#[constant]
+pub const REGISTRY_SEED: &[u8] = b"registry";
+#[constant]
+pub const REGISTRY_PAGE_SEED: &[u8] = b"registry_page";
+#[constant]
+pub const STAKE_SEED: &[u8] = b"stake";
+#[constant]
+pub const STANDING_STAKE_SEED: &[u8] = b"standing_stake";
+// The tier badge mint, which is also its own mint authority
+#[constant]
+pub const TIER_BADGE_SEED: &[u8] = b"tier_badge";
+#[constant]
+pub const VAULT_SEED: &[u8] = b"vault";
+#[constant]
+pub const VOTE_SEED: &[u8] = b"vote";
+#[constant]
+pub const VOTE_COMMITTEE_SEED: &[u8] = b"vote_committee";
+#[constant]
+pub const WALDO_SEED: &[u8] = b"waldo";
+
+// Windows, in seconds
+// Default voting window; the config holds the live one
+#[cfg(not(test))]
+#[constant]
+pub const VOTING_WINDOW: i64 = 300; // 5 minutes for production
+#[cfg(test)]
+pub const VOTING_WINDOW: i64 = 3;   // 3 seconds for testing
+
+// Default gap between the end of voting and the earliest finalize, so a
+// validator clock running ahead can't let a result cut off last-slot votes;
+// the config holds the live one
+#[cfg(not(test))]
+#[constant]
+pub const SETTLEMENT_BUFFER: i64 = 30;
+#[cfg(test)]
+pub const SETTLEMENT_BUFFER: i64 = 1;
+
+// How long after the voting window a challenge may still be finalized before
+// any time-sensitive instruction marks it Expired.
+#[constant]
+pub const FINALIZE_GRACE: i64 = 86_400;
+// Hard ceiling for the config's duration tiers
+#[constant]
+pub const MAX_CHALLENGE_DURATION: u64 = 30 * 86_400;
+#[constant]
+pub const MAX_SCHEDULE_AHEAD: i64 = 30 * 86_400;
+// Longest combined measure-and-vote window of a flash challenge
+#[constant]
+pub const MAX_FLASH_WINDOW: i64 = 300;
+// How long past the last possible finalize a standing stake stays locked, so
+// the arbiter can still slash it
+#[constant]
+pub const STANDING_RELEASE_DELAY: i64 = 86_400;
+// Mandatory wait between proposing and executing an emergency withdrawal
+#[constant]
+pub const EMERGENCY_WITHDRAW_DELAY: i64 = 7 * 86_400;
+
+// Default acceptance threshold for R*, in meters; the config holds the live one
+#[constant]
+pub const DEFAULT_R_STAR_THRESHOLD: u32 = 1_000;
+
+// Caps
+// Default participant cap; the config holds the live one
+#[constant]
+pub const MAX_PARTICIPANTS: u32 = 20;
+// Highest participant cap the config may set: every vote must fit the
+// challenge's roster
+#[constant]
+pub const PARTICIPANT_CAP_CEILING: u32 = 20;
+#[constant]
+pub const MAX_TRANCHES: u8 = 12;
+// Votes a flash challenge holds inline, and how many it needs to settle
+pub const MAX_FLASH_WITNESSES: usize = 8;
+#[constant]
+pub const MIN_FLASH_WITNESSES: u8 = 3;
+// Config duration tiers: short, standard, extended
+pub const DURATION_TIERS: usize = 3;
+// Bytes of a challenge id, which is a PDA seed
+pub const MAX_CHALLENGE_ID_LEN: usize = 32;
+// Locations an any-of challenge may claim, including claimed_lat/lon
+pub const MAX_CANDIDATE_LOCATIONS: usize = 4;
+// Accounts a result callback may pass to its program
+pub const MAX_CALLBACK_ACCOUNTS: usize = 4;
+// Integrator namespaces in the config allowlist
+pub const MAX_INTEGRATORS: usize = 4;
+// Funder programs in the config allowlist
+pub const MAX_FUNDERS: usize = 4;
+// Claims a witness's payout ledger keeps before overwriting the oldest
+pub const PAYOUT_LEDGER_CAPACITY: usize = 16;
+// Approvers an M-of-N stake claim policy may name
+pub const MAX_CLAIM_APPROVERS: usize = 3;
+// Challenges the expiry feed keeps before overwriting the oldest
+pub const EXPIRY_FEED_CAPACITY: usize = 32;
+// Areas the config may bar challenges from claiming
+pub const MAX_EXCLUSION_ZONES: usize = 8;
+// Challenges a registry page lists before the next page is opened
+pub const REGISTRY_PAGE_CAPACITY: usize = 64;
+
+// Votes `Challenge::roster` holds: one per participant at the highest cap.
+pub const ROSTER_CAPACITY: usize = PARTICIPANT_CAP_CEILING as usize;
+
+// External programs and mints used by `claim_reward_swapped`
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+pub const NATIVE_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+// Token-2022, which holds the non-transferable tier badges
+pub const TOKEN_2022_PROGRAM_ID: Pubkey = pubkey!("TokenzQdBNbLqP5VEhdkAS6EPFLC1PHnBqCXEpPxuEb");
+
+// SPL Token and the associated token account program, for token reward pools
+pub const TOKEN_PROGRAM_ID: Pubkey = pubkey!("TokenkegQfeZyiNwAJbNbGKPFXCWuBvf9Ss623VQ5DA");
+pub const ASSOCIATED_TOKEN_PROGRAM_ID: Pubkey = pubkey!("ATokenGPvbdGVxr1b2hvZbsiqW5xWH25efTNsLJA8knL");
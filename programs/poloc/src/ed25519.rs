@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::ed25519_program;
+use anchor_lang::solana_program::sysvar::instructions::{load_current_index_checked, load_instruction_at_checked};
+use crate::errors::PolocError;
+
+// Signature checks by instruction introspection: the transaction carries an
+// Ed25519 program instruction immediately before ours, and we confirm it
+// verified `message` under `signer`. The native program has already rejected
+// the transaction if the signature itself is bad.
+
+const SIGNATURE_OFFSETS_START: usize = 2;
+const SIGNATURE_OFFSETS_LEN: usize = 14;
+// Offsets pointing at this index refer to the Ed25519 instruction's own data.
+const CURRENT_INSTRUCTION: u16 = u16::MAX;
+
+fn read_u16(data: &[u8], at: usize) -> Result<u16> {
+    let bytes = data.get(at..at + 2).ok_or(PolocError::InvalidSignatureInstruction)?;
+    Ok(u16::from_le_bytes([bytes[0], bytes[1]]))
+}
+
+fn slice(data: &[u8], offset: u16, len: usize) -> Result<&[u8]> {
+    let start = offset as usize;
+    data.get(start..start + len).ok_or_else(|| PolocError::InvalidSignatureInstruction.into())
+}
+
+pub fn verify_preceding_signature(
+    instructions_sysvar: &AccountInfo,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    verify_signature_before(instructions_sysvar, 1, signer, message)
+}
+
+/// Same as `verify_preceding_signature` for the Ed25519 instruction `back`
+/// places before ours, when one instruction carries several signatures.
+pub fn verify_signature_before(
+    instructions_sysvar: &AccountInfo,
+    back: u16,
+    signer: &Pubkey,
+    message: &[u8],
+) -> Result<()> {
+    let current = load_current_index_checked(instructions_sysvar)?;
+    require!(back > 0 && current >= back, PolocError::InvalidSignatureInstruction);
+    let ix = load_instruction_at_checked((current - back) as usize, instructions_sysvar)?;
+    require_keys_eq!(ix.program_id, ed25519_program::ID, PolocError::InvalidSignatureInstruction);
+
+    let data = &ix.data;
+    require!(data.first() == Some(&1), PolocError::InvalidSignatureInstruction);
+    require!(
+        data.len() >= SIGNATURE_OFFSETS_START + SIGNATURE_OFFSETS_LEN,
+        PolocError::InvalidSignatureInstruction
+    );
+
+    let at = SIGNATURE_OFFSETS_START;
+    let signature_ix = read_u16(data, at + 2)?;
+    let pubkey_offset = read_u16(data, at + 4)?;
+    let pubkey_ix = read_u16(data, at + 6)?;
+    let message_offset = read_u16(data, at + 8)?;
+    let message_size = read_u16(data, at + 10)?;
+    let message_ix = read_u16(data, at + 12)?;
+    require!(
+        signature_ix == CURRENT_INSTRUCTION && pubkey_ix == CURRENT_INSTRUCTION && message_ix == CURRENT_INSTRUCTION,
+        PolocError::InvalidSignatureInstruction
+    );
+
+    require!(slice(data, pubkey_offset, 32)? == signer.as_ref(), PolocError::InvalidSignatureInstruction);
+    require!(
+        slice(data, message_offset, message_size as usize)? == message,
+        PolocError::InvalidSignatureInstruction
+    );
+    Ok(())
+}
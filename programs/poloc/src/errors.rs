@@ -70,4 +70,287 @@ pub enum PolocError {
 
     #[msg("Stake has been slashed; cannot perform this action.")]
     StakeSlashed,
+
+    #[msg("Unexpected number of remaining accounts")]
+    RemainingAccountsCountMismatch,
+
+    #[msg("Too many remaining accounts for a single instruction")]
+    TooManyRemainingAccounts,
+
+    #[msg("Remaining account is not owned by this program")]
+    InvalidRemainingAccountOwner,
+
+    #[msg("Remaining account does not match its expected PDA")]
+    InvalidRemainingAccountAddress,
+
+    #[msg("Vote accounts must be ordered by ascending challenger pubkey")]
+    MismatchedVoteAccountOrder,
+
+    #[msg("Stake accounts must be ordered by ascending challenger pubkey")]
+    MismatchedStakeAccountOrder,
+
+    #[msg("Challenge must be finalized with all rewards distributed")]
+    ChallengeNotSettled,
+
+    #[msg("Merkle tree does not match the registered archive tree")]
+    InvalidArchiveTree,
+
+    #[msg("Keeper is not eligible for crank tips")]
+    KeeperNotEligible,
+
+    #[msg("Keeper stake is still unbonding")]
+    KeeperUnbonding,
+
+    #[msg("Too many challenges created in the current rate-limit window")]
+    RateLimitExceeded,
+
+    #[msg("Every tranche in the funding schedule is already funded")]
+    TranchesFullyFunded,
+
+    #[msg("Payout proof does not match the posted root")]
+    InvalidPayoutProof,
+
+    #[msg("Evidence does not show matching votes across enough challenges")]
+    InsufficientCollusionEvidence,
+
+    #[msg("Wallet is flagged for collusion")]
+    WalletFlagged,
+
+    #[msg("Wallet is in a post-slash cooldown")]
+    SlashCooldownActive,
+
+    #[msg("Signer is not a registered oracle")]
+    UnknownOracle,
+
+    #[msg("Oracle nonce must exceed the last accepted nonce")]
+    StaleOracleNonce,
+
+    #[msg("Oracle payload hash does not match the submitted result")]
+    OraclePayloadMismatch,
+
+    #[msg("Too many oracles")]
+    TooManyOracles,
+
+    #[msg("No oracle reports supplied")]
+    NoOracleReports,
+
+    #[msg("Oracle reports must be ordered by ascending oracle pubkey")]
+    MismatchedOracleReportOrder,
+
+    #[msg("Challenge has not been settled yet")]
+    ChallengeStillActive,
+
+    #[msg("Missing or mismatched Ed25519 signature instruction")]
+    InvalidSignatureInstruction,
+
+    #[msg("Stake intent has expired")]
+    IntentExpired,
+
+    #[msg("Intent escrow balance is too low")]
+    InsufficientEscrow,
+
+    #[msg("Region policy account does not match the challenge's cell")]
+    InvalidRegionPolicy,
+
+    #[msg("Witness equipment class is below the challenge minimum")]
+    EquipmentClassTooLow,
+
+    #[msg("Device already has an active challenge at a different location")]
+    DeviceLocked,
+
+    #[msg("Previous challenge must be a different, passed challenge by the same prover")]
+    InvalidPreviousChallenge,
+
+    #[msg("Sponsor contributions must be refunded before the challenge closes")]
+    ContributionsOutstanding,
+
+    #[msg("Claims must go through an escrow account while the escrow period is set")]
+    ClaimEscrowRequired,
+
+    #[msg("Claim escrow is still in its dispute period")]
+    ClaimEscrowLocked,
+
+    #[msg("Claim escrow dispute period has ended")]
+    DisputePeriodOver,
+
+    #[msg("Challenge location has not been revealed yet")]
+    LocationNotRevealed,
+
+    #[msg("Stakes closed: the location is revealed or voting rounds have begun")]
+    StakeWindowClosed,
+
+    #[msg("Revealed location does not match the commitment")]
+    LocationCommitmentMismatch,
+
+    #[msg("Reward does not cover the relayer fee")]
+    RewardBelowRelayerFee,
+
+    #[msg("Challenge is frozen by the guardian")]
+    ChallengeFrozen,
+
+    #[msg("Challenge is not frozen")]
+    ChallengeNotFrozen,
+
+    #[msg("Token account has the wrong mint, owner or layout")]
+    InvalidTokenAccount,
+
+    #[msg("Swap returned less than the minimum amount out")]
+    SwapOutputTooLow,
+
+    #[msg("Challenge duration exceeds the longest configured tier")]
+    DurationTooLong,
+
+    #[msg("Challenge takes sealed votes only")]
+    VotesEncrypted,
+
+    #[msg("Challenge does not take sealed votes")]
+    VotesNotEncrypted,
+
+    #[msg("No vote committee is configured")]
+    CommitteeNotConfigured,
+
+    #[msg("Signer is not a vote committee member")]
+    NotCommitteeMember,
+
+    #[msg("Decryption share already submitted")]
+    DecryptionShareAlreadySubmitted,
+
+    #[msg("Not enough decryption shares to tally")]
+    DecryptionThresholdNotMet,
+
+    #[msg("Vote is not sealed")]
+    VoteNotSealed,
+
+    #[msg("Decrypted vote does not match its commitment")]
+    VoteCommitmentMismatch,
+
+    #[msg("Sealed votes must be tallied first")]
+    SealedVotesOutstanding,
+
+    #[msg("Challenge requires a credential attestation to stake")]
+    CredentialRequired,
+
+    #[msg("Attestation does not match the challenge's credential requirement")]
+    InvalidCredential,
+
+    #[msg("Credential attestation has expired")]
+    CredentialExpired,
+
+    #[msg("Vote references a candidate location the challenge doesn't have")]
+    InvalidCandidate,
+
+    #[msg("Result callback accounts are missing or don't match the registration")]
+    CallbackAccountsMismatch,
+
+    #[msg("Challenge id is outside the creator's namespace")]
+    NamespaceMismatch,
+
+    #[msg("Program is not an allowlisted integrator")]
+    UnknownIntegrator,
+
+    #[msg("Campaign budget can't cover this reward pool")]
+    CampaignBudgetExceeded,
+
+    #[msg("Campaign still has challenges whose results aren't reported")]
+    CampaignChallengesOpen,
+
+    #[msg("Challenge result must be reported to its campaign first")]
+    CampaignResultUnreported,
+
+    #[msg("Evidence does not support the stated slash reason")]
+    InvalidEvidence,
+
+    #[msg("Evidence has already been used for a slash")]
+    EvidenceConsumed,
+
+    #[msg("Region stats account does not match the challenge's cell")]
+    InvalidRegionStats,
+
+    #[msg("Creator already has the maximum number of open challenges")]
+    TooManyActiveChallenges,
+
+    #[msg("Prior result is not a recent passed result for this creator and location")]
+    InvalidPriorResult,
+    #[msg("Votes are only accepted while one of the challenge's scheduled rounds is open")]
+    OutsideVotingRound,
+    #[msg("Funder is not a PDA of an allowlisted funder program")]
+    UnknownFunder,
+    #[msg("No higher badge tier has been earned")]
+    NoTierUpgrade,
+    #[msg("Flash challenge already holds this witness's vote or is full")]
+    FlashChallengeFull,
+    #[msg("Result is not a passed result past its TTL, or was already flagged for re-verification")]
+    ResultStillCurrent,
+    #[msg("Challenge is not inconclusive")]
+    ChallengeNotInconclusive,
+    #[msg("Outstanding stakes must be returned first")]
+    StakesOutstanding,
+    #[msg("Standing stake balance is too low")]
+    InsufficientStandingStake,
+    #[msg("Standing stake stays locked until the challenge's dispute period ends")]
+    StandingStakeLocked,
+    #[msg("Instruction does not match the challenge's reward asset")]
+    RewardAssetMismatch,
+    #[msg("Emergency withdrawal timelock has not elapsed")]
+    TimelockNotElapsed,
+    #[msg("Emergency withdrawal exceeds the challenge's balance above rent")]
+    WithdrawalTooLarge,
+    #[msg("Stake can't be withdrawn until the challenge ends and its slash window closes")]
+    StakeStillLocked,
+    #[msg("Fewer oracle reports than the oracle set's quorum")]
+    OracleQuorumNotMet,
+    #[msg("Oracle quorum exceeds the number of registered oracles")]
+    OracleQuorumUnreachable,
+    #[msg("An oracle quorum is set; finalize through the oracles")]
+    OracleQuorumRequired,
+    #[msg("Stake receipt tokens must be burned first")]
+    ReceiptOutstanding,
+    #[msg("Stake has no receipt tokens outstanding")]
+    NoReceiptOutstanding,
+    #[msg("Stake receipts must be minted before voting")]
+    ReceiptAfterVote,
+    #[msg("Challenge would exceed the configured pool cap")]
+    PoolCapExceeded,
+    #[msg("R* must be computed from the votes before finalizing")]
+    RStarNotComputed,
+    #[msg("Challenger reputation is below the challenge's minimum")]
+    ReputationTooLow,
+    #[msg("Measurement is outside the challenge window or too old")]
+    StaleMeasurement,
+    #[msg("Challenge already has participants")]
+    ChallengeHasParticipants,
+    #[msg("Vote account was prepared but holds no vote yet")]
+    VoteNotCast,
+    #[msg("Challenge already has enough participants to finalize")]
+    ExtensionNotNeeded,
+    #[msg("R* threshold is outside the configured bounds")]
+    ThresholdOutOfBounds,
+    #[msg("Winners can still claim; the claim period isn't over")]
+    ClaimPeriodOpen,
+    #[msg("Claim policy must be set before voting")]
+    ClaimPolicyAfterVote,
+    #[msg("Reward is gated by a claim policy; use execute_claim")]
+    MultisigClaimRequired,
+    #[msg("Not enough claim approvals")]
+    InsufficientClaimApprovals,
+    #[msg("Challenge is still open; its expiry work isn't done")]
+    ChallengeStillOpen,
+    #[msg("Vote still has a reward to claim")]
+    VoteStillClaimable,
+    #[msg("Location is in an exclusion zone")]
+    LocationExcluded,
+    #[msg("R* is within the threshold; the waldo bond isn't forfeited")]
+    BondNotForfeited,
+    #[msg("Registry page is full; open the next one")]
+    RegistryPageFull,
+    #[msg("Vote isn't in the challenge's roster")]
+    NotInRoster,
+    #[msg("Vote roster is full")]
+    RosterFull,
+    #[msg("Settlement buffer after voting hasn't passed yet")]
+    SettlementBufferActive,
+    #[msg("Stake is paid back with the vote's reward")]
+    StakeInReward,
+    #[msg("Stake record only settled an overdue challenge; nothing was staked")]
+    VoidStake,
 }
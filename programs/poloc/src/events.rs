@@ -0,0 +1,518 @@
+use anchor_lang::prelude::*;
+use crate::state::{ChallengeStatus, KeeperFault, SlashReason};
+
+// Every event starts with `schema_version`. Fields are only ever appended,
+// never reordered or removed; appending a field bumps this constant. Decoders
+// built against an older version can therefore read the prefix they know and
+// ignore the rest (see `poloc_client::events`).
+pub const EVENT_SCHEMA_VERSION: u8 = 2;
+
+#[event]
+pub struct ChallengeInitialized {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenge_id: String,
+    pub waldo: Pubkey,
+    pub claimed_lat: i32,
+    pub claimed_lon: i32,
+    pub deadline: i64,
+    pub reward_pool: u64,
+    pub context: [u8; 32],              // since v2
+}
+
+// Emitted instead of `ChallengeInitialized` for private challenges. The
+// commitment is keccak(challenge_id || claimed_lat || claimed_lon || salt),
+// little-endian coordinates, with the salt held by the creator.
+#[event]
+pub struct PrivateChallengeInitialized {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenge_id: String,
+    pub waldo: Pubkey,
+    pub location_commitment: [u8; 32],
+    pub deadline: i64,
+    pub reward_pool: u64,
+    pub context: [u8; 32],              // since v2
+}
+
+#[event]
+pub struct Staked {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenger: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct VoteSubmitted {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenger: Pubkey,
+    pub is_valid: bool,
+    pub uncertainty: u32,
+    pub min_rtt: u32,
+}
+
+#[event]
+pub struct ChallengeFinalized {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub status: ChallengeStatus,
+    pub r_star: u32,
+    pub r_star_threshold: u32,
+    pub passed: bool,
+    pub context: [u8; 32],              // since v2
+}
+
+#[event]
+pub struct PayoutRootPosted {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub root: [u8; 32],
+    // Total over all leaves. Each winner's leaf amount is
+    // `poloc_core::rewards::payout_shares` over the winners' vote stakes.
+    pub amount: u64,
+    pub winners: Vec<Pubkey>,
+    // Raw bytes of the vote accounts closed at settlement.
+    pub vote_data: Vec<Vec<u8>>,
+}
+
+#[event]
+pub struct RewardClaimed {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub winner: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ChallengeRefunded {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub waldo: Pubkey,
+    pub amount: u64,
+}
+
+// A challenge withdrawn by its creator before anyone staked. `amount` is
+// everything the challenge and vault held, less a funder's pool.
+#[event]
+pub struct ChallengeCancelled {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub waldo: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ChallengeArchived {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenge_id: String,
+    pub leaf: [u8; 32],
+    pub leaf_index: u64,
+    // Raw account bytes hashed into `leaf`, so the snapshot can be recovered
+    // from transaction logs after the accounts are closed.
+    pub challenge_data: Vec<u8>,
+    pub vote_data: Vec<Vec<u8>>,
+}
+
+#[event]
+pub struct TrancheFunded {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub amount: u64,
+    pub active_tranches: u8,
+    pub total_tranches: u8,
+}
+
+#[event]
+pub struct ContributionReceived {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+    pub reward_pool: u64,
+}
+
+#[event]
+pub struct SponsorRefunded {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub sponsor: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ClaimEscrowSettled {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount: u64,
+    pub released: bool, // false when reverted by governance
+}
+
+#[event]
+pub struct LocationRevealed {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub claimed_lat: i32,
+    pub claimed_lon: i32,
+    pub participant_count: u32,
+}
+
+#[event]
+pub struct OraclePenalized {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub oracle: Pubkey,
+    pub r_star: u32,
+    pub median: u32,
+    pub slashed: u64,
+    pub score: u32,
+}
+
+#[event]
+pub struct KeeperPenalized {
+    pub schema_version: u8,
+    pub keeper: Pubkey,
+    pub operator: Pubkey,
+    pub fault: KeeperFault,
+    pub score: u32,
+    pub slashed: u64,
+}
+
+#[event]
+pub struct EvidenceSubmitted {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenger: Pubkey,
+    pub evidence: Pubkey,
+    pub reason: SlashReason,
+}
+
+#[event]
+pub struct StakeSlashed {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenger: Pubkey,
+    // Lamports forfeited; below the stake for a low-confidence wrong vote.
+    pub amount: u64,
+}
+
+#[event]
+pub struct ChallengeFreezeChanged {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub guardian: Pubkey,
+    pub frozen: bool,
+}
+
+#[event]
+pub struct RewardSwapped {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub winner: Pubkey,
+    pub lamports_in: u64,
+    pub output_mint: Pubkey,
+    pub amount_out: u64,
+}
+
+#[event]
+pub struct AccountingDiscrepancy {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub expected: u64,
+    pub actual: u64,
+}
+
+// The ciphertext is only logged; the vote account keeps its commitment.
+#[event]
+pub struct EncryptedVoteSubmitted {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenger: Pubkey,
+    pub commitment: [u8; 32],
+    pub ciphertext: Vec<u8>,
+}
+
+#[event]
+pub struct CampaignCreated {
+    pub schema_version: u8,
+    pub campaign: Pubkey,
+    pub authority: Pubkey,
+    pub budget: u64,
+}
+
+// Running campaign totals as each challenge's result comes in.
+#[event]
+pub struct CampaignChallengeReported {
+    pub schema_version: u8,
+    pub campaign: Pubkey,
+    pub challenge: Pubkey,
+    pub passed: bool,
+    pub reward_pool: u64,
+    pub passed_count: u32,
+    pub failed_count: u32,
+    pub open_challenges: u32,
+}
+
+#[event]
+pub struct CampaignReconciled {
+    pub schema_version: u8,
+    pub campaign: Pubkey,
+    pub budget: u64,
+    pub allocated: u64,
+    pub returned: u64,
+    pub challenge_count: u32,
+    pub passed_count: u32,
+    pub failed_count: u32,
+}
+
+#[event]
+pub struct CallbackRegistered {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub program: Pubkey,
+    pub account_count: u8,
+}
+
+// Emitted at finalize for any-of challenges; the winning candidate's support
+// replaces the challenge-wide valid counts.
+#[event]
+pub struct CandidateSelected {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub candidate: u8,
+    pub lat: i32,
+    pub lon: i32,
+    pub valid_votes: u32,
+    pub valid_stake: u64,
+}
+
+#[event]
+pub struct DecryptionShareSubmitted {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub member: Pubkey,
+    pub share: Vec<u8>,
+}
+
+// The badge balance equals `tier`.
+#[event]
+pub struct TierBadgeClaimed {
+    pub schema_version: u8,
+    pub wallet: Pubkey,
+    pub tier: u8,
+    pub honest_votes: u64,
+}
+
+// Flash challenges close at finalize, so this is their only lasting record.
+#[event]
+pub struct FlashChallengeFinalized {
+    pub schema_version: u8,
+    pub flash: Pubkey,
+    pub waldo: Pubkey,
+    pub flash_id: u64,
+    pub claimed_lat: i32,
+    pub claimed_lon: i32,
+    pub r_star: u32,
+    pub vote_count: u8,
+    pub passed: bool,
+}
+
+// A passed result's TTL has run out; the prover should re-verify.
+#[event]
+pub struct ReverificationDue {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub waldo: Pubkey,
+    pub expires_at: i64,
+}
+
+// A stake handed back whole because its challenge was inconclusive.
+#[event]
+pub struct StakeReturned {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenger: Pubkey,
+    pub amount: u64,
+}
+
+// A stake locked in the witness's standing stake; unlike `Staked`, nothing
+// joins the reward pool.
+#[event]
+pub struct StandingStakeLocked {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenger: Pubkey,
+    pub amount: u64,
+}
+
+// `forfeited` moved from the standing stake into the challenge's pool.
+#[event]
+pub struct StandingStakeReleased {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenger: Pubkey,
+    pub released: u64,
+    pub forfeited: u64,
+}
+
+// Governance queued lamports to leave a frozen challenge.
+#[event]
+pub struct EmergencyWithdrawalProposed {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct EmergencyWithdrawalCancelled {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+}
+
+// `pool_debit` is the part of `amount` that came out of the reward pool and
+// bond rather than untracked surplus.
+#[event]
+pub struct EmergencyWithdrawalExecuted {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub destination: Pubkey,
+    pub amount: u64,
+    pub pool_debit: u64,
+}
+
+// An unslashed stake withdrawn by its challenger once the challenge ended.
+#[event]
+pub struct Unstaked {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenger: Pubkey,
+    pub amount: u64,
+}
+
+// Receipt tokens minted for a stake's position, and later burned to unlock it.
+#[event]
+pub struct StakeReceiptMinted {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenger: Pubkey,
+    pub receipt_account: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct StakeReceiptRedeemed {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenger: Pubkey,
+    pub holder: Pubkey,
+    pub amount: u64,
+}
+
+// R* taken on-chain from a challenge's votes, ahead of creator finalization.
+#[event]
+pub struct RStarComputed {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub r_star: u32,
+    pub valid_votes: u32,
+}
+
+// Insurance pool lamports added to a claim for the winner's streak.
+#[event]
+pub struct StreakBonusPaid {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub winner: Pubkey,
+    pub streak: u32,
+    pub amount: u64,
+}
+
+// Logged right before a handler fails on a timing check. `opens_at` and
+// `closes_at` bound when the call would have been accepted (inclusive);
+// `error_code` is the error the transaction fails with.
+#[event]
+pub struct TimingWindowMissed {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub error_code: u32,
+    pub now: i64,
+    pub opens_at: i64,
+    pub closes_at: i64,
+}
+
+// Unclaimed rewards moved to the insurance pool; the challenge is closed.
+#[event]
+pub struct UnclaimedRewardsSwept {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct ChallengeExtended {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub deadline: i64,
+    pub added_reward: u64,
+    pub reward_pool: u64,
+}
+
+// An invalid voter's share of a failed claim's forfeited waldo bond.
+#[event]
+pub struct WaldoBondClaimed {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenger: Pubkey,
+    pub amount: u64,
+}
+
+// Post-settlement summary from `emit_challenge_report`: one record of the
+// challenge's timings, votes, payouts and slashes as they stand. Never
+// carries coordinates, so private challenges can report too.
+#[event]
+pub struct ChallengeReported {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub challenge_id: String,
+    pub waldo: Pubkey,
+    pub status: ChallengeStatus,
+    pub passed: bool,
+    pub start_time: i64,
+    pub deadline: i64,
+    pub voting_ends_at: i64,
+    pub claim_deadline: i64,
+    pub expires_at: i64,
+    pub participant_count: u32,
+    pub vote_count: u32,
+    pub valid_vote_count: u32,
+    pub total_staked: u64,
+    pub valid_stake: u64,
+    pub invalid_stake: u64,
+    pub r_star: u32,
+    pub r_star_threshold: u32,
+    pub quorum_met: bool,
+    pub reward_pool: u64,
+    pub rewards_claimed: u64,
+    pub reward_claims: u32,
+    pub waldo_bond: u64,
+    pub bond_claimed_stake: u64,
+    pub slashed_total: u64,
+    pub slash_count: u32,
+    pub context: [u8; 32],
+}
+
+// A vote linked into its challenge's vote chain; see `Challenge::chain_vote`.
+// Indexers can rebuild the chain from these alone.
+#[event]
+pub struct VoteChained {
+    pub schema_version: u8,
+    pub challenge: Pubkey,
+    pub vote: Pubkey,
+    pub chain_index: u32,
+    pub chain_hash: [u8; 32],
+}
@@ -0,0 +1,159 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::compression::{self, SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID};
+use crate::instructions::unstake::require_stakes_returned;
+use crate::utils::close_program_account;
+use crate::validation::load_vote_accounts;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ArchiveChallenge<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = challenge.load()?.campaign == Pubkey::default() || challenge.load()?.campaign_reported != 0
+            @ PolocError::CampaignResultUnreported,
+        // Only rent is left once rewards are distributed; it goes back to the creator.
+        close = waldo
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, challenge_id.as_bytes()],
+        bump = vault.bump,
+        // Rounding dust and the bond go back to the creator with the rent.
+        close = waldo
+    )]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(mut, address = challenge.load()?.waldo @ PolocError::Unauthorized)]
+    pub waldo: SystemAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [ARCHIVE_SEED],
+        bump = archive.bump,
+        has_one = merkle_tree @ PolocError::InvalidArchiveTree
+    )]
+    pub archive: Account<'info, Archive>,
+
+    /// CHECK: Matched against `archive.merkle_tree`; validated by the compression program.
+    #[account(mut)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Address-constrained to spl-account-compression.
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Address-constrained to spl-noop.
+    #[account(address = SPL_NOOP_ID)]
+    pub noop: UncheckedAccount<'info>,
+
+    // Optional: a registered keeper running this crank is tipped from the
+    // challenge's rent and credited with a successful crank.
+    #[account(mut)]
+    pub keeper: Option<Account<'info, Keeper>>,
+
+    #[account(mut)]
+    pub keeper_operator: Option<Signer<'info>>,
+}
+
+/// Leaf committed to the archive tree: keccak over the raw Challenge account
+/// bytes followed by each Vote account's bytes, in ascending challenger order.
+pub fn archive_leaf(challenge_data: &[u8], vote_data: &[Vec<u8>]) -> [u8; 32] {
+    let mut parts: Vec<&[u8]> = Vec::with_capacity(vote_data.len() + 1);
+    parts.push(challenge_data);
+    parts.extend(vote_data.iter().map(|v| v.as_slice()));
+    keccak::hashv(&parts).to_bytes()
+}
+
+// Permissionless: anyone may archive a settled challenge. `remaining_accounts`
+// holds every Vote PDA (ascending by challenger) followed by each vote's
+// challenger wallet in the same order, which receives the vote's rent.
+pub fn handler<'info>(ctx: Context<'_, '_, 'info, 'info, ArchiveChallenge<'info>>, _challenge_id: String) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &ctx.accounts.challenge.load()?;
+
+    require!(
+        challenge.status() == ChallengeStatus::Finalized && challenge.rewards_distributed != 0,
+        PolocError::ChallengeNotSettled
+    );
+    require_stakes_returned(challenge)?;
+
+    // Payout-root settlement already closed the votes and logged their bytes.
+    let vote_count = challenge.archived_vote_count();
+    let remaining = ctx.remaining_accounts;
+    require!(remaining.len() == vote_count * 2, PolocError::RemainingAccountsCountMismatch);
+    let (vote_infos, wallet_infos) = remaining.split_at(vote_count);
+
+    // Validate every account before touching anything.
+    let votes = load_vote_accounts(vote_infos, challenge.challenge_id(), Some(vote_count))?;
+    for (vote, wallet) in votes.iter().zip(wallet_infos) {
+        require_keys_eq!(wallet.key(), vote.challenger, PolocError::InvalidRemainingAccountAddress);
+    }
+
+    let challenge_data = ctx.accounts.challenge.to_account_info().try_borrow_data()?.to_vec();
+    let vote_data = vote_infos
+        .iter()
+        .map(|info| Ok(info.try_borrow_data()?.to_vec()))
+        .collect::<Result<Vec<_>>>()?;
+    let leaf = archive_leaf(&challenge_data, &vote_data);
+
+    let archive = &mut ctx.accounts.archive;
+    let bump = [archive.bump];
+    let signer_seeds: &[&[u8]] = &[ARCHIVE_SEED, &bump];
+    compression::append_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &archive.to_account_info(),
+        &ctx.accounts.noop.to_account_info(),
+        &[signer_seeds],
+        leaf,
+    )?;
+
+    for (vote_info, wallet) in vote_infos.iter().zip(wallet_infos) {
+        close_program_account(vote_info, wallet)?;
+    }
+
+    if let (Some(keeper), Some(operator)) = (ctx.accounts.keeper.as_mut(), ctx.accounts.keeper_operator.as_ref()) {
+        require_keys_eq!(keeper.operator, operator.key(), PolocError::Unauthorized);
+        require!(keeper.is_eligible(), PolocError::KeeperNotEligible);
+
+        let challenge_info = ctx.accounts.challenge.to_account_info();
+        let tip = Keeper::CRANK_TIP.min(challenge_info.lamports());
+        **challenge_info.try_borrow_mut_lamports()? -= tip;
+        **operator.to_account_info().try_borrow_mut_lamports()? += tip;
+
+        keeper.successful_cranks = keeper.successful_cranks.saturating_add(1);
+        keeper.score = keeper.score.saturating_add(1).min(Keeper::MAX_SCORE);
+        keeper.last_active = Clock::get()?.unix_timestamp;
+        msg!("Keeper {} tipped {} lamports", keeper.operator, tip);
+    }
+
+    let archive = &mut ctx.accounts.archive;
+    let leaf_index = archive.leaf_count;
+    archive.leaf_count = archive.leaf_count
+        .checked_add(1)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    msg!("Challenge {} archived as leaf {}", challenge.challenge_id(), leaf_index);
+
+    emit!(ChallengeArchived {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenge_id: challenge.challenge_id().to_string(),
+        leaf,
+        leaf_index,
+        challenge_data,
+        vote_data,
+    });
+
+    Ok(())
+}
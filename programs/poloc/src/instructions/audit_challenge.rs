@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::events::*;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct AuditChallenge<'info> {
+    // Read-only, so frozen challenges can still be audited.
+    #[account(
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+}
+
+// Permissionless accounting check. Stakes, tranches and sponsor contributions
+// all land in `reward_pool`, so the vault should hold exactly the pool, the
+// creator's bond and its rent. A token pool sits in its token account
+// instead. Any difference is emitted rather than failing the transaction, so
+// monitors can index it.
+pub fn handler(ctx: Context<AuditChallenge>, challenge_id: String) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &ctx.accounts.challenge.load()?;
+    let info = ctx.accounts.vault.to_account_info();
+    let rent = Rent::get()?.minimum_balance(info.data_len());
+    let pool = if challenge.is_token_pool() { 0 } else { challenge.reward_pool };
+    let expected = (rent as u128) + (pool as u128) + (challenge.waldo_bond as u128);
+    let actual = info.lamports();
+
+    if expected == actual as u128 {
+        msg!("Challenge {} balanced at {} lamports", challenge_id, actual);
+        return Ok(());
+    }
+
+    let expected = u64::try_from(expected).unwrap_or(u64::MAX);
+    msg!("Challenge {} holds {} lamports, expected {}", challenge_id, actual, expected);
+    emit!(AccountingDiscrepancy {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        expected,
+        actual,
+    });
+    Ok(())
+}
@@ -0,0 +1,195 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes as slot_hashes_sysvar;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::validation::challenge_nonce;
+
+// Localnet/devnet only (`demo` feature): sets up a config, three witness
+// profiles and a sample challenge in one transaction, with the payer as
+// config admin and challenge creator. Never build this into a mainnet program.
+
+pub const DEMO_WITNESSES: usize = 3;
+pub const DEMO_LAT: i32 = 48_858_370;    // Eiffel Tower
+pub const DEMO_LON: i32 = 2_294_481;
+pub const DEMO_DURATION: i64 = 600;
+pub const DEMO_REWARD: u64 = 10_000_000; // 0.01 SOL
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, witnesses: [Pubkey; DEMO_WITNESSES])]
+pub struct BootstrapDemo<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + Config::MAX_SIZE,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = Challenge::MAX_SIZE,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ChallengeVault::MAX_SIZE,
+        seeds = [VAULT_SEED, challenge_id.as_bytes()],
+        bump
+    )]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + WaldoProfile::MAX_SIZE,
+        seeds = [WALDO_SEED, payer.key().as_ref()],
+        bump
+    )]
+    pub waldo_profile: Account<'info, WaldoProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ChallengerProfile::MAX_SIZE,
+        seeds = [CHALLENGER_SEED, witnesses[0].as_ref()],
+        bump
+    )]
+    pub witness_profile_0: Account<'info, ChallengerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ChallengerProfile::MAX_SIZE,
+        seeds = [CHALLENGER_SEED, witnesses[1].as_ref()],
+        bump
+    )]
+    pub witness_profile_1: Account<'info, ChallengerProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ChallengerProfile::MAX_SIZE,
+        seeds = [CHALLENGER_SEED, witnesses[2].as_ref()],
+        bump
+    )]
+    pub witness_profile_2: Account<'info, ChallengerProfile>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Address-constrained to the SlotHashes sysvar.
+    #[account(address = slot_hashes_sysvar::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(
+    ctx: Context<BootstrapDemo>,
+    challenge_id: String,
+    witnesses: [Pubkey; DEMO_WITNESSES],
+) -> Result<()> {
+    let payer = ctx.accounts.payer.key();
+    let clock = Clock::get()?;
+
+    let config = &mut ctx.accounts.config;
+    if config.admin == Pubkey::default() {
+        config.admin = payer;
+        config.guardian = payer;
+        config.max_challenges_per_window = Config::DEFAULT_MAX_CHALLENGES_PER_WINDOW;
+        config.rate_limit_window = Config::DEFAULT_RATE_LIMIT_WINDOW;
+        config.slash_cooldown = Config::DEFAULT_SLASH_COOLDOWN;
+        config.oracle_report_bond = Config::DEFAULT_ORACLE_REPORT_BOND;
+        config.oracle_deviation_band_bps = Config::DEFAULT_ORACLE_DEVIATION_BAND_BPS;
+        config.min_stake = Config::DEFAULT_MIN_STAKE;
+        config.min_participants = Config::DEFAULT_MIN_PARTICIPANTS;
+        config.vote_quorum_bps = Config::DEFAULT_VOTE_QUORUM_BPS;
+        config.stake_quorum_bps = Config::DEFAULT_STAKE_QUORUM_BPS;
+        config.relayer_claim_fee = Config::DEFAULT_RELAYER_CLAIM_FEE;
+        config.retry_band_bps = Config::DEFAULT_RETRY_BAND_BPS;
+        config.retry_discount_bps = Config::DEFAULT_RETRY_DISCOUNT_BPS;
+        config.duration_tiers = Config::DEFAULT_DURATION_TIERS;
+        config.vote_gas_rebate = Config::DEFAULT_VOTE_GAS_REBATE;
+        config.min_witness_compensation = Config::DEFAULT_MIN_WITNESS_COMPENSATION;
+        config.max_active_challenges = Config::DEFAULT_MAX_ACTIVE_CHALLENGES;
+        config.prior_validity_window = Config::DEFAULT_PRIOR_VALIDITY_WINDOW;
+        config.prior_quorum_relief_bps = Config::DEFAULT_PRIOR_QUORUM_RELIEF_BPS;
+        config.result_ttl = Config::DEFAULT_RESULT_TTL;
+        config.voting_window = Config::DEFAULT_VOTING_WINDOW;
+        config.max_participants = Config::DEFAULT_MAX_PARTICIPANTS;
+        config.r_star_threshold = Config::DEFAULT_R_STAR_THRESHOLD;
+        config.max_pool_lamports = Config::DEFAULT_MAX_POOL_LAMPORTS;
+        config.measurement_max_age = Config::DEFAULT_MEASUREMENT_MAX_AGE;
+        config.min_r_star_threshold = Config::DEFAULT_MIN_R_STAR_THRESHOLD;
+        config.max_r_star_threshold = Config::DEFAULT_MAX_R_STAR_THRESHOLD;
+        config.claim_period = Config::DEFAULT_CLAIM_PERIOD;
+        config.exclusion_zones = [ExclusionZone::default(); MAX_EXCLUSION_ZONES];
+        config.min_settlement_buffer = Config::DEFAULT_MIN_SETTLEMENT_BUFFER;
+        config.bump = ctx.bumps.config;
+    }
+
+    let profiles = [
+        (&mut ctx.accounts.witness_profile_0, ctx.bumps.witness_profile_0),
+        (&mut ctx.accounts.witness_profile_1, ctx.bumps.witness_profile_1),
+        (&mut ctx.accounts.witness_profile_2, ctx.bumps.witness_profile_2),
+    ];
+    for ((profile, bump), wallet) in profiles.into_iter().zip(witnesses) {
+        if profile.wallet == Pubkey::default() {
+            profile.wallet = wallet;
+            profile.bump = bump;
+        }
+    }
+
+    let waldo_profile = &mut ctx.accounts.waldo_profile;
+    if waldo_profile.waldo == Pubkey::default() {
+        waldo_profile.waldo = payer;
+        waldo_profile.window_start = clock.unix_timestamp;
+        waldo_profile.bump = ctx.bumps.waldo_profile;
+    }
+    waldo_profile.total_challenges = waldo_profile.total_challenges.saturating_add(1);
+    waldo_profile.active_challenges = waldo_profile.active_challenges.saturating_add(1);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.payer.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        ),
+        DEMO_REWARD,
+    )?;
+    ctx.accounts.vault.challenge = ctx.accounts.challenge.key();
+    ctx.accounts.vault.bump = ctx.bumps.vault;
+
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_init()?;
+    challenge.set_challenge_id(&challenge_id);
+    challenge.waldo = payer;
+    challenge.arbiter = payer;
+    challenge.measurement_nonce = challenge_nonce(&ctx.accounts.slot_hashes, &challenge_key)?;
+    challenge.claimed_lat = DEMO_LAT;
+    challenge.claimed_lon = DEMO_LON;
+    challenge.start_time = clock.unix_timestamp;
+    challenge.deadline = clock.unix_timestamp + DEMO_DURATION;
+    challenge.reward_pool = DEMO_REWARD;
+    challenge.set_status(ChallengeStatus::Active);
+    challenge.r_star_threshold = ctx.accounts.config.r_star_threshold;
+    challenge.voting_window = ctx.accounts.config.voting_window;
+    challenge.measurement_max_age = ctx.accounts.config.measurement_max_age;
+    challenge.bump = ctx.bumps.challenge;
+    challenge.tranche_amount = DEMO_REWARD;
+    challenge.total_tranches = 1;
+    challenge.active_tranches = 1;
+    challenge.holds_active_slot = 1;
+
+    msg!("Demo bootstrapped: challenge {} with {} witness profiles", challenge.challenge_id(), DEMO_WITNESSES);
+    Ok(())
+}
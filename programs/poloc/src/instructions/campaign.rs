@@ -0,0 +1,298 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes as slot_hashes_sysvar;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::claim_escrow::move_lamports;
+use crate::instructions::initialize_challenge::{create_challenge, InitializeChallengeArgs, NewChallengeAccounts};
+
+#[derive(Accounts)]
+#[instruction(campaign_id: String)]
+pub struct CreateCampaign<'info> {
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Campaign::MAX_SIZE,
+        seeds = [CAMPAIGN_SEED, campaign_id.as_bytes()],
+        bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(campaign_id: String, args: InitializeChallengeArgs)]
+pub struct InitializeCampaignChallenge<'info> {
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, campaign_id.as_bytes()],
+        bump = campaign.bump,
+        constraint = campaign.authority == waldo.key() @ PolocError::Unauthorized
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(
+        init,
+        payer = waldo,
+        space = Challenge::MAX_SIZE,
+        seeds = [CHALLENGE_SEED, args.challenge_id.as_bytes()],
+        bump
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        init,
+        payer = waldo,
+        space = 8 + ChallengeVault::MAX_SIZE,
+        seeds = [VAULT_SEED, args.challenge_id.as_bytes()],
+        bump
+    )]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = waldo,
+        space = 8 + WaldoProfile::MAX_SIZE,
+        seeds = [WALDO_SEED, waldo.key().as_ref()],
+        bump
+    )]
+    pub waldo_profile: Account<'info, WaldoProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = waldo,
+        space = 8 + DeviceLock::MAX_SIZE,
+        seeds = [DEVICE_SEED, args.device_hash.as_ref()],
+        bump
+    )]
+    pub device_lock: Account<'info, DeviceLock>,
+
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    #[account(mut)]
+    pub waldo: Signer<'info>,
+
+    /// CHECK: Address-constrained to the SlotHashes sysvar; seeds the measurement nonce.
+    #[account(address = slot_hashes_sysvar::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(campaign_id: String, challenge_id: String)]
+pub struct ReportCampaignResult<'info> {
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, campaign_id.as_bytes()],
+        bump = campaign.bump
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = challenge.load()?.campaign == campaign.key() @ PolocError::InvalidParameters
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+}
+
+#[derive(Accounts)]
+#[instruction(campaign_id: String)]
+pub struct ReconcileCampaign<'info> {
+    #[account(
+        mut,
+        seeds = [CAMPAIGN_SEED, campaign_id.as_bytes()],
+        bump = campaign.bump,
+        has_one = authority @ PolocError::Unauthorized,
+        // Unallocated budget and rent go back to the authority.
+        close = authority
+    )]
+    pub campaign: Account<'info, Campaign>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+}
+
+pub fn create_handler(
+    ctx: Context<CreateCampaign>,
+    campaign_id: String,
+    budget: u64,
+    max_pool: u64,
+) -> Result<()> {
+    require!(campaign_id.len() <= 32, PolocError::InvalidParameters);
+    require!(budget > 0, PolocError::InvalidParameters);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.authority.to_account_info(),
+                to: ctx.accounts.campaign.to_account_info(),
+            },
+        ),
+        budget,
+    )?;
+
+    let campaign = &mut ctx.accounts.campaign;
+    campaign.authority = ctx.accounts.authority.key();
+    campaign.campaign_id = campaign_id;
+    campaign.budget = budget;
+    campaign.allocated = 0;
+    campaign.max_pool = max_pool;
+    campaign.bump = ctx.bumps.campaign;
+
+    msg!("Campaign {} created with a budget of {} lamports", campaign.campaign_id, budget);
+
+    emit!(CampaignCreated {
+        schema_version: EVENT_SCHEMA_VERSION,
+        campaign: campaign.key(),
+        authority: campaign.authority,
+        budget,
+    });
+    Ok(())
+}
+
+// The pool comes out of the campaign budget; the creator still pays rent, the
+// creation fee and the bond. Campaign pools are escrowed whole, not in tranches.
+pub fn create_challenge_handler(
+    ctx: Context<InitializeCampaignChallenge>,
+    campaign_id: String,
+    args: InitializeChallengeArgs,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+    let reward_pool = args.reward_pool;
+    require!(args.tranches <= 1, PolocError::InvalidParameters);
+    require!(
+        accounts.campaign.max_pool == 0 || reward_pool <= accounts.campaign.max_pool,
+        PolocError::CampaignBudgetExceeded
+    );
+    require!(reward_pool <= accounts.campaign.remaining(), PolocError::CampaignBudgetExceeded);
+
+    move_lamports(
+        &accounts.campaign.to_account_info(),
+        &accounts.waldo.to_account_info(),
+        reward_pool,
+    )?;
+    create_challenge(
+        NewChallengeAccounts {
+            challenge: &accounts.challenge,
+            vault: &mut accounts.vault,
+            config: &accounts.config,
+            waldo_profile: &mut accounts.waldo_profile,
+            device_lock: &mut accounts.device_lock,
+            insurance_pool: &mut accounts.insurance_pool,
+            waldo: &accounts.waldo,
+            system_program: &accounts.system_program,
+            slot_hashes: &accounts.slot_hashes,
+            prior_result: None,
+            fee_waivers: None,
+            waiver_attestation: None,
+            expiry_feed: None,
+            registry: None,
+            funder: None,
+            token_pool: None,
+            challenge_bump: ctx.bumps.challenge,
+            vault_bump: ctx.bumps.vault,
+            waldo_profile_bump: ctx.bumps.waldo_profile,
+            device_lock_bump: ctx.bumps.device_lock,
+        },
+        args,
+        accounts.config.r_star_threshold,
+        0,
+        None,
+    )?;
+
+    let campaign = &mut accounts.campaign;
+    accounts.challenge.load_mut()?.campaign = campaign.key();
+    campaign.allocated = campaign.allocated
+        .checked_add(reward_pool)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    campaign.challenge_count = campaign.challenge_count
+        .checked_add(1)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    campaign.open_challenges = campaign.open_challenges
+        .checked_add(1)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    msg!("Campaign {} funded challenge {} with {} lamports",
+         campaign_id, accounts.challenge.load()?.challenge_id(), reward_pool);
+    Ok(())
+}
+
+// Permissionless once the challenge is settled. The challenge can't be
+// refunded or archived until its result is in the campaign totals.
+pub fn report_handler(
+    ctx: Context<ReportCampaignResult>,
+    campaign_id: String,
+    challenge_id: String,
+) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    let campaign = &mut ctx.accounts.campaign;
+    require!(!challenge.is_open(), PolocError::ChallengeStillActive);
+    require!(challenge.campaign_reported == 0, PolocError::InvalidParameters);
+
+    let passed = challenge.status() == ChallengeStatus::Finalized && challenge.passed();
+    challenge.campaign_reported = 1;
+    campaign.open_challenges = campaign.open_challenges.saturating_sub(1);
+    if passed {
+        campaign.passed_count = campaign.passed_count
+            .checked_add(1)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+    } else {
+        campaign.failed_count = campaign.failed_count
+            .checked_add(1)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+    }
+
+    msg!("Campaign {}: challenge {} {} ({} passed, {} failed, {} open)",
+         campaign_id, challenge_id, if passed { "passed" } else { "failed" },
+         campaign.passed_count, campaign.failed_count, campaign.open_challenges);
+
+    emit!(CampaignChallengeReported {
+        schema_version: EVENT_SCHEMA_VERSION,
+        campaign: campaign.key(),
+        challenge: challenge_key,
+        passed,
+        reward_pool: challenge.reward_pool,
+        passed_count: campaign.passed_count,
+        failed_count: campaign.failed_count,
+        open_challenges: campaign.open_challenges,
+    });
+    Ok(())
+}
+
+// Final statement once every challenge has reported; closes the campaign.
+pub fn reconcile_handler(ctx: Context<ReconcileCampaign>, campaign_id: String) -> Result<()> {
+    let campaign = &ctx.accounts.campaign;
+    require!(campaign.open_challenges == 0, PolocError::CampaignChallengesOpen);
+    let returned = campaign.remaining();
+
+    msg!("Campaign {} reconciled: {} of {} lamports allocated, {} returned",
+         campaign_id, campaign.allocated, campaign.budget, returned);
+
+    emit!(CampaignReconciled {
+        schema_version: EVENT_SCHEMA_VERSION,
+        campaign: campaign.key(),
+        budget: campaign.budget,
+        allocated: campaign.allocated,
+        returned,
+        challenge_count: campaign.challenge_count,
+        passed_count: campaign.passed_count,
+        failed_count: campaign.failed_count,
+    });
+    Ok(())
+}
@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::claim_escrow::move_lamports;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct CancelChallenge<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch,
+        // A campaign counts its challenges' results; one that vanished
+        // unreported would leave the campaign short.
+        constraint = challenge.load()?.campaign == Pubkey::default() @ PolocError::CampaignResultUnreported,
+        close = waldo
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    // The pool, bond and rent go back with the vault.
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, challenge_id.as_bytes()],
+        bump = vault.bump,
+        close = waldo
+    )]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(mut, address = challenge.load()?.waldo @ PolocError::Unauthorized)]
+    pub waldo: Signer<'info>,
+
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Account<'info, WaldoProfile>,
+
+    /// CHECK: Must be `challenge.funder`; required when one paid the pool.
+    #[account(mut)]
+    pub funder: Option<UncheckedAccount<'info>>,
+}
+
+// Lets a creator back out of a challenge nobody has staked in yet, e.g. one
+// opened with the wrong coordinates or duration. Once a stake lands the
+// challenge has to run to settlement like any other.
+pub fn handler(ctx: Context<CancelChallenge>, challenge_id: String) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require!(challenge.participant_count == 0, PolocError::ChallengeHasParticipants);
+    require!(challenge.open_contributions == 0, PolocError::ContributionsOutstanding);
+
+    ctx.accounts.waldo_profile.release_active(challenge);
+
+    if challenge.funder != Pubkey::default() {
+        let funder = ctx.accounts.funder.as_ref().ok_or(PolocError::UnknownFunder)?;
+        require_keys_eq!(funder.key(), challenge.funder, PolocError::UnknownFunder);
+        move_lamports(&ctx.accounts.vault.to_account_info(), &funder.to_account_info(), challenge.reward_pool)?;
+        msg!("Returned {} lamports to funder {}", challenge.reward_pool, challenge.funder);
+    }
+
+    msg!("Challenge {} cancelled by its creator", challenge_id);
+    emit!(ChallengeCancelled {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        waldo: challenge.waldo,
+        amount: ctx.accounts.challenge.to_account_info().lamports() + ctx.accounts.vault.to_account_info().lamports(),
+    });
+    Ok(())
+}
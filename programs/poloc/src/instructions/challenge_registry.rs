@@ -0,0 +1,189 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+pub struct InitializeChallengeRegistry<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ChallengeRegistry::MAX_SIZE,
+        seeds = [REGISTRY_SEED],
+        bump
+    )]
+    pub challenge_registry: Account<'info, ChallengeRegistry>,
+
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + RegistryPage::MAX_SIZE,
+        seeds = [REGISTRY_PAGE_SEED, &0u32.to_le_bytes()],
+        bump
+    )]
+    pub registry_page: Account<'info, RegistryPage>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct OpenRegistryPage<'info> {
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Account<'info, ChallengeRegistry>,
+
+    #[account(
+        seeds = [REGISTRY_PAGE_SEED, &challenge_registry.current_page.to_le_bytes()],
+        bump = current_page.bump,
+        constraint = current_page.is_full() @ PolocError::InvalidParameters
+    )]
+    pub current_page: Account<'info, RegistryPage>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RegistryPage::MAX_SIZE,
+        seeds = [REGISTRY_PAGE_SEED, &(challenge_registry.current_page + 1).to_le_bytes()],
+        bump
+    )]
+    pub next_page: Account<'info, RegistryPage>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(page: u32)]
+pub struct DelistChallenge<'info> {
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Account<'info, ChallengeRegistry>,
+
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Account<'info, RegistryPage>,
+
+    /// CHECK: The challenge the entry names; may already be closed. Parsed and
+    /// updated in the handler when it still exists.
+    #[account(mut)]
+    pub challenge: UncheckedAccount<'info>,
+}
+
+// Appends `challenge` to the registry's current page.
+pub(crate) fn list_challenge(
+    registry: &mut ChallengeRegistry,
+    page: &mut RegistryPage,
+    challenge: &mut Challenge,
+    challenge_key: Pubkey,
+) -> Result<()> {
+    require!(page.page == registry.current_page, PolocError::InvalidParameters);
+    require!(!page.is_full(), PolocError::RegistryPageFull);
+
+    let slot = page.count;
+    page.entries[slot as usize] = RegistryEntry { challenge: challenge_key, open: true, tvl: 0 };
+    registry.record_tvl(&mut page.entries[slot as usize], challenge.tvl());
+    page.count += 1;
+    registry.total_listed = registry.total_listed.saturating_add(1);
+    registry.open_count = registry.open_count.saturating_add(1);
+    challenge.listed = 1;
+    challenge.registry_page = page.page;
+    challenge.registry_slot = slot;
+    Ok(())
+}
+
+fn close_entry(registry: &mut ChallengeRegistry, page: &mut RegistryPage, slot: u8, challenge: &Pubkey) -> Result<()> {
+    require!(slot < page.count, PolocError::InvalidParameters);
+    let entry = &mut page.entries[slot as usize];
+    require!(entry.open && entry.challenge == *challenge, PolocError::InvalidParameters);
+    registry.record_tvl(entry, 0);
+    entry.open = false;
+    registry.open_count = registry.open_count.saturating_sub(1);
+    Ok(())
+}
+
+// Marks a settled challenge closed in the registry, if it's listed there and
+// the caller passed the registry. Other settling paths leave it to
+// `delist_challenge`.
+pub(crate) fn delist_settled(
+    registry: Option<&mut Account<ChallengeRegistry>>,
+    page: Option<&mut Account<RegistryPage>>,
+    challenge: &mut Challenge,
+    challenge_key: Pubkey,
+) -> Result<()> {
+    let (Some(registry), Some(page)) = (registry, page) else {
+        return Ok(());
+    };
+    if challenge.listed == 0 || challenge.is_open() {
+        return Ok(());
+    }
+    require!(page.page == challenge.registry_page, PolocError::InvalidParameters);
+    close_entry(registry, page, challenge.registry_slot, &challenge_key)?;
+    challenge.listed = 0;
+    Ok(())
+}
+
+// Brings a listed challenge's share of the registry TVL up to date after its
+// pool or bond moved, if the caller passed the registry. Instructions run
+// without it leave the share stale until the next one that does, or until
+// the entry is closed.
+pub(crate) fn track_tvl(
+    registry: Option<&mut Account<ChallengeRegistry>>,
+    page: Option<&mut Account<RegistryPage>>,
+    challenge: &Challenge,
+    challenge_key: Pubkey,
+) -> Result<()> {
+    let (Some(registry), Some(page)) = (registry, page) else {
+        return Ok(());
+    };
+    if challenge.listed == 0 {
+        return Ok(());
+    }
+    require!(page.page == challenge.registry_page, PolocError::InvalidParameters);
+    require!(challenge.registry_slot < page.count, PolocError::InvalidParameters);
+    let entry = &mut page.entries[challenge.registry_slot as usize];
+    require!(entry.challenge == challenge_key, PolocError::InvalidParameters);
+    if entry.open {
+        registry.record_tvl(entry, challenge.tvl());
+    }
+    Ok(())
+}
+
+pub fn initialize_handler(ctx: Context<InitializeChallengeRegistry>) -> Result<()> {
+    ctx.accounts.challenge_registry.bump = ctx.bumps.challenge_registry;
+    ctx.accounts.registry_page.bump = ctx.bumps.registry_page;
+    msg!("Challenge registry initialized, {} challenges per page", REGISTRY_PAGE_CAPACITY);
+    Ok(())
+}
+
+// Permissionless: the payer only gets to fund the next page once the current
+// one is full.
+pub fn open_page_handler(ctx: Context<OpenRegistryPage>) -> Result<()> {
+    let registry = &mut ctx.accounts.challenge_registry;
+    registry.current_page += 1;
+    let page = &mut ctx.accounts.next_page;
+    page.page = registry.current_page;
+    page.bump = ctx.bumps.next_page;
+    msg!("Registry page {} opened", page.page);
+    Ok(())
+}
+
+// Clears the open flag of the entry at (`page`, `slot`) once its challenge
+// has settled, or is gone entirely. Covers every settling path that doesn't
+// take the registry itself.
+pub fn delist_handler(ctx: Context<DelistChallenge>, page: u32, slot: u8) -> Result<()> {
+    let info = ctx.accounts.challenge.to_account_info();
+    if let Some(mut challenge) = Challenge::load_existing_mut(&info)? {
+        require!(!challenge.is_open(), PolocError::ChallengeStillOpen);
+        require!(challenge.registry_page == page && challenge.registry_slot == slot, PolocError::InvalidParameters);
+        challenge.listed = 0;
+    }
+    close_entry(&mut ctx.accounts.challenge_registry, &mut ctx.accounts.registry_page, slot, &info.key())?;
+
+    msg!("Challenge {} delisted from registry page {}", info.key(), page);
+    Ok(())
+}
@@ -0,0 +1,60 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct EmitChallengeReport<'info> {
+    #[account(seeds = [CHALLENGE_SEED, challenge_id.as_bytes()], bump = challenge.load()?.bump)]
+    pub challenge: AccountLoader<'info, Challenge>,
+}
+
+// Logs a `ChallengeReported` summary for the creator's records. Read-only
+// and permissionless: everything in it is already public in the account.
+// Claims and slashes can still land after settlement, so a later report
+// may differ; the latest one is current.
+pub fn handler(ctx: Context<EmitChallengeReport>, challenge_id: String) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &ctx.accounts.challenge.load()?;
+    require!(
+        !challenge.is_open() && challenge.status() != ChallengeStatus::Scheduled,
+        PolocError::ChallengeNotSettled
+    );
+
+    msg!("Report for challenge {}: {:?}, {} of {} votes valid, {} claims, {} slashes",
+         challenge_id, challenge.status(), challenge.valid_vote_count, challenge.vote_count,
+         challenge.reward_claims, challenge.slash_count);
+    emit!(ChallengeReported {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenge_id,
+        waldo: challenge.waldo,
+        status: challenge.status(),
+        passed: challenge.status() == ChallengeStatus::Finalized && challenge.passed(),
+        start_time: challenge.start_time,
+        deadline: challenge.deadline,
+        voting_ends_at: challenge.voting_ends_at(),
+        claim_deadline: challenge.claim_deadline,
+        expires_at: challenge.expires_at,
+        participant_count: challenge.participant_count,
+        vote_count: challenge.vote_count,
+        valid_vote_count: challenge.valid_vote_count,
+        total_staked: challenge.total_staked,
+        valid_stake: challenge.valid_stake,
+        invalid_stake: challenge.invalid_stake,
+        r_star: challenge.r_star,
+        r_star_threshold: challenge.r_star_threshold,
+        quorum_met: challenge.quorum_met != 0,
+        reward_pool: challenge.reward_pool,
+        rewards_claimed: challenge.rewards_claimed,
+        reward_claims: challenge.reward_claims,
+        waldo_bond: challenge.waldo_bond,
+        bond_claimed_stake: challenge.bond_claimed_stake,
+        slashed_total: challenge.slashed_total,
+        slash_count: challenge.slash_count,
+        context: challenge.context,
+    });
+    Ok(())
+}
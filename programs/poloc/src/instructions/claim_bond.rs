@@ -0,0 +1,102 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::claim_escrow::move_lamports;
+use poloc_core::rewards::stake_share;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ClaimBondShare<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    // Marked processed, as a reward claim would be; a failed claim pays no rewards.
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump = vote.bump,
+        constraint = !vote.processed @ PolocError::AlreadyClaimed,
+        constraint = !vote.receipt_outstanding @ PolocError::ReceiptOutstanding
+    )]
+    pub vote: Account<'info, Vote>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+}
+
+/// The next share of a forfeited waldo bond for `vote`, weighted by its
+/// stake among the invalid votes not yet paid.
+pub(crate) fn bond_share(challenge: &Challenge, vote: &Vote) -> Result<u64> {
+    require!(challenge.status() == ChallengeStatus::Finalized, PolocError::ChallengeNotFinalized);
+    require!(challenge.forfeits_bond(), PolocError::BondNotForfeited);
+    require!(!vote.is_valid && !vote.prepared, PolocError::VotedIncorrectly);
+
+    let unclaimed = challenge.invalid_stake.saturating_sub(challenge.bond_claimed_stake);
+    stake_share(challenge.waldo_bond, vote.stake.max(1), unclaimed).ok_or(PolocError::ArithmeticOverflow.into())
+}
+
+/// Refunds and other closes hand the vault to the creator, so they wait
+/// until the bond is paid out or the claim period is over.
+pub(crate) fn require_bond_settled(challenge: &Challenge, now: i64) -> Result<()> {
+    require!(
+        !challenge.owes_bond() || (challenge.claim_deadline > 0 && now >= challenge.claim_deadline),
+        PolocError::ClaimPeriodOpen
+    );
+    Ok(())
+}
+
+// Pays the share of `bond` behind `stake` out of `vault` and books it.
+pub(crate) fn pay_bond_share(
+    challenge: &mut Challenge,
+    challenge_key: Pubkey,
+    vault: &AccountInfo,
+    challenger: &AccountInfo,
+    stake: u64,
+    share: u64,
+) -> Result<()> {
+    move_lamports(vault, challenger, share)?;
+    challenge.waldo_bond = challenge.waldo_bond
+        .checked_sub(share)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    challenge.bond_claimed_stake = challenge.bond_claimed_stake.saturating_add(stake.max(1));
+
+    msg!("Bond share of {} lamports paid to {}", share, challenger.key());
+    emit!(WaldoBondClaimed {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenger: challenger.key(),
+        amount: share,
+    });
+    Ok(())
+}
+
+// A claim the votes reject costs the creator their bond: it goes to the
+// witnesses who voted against the location, in proportion to their stake.
+// Quorum failures leave the bond in the vault for `refund_failed_challenge`.
+pub fn handler(ctx: Context<ClaimBondShare>, _challenge_id: String) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    let vote = &mut ctx.accounts.vote;
+    let share = bond_share(challenge, vote)?;
+
+    pay_bond_share(
+        challenge,
+        challenge_key,
+        &ctx.accounts.vault.to_account_info(),
+        &ctx.accounts.challenger.to_account_info(),
+        vote.stake,
+        share,
+    )?;
+    vote.processed = true;
+    Ok(())
+}
@@ -0,0 +1,141 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+
+/// Moves a claimed `amount` out of the challenge's vault: straight to the
+/// winner when no escrow period is configured, otherwise into the winner's
+/// `ClaimEscrow` until `release_claim`.
+pub(crate) fn pay_claim<'info>(
+    challenge_key: Pubkey,
+    vault: &Account<'info, ChallengeVault>,
+    winner: &AccountInfo<'info>,
+    escrow: Option<&mut Account<'info, ClaimEscrow>>,
+    escrow_bump: Option<u8>,
+    escrow_period: i64,
+    amount: u64,
+) -> Result<()> {
+    let destination = match (escrow_period > 0, escrow, escrow_bump) {
+        (false, None, _) => winner.clone(),
+        (true, Some(escrow), Some(bump)) => {
+            escrow.challenge = challenge_key;
+            escrow.beneficiary = winner.key();
+            escrow.amount = amount;
+            escrow.release_at = Clock::get()?
+                .unix_timestamp
+                .checked_add(escrow_period)
+                .ok_or(PolocError::ArithmeticOverflow)?;
+            escrow.bump = bump;
+            msg!("Claim of {} lamports escrowed until {}", amount, escrow.release_at);
+            escrow.to_account_info()
+        }
+        _ => return err!(PolocError::ClaimEscrowRequired),
+    };
+
+    // The vault is program-owned, so lamports move directly.
+    move_lamports(&vault.to_account_info(), &destination, amount)
+}
+
+pub(crate) fn move_lamports(from: &AccountInfo, to: &AccountInfo, amount: u64) -> Result<()> {
+    **from.try_borrow_mut_lamports()? = from
+        .lamports()
+        .checked_sub(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    **to.try_borrow_mut_lamports()? = to
+        .lamports()
+        .checked_add(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ReleaseClaim<'info> {
+    #[account(
+        mut,
+        seeds = [CLAIM_ESCROW_SEED, challenge_id.as_bytes(), beneficiary.key().as_ref()],
+        bump = claim_escrow.bump,
+        has_one = beneficiary,
+        close = beneficiary
+    )]
+    pub claim_escrow: Account<'info, ClaimEscrow>,
+
+    #[account(mut)]
+    pub beneficiary: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct RevertClaim<'info> {
+    #[account(
+        mut,
+        seeds = [CLAIM_ESCROW_SEED, challenge_id.as_bytes(), beneficiary.key().as_ref()],
+        bump = claim_escrow.bump,
+        has_one = beneficiary,
+        close = beneficiary
+    )]
+    pub claim_escrow: Account<'info, ClaimEscrow>,
+
+    // Receives the rent the beneficiary paid for the escrow.
+    #[account(mut)]
+    pub beneficiary: SystemAccount<'info>,
+
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+// Permissionless once the dispute period is over, so the payout still reaches
+// the beneficiary if they never come back for it. Closing the escrow sends
+// the amount along with its rent.
+pub fn release_handler(ctx: Context<ReleaseClaim>, challenge_id: String) -> Result<()> {
+    let escrow = &ctx.accounts.claim_escrow;
+    require!(Clock::get()?.unix_timestamp >= escrow.release_at, PolocError::ClaimEscrowLocked);
+
+    msg!("Escrowed claim of {} lamports on {} released to {}",
+         escrow.amount, challenge_id, escrow.beneficiary);
+
+    emit!(ClaimEscrowSettled {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: escrow.challenge,
+        beneficiary: escrow.beneficiary,
+        amount: escrow.amount,
+        released: true,
+    });
+    Ok(())
+}
+
+// Governance reversal during the dispute period: the amount goes to the
+// insurance pool and only the rent returns to the beneficiary.
+pub fn revert_handler(ctx: Context<RevertClaim>, challenge_id: String) -> Result<()> {
+    let escrow = &ctx.accounts.claim_escrow;
+    require!(Clock::get()?.unix_timestamp < escrow.release_at, PolocError::DisputePeriodOver);
+
+    let amount = escrow.amount;
+    move_lamports(
+        &escrow.to_account_info(),
+        &ctx.accounts.insurance_pool.to_account_info(),
+        amount,
+    )?;
+    let pool = &mut ctx.accounts.insurance_pool;
+    pool.total_received = pool.total_received
+        .checked_add(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    msg!("Escrowed claim of {} lamports on {} reverted to the insurance pool",
+         amount, challenge_id);
+
+    emit!(ClaimEscrowSettled {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: escrow.challenge,
+        beneficiary: escrow.beneficiary,
+        amount,
+        released: false,
+    });
+    Ok(())
+}
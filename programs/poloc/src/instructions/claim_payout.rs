@@ -0,0 +1,130 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::merkle;
+use crate::instructions::claim_escrow::pay_claim;
+use crate::instructions::claim_reward::{bond_top_up, fund_streak_bonus, record_payout};
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ClaimPayout<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(
+        mut,
+        seeds = [PAYOUT_SEED, challenge_id.as_bytes()],
+        bump = payout.bump,
+        constraint = payout.challenge == challenge.key() @ PolocError::InvalidParameters
+    )]
+    pub payout: Account<'info, PayoutDistribution>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // Required while `config.claim_escrow_period` is set; omitted otherwise.
+    #[account(
+        init,
+        payer = winner,
+        space = 8 + ClaimEscrow::MAX_SIZE,
+        seeds = [CLAIM_ESCROW_SEED, challenge_id.as_bytes(), winner.key().as_ref()],
+        bump
+    )]
+    pub claim_escrow: Option<Account<'info, ClaimEscrow>>,
+
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + PayoutLedger::MAX_SIZE,
+        seeds = [PAYOUT_LEDGER_SEED, winner.key().as_ref()],
+        bump
+    )]
+    pub payout_ledger: Account<'info, PayoutLedger>,
+
+    // Credited with the correct vote.
+    #[account(mut, seeds = [CHALLENGER_SEED, winner.key().as_ref()], bump = challenger_profile.bump)]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    // Funds the winner's streak bonus.
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// `amount` is the winner's leaf amount, stake-weighted by `finalize_with_payouts`.
+pub fn handler(
+    ctx: Context<ClaimPayout>,
+    _challenge_id: String,
+    index: u32,
+    proof: Vec<[u8; 32]>,
+    amount: u64,
+) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    let payout = &mut ctx.accounts.payout;
+    let winner = &ctx.accounts.winner;
+
+    require!(index < payout.winner_count, PolocError::InvalidPayoutProof);
+    let bit = 1u32 << index;
+    require!(payout.claimed_bitmap & bit == 0, PolocError::AlreadyClaimed);
+
+    let leaf = merkle::payout_leaf(index, &winner.key().to_bytes(), amount);
+    require!(merkle::verify(&proof, &payout.root, leaf), PolocError::InvalidPayoutProof);
+
+    let bonus = fund_streak_bonus(
+        challenge_key,
+        &ctx.accounts.vault,
+        &ctx.accounts.insurance_pool,
+        &mut ctx.accounts.challenger_profile,
+        winner.key(),
+        amount,
+    )?;
+    let paid = amount + bond_top_up(challenge, &ctx.accounts.config, amount) + bonus;
+    pay_claim(
+        challenge_key,
+        &ctx.accounts.vault,
+        &winner.to_account_info(),
+        ctx.accounts.claim_escrow.as_mut(),
+        ctx.bumps.claim_escrow,
+        ctx.accounts.config.claim_escrow_period,
+        paid,
+    )?;
+
+    record_payout(&mut ctx.accounts.payout_ledger, ctx.bumps.payout_ledger, winner.key(), challenge_key, paid)?;
+
+    payout.claimed_bitmap |= bit;
+    ctx.accounts.challenger_profile.record_correct_vote();
+    challenge.reward_pool = challenge.reward_pool
+        .checked_sub(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    challenge.rewards_claimed = challenge.rewards_claimed.saturating_add(amount);
+    challenge.reward_claims = challenge.reward_claims.saturating_add(1);
+    if payout.claimed_bitmap.count_ones() == payout.winner_count {
+        challenge.rewards_distributed = 1;
+    }
+
+    msg!("Payout {} of {} lamports claimed by {}", index, paid, winner.key());
+
+    emit!(RewardClaimed {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        winner: winner.key(),
+        amount: paid,
+    });
+    Ok(())
+}
@@ -1,23 +1,32 @@
 use anchor_lang::prelude::*;
-use anchor_lang::system_program;
 use crate::state::*;
+use crate::constants::*;
 use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::finalize::{settle_if_overdue, SettlementAccounts};
+use crate::instructions::challenge_registry::track_tvl;
+use crate::instructions::claim_escrow::{move_lamports, pay_claim};
+use poloc_core::rewards::{claim_share, stake_share};
+use poloc_core::scoring::streak_bonus;
 
 #[derive(Accounts)]
-#[instruction(challenge_id: String)]
 pub struct ClaimReward<'info> {
+    // See `StakeCtx::challenge`.
     #[account(
         mut,
-        seeds = [b"challenge", challenge_id.as_bytes()],
-        bump = challenge.bump
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
     )]
-    pub challenge: Account<'info, Challenge>,
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge.load()?.challenge_id().as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
 
     // The vote account of the participant claiming their reward.
     // This proves they were an honest voter.
     #[account(
         mut, // We mark the vote as processed to prevent double-claims.
-        seeds = [b"vote", challenge_id.as_bytes(), winner.key().as_ref()],
+        seeds = [VOTE_SEED, challenge.load()?.challenge_id().as_bytes(), winner.key().as_ref()],
         bump = vote.bump,
         constraint = vote.challenger == winner.key() @ PolocError::Unauthorized,
         constraint = !vote.processed @ PolocError::AlreadyClaimed,
@@ -27,54 +36,234 @@ pub struct ClaimReward<'info> {
     #[account(mut)]
     pub winner: Signer<'info>,
 
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // Required while `config.claim_escrow_period` is set; omitted otherwise.
+    #[account(
+        init,
+        payer = winner,
+        space = 8 + ClaimEscrow::MAX_SIZE,
+        seeds = [CLAIM_ESCROW_SEED, challenge.load()?.challenge_id().as_bytes(), winner.key().as_ref()],
+        bump
+    )]
+    pub claim_escrow: Option<Account<'info, ClaimEscrow>>,
+
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + PayoutLedger::MAX_SIZE,
+        seeds = [PAYOUT_LEDGER_SEED, winner.key().as_ref()],
+        bump
+    )]
+    pub payout_ledger: Account<'info, PayoutLedger>,
+
+    // Credited with the correct vote.
+    #[account(mut, seeds = [CHALLENGER_SEED, winner.key().as_ref()], bump = challenger_profile.bump)]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    // Funds the winner's streak bonus.
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to keep its TVL current, or close its entry if an overdue challenge
+    // settles here.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+
     pub system_program: Program<'info, System>,
+
+    // Optional: the creator's profile, to free the slot of an overdue
+    // challenge settled here; without it such a challenge is refused.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Option<Account<'info, WaldoProfile>>,
 }
 
-pub fn handler(ctx: Context<ClaimReward>, _challenge_id: String) -> Result<()> {
-    let challenge = &mut ctx.accounts.challenge;
+pub fn handler(ctx: Context<ClaimReward>) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
     let vote = &mut ctx.accounts.vote;
     let winner = &ctx.accounts.winner;
 
+    // An unfinalized overdue challenge is settled here rather than left for a crank.
+    if settle_if_overdue(challenge, challenge_key, Clock::get()?.unix_timestamp, None, SettlementAccounts {
+        waldo_profile: ctx.accounts.waldo_profile.as_mut(),
+        challenge_registry: ctx.accounts.challenge_registry.as_mut(),
+        registry_page: ctx.accounts.registry_page.as_mut(),
+    })? {
+        return Ok(());
+    }
+
+    let reward_per_participant = claimable_reward(challenge, vote)?;
+    let bonus = fund_streak_bonus(
+        challenge_key,
+        &ctx.accounts.vault,
+        &ctx.accounts.insurance_pool,
+        &mut ctx.accounts.challenger_profile,
+        winner.key(),
+        reward_per_participant,
+    )?;
+    let paid = reward_per_participant
+        + bond_top_up(challenge, &ctx.accounts.config, reward_per_participant)
+        + bonus;
+
+    // Directly to the winner, or into their escrow for the dispute period
+    pay_claim(
+        challenge_key,
+        &ctx.accounts.vault,
+        &winner.to_account_info(),
+        ctx.accounts.claim_escrow.as_mut(),
+        ctx.bumps.claim_escrow,
+        ctx.accounts.config.claim_escrow_period,
+        paid,
+    )?;
+    record_payout(&mut ctx.accounts.payout_ledger, ctx.bumps.payout_ledger, winner.key(), challenge_key, paid)?;
+
+    record_claim(challenge, challenge_key, vote, &mut ctx.accounts.challenger_profile, winner.key(), reward_per_participant, paid)?;
+    track_tvl(ctx.accounts.challenge_registry.as_mut(), ctx.accounts.registry_page.as_mut(), challenge, challenge_key)?;
+    Ok(())
+}
+
+// Checks a claim against a settled challenge and returns the winner's share.
+// Votes under a claim policy are only paid through `execute_claim`.
+pub(crate) fn claimable_reward(challenge: &Challenge, vote: &Vote) -> Result<u64> {
+    require!(!vote.multisig_claim, PolocError::MultisigClaimRequired);
+    reward_share(challenge, vote)
+}
+
+// `claimable_reward` without the claim policy check.
+pub fn reward_share(challenge: &Challenge, vote: &Vote) -> Result<u64> {
     // 1. Check that the challenge is finalized and was successful.
-    require!(challenge.status == ChallengeStatus::Finalized, PolocError::ChallengeNotFinalized);
-    let passed = challenge.r_star <= challenge.r_star_threshold;
-    require!(passed, PolocError::ChallengeFailed);
+    require!(challenge.status() == ChallengeStatus::Finalized, PolocError::ChallengeNotFinalized);
+    require!(challenge.passed(), PolocError::ChallengeFailed);
 
-    // 2. Check that the voter voted correctly (i.e., voted 'valid' for a successful challenge).
-    require!(vote.is_valid, PolocError::VotedIncorrectly);
+    // 2. Check that the voter voted correctly (i.e., voted 'valid' for a successful challenge,
+    //    at the selected candidate location for any-of challenges).
+    require!(
+        vote.is_valid && vote.candidate == challenge.selected_candidate,
+        PolocError::VotedIncorrectly
+    );
 
-    // 3. Calculate reward and transfer funds from the Challenge PDA to the winner.
+    require!(!vote.receipt_outstanding, PolocError::ReceiptOutstanding);
+
+    // 3. Calculate reward: by stake, or by head for votes cast before
+    //    stakes were recorded on them
     require!(challenge.valid_vote_count > 0, PolocError::NoValidVotes);
-    let reward_per_participant = challenge.reward_pool
-        .checked_div(challenge.valid_vote_count as u64)
-        .ok_or(PolocError::ArithmeticOverflow)?;
+    let share = if vote.stake > 0 {
+        let unclaimed = challenge.valid_stake.saturating_sub(challenge.claimed_stake);
+        stake_share(challenge.distributable_pool(), vote.stake, unclaimed)
+    } else {
+        claim_share(challenge.distributable_pool(), challenge.valid_vote_count)
+    };
+    share.ok_or(PolocError::ArithmeticOverflow.into())
+}
 
-    // Prepare signer seeds (the program must sign for the PDA)
-    let bump = challenge.bump;
-    let challenge_id_bytes = challenge.challenge_id.as_bytes();
-    let signer_seeds: &[&[u8]] = &[b"challenge", challenge_id_bytes, &[bump]];
-    let signer = &[signer_seeds];
+// Underwritten challenges guarantee each winner the per-vote gas rebate plus
+// the minimum compensation. A pool share below that is topped up from the
+// creator's bond, which only goes back to the creator once every claim is in.
+pub(crate) fn bond_top_up(challenge: &mut Challenge, config: &Config, share: u64) -> u64 {
+    if challenge.underwrites_witnesses == 0 {
+        return 0;
+    }
+    let guaranteed = config.min_witness_compensation.saturating_add(config.vote_gas_rebate);
+    let top_up = guaranteed.saturating_sub(share).min(challenge.waldo_bond);
+    challenge.waldo_bond -= top_up;
+    if top_up > 0 {
+        msg!("Bond tops up claim by {} lamports", top_up);
+    }
+    top_up
+}
 
-    // Perform the transfer via CPI to the System Program, signed by the PDA
-    let cpi_accounts = system_program::Transfer {
-        from: challenge.to_account_info(),
-        to: winner.to_account_info(),
-    };
-    let cpi_program = ctx.accounts.system_program.to_account_info();
-    let cpi_ctx = CpiContext::new_with_signer(cpi_program, cpi_accounts, signer);
-    system_program::transfer(cpi_ctx, reward_per_participant)?;
+// Extends the winner's streak by the vote being claimed and moves its bonus
+// from the insurance pool into the vault, so it is paid (or escrowed) with
+// the share. A short pool pays what it holds above rent. Every lamport claim
+// path goes through here; token claims have no lamport share to scale a
+// bonus by, so they leave the streak alone.
+pub(crate) fn fund_streak_bonus<'info>(
+    challenge_key: Pubkey,
+    vault: &Account<'info, ChallengeVault>,
+    insurance_pool: &Account<'info, InsurancePool>,
+    profile: &mut ChallengerProfile,
+    winner: Pubkey,
+    share: u64,
+) -> Result<u64> {
+    let streak = profile.streak.saturating_add(1);
+    profile.streak = streak;
+    let pool_info = insurance_pool.to_account_info();
+    let available = pool_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(pool_info.data_len()));
+    let bonus = streak_bonus(share, streak).min(available);
+    if bonus == 0 {
+        return Ok(0);
+    }
+    move_lamports(&pool_info, &vault.to_account_info(), bonus)?;
 
+    msg!("Streak of {} adds {} lamports to the claim", streak, bonus);
+    emit!(StreakBonusPaid {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        winner,
+        streak,
+        amount: bonus,
+    });
+    Ok(bonus)
+}
+
+// Adds a claim to the winner's payout ledger, setting the ledger up on first use.
+pub(crate) fn record_payout(
+    ledger: &mut Account<PayoutLedger>,
+    bump: u8,
+    wallet: Pubkey,
+    challenge: Pubkey,
+    amount: u64,
+) -> Result<()> {
+    if ledger.wallet == Pubkey::default() {
+        ledger.wallet = wallet;
+        ledger.bump = bump;
+    }
+    ledger.record(challenge, amount, Clock::get()?.unix_timestamp);
+    Ok(())
+}
+
+// Bookkeeping once `amount` has left the pool, `paid` of it to the winner.
+pub(crate) fn record_claim(
+    challenge: &mut Challenge,
+    challenge_key: Pubkey,
+    vote: &mut Account<Vote>,
+    profile: &mut ChallengerProfile,
+    winner: Pubkey,
+    amount: u64,
+    paid: u64,
+) -> Result<()> {
     // 4. Update state to prevent double-claiming
     vote.processed = true;
+    profile.record_correct_vote();
     challenge.reward_pool = challenge.reward_pool
-        .checked_sub(reward_per_participant)
+        .checked_sub(amount)
         .ok_or(PolocError::ArithmeticOverflow)?;
+    challenge.claimed_stake = challenge.claimed_stake.saturating_add(vote.stake);
+    challenge.rewards_claimed = challenge.rewards_claimed.saturating_add(amount);
+    challenge.reward_claims = challenge.reward_claims.saturating_add(1);
 
-    // If all rewards depleted, mark distributed
-    if challenge.reward_pool == 0 {
-        challenge.rewards_distributed = true;
+    // If all rewards depleted, mark distributed; pool stakes still go back
+    if challenge.distributable_pool() == 0 {
+        challenge.rewards_distributed = 1;
     }
 
-    msg!("Reward of {} lamports claimed by {}", reward_per_participant, winner.key());
+    trace!("Claim of {} lamports for {} stake leaves {} in the pool, {} of {} valid stake claimed",
+           amount, vote.stake, challenge.reward_pool, challenge.claimed_stake, challenge.valid_stake);
+    msg!("Reward of {} lamports claimed by {}", paid, winner);
+
+    emit!(RewardClaimed {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        winner,
+        amount: paid,
+    });
     Ok(())
 }
@@ -0,0 +1,199 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::claim_escrow::move_lamports;
+use crate::instructions::claim_reward::{
+    bond_top_up, claimable_reward, fund_streak_bonus, record_claim, record_payout,
+};
+use crate::instructions::finalize::{settle_if_overdue, SettlementAccounts};
+
+// SPL Token `SyncNative` instruction discriminant.
+const SYNC_NATIVE: u8 = 17;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ClaimRewardSwapped<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, challenge_id.as_bytes(), winner.key().as_ref()],
+        bump = vote.bump,
+        constraint = vote.challenger == winner.key() @ PolocError::Unauthorized,
+        constraint = !vote.processed @ PolocError::AlreadyClaimed,
+    )]
+    pub vote: Account<'info, Vote>,
+
+    #[account(mut)]
+    pub winner: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The winner's wrapped SOL token account; parsed in the handler.
+    #[account(mut, owner = token_program.key() @ PolocError::InvalidTokenAccount)]
+    pub wsol_account: UncheckedAccount<'info>,
+
+    /// CHECK: The winner's token account for the target mint; parsed in the handler.
+    #[account(mut, owner = token_program.key() @ PolocError::InvalidTokenAccount)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// CHECK: SPL Token or Token-2022; owns both token accounts and is
+    /// invoked for `SyncNative`.
+    #[account(
+        constraint = token_program.key() == TOKEN_PROGRAM_ID || token_program.key() == TOKEN_2022_PROGRAM_ID
+            @ PolocError::InvalidTokenAccount
+    )]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// CHECK: Address-constrained to the Jupiter aggregator.
+    #[account(address = JUPITER_PROGRAM_ID)]
+    pub jupiter_program: UncheckedAccount<'info>,
+
+    // The ledger records the lamports claimed, before the swap.
+    #[account(
+        init_if_needed,
+        payer = winner,
+        space = 8 + PayoutLedger::MAX_SIZE,
+        seeds = [PAYOUT_LEDGER_SEED, winner.key().as_ref()],
+        bump
+    )]
+    pub payout_ledger: Account<'info, PayoutLedger>,
+
+    // Credited with the correct vote.
+    #[account(mut, seeds = [CHALLENGER_SEED, winner.key().as_ref()], bump = challenger_profile.bump)]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    // Funds the winner's streak bonus.
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    pub system_program: Program<'info, System>,
+    // remaining_accounts: the route's accounts, in the order of its instruction.
+
+    // Optional: the creator's profile, to free the slot of an overdue
+    // challenge settled here; without it such a challenge is refused.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Option<Account<'info, WaldoProfile>>,
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to mark its entry closed if an overdue challenge settles here.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+}
+
+// Claims into wrapped SOL and swaps it through a Jupiter route in the same
+// instruction. `route_data` and the remaining accounts are the route
+// instruction as quoted for the winner; the claim fails unless `destination`
+// grows by at least `min_amount_out`. Escrowed claims can't be swapped.
+pub fn handler(
+    ctx: Context<ClaimRewardSwapped>,
+    _challenge_id: String,
+    route_data: Vec<u8>,
+    min_amount_out: u64,
+) -> Result<()> {
+    require!(ctx.accounts.config.claim_escrow_period == 0, PolocError::ClaimEscrowRequired);
+
+    let winner = ctx.accounts.winner.key();
+    let (wsol_mint, wsol_owner, _) = token_account_fields(&ctx.accounts.wsol_account)?;
+    require_keys_eq!(wsol_mint, NATIVE_MINT, PolocError::InvalidTokenAccount);
+    require_keys_eq!(wsol_owner, winner, PolocError::InvalidTokenAccount);
+    let (output_mint, destination_owner, balance_before) = token_account_fields(&ctx.accounts.destination)?;
+    require_keys_eq!(destination_owner, winner, PolocError::InvalidTokenAccount);
+
+    let challenge_key = ctx.accounts.challenge.key();
+    let mut challenge = ctx.accounts.challenge.load_mut()?;
+    if settle_if_overdue(&mut challenge, challenge_key, Clock::get()?.unix_timestamp, None, SettlementAccounts {
+        waldo_profile: ctx.accounts.waldo_profile.as_mut(),
+        challenge_registry: ctx.accounts.challenge_registry.as_mut(),
+        registry_page: ctx.accounts.registry_page.as_mut(),
+    })? {
+        return Ok(());
+    }
+
+    let share = claimable_reward(&challenge, &ctx.accounts.vote)?;
+    let bonus = fund_streak_bonus(
+        challenge_key,
+        &ctx.accounts.vault,
+        &ctx.accounts.insurance_pool,
+        &mut ctx.accounts.challenger_profile,
+        winner,
+        share,
+    )?;
+    let reward = share + bond_top_up(&mut challenge, &ctx.accounts.config, share) + bonus;
+    move_lamports(&ctx.accounts.vault.to_account_info(), &ctx.accounts.wsol_account.to_account_info(), reward)?;
+    record_payout(&mut ctx.accounts.payout_ledger, ctx.bumps.payout_ledger, winner, challenge_key, reward)?;
+    record_claim(&mut challenge, challenge_key, &mut ctx.accounts.vote, &mut ctx.accounts.challenger_profile, winner, share, reward)?;
+    // The route may list the challenge among its accounts.
+    drop(challenge);
+
+    // Credit the new lamports to the wrapped SOL balance
+    invoke(
+        &Instruction {
+            program_id: ctx.accounts.token_program.key(),
+            accounts: vec![AccountMeta::new(ctx.accounts.wsol_account.key(), false)],
+            data: vec![SYNC_NATIVE],
+        },
+        &[ctx.accounts.wsol_account.to_account_info()],
+    )?;
+
+    // The winner signed this transaction, so the route may spend their wSOL.
+    let route_accounts = ctx.remaining_accounts
+        .iter()
+        .map(|info| AccountMeta {
+            pubkey: info.key(),
+            is_signer: info.is_signer,
+            is_writable: info.is_writable,
+        })
+        .collect();
+    invoke(
+        &Instruction {
+            program_id: JUPITER_PROGRAM_ID,
+            accounts: route_accounts,
+            data: route_data,
+        },
+        ctx.remaining_accounts,
+    )?;
+
+    let (_, _, balance_after) = token_account_fields(&ctx.accounts.destination)?;
+    let amount_out = balance_after.saturating_sub(balance_before);
+    require!(amount_out >= min_amount_out, PolocError::SwapOutputTooLow);
+
+    msg!("Swapped {} lamports into {} of mint {}", reward, amount_out, output_mint);
+
+    emit!(RewardSwapped {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        winner,
+        lamports_in: reward,
+        output_mint,
+        amount_out,
+    });
+    Ok(())
+}
+
+// (mint, owner, amount) from the fixed SPL token account layout.
+pub(crate) fn token_account_fields(info: &AccountInfo) -> Result<(Pubkey, Pubkey, u64)> {
+    let data = info.try_borrow_data()?;
+    require!(data.len() >= 72, PolocError::InvalidTokenAccount);
+    let mint = Pubkey::try_from(&data[0..32]).map_err(|_| PolocError::InvalidTokenAccount)?;
+    let owner = Pubkey::try_from(&data[32..64]).map_err(|_| PolocError::InvalidTokenAccount)?;
+    let amount = u64::from_le_bytes(data[64..72].try_into().map_err(|_| PolocError::InvalidTokenAccount)?);
+    Ok((mint, owner, amount))
+}
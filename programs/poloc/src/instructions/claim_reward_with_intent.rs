@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::ed25519::verify_preceding_signature;
+use crate::instructions::claim_escrow::pay_claim;
+use crate::instructions::claim_reward::{
+    bond_top_up, claimable_reward, fund_streak_bonus, record_claim, record_payout,
+};
+use crate::instructions::finalize::{settle_if_overdue, SettlementAccounts};
+
+const CLAIM_INTENT_DOMAIN: &[u8] = b"poloc:claim-intent:v1";
+
+/// Bytes a winner signs to let any relayer claim for them at `relayer_fee`.
+/// The vote is marked processed on claim, so an intent is single-use.
+pub fn claim_intent_message(challenge: &Pubkey, relayer_fee: u64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(CLAIM_INTENT_DOMAIN.len() + 32 + 32 + 8);
+    message.extend_from_slice(CLAIM_INTENT_DOMAIN);
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(challenge.as_ref());
+    message.extend_from_slice(&relayer_fee.to_le_bytes());
+    message
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ClaimRewardWithIntent<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, challenge_id.as_bytes(), winner.key().as_ref()],
+        bump = vote.bump,
+        constraint = vote.challenger == winner.key() @ PolocError::Unauthorized,
+        constraint = !vote.processed @ PolocError::AlreadyClaimed,
+    )]
+    pub vote: Account<'info, Vote>,
+
+    /// CHECK: Winner whose signed intent is verified via the Ed25519 instruction.
+    #[account(mut)]
+    pub winner: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // Same as `ClaimReward::claim_escrow`, with the relayer paying rent.
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + ClaimEscrow::MAX_SIZE,
+        seeds = [CLAIM_ESCROW_SEED, challenge_id.as_bytes(), winner.key().as_ref()],
+        bump
+    )]
+    pub claim_escrow: Option<Account<'info, ClaimEscrow>>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + PayoutLedger::MAX_SIZE,
+        seeds = [PAYOUT_LEDGER_SEED, winner.key().as_ref()],
+        bump
+    )]
+    pub payout_ledger: Account<'info, PayoutLedger>,
+
+    // Credited with the correct vote.
+    #[account(mut, seeds = [CHALLENGER_SEED, winner.key().as_ref()], bump = challenger_profile.bump)]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    // Funds the winner's streak bonus.
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    /// CHECK: Address-constrained to the instructions sysvar.
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // Optional: the creator's profile, to free the slot of an overdue
+    // challenge settled here; without it such a challenge is refused.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Option<Account<'info, WaldoProfile>>,
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to mark its entry closed if an overdue challenge settles here.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+}
+
+// Must directly follow an Ed25519 program instruction over
+// `claim_intent_message(challenge, config.relayer_claim_fee)` signed by
+// `winner`. The fee goes to the relayer and the rest to the winner, so a
+// witness without SOL for fees still receives a net reward.
+pub fn handler(ctx: Context<ClaimRewardWithIntent>, _challenge_id: String) -> Result<()> {
+    let winner = ctx.accounts.winner.key();
+    let challenge_key = ctx.accounts.challenge.key();
+    let fee = ctx.accounts.config.relayer_claim_fee;
+    let message = claim_intent_message(&challenge_key, fee);
+    verify_preceding_signature(&ctx.accounts.instructions.to_account_info(), &winner, &message)?;
+
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    if settle_if_overdue(challenge, challenge_key, Clock::get()?.unix_timestamp, None, SettlementAccounts {
+        waldo_profile: ctx.accounts.waldo_profile.as_mut(),
+        challenge_registry: ctx.accounts.challenge_registry.as_mut(),
+        registry_page: ctx.accounts.registry_page.as_mut(),
+    })? {
+        return Ok(());
+    }
+
+    let reward = claimable_reward(challenge, &ctx.accounts.vote)?;
+    let bonus = fund_streak_bonus(
+        challenge_key,
+        &ctx.accounts.vault,
+        &ctx.accounts.insurance_pool,
+        &mut ctx.accounts.challenger_profile,
+        winner,
+        reward,
+    )?;
+    let gross = reward + bond_top_up(challenge, &ctx.accounts.config, reward) + bonus;
+    require!(gross > fee, PolocError::RewardBelowRelayerFee);
+    let net = gross - fee;
+
+    // The vault is program-owned, so the fee moves directly.
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let relayer_info = ctx.accounts.relayer.to_account_info();
+    **vault_info.try_borrow_mut_lamports()? = vault_info
+        .lamports()
+        .checked_sub(fee)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    **relayer_info.try_borrow_mut_lamports()? = relayer_info
+        .lamports()
+        .checked_add(fee)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    pay_claim(
+        challenge_key,
+        &ctx.accounts.vault,
+        &ctx.accounts.winner.to_account_info(),
+        ctx.accounts.claim_escrow.as_mut(),
+        ctx.bumps.claim_escrow,
+        ctx.accounts.config.claim_escrow_period,
+        net,
+    )?;
+
+    record_payout(&mut ctx.accounts.payout_ledger, ctx.bumps.payout_ledger, winner, challenge_key, net)?;
+
+    msg!("Relayer {} paid {} lamports", ctx.accounts.relayer.key(), fee);
+    record_claim(challenge, challenge_key, &mut ctx.accounts.vote, &mut ctx.accounts.challenger_profile, winner, reward, net)
+}
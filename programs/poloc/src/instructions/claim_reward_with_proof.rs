@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::ed25519::verify_preceding_signature;
+use crate::instructions::claim_escrow::pay_claim;
+use crate::instructions::claim_reward::{
+    bond_top_up, claimable_reward, fund_streak_bonus, record_claim, record_payout,
+};
+use crate::instructions::finalize::{settle_if_overdue, SettlementAccounts};
+
+const CLAIM_PROOF_DOMAIN: &[u8] = b"poloc:claim-proof:v1";
+
+/// Bytes a vote's original key signs to hand its reward to `claimant`, e.g.
+/// a new wallet after key rotation. The vote is named by its position in the
+/// challenge's vote chain, and is marked processed on claim, so a proof is
+/// single-use.
+pub fn claim_proof_message(challenge: &Pubkey, vote_index: u32, claimant: &Pubkey) -> Vec<u8> {
+    let mut message = Vec::with_capacity(CLAIM_PROOF_DOMAIN.len() + 32 + 32 + 4 + 32);
+    message.extend_from_slice(CLAIM_PROOF_DOMAIN);
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(challenge.as_ref());
+    message.extend_from_slice(&vote_index.to_le_bytes());
+    message.extend_from_slice(claimant.as_ref());
+    message
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, challenger: Pubkey)]
+pub struct ClaimRewardWithProof<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger.as_ref()],
+        bump = vote.bump,
+        constraint = vote.challenger == challenger @ PolocError::Unauthorized,
+        constraint = !vote.processed @ PolocError::AlreadyClaimed,
+    )]
+    pub vote: Account<'info, Vote>,
+
+    // Optional: the vote's stake, when `claimant` is its delegated operator.
+    // Unneeded with a signed proof.
+    #[account(seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger.as_ref()], bump = stake_account.bump)]
+    pub stake_account: Option<Account<'info, Stake>>,
+
+    // The wallet the reward is paid to.
+    #[account(mut)]
+    pub claimant: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // Same as `ClaimReward::claim_escrow`, held for the claimant.
+    #[account(
+        init,
+        payer = claimant,
+        space = 8 + ClaimEscrow::MAX_SIZE,
+        seeds = [CLAIM_ESCROW_SEED, challenge_id.as_bytes(), claimant.key().as_ref()],
+        bump
+    )]
+    pub claim_escrow: Option<Account<'info, ClaimEscrow>>,
+
+    #[account(
+        init_if_needed,
+        payer = claimant,
+        space = 8 + PayoutLedger::MAX_SIZE,
+        seeds = [PAYOUT_LEDGER_SEED, claimant.key().as_ref()],
+        bump
+    )]
+    pub payout_ledger: Account<'info, PayoutLedger>,
+
+    // The original key's profile is still the one credited with the vote.
+    #[account(mut, seeds = [CHALLENGER_SEED, challenger.as_ref()], bump = challenger_profile.bump)]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    // Funds the streak bonus, as in `claim_reward`.
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    /// CHECK: Address-constrained to the instructions sysvar.
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // Optional: the creator's profile, to free the slot of an overdue
+    // challenge settled here; without it such a challenge is refused.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Option<Account<'info, WaldoProfile>>,
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to mark its entry closed if an overdue challenge settles here.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+}
+
+// `claim_reward` for a vote cast under another key. The claimant proves the
+// vote is theirs either as the stake's delegated operator, or by a directly
+// preceding Ed25519 instruction over `claim_proof_message` signed by the key
+// that cast it.
+pub fn handler(ctx: Context<ClaimRewardWithProof>, _challenge_id: String, challenger: Pubkey) -> Result<()> {
+    let claimant = ctx.accounts.claimant.key();
+    let delegated = ctx.accounts.stake_account.as_ref().is_some_and(|stake| stake.operator == claimant);
+    if claimant != challenger && !delegated {
+        let message = claim_proof_message(&ctx.accounts.challenge.key(), ctx.accounts.vote.chain_index, &claimant);
+        verify_preceding_signature(&ctx.accounts.instructions.to_account_info(), &challenger, &message)?;
+    }
+
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    if settle_if_overdue(challenge, challenge_key, Clock::get()?.unix_timestamp, None, SettlementAccounts {
+        waldo_profile: ctx.accounts.waldo_profile.as_mut(),
+        challenge_registry: ctx.accounts.challenge_registry.as_mut(),
+        registry_page: ctx.accounts.registry_page.as_mut(),
+    })? {
+        return Ok(());
+    }
+
+    let reward = claimable_reward(challenge, &ctx.accounts.vote)?;
+    let bonus = fund_streak_bonus(
+        challenge_key,
+        &ctx.accounts.vault,
+        &ctx.accounts.insurance_pool,
+        &mut ctx.accounts.challenger_profile,
+        claimant,
+        reward,
+    )?;
+    let paid = reward + bond_top_up(challenge, &ctx.accounts.config, reward) + bonus;
+
+    pay_claim(
+        challenge_key,
+        &ctx.accounts.vault,
+        &ctx.accounts.claimant.to_account_info(),
+        ctx.accounts.claim_escrow.as_mut(),
+        ctx.bumps.claim_escrow,
+        ctx.accounts.config.claim_escrow_period,
+        paid,
+    )?;
+    record_payout(&mut ctx.accounts.payout_ledger, ctx.bumps.payout_ledger, claimant, challenge_key, paid)?;
+
+    msg!("Vote cast by {} claimed from {}", challenger, claimant);
+    record_claim(challenge, challenge_key, &mut ctx.accounts.vote, &mut ctx.accounts.challenger_profile, claimant, reward, paid)
+}
@@ -0,0 +1,26 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+pub struct ClearCollusionFlag<'info> {
+    #[account(
+        mut,
+        seeds = [COLLUSION_SEED, collusion_flag.wallet.as_ref()],
+        bump = collusion_flag.bump,
+        close = admin
+    )]
+    pub collusion_flag: Account<'info, CollusionFlag>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ClearCollusionFlag>) -> Result<()> {
+    msg!("Collusion flag on {} cleared", ctx.accounts.collusion_flag.wallet);
+    Ok(())
+}
@@ -0,0 +1,171 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes as slot_hashes_sysvar;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::instructions::initialize_challenge::{create_challenge, InitializeChallengeArgs, NewChallengeAccounts};
+
+/// Arguments for `clone_challenge`. Everything not listed is copied from the
+/// source challenge.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CloneChallengeArgs {
+    pub challenge_id: String,          // ID of the new challenge
+    pub r_star_threshold: Option<u32>, // Defaults to the source's threshold
+    pub duration: Option<u64>,         // Defaults to the source's duration
+    pub location_salt: [u8; 32],       // Fresh salt for private commitments
+    pub device_hash: [u8; 32],
+    pub start_time: i64,               // Scheduled start; 0 or past = start now
+}
+
+#[derive(Accounts)]
+#[instruction(args: CloneChallengeArgs)]
+pub struct CloneChallenge<'info> {
+    #[account(
+        constraint = source.load()?.waldo == waldo.key() @ PolocError::Unauthorized,
+        constraint = !source.load()?.is_open() @ PolocError::ChallengeStillActive,
+        constraint = source.load()?.location_known() @ PolocError::LocationNotRevealed,
+        constraint = source.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !source.load()?.is_token_pool() @ PolocError::RewardAssetMismatch,
+    )]
+    pub source: AccountLoader<'info, Challenge>,
+
+    #[account(
+        init,
+        payer = waldo,
+        space = Challenge::MAX_SIZE,
+        seeds = [CHALLENGE_SEED, args.challenge_id.as_bytes()],
+        bump
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        init,
+        payer = waldo,
+        space = 8 + ChallengeVault::MAX_SIZE,
+        seeds = [VAULT_SEED, args.challenge_id.as_bytes()],
+        bump
+    )]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = waldo,
+        space = 8 + WaldoProfile::MAX_SIZE,
+        seeds = [WALDO_SEED, waldo.key().as_ref()],
+        bump
+    )]
+    pub waldo_profile: Account<'info, WaldoProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = waldo,
+        space = 8 + DeviceLock::MAX_SIZE,
+        seeds = [DEVICE_SEED, args.device_hash.as_ref()],
+        bump
+    )]
+    pub device_lock: Account<'info, DeviceLock>,
+
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    #[account(mut)]
+    pub waldo: Signer<'info>,
+
+    /// CHECK: Address-constrained to the SlotHashes sysvar; seeds the measurement nonce.
+    #[account(address = slot_hashes_sysvar::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Retries a settled challenge under a new ID, funded afresh by the creator
+// with the source's full tranche schedule. A revealed cloaked source is
+// cloned with its coordinates in the clear, but stays private. Retrying a
+// near miss (R* just over the threshold) gets the configured discount on the
+// creation fee and bond.
+pub fn handler(ctx: Context<CloneChallenge>, args: CloneChallengeArgs) -> Result<()> {
+    let source = ctx.accounts.source.load()?;
+    let r_star_threshold = args.r_star_threshold.unwrap_or(source.r_star_threshold);
+    require!(r_star_threshold > 0, PolocError::InvalidParameters);
+
+    let source_duration = source.deadline
+        .checked_sub(source.start_time)
+        .ok_or(PolocError::ArithmeticOverflow)? as u64;
+    let reward_pool = source.tranche_amount
+        .checked_mul(source.total_tranches as u64)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    let init_args = InitializeChallengeArgs {
+        challenge_id: args.challenge_id,
+        // Finalize moves claimed_lat/lon to the selected candidate; clone the set as created.
+        claimed_lat: if source.candidate_count > 0 { source.candidates[0].lat } else { source.claimed_lat },
+        claimed_lon: if source.candidate_count > 0 { source.candidates[0].lon } else { source.claimed_lon },
+        duration: args.duration.unwrap_or(source_duration),
+        reward_pool,
+        tranches: source.total_tranches,
+        private: source.private != 0,
+        location_salt: args.location_salt,
+        min_equipment_class: source.min_equipment_class,
+        device_hash: args.device_hash,
+        start_time: args.start_time,
+        location_commitment: [0u8; 32],
+        underwrite_witnesses: source.underwrites_witnesses != 0,
+        encrypted_votes: source.encrypted_votes != 0,
+        required_credential: source.required_credential,
+        required_schema: source.required_schema,
+        alternate_locations: source.candidates[1..source.candidate_count.max(1) as usize]
+            .iter()
+            .map(|c| [c.lat, c.lon])
+            .collect(),
+        arbiter: if source.arbiter == source.waldo { Pubkey::default() } else { source.arbiter },
+        vote_schedule: source.vote_schedule,
+        context: source.context,
+        min_reputation: source.min_reputation,
+        threshold_meters: 0,
+        rtt_attested: source.rtt_attested != 0,
+    };
+    let discount_bps = if source.status() == ChallengeStatus::Inconclusive {
+        ctx.accounts.config.retry_discount_bps
+    } else {
+        0
+    };
+    msg!("Cloning challenge {} as {} (retry discount {} bps)",
+         source.challenge_id(), init_args.challenge_id, discount_bps);
+
+    // Only the source's creator may clone, so integrator challenges stay in
+    // their namespace.
+    let namespace = IntegratorNamespace::prefix_of(source.challenge_id());
+    drop(source);
+    let accounts = ctx.accounts;
+    create_challenge(
+        NewChallengeAccounts {
+            challenge: &accounts.challenge,
+            vault: &mut accounts.vault,
+            config: &accounts.config,
+            waldo_profile: &mut accounts.waldo_profile,
+            device_lock: &mut accounts.device_lock,
+            insurance_pool: &mut accounts.insurance_pool,
+            waldo: &accounts.waldo,
+            system_program: &accounts.system_program,
+            slot_hashes: &accounts.slot_hashes,
+            prior_result: None,
+            fee_waivers: None,
+            waiver_attestation: None,
+            expiry_feed: None,
+            registry: None,
+            funder: None,
+            token_pool: None,
+            challenge_bump: ctx.bumps.challenge,
+            vault_bump: ctx.bumps.vault,
+            waldo_profile_bump: ctx.bumps.waldo_profile,
+            device_lock_bump: ctx.bumps.device_lock,
+        },
+        init_args,
+        r_star_threshold,
+        discount_bps,
+        namespace,
+    )
+}
@@ -0,0 +1,100 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::instructions::claim_reward::reward_share;
+use crate::instructions::claim_bond::bond_share;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct CloseVote<'info> {
+    /// CHECK: The vote's challenge; may already be swept, refunded or
+    /// archived. Parsed in the handler when it still exists.
+    #[account(mut, seeds = [CHALLENGE_SEED, challenge_id.as_bytes()], bump)]
+    pub challenge: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump = vote.bump,
+        constraint = !vote.receipt_outstanding @ PolocError::ReceiptOutstanding,
+        close = challenger
+    )]
+    pub vote: Account<'info, Vote>,
+
+    #[account(mut, address = vote.challenger @ PolocError::Unauthorized)]
+    pub challenger: SystemAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct CloseStake<'info> {
+    /// CHECK: The stake's challenge; may already be swept, refunded or
+    /// archived. Parsed in the handler when it still exists.
+    #[account(seeds = [CHALLENGE_SEED, challenge_id.as_bytes()], bump)]
+    pub challenge: UncheckedAccount<'info>,
+
+    // Standing stakes are closed by `release_standing_stake`.
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump = stake_account.bump,
+        constraint = !stake_account.standing @ PolocError::InvalidParameters,
+        constraint = !stake_account.receipt_outstanding @ PolocError::ReceiptOutstanding,
+        close = challenger
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    #[account(mut, address = stake_account.challenger @ PolocError::Unauthorized)]
+    pub challenger: SystemAccount<'info>,
+}
+
+// Refunds a vote's rent to its challenger once the vote has nothing left to
+// do: its reward is claimed or it never earned one, and the slash window is
+// over. Permissionless, since the rent can only go back to the challenger.
+// Void records, and votes of a challenge that is already gone, can always be
+// closed.
+pub fn close_vote_handler(ctx: Context<CloseVote>, challenge_id: String) -> Result<()> {
+    let challenge_info = ctx.accounts.challenge.to_account_info();
+    if let Some(mut challenge) = Challenge::load_existing_mut(&challenge_info)?.filter(|_| !ctx.accounts.vote.void) {
+        let vote = &ctx.accounts.vote;
+        require!(!vote.prepared, PolocError::VoteNotCast);
+        require!(!challenge.is_open(), PolocError::ChallengeStillOpen);
+        require!(Clock::get()?.unix_timestamp >= challenge.slash_window_ends_at(), PolocError::StakeStillLocked);
+        require!(
+            vote.processed || (reward_share(&challenge, vote).is_err() && bond_share(&challenge, vote).is_err()),
+            PolocError::VoteStillClaimable
+        );
+
+        // `archive_challenge` takes only the vote accounts still open.
+        challenge.closed_votes = challenge.closed_votes.saturating_add(1);
+    }
+
+    msg!("Vote of {} in challenge {} closed", ctx.accounts.challenger.key(), challenge_id);
+    Ok(())
+}
+
+// Whether only rent is left in `stake`: it is void, slashed outside the pool
+// with the slash window over, or left behind by a challenge that is gone.
+// Pool stakes, slashed or not, come back with their lamports through
+// `unstake` and `return_stake` instead.
+pub fn require_stake_settled(challenge: Option<&Challenge>, stake: &Stake, now: i64) -> Result<()> {
+    let Some(challenge) = challenge.filter(|_| !stake.void) else {
+        return Ok(());
+    };
+    require!(stake.slashed, PolocError::InvalidParameters);
+    require!(!stake.pooled, PolocError::StakesOutstanding);
+    require!(!challenge.is_open(), PolocError::ChallengeStillOpen);
+    require!(now >= challenge.slash_window_ends_at(), PolocError::StakeStillLocked);
+    Ok(())
+}
+
+// Refunds the rent of a settled stake; see `require_stake_settled`.
+pub fn close_stake_handler(ctx: Context<CloseStake>, challenge_id: String) -> Result<()> {
+    let challenge_info = ctx.accounts.challenge.to_account_info();
+    let challenge = Challenge::load_existing(&challenge_info)?;
+    require_stake_settled(challenge.as_deref(), &ctx.accounts.stake_account, Clock::get()?.unix_timestamp)?;
+
+    msg!("Stake of {} in challenge {} closed", ctx.accounts.challenger.key(), challenge_id);
+    Ok(())
+}
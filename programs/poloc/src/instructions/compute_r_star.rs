@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::validation::require_in_window;
+use crate::instructions::finalize::r_star_from_entries;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ComputeRStar<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+}
+
+// Permissionless once voting closes. Takes R* as the stake-weighted median
+// uncertainty of the valid votes and stores it, so the creator no longer
+// reports their own result. The votes come from the roster, so none can be
+// left out to move the median; running it again gives the same value.
+pub fn handler(ctx: Context<ComputeRStar>, challenge_id: String) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require!(challenge.location_known(), PolocError::LocationNotRevealed);
+    require_in_window(
+        challenge_key,
+        Clock::get()?.unix_timestamp,
+        challenge.voting_ends_at() + 1,
+        i64::MAX,
+        PolocError::ChallengeExpired,
+    )?;
+    require!(challenge.sealed_votes == 0, PolocError::SealedVotesOutstanding);
+
+    let (r_star, valid_votes) = r_star_from_entries(challenge, challenge.roster_entries().iter().copied());
+    trace!("R* from {} valid of {} vote(s): {}m", valid_votes, challenge.vote_count, r_star);
+    challenge.r_star = r_star;
+    challenge.r_star_computed = 1;
+
+    msg!("Challenge {} R* computed on-chain: {}m", challenge_id, r_star);
+    emit!(RStarComputed {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        r_star,
+        valid_votes,
+    });
+    Ok(())
+}
@@ -0,0 +1,104 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::validation::require_in_window;
+use crate::events::*;
+use crate::instructions::challenge_registry::track_tvl;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct Contribute<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = sponsor,
+        space = 8 + Contribution::MAX_SIZE,
+        seeds = [CONTRIBUTION_SEED, challenge_id.as_bytes(), sponsor.key().as_ref()],
+        bump
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(mut)]
+    pub sponsor: Signer<'info>,
+
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to keep its TVL current.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<Contribute>, challenge_id: String, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require_in_window(challenge_key, clock.unix_timestamp, i64::MIN, challenge.deadline, PolocError::ChallengeExpired)?;
+    require!(amount > 0, PolocError::InvalidParameters);
+    ctx.accounts.config.check_pool_cap(challenge.committed_tvl().saturating_add(amount))?;
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.sponsor.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let contribution = &mut ctx.accounts.contribution;
+    if contribution.sponsor == Pubkey::default() {
+        contribution.challenge = challenge_key;
+        contribution.sponsor = ctx.accounts.sponsor.key();
+        contribution.bump = ctx.bumps.contribution;
+        challenge.open_contributions = challenge.open_contributions
+            .checked_add(1)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+    }
+    contribution.amount = contribution.amount
+        .checked_add(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    challenge.sponsor_total = challenge.sponsor_total
+        .checked_add(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    challenge.reward_pool = challenge.reward_pool
+        .checked_add(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    msg!("Sponsor {} contributed {} lamports to challenge {}",
+         contribution.sponsor, amount, challenge_id);
+
+    emit!(ContributionReceived {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        sponsor: contribution.sponsor,
+        amount,
+        reward_pool: challenge.reward_pool,
+    });
+    track_tvl(ctx.accounts.challenge_registry.as_mut(), ctx.accounts.registry_page.as_mut(), challenge, challenge_key)?;
+
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct DelegateStake<'info> {
+    #[account(
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    // Only the owner may (re)assign the operator; reward rights stay with it.
+    pub challenger: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DelegateStake>, challenge_id: String, operator: Pubkey) -> Result<()> {
+    require!(ctx.accounts.challenge.load()?.is_open(), PolocError::ChallengeNotActive);
+
+    let stake_account = &mut ctx.accounts.stake_account;
+    require!(!stake_account.slashed, PolocError::StakeSlashed);
+    stake_account.operator = operator;
+
+    msg!("Stake of {} for challenge {} delegated to operator {}",
+         stake_account.challenger, challenge_id, operator);
+    Ok(())
+}
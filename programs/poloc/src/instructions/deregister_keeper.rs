@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+pub struct DeregisterKeeper<'info> {
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, operator.key().as_ref()],
+        bump = keeper.bump,
+        has_one = operator @ PolocError::Unauthorized,
+        // Returns the remaining stake plus rent to the operator.
+        close = operator
+    )]
+    pub keeper: Account<'info, Keeper>,
+
+    #[account(mut)]
+    pub operator: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<DeregisterKeeper>) -> Result<()> {
+    let keeper = &ctx.accounts.keeper;
+    let clock = Clock::get()?;
+
+    // Keep the stake slashable for a while after the last crank.
+    require!(
+        clock.unix_timestamp >= keeper.last_active + Keeper::UNBONDING_PERIOD,
+        PolocError::KeeperUnbonding
+    );
+
+    msg!("Keeper {} deregistered, returning {} lamports of stake", keeper.operator, keeper.stake);
+    Ok(())
+}
@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::claim_escrow::move_lamports;
+use crate::program::Poloc;
+
+// Last-resort recovery of lamports stranded in a challenge's vault by a bug. It
+// is gated on the program's upgrade authority, like `penalize_keeper`, and
+// only touches a challenge the guardian has frozen, so the challenge's own
+// flows can't race it. Nothing moves until EMERGENCY_WITHDRAW_DELAY after the
+// proposal, which leaves participants time to see it coming.
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ProposeEmergencyWithdraw<'info> {
+    #[account(
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen != 0 @ PolocError::ChallengeNotFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + EmergencyWithdrawal::MAX_SIZE,
+        seeds = [EMERGENCY_WITHDRAWAL_SEED, challenge.key().as_ref()],
+        bump
+    )]
+    pub withdrawal: Account<'info, EmergencyWithdrawal>,
+
+    /// CHECK: Receives the lamports; chosen by governance.
+    pub destination: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Poloc>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ PolocError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct CancelEmergencyWithdraw<'info> {
+    #[account(seeds = [CHALLENGE_SEED, challenge_id.as_bytes()], bump = challenge.load()?.bump)]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [EMERGENCY_WITHDRAWAL_SEED, challenge.key().as_ref()],
+        bump = withdrawal.bump,
+        close = authority
+    )]
+    pub withdrawal: Account<'info, EmergencyWithdrawal>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Poloc>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ PolocError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ExecuteEmergencyWithdraw<'info> {
+    // Unfreezing the challenge during the timelock blocks the withdrawal.
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen != 0 @ PolocError::ChallengeNotFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(
+        mut,
+        seeds = [EMERGENCY_WITHDRAWAL_SEED, challenge.key().as_ref()],
+        bump = withdrawal.bump,
+        close = authority
+    )]
+    pub withdrawal: Account<'info, EmergencyWithdrawal>,
+
+    /// CHECK: Address-constrained to the proposed destination.
+    #[account(mut, address = withdrawal.destination @ PolocError::Unauthorized)]
+    pub destination: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Poloc>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ PolocError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+}
+
+pub fn propose_handler(ctx: Context<ProposeEmergencyWithdraw>, challenge_id: String, amount: u64) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let withdrawal = &mut ctx.accounts.withdrawal;
+    withdrawal.challenge = ctx.accounts.challenge.key();
+    withdrawal.destination = ctx.accounts.destination.key();
+    withdrawal.amount = amount;
+    withdrawal.proposed_at = now;
+    withdrawal.executable_at = now + EMERGENCY_WITHDRAW_DELAY;
+    withdrawal.bump = ctx.bumps.withdrawal;
+
+    msg!("Emergency withdrawal of {} lamports from challenge {} to {} proposed, executable at {}",
+         amount, challenge_id, withdrawal.destination, withdrawal.executable_at);
+    emit!(EmergencyWithdrawalProposed {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: withdrawal.challenge,
+        destination: withdrawal.destination,
+        amount,
+        executable_at: withdrawal.executable_at,
+    });
+    Ok(())
+}
+
+pub fn cancel_handler(ctx: Context<CancelEmergencyWithdraw>, challenge_id: String) -> Result<()> {
+    msg!("Emergency withdrawal from challenge {} cancelled", challenge_id);
+    emit!(EmergencyWithdrawalCancelled {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: ctx.accounts.challenge.key(),
+    });
+    Ok(())
+}
+
+// Takes untracked surplus first, so a withdrawal of stranded lamports leaves
+// the pool's accounting alone; anything beyond it is debited from the pool,
+// then the bond.
+pub fn execute_handler(ctx: Context<ExecuteEmergencyWithdraw>, challenge_id: String) -> Result<()> {
+    let withdrawal = &ctx.accounts.withdrawal;
+    require!(Clock::get()?.unix_timestamp >= withdrawal.executable_at, PolocError::TimelockNotElapsed);
+
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    let info = ctx.accounts.vault.to_account_info();
+    let rent = Rent::get()?.minimum_balance(info.data_len());
+    let available = info.lamports().saturating_sub(rent);
+    let amount = withdrawal.amount;
+    require!(amount <= available, PolocError::WithdrawalTooLarge);
+
+    let pool = if challenge.is_token_pool() { 0 } else { challenge.reward_pool };
+    let surplus = available.saturating_sub(pool.saturating_add(challenge.waldo_bond));
+    let pool_debit = amount.saturating_sub(surplus);
+    let from_pool = pool_debit.min(pool);
+    challenge.reward_pool -= from_pool;
+    challenge.waldo_bond = challenge.waldo_bond.saturating_sub(pool_debit - from_pool);
+
+    move_lamports(&info, &ctx.accounts.destination.to_account_info(), amount)?;
+
+    msg!("Emergency withdrawal of {} lamports from challenge {} to {} ({} from pool and bond)",
+         amount, challenge_id, withdrawal.destination, pool_debit);
+    emit!(EmergencyWithdrawalExecuted {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        destination: withdrawal.destination,
+        amount,
+        pool_debit,
+    });
+    Ok(())
+}
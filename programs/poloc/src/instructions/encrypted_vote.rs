@@ -0,0 +1,338 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::finalize::{settle_if_overdue, SettlementAccounts};
+use crate::instructions::vote::{check_voting_window, vote_in_range};
+use crate::validation::{load_stake_accounts, load_vote_accounts, require_in_window};
+use poloc_core::slashing::MAX_CONFIDENCE_BPS;
+
+const SEALED_VOTE_DOMAIN: &[u8] = b"poloc:sealed-vote:v1";
+const MAX_CIPHERTEXT_LEN: usize = 256;
+
+/// Commitment a sealed vote is bound to. The voter encrypts the same fields
+/// and nonce to `Config::vote_committee_key`; the tally checks the decrypted
+/// vote against this, so the committee cannot alter it.
+pub fn sealed_vote_commitment(
+    challenge: &Pubkey,
+    challenger: &Pubkey,
+    is_valid: bool,
+    uncertainty: u32,
+    min_rtt: u32,
+    confidence: u16,
+    nonce: &[u8; 32],
+) -> [u8; 32] {
+    keccak::hashv(&[
+        SEALED_VOTE_DOMAIN,
+        challenge.as_ref(),
+        challenger.as_ref(),
+        &[is_valid as u8],
+        &uncertainty.to_le_bytes(),
+        &min_rtt.to_le_bytes(),
+        &confidence.to_le_bytes(),
+        nonce,
+    ])
+    .to_bytes()
+}
+
+/// A sealed vote's contents as recovered by the committee.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct DecryptedVote {
+    pub is_valid: bool,
+    pub uncertainty: u32,
+    pub min_rtt: u32,
+    pub nonce: [u8; 32],
+    pub confidence: u16,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct SubmitEncryptedVote<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump = stake_account.bump,
+        constraint = stake_account.operator == operator.key() @ PolocError::Unauthorized
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + Vote::MAX_SIZE,
+        seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump
+    )]
+    pub vote_account: Account<'info, Vote>,
+
+    /// CHECK: Stake owner; only used to derive the stake and vote PDAs.
+    pub challenger: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // Optional: the creator's profile, to free the slot of an overdue
+    // challenge settled here; without it such a challenge is refused.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Option<Account<'info, WaldoProfile>>,
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to mark its entry closed if an overdue challenge settles here.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct SubmitDecryptionShare<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(seeds = [VOTE_COMMITTEE_SEED], bump = vote_committee.bump)]
+    pub vote_committee: Account<'info, VoteCommittee>,
+
+    pub member: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct DecryptAndTally<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(seeds = [VOTE_COMMITTEE_SEED], bump = vote_committee.bump)]
+    pub vote_committee: Account<'info, VoteCommittee>,
+
+    pub member: Signer<'info>,
+
+    // remaining_accounts: sealed Vote PDAs (ascending by challenger), then
+    // their Stake PDAs in the same order.
+}
+
+// Counted only once tallied; until then other witnesses can't see which way
+// the window is leaning.
+pub fn submit_handler(
+    ctx: Context<SubmitEncryptedVote>,
+    challenge_id: String,
+    challenger_id: String,
+    commitment: [u8; 32],
+    ciphertext: Vec<u8>,
+) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    let vote_account = &mut ctx.accounts.vote_account;
+    let clock = Clock::get()?;
+
+    challenge.activate_if_started(clock.unix_timestamp);
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require!(challenge.location_known(), PolocError::LocationNotRevealed);
+
+    if settle_if_overdue(challenge, challenge_key, clock.unix_timestamp, None, SettlementAccounts {
+        waldo_profile: ctx.accounts.waldo_profile.as_mut(),
+        challenge_registry: ctx.accounts.challenge_registry.as_mut(),
+        registry_page: ctx.accounts.registry_page.as_mut(),
+    })? {
+        // Same as `submit_vote`: a void record.
+        vote_account.challenger = ctx.accounts.challenger.key();
+        vote_account.challenge_id = challenge_id;
+        vote_account.bump = ctx.bumps.vote_account;
+        vote_account.void = true;
+        return Ok(());
+    }
+
+    check_voting_window(challenge, challenge_key, clock.unix_timestamp)?;
+    require!(challenge.encrypted_votes != 0, PolocError::VotesNotEncrypted);
+    require!(!ctx.accounts.stake_account.slashed, PolocError::StakeSlashed);
+    require!(
+        !ciphertext.is_empty() && ciphertext.len() <= MAX_CIPHERTEXT_LEN,
+        PolocError::InvalidParameters
+    );
+
+    vote_account.challenger = ctx.accounts.challenger.key();
+    vote_account.challenge_id = challenge_id.clone();
+    vote_account.challenger_id = challenger_id;
+    vote_account.timestamp = clock.unix_timestamp;
+    vote_account.processed = false;
+    vote_account.commitment = commitment;
+    vote_account.sealed = true;
+    vote_account.receipt_outstanding = ctx.accounts.stake_account.receipt_outstanding;
+    vote_account.stake = ctx.accounts.stake_account.amount;
+    vote_account.multisig_claim = ctx.accounts.stake_account.has_claim_policy();
+    vote_account.bump = ctx.bumps.vote_account;
+    let vote_key = vote_account.key();
+    challenge.chain_vote(&vote_key, vote_account)?;
+    challenge.push_roster(vote_account)?;
+
+    challenge.sealed_votes = challenge.sealed_votes
+        .checked_add(1)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    msg!("Sealed vote submitted by {} for challenge {}", ctx.accounts.challenger.key(), challenge_id);
+
+    emit!(EncryptedVoteSubmitted {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenger: ctx.accounts.challenger.key(),
+        commitment,
+        ciphertext,
+    });
+    emit!(VoteChained {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        vote: vote_key,
+        chain_index: vote_account.chain_index,
+        chain_hash: vote_account.chain_hash,
+    });
+    Ok(())
+}
+
+// Shares are only accepted once voting has closed, so nobody can decrypt
+// early.
+pub fn share_handler(ctx: Context<SubmitDecryptionShare>, challenge_id: String, share: Vec<u8>) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    require!(challenge.encrypted_votes != 0, PolocError::VotesNotEncrypted);
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require_in_window(
+        challenge_key,
+        Clock::get()?.unix_timestamp,
+        challenge.voting_ends_at() + 1,
+        i64::MAX,
+        PolocError::VotingNotOpen,
+    )?;
+
+    let member = ctx.accounts.member.key();
+    let index = ctx.accounts.vote_committee
+        .member_index(&member)
+        .ok_or(PolocError::NotCommitteeMember)?;
+    let bit = 1u8 << index;
+    require!(challenge.decryption_shares & bit == 0, PolocError::DecryptionShareAlreadySubmitted);
+    challenge.decryption_shares |= bit;
+
+    msg!("Decryption share {}/{} for challenge {} from {}",
+         challenge.decryption_shares.count_ones(), ctx.accounts.vote_committee.threshold,
+         challenge_id, member);
+
+    emit!(DecryptionShareSubmitted {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        member,
+        share,
+    });
+    Ok(())
+}
+
+// Any committee member may post the decrypted votes once the threshold of
+// shares is in; may be called in batches. Each entry must match its vote's
+// commitment. `None` voids a vote whose ciphertext would not decrypt, and
+// out-of-range contents count as an invalid vote, so no single voter can hold
+// up settlement.
+pub fn tally_handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, DecryptAndTally<'info>>,
+    challenge_id: String,
+    votes: Vec<Option<DecryptedVote>>,
+) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    let committee = &ctx.accounts.vote_committee;
+    require!(challenge.encrypted_votes != 0, PolocError::VotesNotEncrypted);
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require!(committee.member_index(&ctx.accounts.member.key()).is_some(), PolocError::NotCommitteeMember);
+    require!(
+        challenge.decryption_shares.count_ones() >= committee.threshold as u32,
+        PolocError::DecryptionThresholdNotMet
+    );
+
+    let count = votes.len();
+    let remaining = ctx.remaining_accounts;
+    require!(remaining.len() == count * 2, PolocError::RemainingAccountsCountMismatch);
+    let (vote_infos, stake_infos) = remaining.split_at(count);
+    let loaded_votes = load_vote_accounts(vote_infos, &challenge_id, Some(count))?;
+    let stakes = load_stake_accounts(stake_infos, &challenge_id, Some(count))?;
+
+    for (((mut vote, info), stake), decrypted) in loaded_votes.into_iter()
+        .zip(vote_infos)
+        .zip(&stakes)
+        .zip(votes)
+    {
+        require!(vote.sealed, PolocError::VoteNotSealed);
+        require_keys_eq!(stake.challenger, vote.challenger, PolocError::InvalidRemainingAccountAddress);
+
+        let is_valid = match decrypted {
+            Some(d) => {
+                let expected = sealed_vote_commitment(
+                    &challenge_key, &vote.challenger, d.is_valid, d.uncertainty, d.min_rtt, d.confidence, &d.nonce,
+                );
+                require!(expected == vote.commitment, PolocError::VoteCommitmentMismatch);
+                vote.uncertainty = d.uncertainty;
+                vote.min_rtt = d.min_rtt;
+                vote.confidence = d.confidence.min(MAX_CONFIDENCE_BPS);
+                d.is_valid && vote_in_range(d.uncertainty, d.min_rtt)
+            }
+            None => {
+                msg!("Sealed vote from {} voided", vote.challenger);
+                false
+            }
+        };
+        vote.is_valid = is_valid;
+        vote.stake = stake.amount;
+        vote.sealed = false;
+        vote.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+        challenge.update_roster(&vote)?;
+
+        challenge.sealed_votes = challenge.sealed_votes
+            .checked_sub(1)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+        challenge.vote_count = challenge.vote_count
+            .checked_add(1)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+        if is_valid {
+            challenge.valid_vote_count = challenge.valid_vote_count
+                .checked_add(1)
+                .ok_or(PolocError::ArithmeticOverflow)?;
+            challenge.valid_stake = challenge.valid_stake
+                .checked_add(stake.amount)
+                .ok_or(PolocError::ArithmeticOverflow)?;
+        } else {
+            challenge.invalid_stake = challenge.invalid_stake
+                .checked_add(stake.amount.max(1))
+                .ok_or(PolocError::ArithmeticOverflow)?;
+        }
+
+        emit!(VoteSubmitted {
+            schema_version: EVENT_SCHEMA_VERSION,
+            challenge: challenge_key,
+            challenger: vote.challenger,
+            is_valid,
+            uncertainty: vote.uncertainty,
+            min_rtt: vote.min_rtt,
+        });
+    }
+
+    msg!("Tallied {} sealed vote(s) for challenge {}; {} left",
+         count, challenge_id, challenge.sealed_votes);
+    Ok(())
+}
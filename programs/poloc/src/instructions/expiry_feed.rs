@@ -0,0 +1,63 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+pub struct InitializeExpiryFeed<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + ExpiryFeed::MAX_SIZE,
+        seeds = [EXPIRY_FEED_SEED],
+        bump
+    )]
+    pub expiry_feed: Account<'info, ExpiryFeed>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ConsumeExpiryEntry<'info> {
+    #[account(mut, seeds = [EXPIRY_FEED_SEED], bump = expiry_feed.bump)]
+    pub expiry_feed: Account<'info, ExpiryFeed>,
+
+    /// CHECK: The challenge the entry names; may already be closed. Parsed in
+    /// the handler when it still exists.
+    pub challenge: UncheckedAccount<'info>,
+
+    pub keeper: Signer<'info>,
+}
+
+// Once created, challenges record themselves here when the caller passes the
+// feed; see `create_challenge`.
+pub fn initialize_handler(ctx: Context<InitializeExpiryFeed>) -> Result<()> {
+    ctx.accounts.expiry_feed.bump = ctx.bumps.expiry_feed;
+    msg!("Expiry feed initialized with {} slots", EXPIRY_FEED_CAPACITY);
+    Ok(())
+}
+
+// Clears the entry at `index` once its challenge is no longer open, or is
+// gone entirely, so pollers only see work that's still due. Permissionless:
+// it can only drop entries nobody needs.
+pub fn consume_handler(ctx: Context<ConsumeExpiryEntry>, index: u8) -> Result<()> {
+    let feed = &mut ctx.accounts.expiry_feed;
+    let challenge_info = ctx.accounts.challenge.to_account_info();
+    let entry = feed.entries.get_mut(index as usize).ok_or(PolocError::InvalidParameters)?;
+    require!(!entry.is_consumed() && entry.challenge == challenge_info.key(), PolocError::InvalidParameters);
+
+    if let Some(challenge) = Challenge::load_existing(&challenge_info)? {
+        require!(!challenge.is_open(), PolocError::ChallengeStillOpen);
+    }
+
+    let deadline = entry.deadline;
+    *entry = ExpiryEntry::default();
+    msg!("Expiry entry {} consumed: challenge {}, deadline {}", index, challenge_info.key(), deadline);
+    Ok(())
+}
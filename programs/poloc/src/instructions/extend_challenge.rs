@@ -0,0 +1,105 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::validation::{require_in_window, resolve_region_limits};
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ExtendChallenge<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: RegionPolicy PDA for the challenge's cell; may be uninitialized.
+    /// Validated by `resolve_region_limits`.
+    pub region_policy: UncheckedAccount<'info>,
+
+    // The prover's device stays locked to this location until the new deadline.
+    #[account(mut, constraint = device_lock.challenge == challenge.key() @ PolocError::InvalidParameters)]
+    pub device_lock: Account<'info, DeviceLock>,
+
+    #[account(mut, address = challenge.load()?.waldo @ PolocError::Unauthorized)]
+    pub waldo: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Gives a challenge that is short of participants more time and a bigger
+// pool to attract them. The extended duration must still fit a tier, and the
+// pool must stay under the TVL cap.
+pub fn handler(
+    ctx: Context<ExtendChallenge>,
+    challenge_id: String,
+    additional_duration: u64,
+    additional_reward: u64,
+) -> Result<()> {
+    let clock = Clock::get()?;
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require_in_window(challenge_key, clock.unix_timestamp, i64::MIN, challenge.deadline, PolocError::ChallengeExpired)?;
+    require!(additional_duration > 0, PolocError::InvalidParameters);
+
+    let (_, min_participants) = resolve_region_limits(
+        &ctx.accounts.region_policy.to_account_info(),
+        challenge,
+        &ctx.accounts.config,
+    )?;
+    require!(challenge.participant_count < min_participants, PolocError::ExtensionNotNeeded);
+
+    let deadline = i64::try_from(additional_duration)
+        .ok()
+        .and_then(|extra| challenge.deadline.checked_add(extra))
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    ctx.accounts.config
+        .duration_tier((deadline - challenge.start_time) as u64)
+        .ok_or(PolocError::DurationTooLong)?;
+
+    if additional_reward > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.waldo.to_account_info(),
+                    to: ctx.accounts.vault.to_account_info(),
+                },
+            ),
+            additional_reward,
+        )?;
+    }
+
+    challenge.deadline = deadline;
+    challenge.reward_pool = challenge.reward_pool
+        .checked_add(additional_reward)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    ctx.accounts.config.check_pool_cap(challenge.committed_tvl())?;
+
+    let lock = &mut ctx.accounts.device_lock;
+    lock.locked_until = lock.locked_until.max(deadline);
+
+    msg!("Challenge {} extended to {} with {} more lamports ({} in the pool)",
+         challenge_id, deadline, additional_reward, challenge.reward_pool);
+
+    emit!(ChallengeExtended {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        deadline,
+        added_reward: additional_reward,
+        reward_pool: challenge.reward_pool,
+    });
+    Ok(())
+}
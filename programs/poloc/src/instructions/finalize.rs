@@ -1,60 +1,398 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::constants::*;
 use crate::errors::PolocError;
+use crate::events::*;
+use crate::validation::{record_region_stats, require_in_window, resolve_region_limits};
+use crate::instructions::register_callback::invoke_result_callback;
+use crate::instructions::challenge_registry::delist_settled;
+use crate::utils::distance_m;
+use poloc_core::rstar::{weighted_quantile_u32, DEFAULT_BETA_BPS};
+
+/// What a lazy settle updates besides the challenge, from the settling
+/// instruction's optional accounts: the creator's profile, whose concurrency
+/// slot it frees, and the registry listing it closes.
+pub(crate) struct SettlementAccounts<'a, 'info> {
+    pub waldo_profile: Option<&'a mut Account<'info, WaldoProfile>>,
+    pub challenge_registry: Option<&'a mut Account<'info, ChallengeRegistry>>,
+    pub registry_page: Option<&'a mut Account<'info, RegistryPage>>,
+}
+
+/// Moves an Active challenge whose schedule has run out to its terminal
+/// status. Returns true when it did; callers then return `Ok(())` instead of
+/// erroring, since an error would roll the transition back.
+///
+/// `limits` is the resolved minimum participants and the cell's RegionStats
+/// when the caller has them; without them only the Expired transition, which
+/// the stats don't count, is applied. Without the creator's profile an
+/// overdue challenge is refused with `ChallengeExpired` rather than settled
+/// with its slot still taken. An unpassed registry leaves the listing to
+/// `delist_challenge`, as in `finalize_challenge`.
+pub(crate) fn settle_if_overdue(
+    challenge: &mut Challenge,
+    challenge_key: Pubkey,
+    now: i64,
+    limits: Option<(u32, &AccountInfo)>,
+    settlement: SettlementAccounts,
+) -> Result<bool> {
+    if !challenge.is_open() {
+        return Ok(false);
+    }
+    let under_subscribed = limits.is_some_and(|(min, _)| challenge.participant_count < min);
+    let status = if now > challenge.deadline && under_subscribed {
+        ChallengeStatus::InsufficientParticipants
+    } else if now > challenge.voting_ends_at() + FINALIZE_GRACE {
+        ChallengeStatus::Expired
+    } else {
+        return Ok(false);
+    };
+
+    let waldo_profile = settlement.waldo_profile.ok_or(PolocError::ChallengeExpired)?;
+    challenge.set_status(status);
+    msg!("Challenge {} is overdue; marked {:?}", challenge.challenge_id(), challenge.status());
+    emit!(ChallengeFinalized {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        status: challenge.status(),
+        r_star: challenge.r_star,
+        r_star_threshold: challenge.r_star_threshold,
+        passed: false,
+        context: challenge.context,
+    });
+    if let Some((_, region_stats)) = limits {
+        let cell = RegionPolicy::cell(challenge.claimed_lat, challenge.claimed_lon);
+        record_region_stats(region_stats, cell, challenge)?;
+    }
+    waldo_profile.release_active(challenge);
+    delist_settled(settlement.challenge_registry, settlement.registry_page, challenge, challenge_key)?;
+    Ok(true)
+}
+
+/// Fails until the config's settlement buffer has passed since voting
+/// closed. Returns when it did.
+pub(crate) fn require_settleable(challenge: &Challenge, challenge_key: Pubkey, config: &Config, now: i64) -> Result<i64> {
+    let settleable_at = challenge.settleable_at(config.min_settlement_buffer);
+    require_in_window(challenge_key, now, settleable_at, i64::MAX, PolocError::SettlementBufferActive)?;
+    Ok(settleable_at)
+}
+
+/// `require_settleable` for the instructions that settle a result, which
+/// also record the effective timestamps on the challenge.
+pub(crate) fn begin_settlement(challenge: &mut Challenge, challenge_key: Pubkey, config: &Config, now: i64) -> Result<()> {
+    challenge.settleable_at = require_settleable(challenge, challenge_key, config, now)?;
+    challenge.voting_closed_at = challenge.voting_ends_at();
+    challenge.finalized_at = now;
+    Ok(())
+}
+
+// Fastest plausible travel between two challenges (roughly airliner speed).
+pub(crate) const MAX_TRAVEL_SPEED_MPS: u64 = 300;
+
+/// Consistency guard run at finalize: given the prover's most recent passed
+/// challenge, flags the result when getting from one location to the other
+/// in the time between the two challenge windows would be physically
+/// impossible. Both R* radii are allowed as slack. Recency is the caller's
+/// claim; an older challenge only makes the check more lenient.
+pub(crate) fn consistency_guard(challenge: &mut Challenge, previous: &Challenge) -> Result<()> {
+    require!(
+        previous.waldo == challenge.waldo
+            && previous.challenge_id() != challenge.challenge_id()
+            && previous.status() == ChallengeStatus::Finalized
+            && previous.passed(),
+        PolocError::InvalidPreviousChallenge
+    );
+
+    // Gap between the two windows; overlapping windows leave no travel time.
+    let gap = if challenge.start_time >= previous.deadline {
+        challenge.start_time - previous.deadline
+    } else if previous.start_time >= challenge.deadline {
+        previous.start_time - challenge.deadline
+    } else {
+        0
+    };
+    let reachable = (gap as u64)
+        .saturating_mul(MAX_TRAVEL_SPEED_MPS)
+        .saturating_add(previous.r_star as u64)
+        .saturating_add(challenge.r_star as u64);
+    let distance = distance_m(
+        previous.claimed_lat,
+        previous.claimed_lon,
+        challenge.claimed_lat,
+        challenge.claimed_lon,
+    );
+
+    challenge.travel_inconsistent = (distance > reachable) as u8;
+    if challenge.travel_inconsistent != 0 {
+        msg!("Impossible travel from {}: {}m in {}s", previous.challenge_id(), distance, gap);
+    }
+    Ok(())
+}
+
+/// The candidate `select_candidate` will pick, or None for single-location
+/// challenges. Tallies are final once voting closes, so this is stable.
+fn leading_candidate(challenge: &Challenge) -> Option<(u8, CandidateLocation)> {
+    if challenge.candidate_count == 0 {
+        return None;
+    }
+    let candidates = &challenge.candidates[..challenge.candidate_count as usize];
+    let leading = candidates
+        .iter()
+        .enumerate()
+        .rev()
+        .max_by_key(|(_, c)| (c.valid_stake, c.valid_votes))
+        .map(|(i, c)| (i as u8, *c))
+        .unwrap_or_default();
+    Some(leading)
+}
+
+/// Stake-weighted median uncertainty of the valid votes (for any-of
+/// challenges, those supporting the leading candidate), with how many were
+/// counted. Weighting by stake keeps many small sybil stakes from moving it.
+/// Votes without a recorded stake weigh one lamport. No valid votes gives
+/// `u32::MAX`, which fails any threshold. Takes the vote summaries
+/// `Challenge::roster` holds.
+pub(crate) fn r_star_from_entries(challenge: &Challenge, entries: impl Iterator<Item = RosterEntry>) -> (u32, u32) {
+    let candidate = leading_candidate(challenge).map(|(index, _)| index);
+    let mut weighted: Vec<(u32, u64)> = entries
+        .filter(|e| e.is_valid != 0 && (candidate.is_none() || candidate == Some(e.candidate)))
+        .map(|e| (e.uncertainty, e.stake.max(1)))
+        .collect();
+    let counted = weighted.len() as u32;
+    (weighted_quantile_u32(&mut weighted, DEFAULT_BETA_BPS).unwrap_or(u32::MAX), counted)
+}
+
+/// For any-of challenges, picks the candidate with the most valid stake
+/// (then valid votes, then lowest index) and makes it the challenge's
+/// location. Only its supporters count as valid from here on, so quorum and
+/// rewards are judged on the location that was actually selected.
+pub(crate) fn select_candidate(challenge: &mut Challenge, challenge_key: Pubkey) {
+    let Some((index, best)) = leading_candidate(challenge) else {
+        return;
+    };
+
+    challenge.selected_candidate = index;
+    challenge.claimed_lat = best.lat;
+    challenge.claimed_lon = best.lon;
+    challenge.valid_vote_count = best.valid_votes;
+    challenge.valid_stake = best.valid_stake;
+    trace!("Selected candidate {} of {} at ({}, {})", index, challenge.candidate_count, best.lat, best.lon);
+
+    emit!(CandidateSelected {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        candidate: index,
+        lat: best.lat,
+        lon: best.lon,
+        valid_votes: best.valid_votes,
+        valid_stake: best.valid_stake,
+    });
+}
+
+/// Sets `quorum_met`: valid votes must be at least `vote_quorum_bps` of all
+/// votes and carry at least `stake_quorum_bps` of all stake, so neither many
+/// small wallets nor one large one can pass a challenge alone. A challenge
+/// opened with a prior result has both requirements cut by its relief.
+pub(crate) fn apply_quorum(challenge: &mut Challenge, config: &Config) {
+    let kept_bps = 10_000u128.saturating_sub(challenge.quorum_relief_bps as u128);
+    let meets = |part: u64, whole: u64, bps: u16| {
+        part as u128 * 10_000 * 10_000 >= whole as u128 * bps as u128 * kept_bps
+    };
+    challenge.quorum_met = (meets(
+        challenge.valid_vote_count as u64,
+        challenge.vote_count as u64,
+        config.vote_quorum_bps,
+    ) && meets(challenge.valid_stake, challenge.total_staked, config.stake_quorum_bps)) as u8;
+    if challenge.quorum_met == 0 {
+        trace!("Quorum not met: {}/{} valid votes, {}/{} lamports of stake",
+             challenge.valid_vote_count, challenge.vote_count,
+             challenge.valid_stake, challenge.total_staked);
+    }
+}
+
+/// Settles a challenge whose quorum held but whose R* landed within the
+/// retry band above the threshold as Inconclusive rather than failed: too
+/// close to call, so stakes are returned and nobody is paid or slashed.
+pub(crate) fn apply_inconclusive_band(challenge: &mut Challenge, config: &Config) {
+    if challenge.quorum_met != 0 && challenge.in_inconclusive_band(config.retry_band_bps) {
+        challenge.set_status(ChallengeStatus::Inconclusive);
+        // Standing stakes are already counted; every stake is open now.
+        challenge.open_stakes = challenge.participant_count;
+        trace!("R*={}m is within {} bps of the {}m threshold; inconclusive",
+             challenge.r_star, config.retry_band_bps, challenge.r_star_threshold);
+    }
+}
+
+/// Starts the result's TTL; a result never expires when the config's is 0.
+pub(crate) fn set_result_expiry(challenge: &mut Challenge, config: &Config, now: i64) {
+    challenge.expires_at = if config.result_ttl > 0 { now.saturating_add(config.result_ttl) } else { 0 };
+    challenge.reverification_flagged = 0;
+}
+
+/// Starts the claim period; unclaimed rewards are never swept when the
+/// config's is 0.
+pub(crate) fn set_claim_deadline(challenge: &mut Challenge, config: &Config, now: i64) {
+    challenge.claim_deadline = if config.claim_period > 0 { now.saturating_add(config.claim_period) } else { 0 };
+}
+
+/// The creator has every reason to report a passing R*, so once the oracle
+/// set carries a quorum only `finalize_with_oracles` may settle challenges.
+fn check_creator_finalize(oracle_set: &AccountInfo) -> Result<()> {
+    if oracle_set.owner == &crate::ID && !oracle_set.data_is_empty() {
+        let oracle_set = OracleSet::try_deserialize(&mut &oracle_set.try_borrow_data()?[..])?;
+        require!(oracle_set.quorum == 0, PolocError::OracleQuorumRequired);
+    }
+    Ok(())
+}
 
 #[derive(Accounts)]
 #[instruction(challenge_id: String)]
 pub struct FinalizeChallenge<'info> {
     #[account(
         mut,
-        seeds = [b"challenge", challenge_id.as_bytes()],
-        bump = challenge.bump
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
     )]
-    pub challenge: Account<'info, Challenge>,
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: RegionPolicy PDA for the challenge's cell; may be uninitialized.
+    /// Validated by `resolve_region_limits`.
+    pub region_policy: UncheckedAccount<'info>,
+
+    /// CHECK: RegionStats PDA for the same cell; may be uninitialized.
+    /// Validated by `record_region_stats`.
+    #[account(mut)]
+    pub region_stats: UncheckedAccount<'info>,
+
+    // The creator's profile, to free the challenge's concurrency slot.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Account<'info, WaldoProfile>,
     
     // The authority is the trusted oracle (in this case, the challenge creator)
     // who runs the off-chain script and submits the result.
-    #[account(address = challenge.waldo @ PolocError::Unauthorized)]
+    #[account(address = challenge.load()?.waldo @ PolocError::Unauthorized)]
     pub authority: Signer<'info>,
+
+    /// CHECK: OracleSet PDA; may be uninitialized. Validated by
+    /// `check_creator_finalize`.
+    #[account(seeds = [ORACLE_SET_SEED], bump)]
+    pub oracle_set: UncheckedAccount<'info>,
+
+    // Optional: the prover's most recent passed challenge, for `consistency_guard`.
+    pub previous_challenge: Option<AccountLoader<'info, Challenge>>,
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to mark its entry closed.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+
+    // remaining_accounts: the registered result callback program, the
+    // challenge's callback PDA and the registered accounts, if it has one.
 }
 
-// The handler now accepts the pre-calculated r_star from your JS script.
-pub fn handler(
-    ctx: Context<FinalizeChallenge>,
+// `r_star` is kept only so existing callers still encode and must be 0; R*
+// is computed from the roster instead.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeChallenge<'info>>,
     challenge_id: String,
-    r_star_from_js: u32, // <-- The result from your off-chain calculation
+    r_star: u32,
 ) -> Result<()> {
-    let challenge = &mut ctx.accounts.challenge;
+    require!(r_star == 0, PolocError::InvalidParameters);
+    let challenge_key = ctx.accounts.challenge.key();
+    let mut challenge = ctx.accounts.challenge.load_mut()?;
     let clock = Clock::get()?;
-    #[cfg(not(test))]
-const VOTING_WINDOW: i64 = 300; // 5 minutes for production
-#[cfg(test)]
-const VOTING_WINDOW: i64 = 3;   // 3 seconds for testing
     
     // 1. Validate that the challenge is in the correct state to be finalized.
-    require!(challenge.status == ChallengeStatus::Active, PolocError::ChallengeNotActive);
-    require!(clock.unix_timestamp > challenge.deadline + VOTING_WINDOW, PolocError::ChallengeExpired);
+    check_creator_finalize(&ctx.accounts.oracle_set)?;
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require!(challenge.location_known(), PolocError::LocationNotRevealed);
+    require_in_window(
+        challenge_key,
+        clock.unix_timestamp,
+        challenge.voting_ends_at() + 1,
+        i64::MAX,
+        PolocError::ChallengeExpired,
+    )?;
+    begin_settlement(&mut challenge, challenge_key, &ctx.accounts.config, clock.unix_timestamp)?;
+    // Any-of selection may move the location; stats stay with the starting cell.
+    let cell = RegionPolicy::cell(challenge.claimed_lat, challenge.claimed_lon);
+
+    // Settlement ends the funding schedule. Unfunded tranches were never
+    // escrowed, so dropping them releases the creator from the remainder.
+    if challenge.active_tranches < challenge.total_tranches {
+        msg!("Dropping {} unfunded tranche(s) of {} lamports",
+             challenge.total_tranches - challenge.active_tranches, challenge.tranche_amount);
+        challenge.total_tranches = challenge.active_tranches;
+    }
 
     // 2. You can still check for minimum participation.
-    if challenge.participant_count < 3 {
-        challenge.status = ChallengeStatus::InsufficientParticipants;
+    let (_, min_participants) = resolve_region_limits(
+        &ctx.accounts.region_policy.to_account_info(),
+        &challenge,
+        &ctx.accounts.config,
+    )?;
+    if challenge.participant_count < min_participants {
+        challenge.set_status(ChallengeStatus::InsufficientParticipants);
         msg!("Challenge {} finalized: insufficient participants ({})", 
              challenge_id, challenge.participant_count);
-        return Ok(());
+        emit!(ChallengeFinalized {
+            schema_version: EVENT_SCHEMA_VERSION,
+            challenge: challenge_key,
+            status: challenge.status(),
+            r_star: challenge.r_star,
+            r_star_threshold: challenge.r_star_threshold,
+            passed: false,
+            context: challenge.context,
+        });
+        record_region_stats(&ctx.accounts.region_stats.to_account_info(), cell, &challenge)?;
+        ctx.accounts.waldo_profile.release_active(&mut challenge);
+        delist_settled(ctx.accounts.challenge_registry.as_mut(), ctx.accounts.registry_page.as_mut(), &mut challenge, challenge_key)?;
+        drop(challenge);
+        return invoke_result_callback(&ctx.accounts.challenge, ctx.remaining_accounts, false);
     }
+    require!(challenge.sealed_votes == 0, PolocError::SealedVotesOutstanding);
     
-    // 3. The on-chain program now TRUSTS the submitted r_star value.
-    // All complex math is handled off-chain.
-    challenge.r_star = r_star_from_js;
+    // 3. R* comes from the votes in the roster, not from the creator.
+    let (r_star, _) = r_star_from_entries(&challenge, challenge.roster_entries().iter().copied());
+    challenge.r_star = r_star;
+    challenge.r_star_computed = 1;
     
     // 4. Update the challenge status to Finalized.
-    challenge.status = ChallengeStatus::Finalized;
+    challenge.set_status(ChallengeStatus::Finalized);
+    select_candidate(&mut challenge, challenge_key);
+    if let Some(previous) = &ctx.accounts.previous_challenge {
+        let previous = previous.load()?;
+        consistency_guard(&mut challenge, &previous)?;
+    }
+    apply_quorum(&mut challenge, &ctx.accounts.config);
+    apply_inconclusive_band(&mut challenge, &ctx.accounts.config);
+    set_result_expiry(&mut challenge, &ctx.accounts.config, clock.unix_timestamp);
+    set_claim_deadline(&mut challenge, &ctx.accounts.config, clock.unix_timestamp);
     
-    let passed = challenge.r_star <= challenge.r_star_threshold;
+    let passed = challenge.passed();
     
     msg!("Challenge {} finalized by oracle.", challenge_id);
-    msg!("Submitted R*={}m, threshold={}m, passed={}",
+    trace!("Computed R*={}m, threshold={}m, passed={}",
          challenge.r_star, challenge.r_star_threshold, passed);
+
+    emit!(ChallengeFinalized {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        status: challenge.status(),
+        r_star: challenge.r_star,
+        r_star_threshold: challenge.r_star_threshold,
+        passed,
+        context: challenge.context,
+    });
+    record_region_stats(&ctx.accounts.region_stats.to_account_info(), cell, &challenge)?;
+    ctx.accounts.waldo_profile.release_active(&mut challenge);
+    delist_settled(ctx.accounts.challenge_registry.as_mut(), ctx.accounts.registry_page.as_mut(), &mut challenge, challenge_key)?;
     
-    Ok(())
+    drop(challenge);
+    invoke_result_callback(&ctx.accounts.challenge, ctx.remaining_accounts, passed)
 }
@@ -0,0 +1,172 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::validation::{record_region_stats, require_in_window, resolve_region_limits};
+use crate::validation::load_oracle_reports;
+use poloc_core::rstar::{quantile_u32, DEFAULT_BETA_BPS};
+use crate::instructions::finalize::{
+    apply_inconclusive_band, apply_quorum, begin_settlement, consistency_guard, select_candidate, set_claim_deadline,
+    set_result_expiry,
+};
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct FinalizeWithOracles<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [ORACLE_SET_SEED], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    /// CHECK: RegionPolicy PDA for the challenge's cell; may be uninitialized.
+    /// Validated by `resolve_region_limits`.
+    pub region_policy: UncheckedAccount<'info>,
+
+    /// CHECK: RegionStats PDA for the same cell; may be uninitialized.
+    /// Validated by `record_region_stats`.
+    #[account(mut)]
+    pub region_stats: UncheckedAccount<'info>,
+
+    // The creator's profile, to free the challenge's concurrency slot.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Account<'info, WaldoProfile>,
+    // Optional: the prover's most recent passed challenge, for `consistency_guard`.
+    pub previous_challenge: Option<AccountLoader<'info, Challenge>>,
+}
+
+// Permissionless. `remaining_accounts` holds the (writable) OracleReport PDAs for this
+// challenge, ascending by oracle; the median R* is taken as the result.
+// Oracles outside the configured band around the median lose score and their
+// report bond, which goes to the insurance pool.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeWithOracles<'info>>,
+    challenge_id: String,
+) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    let clock = Clock::get()?;
+
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require!(challenge.location_known(), PolocError::LocationNotRevealed);
+    require_in_window(
+        challenge_key,
+        clock.unix_timestamp,
+        challenge.voting_ends_at() + 1,
+        i64::MAX,
+        PolocError::ChallengeExpired,
+    )?;
+    begin_settlement(challenge, challenge_key, &ctx.accounts.config, clock.unix_timestamp)?;
+    let cell = RegionPolicy::cell(challenge.claimed_lat, challenge.claimed_lon);
+    // Under-subscribed challenges settle through `finalize_challenge` and a refund.
+    let (_, min_participants) = resolve_region_limits(
+        &ctx.accounts.region_policy.to_account_info(),
+        challenge,
+        &ctx.accounts.config,
+    )?;
+    require!(challenge.participant_count >= min_participants, PolocError::InsufficientParticipants);
+    require!(challenge.sealed_votes == 0, PolocError::SealedVotesOutstanding);
+
+    let reports = load_oracle_reports(ctx.remaining_accounts, &challenge_id)?;
+    require!(!reports.is_empty(), PolocError::NoOracleReports);
+    require!(
+        reports.len() >= ctx.accounts.oracle_set.required_reports(),
+        PolocError::OracleQuorumNotMet
+    );
+    for report in &reports {
+        // Reports from oracles removed since submission no longer count.
+        require!(
+            ctx.accounts.oracle_set.oracles.iter().any(|o| o.authority == report.oracle),
+            PolocError::UnknownOracle
+        );
+        require_keys_eq!(report.challenge, challenge_key, PolocError::OraclePayloadMismatch);
+    }
+
+    let mut values: Vec<u32> = reports.iter().map(|r| r.r_star).collect();
+    let r_star = quantile_u32(&mut values, DEFAULT_BETA_BPS).ok_or(PolocError::NoOracleReports)?;
+    trace!("Oracle R* values {:?}, median {}m", values, r_star);
+
+    let band_bps = ctx.accounts.config.oracle_deviation_band_bps as u64;
+    let allowed = (r_star.max(1) as u64) * band_bps / 10_000;
+    let pool_info = ctx.accounts.insurance_pool.to_account_info();
+    for (report, info) in reports.iter().zip(ctx.remaining_accounts) {
+        if (report.r_star.abs_diff(r_star) as u64) <= allowed || report.penalized {
+            continue;
+        }
+
+        let slashed = report.bond;
+        if slashed > 0 {
+            **info.try_borrow_mut_lamports()? -= slashed;
+            **pool_info.try_borrow_mut_lamports()? += slashed;
+        }
+        let mut penalized = report.clone();
+        penalized.bond = 0;
+        penalized.penalized = true;
+        penalized.try_serialize(&mut &mut info.try_borrow_mut_data()?[..])?;
+
+        let entry = ctx.accounts.oracle_set
+            .entry_mut(&report.oracle)
+            .ok_or(PolocError::UnknownOracle)?;
+        entry.score = entry.score.saturating_sub(OracleSet::DEVIATION_SCORE_PENALTY);
+        let score = entry.score;
+
+        let pool = &mut ctx.accounts.insurance_pool;
+        pool.total_received = pool.total_received.saturating_add(slashed);
+
+        msg!("Oracle {} deviated (R*={}m vs median {}m): slashed {} lamports",
+             report.oracle, report.r_star, r_star, slashed);
+        emit!(OraclePenalized {
+            schema_version: EVENT_SCHEMA_VERSION,
+            challenge: challenge_key,
+            oracle: report.oracle,
+            r_star: report.r_star,
+            median: r_star,
+            slashed,
+            score,
+        });
+    }
+
+    if challenge.active_tranches < challenge.total_tranches {
+        challenge.total_tranches = challenge.active_tranches;
+    }
+    challenge.r_star = r_star;
+    challenge.set_status(ChallengeStatus::Finalized);
+    select_candidate(challenge, challenge_key);
+    if let Some(previous) = &ctx.accounts.previous_challenge {
+        let previous = previous.load()?;
+        consistency_guard(challenge, &previous)?;
+    }
+    apply_quorum(challenge, &ctx.accounts.config);
+    apply_inconclusive_band(challenge, &ctx.accounts.config);
+    set_result_expiry(challenge, &ctx.accounts.config, clock.unix_timestamp);
+    set_claim_deadline(challenge, &ctx.accounts.config, clock.unix_timestamp);
+    let passed = challenge.passed();
+
+    msg!("Challenge {} finalized from {} oracle report(s): R*={}m, passed={}",
+         challenge_id, reports.len(), r_star, passed);
+
+    emit!(ChallengeFinalized {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        status: challenge.status(),
+        r_star: challenge.r_star,
+        r_star_threshold: challenge.r_star_threshold,
+        passed,
+        context: challenge.context,
+    });
+
+    ctx.accounts.waldo_profile.release_active(challenge);
+    record_region_stats(&ctx.accounts.region_stats.to_account_info(), cell, challenge)
+}
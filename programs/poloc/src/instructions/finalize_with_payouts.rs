@@ -0,0 +1,203 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::validation::{record_region_stats, require_in_window, resolve_region_limits};
+use crate::merkle;
+use crate::utils::close_program_account;
+use crate::validation::load_vote_accounts;
+use crate::instructions::claim_bond::pay_bond_share;
+use poloc_core::rewards::payout_shares;
+use crate::instructions::finalize::{
+    apply_inconclusive_band, apply_quorum, begin_settlement, consistency_guard, r_star_from_entries, select_candidate,
+    set_claim_deadline, set_result_expiry,
+};
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct FinalizeWithPayouts<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    // Pays a forfeited bond straight to the invalid voters; see `handler`.
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + PayoutDistribution::MAX_SIZE,
+        seeds = [PAYOUT_SEED, challenge_id.as_bytes()],
+        bump
+    )]
+    pub payout: Account<'info, PayoutDistribution>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: RegionPolicy PDA for the challenge's cell; may be uninitialized.
+    /// Validated by `resolve_region_limits`.
+    pub region_policy: UncheckedAccount<'info>,
+
+    /// CHECK: RegionStats PDA for the same cell; may be uninitialized.
+    /// Validated by `record_region_stats`.
+    #[account(mut)]
+    pub region_stats: UncheckedAccount<'info>,
+
+    // The creator's profile, to free the challenge's concurrency slot.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Account<'info, WaldoProfile>,
+
+    // Same trusted oracle as `finalize_challenge`.
+    #[account(mut, address = challenge.load()?.waldo @ PolocError::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+    // Optional: the prover's most recent passed challenge, for `consistency_guard`.
+    pub previous_challenge: Option<AccountLoader<'info, Challenge>>,
+}
+
+// Alternative to `finalize_challenge` + `claim_reward`. `remaining_accounts`
+// holds every Vote PDA (ascending by challenger) followed by each vote's
+// challenger wallet, so they can be closed right away. R* and the payout root
+// are built from the roster on-chain; winners claim later via `claim_payout`.
+// The `r_star` argument must be 0.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FinalizeWithPayouts<'info>>,
+    challenge_id: String,
+    r_star: u32,
+) -> Result<()> {
+    require!(r_star == 0, PolocError::InvalidParameters);
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    let clock = Clock::get()?;
+
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require!(challenge.location_known(), PolocError::LocationNotRevealed);
+    require_in_window(
+        challenge_key,
+        clock.unix_timestamp,
+        challenge.voting_ends_at() + 1,
+        i64::MAX,
+        PolocError::ChallengeExpired,
+    )?;
+    begin_settlement(challenge, challenge_key, &ctx.accounts.config, clock.unix_timestamp)?;
+    let cell = RegionPolicy::cell(challenge.claimed_lat, challenge.claimed_lon);
+    // Under-subscribed challenges settle through `finalize_challenge` and a refund.
+    let (_, min_participants) = resolve_region_limits(
+        &ctx.accounts.region_policy.to_account_info(),
+        challenge,
+        &ctx.accounts.config,
+    )?;
+    require!(challenge.participant_count >= min_participants, PolocError::InsufficientParticipants);
+    require!(challenge.sealed_votes == 0, PolocError::SealedVotesOutstanding);
+
+    let vote_count = challenge.vote_count as usize;
+    let remaining = ctx.remaining_accounts;
+    require!(remaining.len() == vote_count * 2, PolocError::RemainingAccountsCountMismatch);
+    let (vote_infos, wallet_infos) = remaining.split_at(vote_count);
+
+    let votes = load_vote_accounts(vote_infos, &challenge_id, Some(vote_count))?;
+    for (vote, wallet) in votes.iter().zip(wallet_infos) {
+        require_keys_eq!(wallet.key(), vote.challenger, PolocError::InvalidRemainingAccountAddress);
+    }
+
+    if challenge.active_tranches < challenge.total_tranches {
+        challenge.total_tranches = challenge.active_tranches;
+    }
+    let (r_star, _) = r_star_from_entries(challenge, challenge.roster_entries().iter().copied());
+    challenge.r_star = r_star;
+    challenge.r_star_computed = 1;
+    challenge.set_status(ChallengeStatus::Finalized);
+    select_candidate(challenge, challenge_key);
+    if let Some(previous) = &ctx.accounts.previous_challenge {
+        let previous = previous.load()?;
+        consistency_guard(challenge, &previous)?;
+    }
+    apply_quorum(challenge, &ctx.accounts.config);
+    apply_inconclusive_band(challenge, &ctx.accounts.config);
+    set_result_expiry(challenge, &ctx.accounts.config, clock.unix_timestamp);
+    set_claim_deadline(challenge, &ctx.accounts.config, clock.unix_timestamp);
+    let passed = challenge.passed();
+
+    // Winners are the valid voters of a passing challenge, in casting order,
+    // each paid in proportion to their stake.
+    let (winners, stakes): (Vec<Pubkey>, Vec<u64>) = if passed {
+        challenge.roster_entries().iter()
+            .filter(|e| e.is_valid != 0 && e.candidate == challenge.selected_candidate)
+            .map(|e| (e.challenger, e.stake))
+            .unzip()
+    } else {
+        (Vec::new(), Vec::new())
+    };
+    let shares = payout_shares(challenge.distributable_pool(), &stakes);
+    let amount: u64 = shares.iter().sum();
+    let leaves: Vec<[u8; 32]> = winners
+        .iter()
+        .zip(&shares)
+        .enumerate()
+        .map(|(i, (winner, share))| merkle::payout_leaf(i as u32, &winner.to_bytes(), *share))
+        .collect();
+
+    let payout = &mut ctx.accounts.payout;
+    payout.challenge = challenge_key;
+    payout.root = merkle::root(&leaves);
+    payout.amount = amount;
+    payout.winner_count = winners.len() as u32;
+    payout.claimed_bitmap = 0;
+    payout.bump = ctx.bumps.payout;
+
+    // The votes close below, so a forfeited bond can't be claimed later; it
+    // is split between the invalid voters' wallets here.
+    if challenge.owes_bond() {
+        let (wallets, stakes): (Vec<&AccountInfo>, Vec<u64>) = votes.iter()
+            .zip(wallet_infos)
+            .filter(|(v, _)| !v.is_valid)
+            .map(|(v, wallet)| (wallet, v.stake))
+            .unzip();
+        let vault = ctx.accounts.vault.to_account_info();
+        for ((wallet, stake), share) in wallets.into_iter().zip(&stakes).zip(payout_shares(challenge.waldo_bond, &stakes)) {
+            pay_bond_share(challenge, challenge_key, &vault, wallet, *stake, share)?;
+        }
+    }
+
+    let vote_data = vote_infos
+        .iter()
+        .map(|info| Ok(info.try_borrow_data()?.to_vec()))
+        .collect::<Result<Vec<_>>>()?;
+    for (vote_info, wallet) in vote_infos.iter().zip(wallet_infos) {
+        close_program_account(vote_info, wallet)?;
+    }
+    challenge.votes_closed = 1;
+
+    msg!("Challenge {} finalized with payout root: R*={}m, passed={}, {} winner(s) sharing {} lamports",
+         challenge_id, challenge.r_star, passed, winners.len(), amount);
+
+    emit!(ChallengeFinalized {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        status: challenge.status(),
+        r_star: challenge.r_star,
+        r_star_threshold: challenge.r_star_threshold,
+        passed,
+        context: challenge.context,
+    });
+    emit!(PayoutRootPosted {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        root: payout.root,
+        amount,
+        winners,
+        vote_data,
+    });
+
+    ctx.accounts.waldo_profile.release_active(challenge);
+    record_region_stats(&ctx.accounts.region_stats.to_account_info(), cell, challenge)
+}
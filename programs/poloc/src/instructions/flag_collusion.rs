@@ -0,0 +1,51 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::validation::check_collusion_evidence;
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct FlagCollusion<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + CollusionFlag::MAX_SIZE,
+        seeds = [COLLUSION_SEED, wallet.as_ref()],
+        bump
+    )]
+    pub collusion_flag: Account<'info, CollusionFlag>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// `remaining_accounts` holds the evidence: `[wallet_vote, partner_vote]` pairs,
+// one pair per challenge.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, FlagCollusion<'info>>,
+    wallet: Pubkey,
+    partner: Pubkey,
+) -> Result<()> {
+    let evidence_count = check_collusion_evidence(
+        ctx.remaining_accounts,
+        &wallet,
+        &partner,
+        CollusionFlag::MIN_EVIDENCE_CHALLENGES,
+    )?;
+
+    let flag = &mut ctx.accounts.collusion_flag;
+    flag.wallet = wallet;
+    flag.partner = partner;
+    flag.evidence_count = evidence_count as u8;
+    flag.flagged_at = Clock::get()?.unix_timestamp;
+    flag.bump = ctx.bumps.collusion_flag;
+
+    msg!("Wallet {} flagged for colluding with {} across {} challenges", wallet, partner, evidence_count);
+    Ok(())
+}
@@ -0,0 +1,59 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct FlagReverificationDue<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    // Optional: a registered keeper running this crank is credited with a
+    // successful crank. There is no tip; the challenge only holds its rent.
+    #[account(mut)]
+    pub keeper: Option<Account<'info, Keeper>>,
+
+    pub keeper_operator: Option<Signer<'info>>,
+}
+
+// Permissionless: once a passed result's TTL runs out, anyone may announce
+// it so the prover and consumers know a new proof is due. Each result is
+// announced once.
+pub fn handler(ctx: Context<FlagReverificationDue>, challenge_id: String) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    // Failed results were never current, so there's nothing to re-verify.
+    require!(challenge.status() == ChallengeStatus::Finalized, PolocError::ChallengeNotFinalized);
+    require!(
+        challenge.passed()
+            && challenge.expires_at != 0
+            && now >= challenge.expires_at
+            && challenge.reverification_flagged == 0,
+        PolocError::ResultStillCurrent
+    );
+    challenge.reverification_flagged = 1;
+
+    if let (Some(keeper), Some(operator)) = (ctx.accounts.keeper.as_mut(), ctx.accounts.keeper_operator.as_ref()) {
+        require_keys_eq!(keeper.operator, operator.key(), PolocError::Unauthorized);
+        keeper.successful_cranks = keeper.successful_cranks.saturating_add(1);
+        keeper.score = keeper.score.saturating_add(1).min(Keeper::MAX_SCORE);
+        keeper.last_active = now;
+    }
+
+    msg!("Challenge {} result expired at {}; re-verification due", challenge_id, challenge.expires_at);
+    emit!(ReverificationDue {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        waldo: challenge.waldo,
+        expires_at: challenge.expires_at,
+    });
+    Ok(())
+}
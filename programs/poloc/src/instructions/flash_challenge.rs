@@ -0,0 +1,185 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use anchor_lang::solana_program::sysvar::slot_hashes as slot_hashes_sysvar;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::ed25519::verify_preceding_signature;
+use crate::instructions::claim_escrow::move_lamports;
+use crate::instructions::vote::{beacon_message, vote_in_range};
+use crate::validation::{challenge_nonce, require_in_window};
+use poloc_core::rstar::quantile_u32;
+
+// Flash R* is the median of the witnesses' uncertainties.
+const FLASH_R_STAR_BETA_BPS: u16 = 5_000;
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OpenFlashChallengeArgs {
+    pub flash_id: u64,
+    pub claimed_lat: i32,         // Latitude in micro-degrees (lat * 1e6)
+    pub claimed_lon: i32,         // Longitude in micro-degrees (lon * 1e6)
+    pub window: i64,              // Seconds witnesses have to measure and vote
+    pub r_star_threshold: u32,    // Meters
+    pub reward_per_witness: u64,  // Lamports, escrowed for every witness slot
+}
+
+#[derive(Accounts)]
+#[instruction(args: OpenFlashChallengeArgs)]
+pub struct OpenFlashChallenge<'info> {
+    #[account(
+        init,
+        payer = waldo,
+        space = 8 + FlashChallenge::MAX_SIZE,
+        seeds = [FLASH_SEED, waldo.key().as_ref(), &args.flash_id.to_le_bytes()],
+        bump
+    )]
+    pub flash: Account<'info, FlashChallenge>,
+
+    #[account(mut)]
+    pub waldo: Signer<'info>,
+
+    /// CHECK: Address-constrained to the SlotHashes sysvar; seeds the measurement nonce.
+    #[account(address = slot_hashes_sysvar::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SubmitFlashVote<'info> {
+    #[account(mut)]
+    pub flash: Account<'info, FlashChallenge>,
+
+    // Only wallets with a witness history may vote, and not while cooling down.
+    #[account(seeds = [CHALLENGER_SEED, witness.key().as_ref()], bump = challenger_profile.bump)]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    pub witness: Signer<'info>,
+
+    /// CHECK: Address-constrained to the Instructions sysvar; holds the beacon signature.
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct FinalizeFlashChallenge<'info> {
+    #[account(mut, has_one = waldo @ PolocError::Unauthorized, close = waldo)]
+    pub flash: Account<'info, FlashChallenge>,
+
+    #[account(mut)]
+    pub waldo: SystemAccount<'info>,
+    // remaining_accounts: every voter's wallet, writable, in vote order.
+}
+
+// No stakes, fees or tranches: the waldo escrows a fixed reward for every
+// witness slot and gets back whatever isn't paid out.
+pub fn open_handler(ctx: Context<OpenFlashChallenge>, args: OpenFlashChallengeArgs) -> Result<()> {
+    require!(args.window > 0 && args.window <= MAX_FLASH_WINDOW, PolocError::InvalidParameters);
+    require!(args.r_star_threshold > 0, PolocError::InvalidParameters);
+    require!(args.claimed_lat.unsigned_abs() <= 90_000_000, PolocError::InvalidParameters);
+    require!(args.claimed_lon.unsigned_abs() <= 180_000_000, PolocError::InvalidParameters);
+    let escrow = args.reward_per_witness
+        .checked_mul(MAX_FLASH_WITNESSES as u64)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    let flash_key = ctx.accounts.flash.key();
+    let flash = &mut ctx.accounts.flash;
+    flash.waldo = ctx.accounts.waldo.key();
+    flash.flash_id = args.flash_id;
+    flash.claimed_lat = args.claimed_lat;
+    flash.claimed_lon = args.claimed_lon;
+    flash.deadline = Clock::get()?.unix_timestamp + args.window;
+    flash.r_star_threshold = args.r_star_threshold;
+    flash.reward_per_witness = args.reward_per_witness;
+    flash.measurement_nonce = challenge_nonce(&ctx.accounts.slot_hashes.to_account_info(), &flash_key)?;
+    flash.vote_count = 0;
+    flash.bump = ctx.bumps.flash;
+
+    if escrow > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.waldo.to_account_info(),
+                    to: ctx.accounts.flash.to_account_info(),
+                },
+            ),
+            escrow,
+        )?;
+    }
+
+    msg!("Flash challenge {} opened by {} until {}", args.flash_id, ctx.accounts.waldo.key(), ctx.accounts.flash.deadline);
+    Ok(())
+}
+
+// Measuring and voting share one window: a vote is the measurement itself,
+// carried by the prover's signed beacon echo like a valid `submit_vote`.
+pub fn vote_handler(ctx: Context<SubmitFlashVote>, uncertainty: u32, min_rtt: u32) -> Result<()> {
+    let flash = &mut ctx.accounts.flash;
+    let witness = ctx.accounts.witness.key();
+    let now = Clock::get()?.unix_timestamp;
+
+    require_in_window(flash.key(), now, i64::MIN, flash.deadline, PolocError::VotingClosed)?;
+    require!(now >= ctx.accounts.challenger_profile.cooldown_until, PolocError::SlashCooldownActive);
+    require_keys_neq!(witness, flash.waldo, PolocError::Unauthorized);
+    require!(vote_in_range(uncertainty, min_rtt), PolocError::InvalidParameters);
+    require!(
+        (flash.vote_count as usize) < MAX_FLASH_WITNESSES && flash.votes().iter().all(|v| v.witness != witness),
+        PolocError::FlashChallengeFull
+    );
+    let message = beacon_message(&flash.key(), &flash.measurement_nonce, &witness);
+    verify_preceding_signature(&ctx.accounts.instructions.to_account_info(), &flash.waldo, &message)?;
+
+    let index = flash.vote_count as usize;
+    flash.votes[index] = FlashVote { witness, uncertainty };
+    flash.vote_count += 1;
+
+    msg!("Flash vote {} of {} from {}: {} m", flash.vote_count, MAX_FLASH_WITNESSES, witness, uncertainty);
+    Ok(())
+}
+
+// Permissionless once the window closes. With enough votes, R* is their
+// median uncertainty and every voter is paid; otherwise nobody is. The
+// account closes either way, returning rent and the unpaid escrow.
+pub fn finalize_handler<'info>(ctx: Context<'_, '_, 'info, 'info, FinalizeFlashChallenge<'info>>) -> Result<()> {
+    let flash = &ctx.accounts.flash;
+    require_in_window(
+        flash.key(),
+        Clock::get()?.unix_timestamp,
+        flash.deadline + 1,
+        i64::MAX,
+        PolocError::VotingNotOpen,
+    )?;
+
+    let votes = flash.votes();
+    let settled = flash.vote_count >= MIN_FLASH_WITNESSES;
+    let mut uncertainties: Vec<u32> = votes.iter().map(|v| v.uncertainty).collect();
+    let r_star = if settled { quantile_u32(&mut uncertainties, FLASH_R_STAR_BETA_BPS).unwrap_or(0) } else { 0 };
+    let passed = settled && r_star <= flash.r_star_threshold;
+
+    if settled && flash.reward_per_witness > 0 {
+        require!(ctx.remaining_accounts.len() == votes.len(), PolocError::InvalidParameters);
+        let flash_info = flash.to_account_info();
+        for (vote, wallet) in votes.iter().zip(ctx.remaining_accounts) {
+            require_keys_eq!(wallet.key(), vote.witness, PolocError::InvalidParameters);
+            move_lamports(&flash_info, wallet, flash.reward_per_witness)?;
+        }
+    }
+
+    msg!("Flash challenge {} settled: R* {} m over {} vote(s), passed: {}",
+         flash.flash_id, r_star, flash.vote_count, passed);
+    emit!(FlashChallengeFinalized {
+        schema_version: EVENT_SCHEMA_VERSION,
+        flash: flash.key(),
+        waldo: flash.waldo,
+        flash_id: flash.flash_id,
+        claimed_lat: flash.claimed_lat,
+        claimed_lon: flash.claimed_lon,
+        r_star,
+        vote_count: flash.vote_count,
+        passed,
+    });
+    Ok(())
+}
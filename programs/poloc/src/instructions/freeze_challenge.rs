@@ -0,0 +1,54 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct SetChallengeFrozen<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = guardian @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    pub guardian: Signer<'info>,
+}
+
+// Kill-switch for a single challenge under a suspected exploit or dispute.
+// Every handler that takes the challenge refuses it while frozen; its
+// deadlines keep running.
+pub fn freeze_handler(ctx: Context<SetChallengeFrozen>, challenge_id: String) -> Result<()> {
+    set_frozen(ctx, challenge_id, true)
+}
+
+pub fn unfreeze_handler(ctx: Context<SetChallengeFrozen>, challenge_id: String) -> Result<()> {
+    set_frozen(ctx, challenge_id, false)
+}
+
+fn set_frozen(ctx: Context<SetChallengeFrozen>, challenge_id: String, frozen: bool) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    if frozen {
+        require!(challenge.frozen == 0, PolocError::ChallengeFrozen);
+    } else {
+        require!(challenge.frozen != 0, PolocError::ChallengeNotFrozen);
+    }
+    challenge.frozen = frozen as u8;
+
+    msg!("Challenge {} {} by guardian {}",
+         challenge_id, if frozen { "frozen" } else { "unfrozen" }, ctx.accounts.guardian.key());
+
+    emit!(ChallengeFreezeChanged {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        guardian: ctx.accounts.guardian.key(),
+        frozen,
+    });
+    Ok(())
+}
@@ -0,0 +1,72 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::validation::require_in_window;
+use crate::events::*;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct FundTranche<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(mut, address = challenge.load()?.waldo @ PolocError::Unauthorized)]
+    pub waldo: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<FundTranche>, challenge_id: String) -> Result<()> {
+    let clock = Clock::get()?;
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+
+    // Tranches can only be added while the challenge is still running.
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require_in_window(challenge_key, clock.unix_timestamp, i64::MIN, challenge.deadline, PolocError::ChallengeExpired)?;
+    require!(
+        challenge.active_tranches < challenge.total_tranches,
+        PolocError::TranchesFullyFunded
+    );
+
+    let amount = challenge.tranche_amount;
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.waldo.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    challenge.reward_pool = challenge.reward_pool
+        .checked_add(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    challenge.active_tranches += 1;
+
+    msg!("Challenge {} funded tranche {}/{} ({} lamports)",
+         challenge_id, challenge.active_tranches, challenge.total_tranches, amount);
+
+    emit!(TrancheFunded {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        amount,
+        active_tranches: challenge.active_tranches,
+        total_tranches: challenge.total_tranches,
+    });
+
+    Ok(())
+}
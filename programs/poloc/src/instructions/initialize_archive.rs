@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::compression::{self, SPL_ACCOUNT_COMPRESSION_ID, SPL_NOOP_ID};
+use crate::program::Poloc;
+
+#[derive(Accounts)]
+pub struct InitializeArchive<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Archive::MAX_SIZE,
+        seeds = [ARCHIVE_SEED],
+        bump
+    )]
+    pub archive: Account<'info, Archive>,
+
+    /// CHECK: Pre-allocated by the client (sized for `max_depth`/`max_buffer_size`)
+    /// and owned by spl-account-compression, which validates it in the CPI.
+    #[account(mut, owner = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // Only the program's upgrade authority may register the archive tree.
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Poloc>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ PolocError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    /// CHECK: Address-constrained to spl-account-compression.
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+
+    /// CHECK: Address-constrained to spl-noop.
+    #[account(address = SPL_NOOP_ID)]
+    pub noop: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeArchive>, max_depth: u32, max_buffer_size: u32) -> Result<()> {
+    let archive = &mut ctx.accounts.archive;
+    archive.admin = ctx.accounts.admin.key();
+    archive.merkle_tree = ctx.accounts.merkle_tree.key();
+    archive.leaf_count = 0;
+    archive.bump = ctx.bumps.archive;
+
+    let bump = [archive.bump];
+    let signer_seeds: &[&[u8]] = &[ARCHIVE_SEED, &bump];
+
+    compression::init_empty_merkle_tree(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        &archive.to_account_info(),
+        &ctx.accounts.noop.to_account_info(),
+        &[signer_seeds],
+        max_depth,
+        max_buffer_size,
+    )?;
+
+    msg!("Archive tree {} initialized (depth {}, buffer {})", archive.merkle_tree, max_depth, max_buffer_size);
+    Ok(())
+}
@@ -1,77 +1,544 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes as slot_hashes_sysvar;
 use crate::state::*;
+use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
+use crate::validation::{challenge_nonce, fee_waived};
+use crate::utils::distance_m;
+use crate::instructions::challenge_registry::list_challenge;
+
+/// Arguments for `initialize_challenge`. New optional fields should be appended
+/// at the end so existing clients keep serializing a valid prefix.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InitializeChallengeArgs {
+    pub challenge_id: String,
+    pub claimed_lat: i32,      // Latitude in micro-degrees (lat * 1e6)
+    pub claimed_lon: i32,      // Longitude in micro-degrees (lon * 1e6)
+    pub duration: u64,         // Duration in seconds
+    pub reward_pool: u64,      // Reward pool in lamports
+    pub tranches: u8,          // Split reward_pool into this many funding tranches (0 or 1 = all up front)
+    pub private: bool,         // Emit only a location commitment and skip registry indexing
+    pub location_salt: [u8; 32], // Salt for the commitment; ignored unless `private`
+    pub min_equipment_class: u8, // Minimum witness equipment class to stake (0 = any)
+    pub device_hash: [u8; 32], // Hash of the prover device identifier
+    pub start_time: i64,       // Scheduled start; 0 or past = start now
+    pub location_commitment: [u8; 32], // Non-zero cloaks the location: claimed_lat/lon must be 0 until `reveal_location`
+    pub underwrite_witnesses: bool, // Let the bond top winners up to the config's rebate + compensation floor
+    pub encrypted_votes: bool, // Votes are sealed to the config's committee key until `decrypt_and_tally`
+    pub required_credential: Pubkey, // Attestation issuer every staker must hold (default = anyone may stake)
+    pub required_schema: Pubkey, // Schema the attestation must follow; ignored unless `required_credential` is set
+    pub alternate_locations: Vec<[i32; 2]>, // Other [lat, lon] the prover may be at; any one of them passes
+    pub arbiter: Pubkey,       // Neutral party who slashes instead of the creator (default = creator)
+    pub vote_schedule: VoteSchedule, // Repeating voting rounds from the start (default = after the deadline only)
+    pub context: [u8; 32],     // Integrator's business object, e.g. an order ID hash; echoed in events and results
+    pub min_reputation: i32,   // Lowest challenger reputation score to stake; newcomers have 0 (i32::MIN = any)
+    pub threshold_meters: u32, // R* acceptance threshold within the config's bounds (0 = the config's default)
+    pub rtt_attested: bool,    // Valid votes must carry the witness's signed timing; see `rtt_message`
+}
 
 #[derive(Accounts)]
-#[instruction(challenge_id: String)]
+#[instruction(args: InitializeChallengeArgs)]
 pub struct InitializeChallenge<'info> {
     #[account(
         init,
         payer = waldo,
         space = Challenge::MAX_SIZE,
-        seeds = [b"challenge", challenge_id.as_bytes()],
+        seeds = [CHALLENGE_SEED, args.challenge_id.as_bytes()],
+        bump
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        init,
+        payer = waldo,
+        space = 8 + ChallengeVault::MAX_SIZE,
+        seeds = [VAULT_SEED, args.challenge_id.as_bytes()],
+        bump
+    )]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = waldo,
+        space = 8 + WaldoProfile::MAX_SIZE,
+        seeds = [WALDO_SEED, waldo.key().as_ref()],
+        bump
+    )]
+    pub waldo_profile: Account<'info, WaldoProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = waldo,
+        space = 8 + DeviceLock::MAX_SIZE,
+        seeds = [DEVICE_SEED, args.device_hash.as_ref()],
         bump
     )]
-    pub challenge: Account<'info, Challenge>,
+    pub device_lock: Account<'info, DeviceLock>,
+
+    // Receives the creation fee.
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
     
     #[account(mut)]
     pub waldo: Signer<'info>,
     
+    /// CHECK: Address-constrained to the SlotHashes sysvar; seeds the measurement nonce.
+    #[account(address = slot_hashes_sysvar::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    // Optional: the creator's recent passed result here, offered as a prior
+    // to lower this challenge's quorum. See `prior_relief`.
+    pub prior_result: Option<AccountLoader<'info, Challenge>>,
+
+    // Optional: the fee waiver list, to skip the creation fee and bond.
+    #[account(seeds = [FEE_WAIVER_SEED], bump = fee_waivers.bump)]
+    pub fee_waivers: Option<Account<'info, FeeWaiverList>>,
+
+    /// CHECK: The creator's attestation when the waiver list is
+    /// credential-gated; validated by `fee_waived`.
+    pub waiver_attestation: Option<UncheckedAccount<'info>>,
+
+    // Optional: the expiry feed, to list this challenge's deadline for keepers.
+    #[account(mut, seeds = [EXPIRY_FEED_SEED], bump = expiry_feed.bump)]
+    pub expiry_feed: Option<Account<'info, ExpiryFeed>>,
+
+    // Optional: the challenge registry and its current page, to list this
+    // challenge for enumeration. Private challenges are never listed.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+
     pub system_program: Program<'info, System>,
 }
 
-pub fn handler(
-    ctx: Context<InitializeChallenge>,
-    challenge_id: String,
-    claimed_lat: i32,
-    claimed_lon: i32,
-    duration: u64,
-    reward_pool: u64,
+pub fn handler(ctx: Context<InitializeChallenge>, args: InitializeChallengeArgs) -> Result<()> {
+    let accounts = ctx.accounts;
+    create_challenge(
+        NewChallengeAccounts {
+            challenge: &accounts.challenge,
+            vault: &mut accounts.vault,
+            config: &accounts.config,
+            waldo_profile: &mut accounts.waldo_profile,
+            device_lock: &mut accounts.device_lock,
+            insurance_pool: &mut accounts.insurance_pool,
+            waldo: &accounts.waldo,
+            system_program: &accounts.system_program,
+            slot_hashes: &accounts.slot_hashes,
+            prior_result: accounts.prior_result.as_ref(),
+            fee_waivers: accounts.fee_waivers.as_ref(),
+            waiver_attestation: accounts.waiver_attestation.as_ref(),
+            expiry_feed: accounts.expiry_feed.as_mut(),
+            registry: accounts.challenge_registry.as_mut().zip(accounts.registry_page.as_mut()),
+            funder: None,
+            token_pool: None,
+            challenge_bump: ctx.bumps.challenge,
+            vault_bump: ctx.bumps.vault,
+            waldo_profile_bump: ctx.bumps.waldo_profile,
+            device_lock_bump: ctx.bumps.device_lock,
+        },
+        args,
+        accounts.config.r_star_threshold,
+        0,
+        None,
+    )
+}
+
+/// Accounts `create_challenge` writes, shared by every instruction that opens
+/// a challenge.
+pub(crate) struct NewChallengeAccounts<'a, 'info> {
+    pub challenge: &'a AccountLoader<'info, Challenge>,
+    pub vault: &'a mut Account<'info, ChallengeVault>,
+    pub config: &'a Account<'info, Config>,
+    pub waldo_profile: &'a mut Account<'info, WaldoProfile>,
+    pub device_lock: &'a mut Account<'info, DeviceLock>,
+    pub insurance_pool: &'a mut Account<'info, InsurancePool>,
+    pub waldo: &'a Signer<'info>,
+    pub system_program: &'a Program<'info, System>,
+    pub slot_hashes: &'a UncheckedAccount<'info>,
+    pub prior_result: Option<&'a AccountLoader<'info, Challenge>>,
+    // Waive the creation fee and bond when the creator qualifies; see `fee_waived`.
+    pub fee_waivers: Option<&'a Account<'info, FeeWaiverList>>,
+    pub waiver_attestation: Option<&'a UncheckedAccount<'info>>,
+    // Lists the new challenge for keepers; see `expiry_feed`.
+    pub expiry_feed: Option<&'a mut Account<'info, ExpiryFeed>>,
+    // Lists the new challenge for enumeration; see `list_challenge`.
+    pub registry: Option<(&'a mut Account<'info, ChallengeRegistry>, &'a mut Account<'info, RegistryPage>)>,
+    // Pays the first tranche instead of `waldo`; see `initialize_funded_challenge`.
+    pub funder: Option<&'a Signer<'info>>,
+    // (mint, vault) of an SPL token pool, whose caller moves the tokens; see `token_pool`.
+    pub token_pool: Option<(Pubkey, Pubkey)>,
+    pub challenge_bump: u8,
+    pub vault_bump: u8,
+    pub waldo_profile_bump: u8,
+    pub device_lock_bump: u8,
+}
+
+/// Quorum relief earned by `prior`: the same creator's passed,
+/// travel-consistent result whose deadline is within the config's validity
+/// window and whose R* circle contains the new location. Offering it is the
+/// creator's choice, so "recently verified here" only counts with consent.
+fn prior_relief(prior: &Challenge, waldo: &Pubkey, lat: i32, lon: i32, now: i64, config: &Config) -> Result<u16> {
+    require!(
+        prior.waldo == *waldo
+            && prior.status() == ChallengeStatus::Finalized
+            && prior.passed()
+            && prior.travel_inconsistent == 0,
+        PolocError::InvalidPriorResult
+    );
+    require!(
+        now <= prior.deadline.saturating_add(config.prior_validity_window),
+        PolocError::InvalidPriorResult
+    );
+    require!(
+        distance_m(prior.claimed_lat, prior.claimed_lon, lat, lon) <= prior.r_star as u64,
+        PolocError::InvalidPriorResult
+    );
+    Ok(config.prior_quorum_relief_bps)
+}
+
+/// `r_star_threshold` applies unless the args request one of their own.
+/// `discount_bps` comes off both the creation fee and the waldo bond.
+/// `namespace` is the integrator prefix the id must carry; `None` for user
+/// challenges, whose ids may not start with one.
+pub(crate) fn create_challenge(
+    accounts: NewChallengeAccounts,
+    args: InitializeChallengeArgs,
+    r_star_threshold: u32,
+    discount_bps: u16,
+    namespace: Option<u8>,
 ) -> Result<()> {
-    let challenge = &mut ctx.accounts.challenge;
+    let InitializeChallengeArgs {
+        challenge_id,
+        claimed_lat,
+        claimed_lon,
+        duration,
+        reward_pool,
+        tranches,
+        private,
+        location_salt,
+        min_equipment_class,
+        device_hash,
+        start_time,
+        location_commitment,
+        underwrite_witnesses,
+        encrypted_votes,
+        required_credential,
+        required_schema,
+        alternate_locations,
+        arbiter,
+        vote_schedule,
+        context,
+        min_reputation,
+        threshold_meters,
+        rtt_attested,
+    } = args;
+    let challenge_key = accounts.challenge.key();
+    let challenge = &mut accounts.challenge.load_init()?;
     let clock = Clock::get()?;
     
     // Validate parameters
-    require!(duration > 0 && duration <= 86400, PolocError::InvalidParameters); // Max 24 hours
+    require!(
+        IntegratorNamespace::prefix_of(&challenge_id) == namespace,
+        PolocError::NamespaceMismatch
+    );
+    require!(duration > 0, PolocError::InvalidParameters);
+    let tier = *accounts.config
+        .duration_tier(duration)
+        .ok_or(PolocError::DurationTooLong)?;
     require!(reward_pool > 0, PolocError::InvalidParameters);
-    require!(claimed_lat.abs() <= 90_000_000, PolocError::InvalidParameters); // Valid latitude
-    require!(claimed_lon.abs() <= 180_000_000, PolocError::InvalidParameters); // Valid longitude
+    require!(claimed_lat.unsigned_abs() <= 90_000_000, PolocError::InvalidParameters); // Valid latitude
+    require!(claimed_lon.unsigned_abs() <= 180_000_000, PolocError::InvalidParameters); // Valid longitude
+    // Instruction data is public, so cloaked coordinates must not be sent yet.
+    let cloaked = location_commitment != [0u8; 32];
+    require!(!cloaked || (claimed_lat == 0 && claimed_lon == 0), PolocError::InvalidParameters);
+    // Any-of sets are public and counted per candidate, so no cloaking or sealing.
+    require!(alternate_locations.len() < MAX_CANDIDATE_LOCATIONS, PolocError::InvalidParameters);
+    require!(
+        alternate_locations.is_empty() || (!cloaked && !encrypted_votes),
+        PolocError::InvalidParameters
+    );
+    for [lat, lon] in &alternate_locations {
+        require!(lat.unsigned_abs() <= 90_000_000 && lon.unsigned_abs() <= 180_000_000, PolocError::InvalidParameters);
+        accounts.config.check_not_excluded(*lat, *lon)?;
+    }
+    // Cloaked locations are checked when revealed.
+    if !cloaked {
+        accounts.config.check_not_excluded(claimed_lat, claimed_lon)?;
+    }
+    require!(
+        min_equipment_class <= ChallengerProfile::EQUIPMENT_FIXED_FIBER_PROBE,
+        PolocError::InvalidParameters
+    );
+    // At least one round must open before voting closes.
+    require!(vote_schedule.is_well_formed(), PolocError::InvalidParameters);
+    require!(
+        vote_schedule.period as u64 <= duration.saturating_add(accounts.config.voting_window as u64),
+        PolocError::InvalidParameters
+    );
+
+    // Only the first tranche is escrowed now; the rest arrive via `fund_tranche`.
+    let tranches = tranches.max(1);
+    require!(tranches <= MAX_TRANCHES, PolocError::InvalidParameters);
+    require!(reward_pool % tranches as u64 == 0, PolocError::InvalidParameters);
+    let tranche_amount = reward_pool / tranches as u64;
+    // Tranches and bond top-ups are paid in lamports.
+    require!(
+        accounts.token_pool.is_none() || (tranches == 1 && !underwrite_witnesses),
+        PolocError::InvalidParameters
+    );
+
+    // Per-creator rate limit
+    let config = accounts.config;
+    let profile = accounts.waldo_profile;
+    if profile.waldo == Pubkey::default() {
+        profile.waldo = accounts.waldo.key();
+        profile.window_start = clock.unix_timestamp;
+        profile.bump = accounts.waldo_profile_bump;
+    }
+    require!(
+        profile.try_record_challenge(
+            clock.unix_timestamp,
+            config.rate_limit_window,
+            config.max_challenges_per_window,
+        ),
+        PolocError::RateLimitExceeded
+    );
+    // Concurrency cap: slots free up as challenges settle.
+    require!(
+        config.max_active_challenges == 0 || profile.active_challenges < config.max_active_challenges,
+        PolocError::TooManyActiveChallenges
+    );
+    profile.active_challenges += 1;
     
+    // A future start schedules the challenge; its window runs from there.
+    let scheduled = start_time > clock.unix_timestamp;
+    require!(
+        start_time <= clock.unix_timestamp + MAX_SCHEDULE_AHEAD,
+        PolocError::InvalidParameters
+    );
+    let start_time = start_time.max(clock.unix_timestamp);
+
+    // One location per device while its previous challenge is still open.
+    // A cloaked location can't be compared, so it never counts as the same.
+    let deadline = start_time + duration as i64;
+    let lock = accounts.device_lock;
+    let overlapping = lock.challenge != Pubkey::default() && clock.unix_timestamp <= lock.locked_until;
+    let same_location = !cloaked && lock.claimed_lat == claimed_lat && lock.claimed_lon == claimed_lon;
+    require!(!overlapping || same_location, PolocError::DeviceLocked);
+    lock.device_hash = device_hash;
+    lock.challenge = challenge_key;
+    lock.claimed_lat = claimed_lat;
+    lock.claimed_lon = claimed_lon;
+    lock.locked_until = lock.locked_until.max(deadline);
+    lock.bump = accounts.device_lock_bump;
+
     // Initialize state fields
-    challenge.challenge_id = challenge_id.clone();
-    challenge.waldo = ctx.accounts.waldo.key();
+    challenge.set_challenge_id(&challenge_id);
+    challenge.waldo = accounts.waldo.key();
+    // The creator profits from slashing witnesses who voted against them, so
+    // they may hand that power to someone else.
+    require_keys_neq!(arbiter, accounts.waldo.key(), PolocError::InvalidParameters);
+    challenge.arbiter = if arbiter == Pubkey::default() { accounts.waldo.key() } else { arbiter };
+    challenge.measurement_nonce = challenge_nonce(&accounts.slot_hashes.to_account_info(), &challenge_key)?;
     challenge.claimed_lat = claimed_lat;
     challenge.claimed_lon = claimed_lon;
-    challenge.start_time = clock.unix_timestamp;
-    challenge.deadline = clock.unix_timestamp + duration as i64;
+    challenge.start_time = start_time;
+    challenge.deadline = deadline;
     // reward_pool field is set below after funds are transferred
-    challenge.status = ChallengeStatus::Active;
+    challenge.set_status(if scheduled { ChallengeStatus::Scheduled } else { ChallengeStatus::Active });
     challenge.participant_count = 0;
     challenge.vote_count = 0;
     challenge.valid_vote_count = 0;
     challenge.r_star = 0;
-    challenge.r_star_threshold = 1000; // 1km default threshold
-    challenge.rewards_distributed = false;
-    challenge.bump = ctx.bumps.challenge;
+    challenge.r_star_threshold = accounts.config.resolve_r_star_threshold(threshold_meters, r_star_threshold)?;
+    challenge.rewards_distributed = 0;
+    challenge.bump = accounts.challenge_bump;
+    challenge.tranche_amount = tranche_amount;
+    challenge.total_tranches = tranches;
+    challenge.active_tranches = 1;
+    challenge.holds_active_slot = 1;
+    challenge.prior_result = Pubkey::default();
+    challenge.quorum_relief_bps = 0;
+    if let Some(prior) = accounts.prior_result {
+        // Cloaked coordinates aren't known yet, so there's nothing to compare.
+        require!(!cloaked, PolocError::InvalidPriorResult);
+        let prior_result = prior.load()?;
+        challenge.quorum_relief_bps =
+            prior_relief(&prior_result, &accounts.waldo.key(), claimed_lat, claimed_lon, clock.unix_timestamp, config)?;
+        challenge.prior_result = prior.key();
+        msg!("Prior result {} waives {} bps of quorum", prior_result.challenge_id(), challenge.quorum_relief_bps);
+    }
+    challenge.private = (private || cloaked) as u8;
+    challenge.location_commitment = location_commitment;
+    challenge.min_equipment_class = min_equipment_class;
+    challenge.min_reputation = min_reputation;
+    challenge.underwrites_witnesses = underwrite_witnesses as u8;
+    challenge.vote_schedule = vote_schedule;
+    // Stakes close as the first round opens, so it can't open at the start.
+    require!(challenge.staking_ends_at() >= challenge.start_time, PolocError::InvalidParameters);
+    challenge.context = context;
+    challenge.voting_window = config.voting_window;
+    challenge.measurement_max_age = config.measurement_max_age;
+    // Sealed votes can't be checked against a timing signature until tallied.
+    require!(!(rtt_attested && encrypted_votes), PolocError::InvalidParameters);
+    challenge.rtt_attested = rtt_attested as u8;
+    require!(
+        !encrypted_votes || config.vote_committee_key != [0u8; 32],
+        PolocError::CommitteeNotConfigured
+    );
+    challenge.encrypted_votes = encrypted_votes as u8;
+    challenge.sealed_votes = 0;
+    challenge.decryption_shares = 0;
+    if required_credential != Pubkey::default() {
+        require!(
+            config.attestation_program != Pubkey::default() && required_schema != Pubkey::default(),
+            PolocError::InvalidParameters
+        );
+    }
+    challenge.required_credential = required_credential;
+    challenge.required_schema = required_schema;
+    challenge.candidates = [CandidateLocation::default(); MAX_CANDIDATE_LOCATIONS];
+    challenge.candidate_count = 0;
+    challenge.selected_candidate = 0;
+    if !alternate_locations.is_empty() {
+        let locations: Vec<[i32; 2]> = std::iter::once([claimed_lat, claimed_lon])
+            .chain(alternate_locations)
+            .collect();
+        for (slot, [lat, lon]) in challenge.candidates.iter_mut().zip(&locations) {
+            slot.lat = *lat;
+            slot.lon = *lon;
+        }
+        challenge.candidate_count = locations.len() as u8;
+    }
 
-    // Transfer the initial reward_pool lamports from waldo -> challenge PDA
-    // This ensures the PDA actually holds the funds.
-    if reward_pool > 0 {
+    // Creation fee and bond, both discounted for near-miss retries and
+    // skipped entirely for waived creators
+    let waived = fee_waived(
+        accounts.fee_waivers.map(|list| &**list),
+        accounts.waiver_attestation.map(|a| a.as_ref()),
+        config,
+        &accounts.waldo.key(),
+        clock.unix_timestamp,
+    )?;
+    let kept_bps = if waived { 0 } else { 10_000u128.saturating_sub(discount_bps as u128) };
+    let discounted = |amount: u64| (amount as u128 * kept_bps / 10_000) as u64;
+    // Longer tiers hold witnesses' attention for longer and pay more
+    let base_fee = reward_pool as u128 * config.creation_fee_bps as u128 / 10_000;
+    // A token pool's size says nothing in lamports, so it pays no fee.
+    let fee = if accounts.token_pool.is_some() {
+        0
+    } else {
+        discounted((base_fee * tier.fee_multiplier_bps as u128 / 10_000) as u64)
+    };
+    let bond = discounted(config.waldo_bond);
+    if fee > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: accounts.waldo.to_account_info(),
+                    to: accounts.insurance_pool.to_account_info(),
+                },
+            ),
+            fee,
+        )?;
+        accounts.insurance_pool.total_received = accounts.insurance_pool.total_received
+            .checked_add(fee)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+    }
+    if bond > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                accounts.system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: accounts.waldo.to_account_info(),
+                    to: accounts.vault.to_account_info(),
+                },
+            ),
+            bond,
+        )?;
+    }
+    challenge.waldo_bond = bond;
+
+    // Transfer the first tranche from waldo (or the funder) -> challenge vault
+    // This ensures the vault actually holds the funds.
+    accounts.vault.challenge = challenge_key;
+    accounts.vault.bump = accounts.vault_bump;
+    challenge.funder = accounts.funder.map_or(Pubkey::default(), |funder| funder.key());
+    (challenge.reward_mint, challenge.reward_vault) = accounts.token_pool.unwrap_or_default();
+    if tranche_amount > 0 && accounts.token_pool.is_none() {
         let cpi_accounts = anchor_lang::system_program::Transfer {
-            from: ctx.accounts.waldo.to_account_info(),
-            to: challenge.to_account_info(),
+            from: accounts.funder.unwrap_or(accounts.waldo).to_account_info(),
+            to: accounts.vault.to_account_info(),
         };
-        let cpi_ctx = CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts);
-        anchor_lang::system_program::transfer(cpi_ctx, reward_pool)?;
-        // Update the on-chain accounting to match the actual lamports in the PDA
-        challenge.reward_pool = reward_pool;
-    } else {
-        challenge.reward_pool = 0;
+        let cpi_ctx = CpiContext::new(accounts.system_program.to_account_info(), cpi_accounts);
+        anchor_lang::system_program::transfer(cpi_ctx, tranche_amount)?;
+    }
+    // Update the on-chain accounting to match the actual lamports (or tokens) held
+    challenge.reward_pool = tranche_amount;
+    config.check_pool_cap(challenge.committed_tvl())?;
+
+    msg!("Challenge {} initialized by {}", challenge.challenge_id(), challenge.waldo);
+    if fee > 0 || bond > 0 {
+        msg!("Creation fee {} lamports, bond {} lamports", fee, bond);
+    } else if waived {
+        msg!("Creation fee and bond waived");
+    }
+    if scheduled {
+        msg!("Scheduled to start at {}", start_time);
+    }
+
+    // Only the key and deadline, so private challenges are listed too.
+    if let Some(feed) = accounts.expiry_feed {
+        feed.record(challenge_key, challenge.deadline);
+    }
+
+    if let (Some((registry, page)), 0) = (accounts.registry, challenge.private) {
+        list_challenge(registry, page, challenge, challenge_key)?;
+    }
+
+    // Coordinates still live in the account itself; private only keeps them
+    // out of logs and indexers.
+    if challenge.private != 0 {
+        let location_commitment = if cloaked {
+            location_commitment
+        } else {
+            Challenge::commit_location(challenge.challenge_id(), claimed_lat, claimed_lon, &location_salt)
+        };
+        msg!("Deadline: {}", challenge.deadline);
+
+        emit!(PrivateChallengeInitialized {
+            schema_version: EVENT_SCHEMA_VERSION,
+            challenge: challenge_key,
+            challenge_id: challenge.challenge_id().to_string(),
+            waldo: challenge.waldo,
+            location_commitment,
+            deadline: challenge.deadline,
+            reward_pool: challenge.reward_pool,
+            context,
+        });
+        return Ok(());
     }
 
-    msg!("Challenge {} initialized by {}", challenge.challenge_id, challenge.waldo);
     msg!("Location: ({}, {})", claimed_lat, claimed_lon);
     msg!("Deadline: {}", challenge.deadline);
+
+    emit!(ChallengeInitialized {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenge_id: challenge.challenge_id().to_string(),
+        waldo: challenge.waldo,
+        claimed_lat,
+        claimed_lon,
+        deadline: challenge.deadline,
+        reward_pool: challenge.reward_pool,
+        context,
+    });
     
     Ok(())
 }
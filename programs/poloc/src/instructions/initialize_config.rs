@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::program::Poloc;
+
+#[derive(Accounts)]
+pub struct InitializeConfig<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + Config::MAX_SIZE,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    // Only the program's upgrade authority may create the config; it then
+    // becomes the config admin.
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Poloc>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(admin.key()) @ PolocError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeConfig>) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+    config.admin = ctx.accounts.admin.key();
+    config.guardian = ctx.accounts.admin.key();
+    config.max_challenges_per_window = Config::DEFAULT_MAX_CHALLENGES_PER_WINDOW;
+    config.rate_limit_window = Config::DEFAULT_RATE_LIMIT_WINDOW;
+    config.slash_cooldown = Config::DEFAULT_SLASH_COOLDOWN;
+    config.oracle_report_bond = Config::DEFAULT_ORACLE_REPORT_BOND;
+    config.oracle_deviation_band_bps = Config::DEFAULT_ORACLE_DEVIATION_BAND_BPS;
+    config.min_stake = Config::DEFAULT_MIN_STAKE;
+    config.min_participants = Config::DEFAULT_MIN_PARTICIPANTS;
+    config.vote_quorum_bps = Config::DEFAULT_VOTE_QUORUM_BPS;
+    config.stake_quorum_bps = Config::DEFAULT_STAKE_QUORUM_BPS;
+    config.relayer_claim_fee = Config::DEFAULT_RELAYER_CLAIM_FEE;
+    config.retry_band_bps = Config::DEFAULT_RETRY_BAND_BPS;
+    config.retry_discount_bps = Config::DEFAULT_RETRY_DISCOUNT_BPS;
+    config.duration_tiers = Config::DEFAULT_DURATION_TIERS;
+    config.vote_gas_rebate = Config::DEFAULT_VOTE_GAS_REBATE;
+    config.min_witness_compensation = Config::DEFAULT_MIN_WITNESS_COMPENSATION;
+    config.max_active_challenges = Config::DEFAULT_MAX_ACTIVE_CHALLENGES;
+    config.prior_validity_window = Config::DEFAULT_PRIOR_VALIDITY_WINDOW;
+    config.prior_quorum_relief_bps = Config::DEFAULT_PRIOR_QUORUM_RELIEF_BPS;
+    config.result_ttl = Config::DEFAULT_RESULT_TTL;
+    config.voting_window = Config::DEFAULT_VOTING_WINDOW;
+    config.max_participants = Config::DEFAULT_MAX_PARTICIPANTS;
+    config.r_star_threshold = Config::DEFAULT_R_STAR_THRESHOLD;
+    config.max_pool_lamports = Config::DEFAULT_MAX_POOL_LAMPORTS;
+    config.measurement_max_age = Config::DEFAULT_MEASUREMENT_MAX_AGE;
+    config.min_r_star_threshold = Config::DEFAULT_MIN_R_STAR_THRESHOLD;
+    config.max_r_star_threshold = Config::DEFAULT_MAX_R_STAR_THRESHOLD;
+    config.claim_period = Config::DEFAULT_CLAIM_PERIOD;
+    config.exclusion_zones = [ExclusionZone::default(); MAX_EXCLUSION_ZONES];
+    config.min_settlement_buffer = Config::DEFAULT_MIN_SETTLEMENT_BUFFER;
+    config.bump = ctx.bumps.config;
+
+    msg!("Config initialized with admin {}", config.admin);
+    Ok(())
+}
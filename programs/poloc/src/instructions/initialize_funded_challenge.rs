@@ -0,0 +1,123 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes as slot_hashes_sysvar;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::instructions::initialize_challenge::{create_challenge, InitializeChallengeArgs, NewChallengeAccounts};
+
+// Called via CPI by an allowlisted funder program (a DAO treasury, a game)
+// that pays the reward pool from one of its system-owned PDA vaults, signed
+// with `invoke_signed`. `funder_seeds` are the vault's seeds, bump included,
+// so the vault can be checked to belong to `funder_program`. The waldo still
+// signs and pays rent, the creation fee and the bond, and proves as usual.
+#[derive(Accounts)]
+#[instruction(args: InitializeChallengeArgs)]
+pub struct InitializeFundedChallenge<'info> {
+    #[account(
+        init,
+        payer = waldo,
+        space = Challenge::MAX_SIZE,
+        seeds = [CHALLENGE_SEED, args.challenge_id.as_bytes()],
+        bump
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        init,
+        payer = waldo,
+        space = 8 + ChallengeVault::MAX_SIZE,
+        seeds = [VAULT_SEED, args.challenge_id.as_bytes()],
+        bump
+    )]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = waldo,
+        space = 8 + WaldoProfile::MAX_SIZE,
+        seeds = [WALDO_SEED, waldo.key().as_ref()],
+        bump
+    )]
+    pub waldo_profile: Account<'info, WaldoProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = waldo,
+        space = 8 + DeviceLock::MAX_SIZE,
+        seeds = [DEVICE_SEED, args.device_hash.as_ref()],
+        bump
+    )]
+    pub device_lock: Account<'info, DeviceLock>,
+
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    /// CHECK: Allowlisted in `Config::funders`; `funder` must be its PDA.
+    #[account(executable)]
+    pub funder_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub funder: Signer<'info>,
+
+    #[account(mut)]
+    pub waldo: Signer<'info>,
+
+    /// CHECK: Address-constrained to the SlotHashes sysvar; seeds the measurement nonce.
+    #[account(address = slot_hashes_sysvar::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// The pool is escrowed whole, like a campaign's, and a failed challenge's
+// pool goes back to the funder rather than the waldo.
+pub fn handler(
+    ctx: Context<InitializeFundedChallenge>,
+    args: InitializeChallengeArgs,
+    funder_seeds: Vec<Vec<u8>>,
+) -> Result<()> {
+    let accounts = ctx.accounts;
+    let funder_program = accounts.funder_program.key();
+    require!(accounts.config.is_funder(&funder_program), PolocError::UnknownFunder);
+    let seeds: Vec<&[u8]> = funder_seeds.iter().map(Vec::as_slice).collect();
+    let vault = Pubkey::create_program_address(&seeds, &funder_program)
+        .map_err(|_| PolocError::UnknownFunder)?;
+    require_keys_eq!(vault, accounts.funder.key(), PolocError::UnknownFunder);
+    require_keys_neq!(accounts.funder.key(), accounts.waldo.key(), PolocError::UnknownFunder);
+    require!(args.tranches <= 1, PolocError::InvalidParameters);
+
+    let reward_pool = args.reward_pool;
+    create_challenge(
+        NewChallengeAccounts {
+            challenge: &accounts.challenge,
+            vault: &mut accounts.vault,
+            config: &accounts.config,
+            waldo_profile: &mut accounts.waldo_profile,
+            device_lock: &mut accounts.device_lock,
+            insurance_pool: &mut accounts.insurance_pool,
+            waldo: &accounts.waldo,
+            system_program: &accounts.system_program,
+            slot_hashes: &accounts.slot_hashes,
+            prior_result: None,
+            fee_waivers: None,
+            waiver_attestation: None,
+            expiry_feed: None,
+            registry: None,
+            funder: Some(&accounts.funder),
+            token_pool: None,
+            challenge_bump: ctx.bumps.challenge,
+            vault_bump: ctx.bumps.vault,
+            waldo_profile_bump: ctx.bumps.waldo_profile,
+            device_lock_bump: ctx.bumps.device_lock,
+        },
+        args,
+        accounts.config.r_star_threshold,
+        0,
+        None,
+    )?;
+    msg!("Pool of {} lamports funded by {} ({})", reward_pool, accounts.funder.key(), funder_program);
+    Ok(())
+}
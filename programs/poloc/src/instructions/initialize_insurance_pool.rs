@@ -0,0 +1,33 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+pub struct InitializeInsurancePool<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + InsurancePool::MAX_SIZE,
+        seeds = [INSURANCE_SEED],
+        bump
+    )]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeInsurancePool>) -> Result<()> {
+    let pool = &mut ctx.accounts.insurance_pool;
+    pool.total_received = 0;
+    pool.bump = ctx.bumps.insurance_pool;
+
+    msg!("Insurance pool initialized");
+    Ok(())
+}
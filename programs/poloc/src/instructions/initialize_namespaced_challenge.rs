@@ -0,0 +1,110 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::slot_hashes as slot_hashes_sysvar;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::instructions::initialize_challenge::{create_challenge, InitializeChallengeArgs, NewChallengeAccounts};
+
+// Called by an allowlisted integrator program via CPI. `waldo` is the
+// integrator's authority PDA, signed with `invoke_signed`; it funds the
+// challenge and becomes its creator, so only the integrator program can
+// finalize or clone it.
+#[derive(Accounts)]
+#[instruction(args: InitializeChallengeArgs)]
+pub struct InitializeNamespacedChallenge<'info> {
+    #[account(
+        init,
+        payer = waldo,
+        space = Challenge::MAX_SIZE,
+        seeds = [CHALLENGE_SEED, args.challenge_id.as_bytes()],
+        bump
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        init,
+        payer = waldo,
+        space = 8 + ChallengeVault::MAX_SIZE,
+        seeds = [VAULT_SEED, args.challenge_id.as_bytes()],
+        bump
+    )]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = waldo,
+        space = 8 + WaldoProfile::MAX_SIZE,
+        seeds = [WALDO_SEED, waldo.key().as_ref()],
+        bump
+    )]
+    pub waldo_profile: Account<'info, WaldoProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = waldo,
+        space = 8 + DeviceLock::MAX_SIZE,
+        seeds = [DEVICE_SEED, args.device_hash.as_ref()],
+        bump
+    )]
+    pub device_lock: Account<'info, DeviceLock>,
+
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    /// CHECK: Allowlisted in `Config::integrators`; `waldo` must be its
+    /// authority PDA.
+    #[account(executable)]
+    pub integrator_program: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [INTEGRATOR_AUTHORITY_SEED],
+        bump,
+        seeds::program = integrator_program.key()
+    )]
+    pub waldo: Signer<'info>,
+
+    /// CHECK: Address-constrained to the SlotHashes sysvar; seeds the measurement nonce.
+    #[account(address = slot_hashes_sysvar::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeNamespacedChallenge>, args: InitializeChallengeArgs) -> Result<()> {
+    let accounts = ctx.accounts;
+    let prefix = accounts.config
+        .integrator_prefix(&accounts.integrator_program.key())
+        .ok_or(PolocError::UnknownIntegrator)?;
+    create_challenge(
+        NewChallengeAccounts {
+            challenge: &accounts.challenge,
+            vault: &mut accounts.vault,
+            config: &accounts.config,
+            waldo_profile: &mut accounts.waldo_profile,
+            device_lock: &mut accounts.device_lock,
+            insurance_pool: &mut accounts.insurance_pool,
+            waldo: &accounts.waldo,
+            system_program: &accounts.system_program,
+            slot_hashes: &accounts.slot_hashes,
+            prior_result: None,
+            fee_waivers: None,
+            waiver_attestation: None,
+            expiry_feed: None,
+            registry: None,
+            funder: None,
+            token_pool: None,
+            challenge_bump: ctx.bumps.challenge,
+            vault_bump: ctx.bumps.vault,
+            waldo_profile_bump: ctx.bumps.waldo_profile,
+            device_lock_bump: ctx.bumps.device_lock,
+        },
+        args,
+        accounts.config.r_star_threshold,
+        0,
+        Some(prefix),
+    )
+}
@@ -0,0 +1,57 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+pub struct InitializeOracleSet<'info> {
+    #[account(
+        init,
+        payer = admin,
+        space = 8 + OracleSet::MAX_SIZE,
+        seeds = [ORACLE_SET_SEED],
+        bump
+    )]
+    pub oracle_set: Account<'info, OracleSet>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<InitializeOracleSet>, oracles: Vec<Pubkey>) -> Result<()> {
+    let oracle_set = &mut ctx.accounts.oracle_set;
+    oracle_set.bump = ctx.bumps.oracle_set;
+    oracle_set.oracles = Vec::new();
+    oracle_set.quorum = 0;
+    apply_oracles(oracle_set, oracles)?;
+
+    msg!("Oracle set initialized with {} oracle(s)", oracle_set.oracles.len());
+    Ok(())
+}
+
+// Replaces the registered oracles, keeping the nonce of any that stay so
+// their old results can't be replayed after a membership change.
+pub(crate) fn apply_oracles(oracle_set: &mut OracleSet, oracles: Vec<Pubkey>) -> Result<()> {
+    require!(oracles.len() <= OracleSet::MAX_ORACLES, PolocError::TooManyOracles);
+
+    let mut entries: Vec<OracleEntry> = Vec::with_capacity(oracles.len());
+    for authority in oracles {
+        require!(
+            !entries.iter().any(|e| e.authority == authority),
+            PolocError::InvalidParameters
+        );
+        let entry = match oracle_set.entry_mut(&authority) {
+            Some(existing) => *existing,
+            None => OracleEntry::new(authority),
+        };
+        entries.push(entry);
+    }
+    require!(oracle_set.quorum as usize <= entries.len(), PolocError::OracleQuorumUnreachable);
+    oracle_set.oracles = entries;
+    Ok(())
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+#[instruction(cell_lat: i16, cell_lon: i16)]
+pub struct InitializeRegionStats<'info> {
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + RegionStats::MAX_SIZE,
+        seeds = [REGION_STATS_SEED, &cell_lat.to_le_bytes(), &cell_lon.to_le_bytes()],
+        bump
+    )]
+    pub region_stats: Account<'info, RegionStats>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Permissionless: whoever wants a cell tracked pays its rent. Counting starts
+// with the next challenge finalized there.
+pub fn handler(ctx: Context<InitializeRegionStats>, cell_lat: i16, cell_lon: i16) -> Result<()> {
+    require!((-90..90).contains(&cell_lat), PolocError::InvalidParameters);
+    require!((-180..180).contains(&cell_lon), PolocError::InvalidParameters);
+
+    let stats = &mut ctx.accounts.region_stats;
+    stats.cell_lat = cell_lat;
+    stats.cell_lon = cell_lon;
+    stats.bump = ctx.bumps.region_stats;
+
+    msg!("Region ({}, {}) stats initialized", cell_lat, cell_lon);
+    Ok(())
+}
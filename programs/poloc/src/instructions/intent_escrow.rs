@@ -0,0 +1,69 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+pub struct FundIntentEscrow<'info> {
+    #[account(
+        init_if_needed,
+        payer = witness,
+        space = 8 + IntentEscrow::MAX_SIZE,
+        seeds = [INTENT_ESCROW_SEED, witness.key().as_ref()],
+        bump
+    )]
+    pub intent_escrow: Account<'info, IntentEscrow>,
+
+    #[account(mut)]
+    pub witness: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawIntentEscrow<'info> {
+    #[account(
+        mut,
+        seeds = [INTENT_ESCROW_SEED, witness.key().as_ref()],
+        bump = intent_escrow.bump,
+        has_one = witness @ PolocError::Unauthorized
+    )]
+    pub intent_escrow: Account<'info, IntentEscrow>,
+
+    #[account(mut)]
+    pub witness: Signer<'info>,
+}
+
+pub fn fund_handler(ctx: Context<FundIntentEscrow>, amount: u64) -> Result<()> {
+    let escrow = &mut ctx.accounts.intent_escrow;
+    escrow.witness = ctx.accounts.witness.key();
+    escrow.bump = ctx.bumps.intent_escrow;
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.witness.to_account_info(),
+                to: ctx.accounts.intent_escrow.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    msg!("Intent escrow for {} funded with {} lamports", ctx.accounts.witness.key(), amount);
+    Ok(())
+}
+
+pub fn withdraw_handler(ctx: Context<WithdrawIntentEscrow>, amount: u64) -> Result<()> {
+    let escrow_info = ctx.accounts.intent_escrow.to_account_info();
+    let rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+    let available = escrow_info.lamports().saturating_sub(rent);
+    require!(amount <= available, PolocError::InsufficientEscrow);
+
+    **escrow_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.witness.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    msg!("Withdrew {} lamports from intent escrow of {}", amount, ctx.accounts.witness.key());
+    Ok(())
+}
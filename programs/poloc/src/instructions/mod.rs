@@ -3,13 +3,370 @@ pub mod stake;
 pub mod vote;
 pub mod finalize;
 pub mod refund_failed_challenge;
+pub mod cancel_challenge;
 pub mod slash;
 pub mod claim_reward;
+pub mod initialize_archive;
+pub mod archive_challenge;
+pub mod verify_archived_challenge;
+pub mod register_keeper;
+pub mod deregister_keeper;
+pub mod penalize_keeper;
+pub mod initialize_config;
+pub mod update_config;
+pub mod freeze_challenge;
+pub mod fund_tranche;
+pub mod delegate_stake;
+pub mod finalize_with_payouts;
+pub mod claim_payout;
+pub mod flag_collusion;
+pub mod clear_collusion_flag;
+pub mod initialize_oracle_set;
+pub mod set_oracles;
+pub mod submit_oracle_result;
+pub mod finalize_with_oracles;
+pub mod initialize_insurance_pool;
+pub mod reclaim_oracle_report;
+pub mod intent_escrow;
+pub mod stake_with_intent;
+pub mod set_region_policy;
+pub mod set_equipment_class;
+pub mod contribute;
+pub mod refund_sponsor;
+pub mod claim_escrow;
+pub mod reveal_location;
+pub mod claim_reward_swapped;
+pub mod claim_reward_with_intent;
+pub mod sync_protocol_info;
+pub mod clone_challenge;
+pub mod audit_challenge;
+pub mod set_vote_committee;
+pub mod encrypted_vote;
+pub mod register_callback;
+pub mod initialize_namespaced_challenge;
+pub mod campaign;
+pub mod submit_evidence;
+pub mod initialize_region_stats;
+pub mod initialize_funded_challenge;
+pub mod tier_badge;
+pub mod flash_challenge;
+pub mod flag_reverification_due;
+pub mod return_stake;
+pub mod standing_stake;
+pub mod token_pool;
+pub mod emergency_withdraw;
+pub mod unstake;
+pub mod stake_receipt;
+pub mod compute_r_star;
+pub mod set_fee_waivers;
+pub mod rotate_oracle_key;
+pub mod prepare_vote_account;
+pub mod extend_challenge;
+pub mod sweep_unclaimed;
+pub mod multisig_claim;
+pub mod expiry_feed;
+pub mod close_settled;
+pub mod claim_bond;
+pub mod challenge_report;
+pub mod challenge_registry;
+pub mod claim_reward_with_proof;
+#[cfg(feature = "demo")]
+pub mod bootstrap_demo;
 
-pub use initialize_challenge::*;
-pub use stake::*;
-pub use vote::*;
-pub use finalize::*;
-pub use claim_reward::*;
-pub use refund_failed_challenge::*;
-pub use slash::*;
\ No newline at end of file
+pub use initialize_challenge::{InitializeChallenge, InitializeChallengeArgs};
+pub use stake::StakeCtx;
+pub use vote::{SubmitVote, SubmitVoteArgs, SubmitVoteByKeyArgs};
+pub use finalize::FinalizeChallenge;
+pub use refund_failed_challenge::RefundFailedChallenge;
+pub use cancel_challenge::CancelChallenge;
+pub use slash::Slash;
+pub use claim_reward::ClaimReward;
+pub use initialize_archive::InitializeArchive;
+pub use archive_challenge::ArchiveChallenge;
+pub use verify_archived_challenge::{VerifyArchivedChallenge, ArchivedChallengeResult};
+pub use register_keeper::RegisterKeeper;
+pub use deregister_keeper::DeregisterKeeper;
+pub use penalize_keeper::PenalizeKeeper;
+pub use initialize_config::InitializeConfig;
+pub use update_config::{UpdateConfig, UpdateConfigArgs};
+pub use freeze_challenge::SetChallengeFrozen;
+pub use fund_tranche::FundTranche;
+pub use delegate_stake::DelegateStake;
+pub use finalize_with_payouts::FinalizeWithPayouts;
+pub use claim_payout::ClaimPayout;
+pub use flag_collusion::FlagCollusion;
+pub use clear_collusion_flag::ClearCollusionFlag;
+pub use initialize_oracle_set::InitializeOracleSet;
+pub use set_oracles::SetOracles;
+pub use submit_oracle_result::SubmitOracleResult;
+pub use finalize_with_oracles::FinalizeWithOracles;
+pub use initialize_insurance_pool::InitializeInsurancePool;
+pub use reclaim_oracle_report::ReclaimOracleReport;
+pub use intent_escrow::{FundIntentEscrow, WithdrawIntentEscrow};
+pub use stake_with_intent::StakeWithIntent;
+pub use set_region_policy::SetRegionPolicy;
+pub use set_equipment_class::SetEquipmentClass;
+pub use contribute::Contribute;
+pub use refund_sponsor::RefundSponsor;
+pub use claim_escrow::{ReleaseClaim, RevertClaim};
+pub use reveal_location::RevealLocation;
+pub use claim_reward_swapped::ClaimRewardSwapped;
+pub use claim_reward_with_intent::ClaimRewardWithIntent;
+pub use sync_protocol_info::SyncProtocolInfo;
+pub use clone_challenge::{CloneChallenge, CloneChallengeArgs};
+pub use audit_challenge::AuditChallenge;
+pub use set_vote_committee::SetVoteCommittee;
+pub use encrypted_vote::{
+    SubmitEncryptedVote, SubmitDecryptionShare, DecryptAndTally, DecryptedVote,
+};
+pub use register_callback::RegisterCallback;
+pub use initialize_namespaced_challenge::InitializeNamespacedChallenge;
+pub use campaign::{
+    CreateCampaign, InitializeCampaignChallenge, ReportCampaignResult, ReconcileCampaign,
+};
+pub use submit_evidence::SubmitEvidence;
+pub use initialize_region_stats::InitializeRegionStats;
+pub use initialize_funded_challenge::InitializeFundedChallenge;
+pub use tier_badge::{InitializeTierBadgeMint, ClaimTierBadge};
+pub use flash_challenge::{
+    OpenFlashChallenge, SubmitFlashVote, FinalizeFlashChallenge, OpenFlashChallengeArgs,
+};
+pub use flag_reverification_due::FlagReverificationDue;
+pub use return_stake::ReturnStake;
+pub use standing_stake::{
+    FundStandingStake, WithdrawStandingStake, StakeFromStanding, ReleaseStandingStake,
+};
+pub use token_pool::{
+    InitializeTokenChallenge, StakeTokens, ClaimRewardTokens, RefundTokenChallenge,
+    ReturnTokenStake,
+};
+pub use emergency_withdraw::{
+    ProposeEmergencyWithdraw, CancelEmergencyWithdraw, ExecuteEmergencyWithdraw,
+};
+pub use unstake::Unstake;
+pub use stake_receipt::{InitializeReceiptMint, MintStakeReceipt, RedeemStakeReceipt};
+pub use compute_r_star::ComputeRStar;
+pub use set_fee_waivers::SetFeeWaivers;
+pub use rotate_oracle_key::RotateOracleKey;
+pub use prepare_vote_account::PrepareVoteAccount;
+pub use extend_challenge::ExtendChallenge;
+pub use sweep_unclaimed::SweepUnclaimed;
+pub use multisig_claim::{SetClaimPolicy, ApproveClaim, ExecuteClaim};
+pub use expiry_feed::{InitializeExpiryFeed, ConsumeExpiryEntry};
+pub use close_settled::{CloseVote, CloseStake};
+pub use claim_bond::ClaimBondShare;
+pub use challenge_report::EmitChallengeReport;
+pub use challenge_registry::{InitializeChallengeRegistry, OpenRegistryPage, DelistChallenge};
+pub use claim_reward_with_proof::ClaimRewardWithProof;
+#[cfg(feature = "demo")]
+pub use bootstrap_demo::BootstrapDemo;
+
+// `#[program]` reaches the client account modules that `#[derive(Accounts)]`
+// generates beside each struct through the crate root.
+pub(crate) use self::{
+    initialize_challenge::__client_accounts_initialize_challenge,
+    stake::__client_accounts_stake_ctx,
+    vote::__client_accounts_submit_vote,
+    finalize::__client_accounts_finalize_challenge,
+    refund_failed_challenge::__client_accounts_refund_failed_challenge,
+    cancel_challenge::__client_accounts_cancel_challenge,
+    slash::__client_accounts_slash,
+    claim_reward::__client_accounts_claim_reward,
+    initialize_archive::__client_accounts_initialize_archive,
+    archive_challenge::__client_accounts_archive_challenge,
+    verify_archived_challenge::__client_accounts_verify_archived_challenge,
+    register_keeper::__client_accounts_register_keeper,
+    deregister_keeper::__client_accounts_deregister_keeper,
+    penalize_keeper::__client_accounts_penalize_keeper,
+    initialize_config::__client_accounts_initialize_config,
+    update_config::__client_accounts_update_config,
+    freeze_challenge::__client_accounts_set_challenge_frozen,
+    fund_tranche::__client_accounts_fund_tranche,
+    delegate_stake::__client_accounts_delegate_stake,
+    finalize_with_payouts::__client_accounts_finalize_with_payouts,
+    claim_payout::__client_accounts_claim_payout,
+    flag_collusion::__client_accounts_flag_collusion,
+    clear_collusion_flag::__client_accounts_clear_collusion_flag,
+    initialize_oracle_set::__client_accounts_initialize_oracle_set,
+    set_oracles::__client_accounts_set_oracles,
+    submit_oracle_result::__client_accounts_submit_oracle_result,
+    finalize_with_oracles::__client_accounts_finalize_with_oracles,
+    initialize_insurance_pool::__client_accounts_initialize_insurance_pool,
+    reclaim_oracle_report::__client_accounts_reclaim_oracle_report,
+    intent_escrow::__client_accounts_fund_intent_escrow,
+    intent_escrow::__client_accounts_withdraw_intent_escrow,
+    stake_with_intent::__client_accounts_stake_with_intent,
+    set_region_policy::__client_accounts_set_region_policy,
+    set_equipment_class::__client_accounts_set_equipment_class,
+    contribute::__client_accounts_contribute,
+    refund_sponsor::__client_accounts_refund_sponsor,
+    claim_escrow::__client_accounts_release_claim,
+    claim_escrow::__client_accounts_revert_claim,
+    reveal_location::__client_accounts_reveal_location,
+    claim_reward_swapped::__client_accounts_claim_reward_swapped,
+    claim_reward_with_intent::__client_accounts_claim_reward_with_intent,
+    sync_protocol_info::__client_accounts_sync_protocol_info,
+    clone_challenge::__client_accounts_clone_challenge,
+    audit_challenge::__client_accounts_audit_challenge,
+    set_vote_committee::__client_accounts_set_vote_committee,
+    encrypted_vote::__client_accounts_submit_encrypted_vote,
+    encrypted_vote::__client_accounts_submit_decryption_share,
+    encrypted_vote::__client_accounts_decrypt_and_tally,
+    register_callback::__client_accounts_register_callback,
+    initialize_namespaced_challenge::__client_accounts_initialize_namespaced_challenge,
+    campaign::__client_accounts_create_campaign,
+    campaign::__client_accounts_initialize_campaign_challenge,
+    campaign::__client_accounts_report_campaign_result,
+    campaign::__client_accounts_reconcile_campaign,
+    submit_evidence::__client_accounts_submit_evidence,
+    initialize_region_stats::__client_accounts_initialize_region_stats,
+    initialize_funded_challenge::__client_accounts_initialize_funded_challenge,
+    tier_badge::__client_accounts_initialize_tier_badge_mint,
+    tier_badge::__client_accounts_claim_tier_badge,
+    flash_challenge::__client_accounts_open_flash_challenge,
+    flash_challenge::__client_accounts_submit_flash_vote,
+    flash_challenge::__client_accounts_finalize_flash_challenge,
+    flag_reverification_due::__client_accounts_flag_reverification_due,
+    return_stake::__client_accounts_return_stake,
+    standing_stake::__client_accounts_fund_standing_stake,
+    standing_stake::__client_accounts_withdraw_standing_stake,
+    standing_stake::__client_accounts_stake_from_standing,
+    standing_stake::__client_accounts_release_standing_stake,
+    token_pool::__client_accounts_initialize_token_challenge,
+    token_pool::__client_accounts_stake_tokens,
+    token_pool::__client_accounts_claim_reward_tokens,
+    token_pool::__client_accounts_refund_token_challenge,
+    token_pool::__client_accounts_return_token_stake,
+    emergency_withdraw::__client_accounts_propose_emergency_withdraw,
+    emergency_withdraw::__client_accounts_cancel_emergency_withdraw,
+    emergency_withdraw::__client_accounts_execute_emergency_withdraw,
+    unstake::__client_accounts_unstake,
+    stake_receipt::__client_accounts_initialize_receipt_mint,
+    stake_receipt::__client_accounts_mint_stake_receipt,
+    stake_receipt::__client_accounts_redeem_stake_receipt,
+    compute_r_star::__client_accounts_compute_r_star,
+    set_fee_waivers::__client_accounts_set_fee_waivers,
+    rotate_oracle_key::__client_accounts_rotate_oracle_key,
+    prepare_vote_account::__client_accounts_prepare_vote_account,
+    extend_challenge::__client_accounts_extend_challenge,
+    sweep_unclaimed::__client_accounts_sweep_unclaimed,
+    multisig_claim::__client_accounts_set_claim_policy,
+    multisig_claim::__client_accounts_approve_claim,
+    multisig_claim::__client_accounts_execute_claim,
+    expiry_feed::__client_accounts_initialize_expiry_feed,
+    expiry_feed::__client_accounts_consume_expiry_entry,
+    close_settled::__client_accounts_close_vote,
+    close_settled::__client_accounts_close_stake,
+    claim_bond::__client_accounts_claim_bond_share,
+    challenge_report::__client_accounts_emit_challenge_report,
+    challenge_registry::__client_accounts_initialize_challenge_registry,
+    challenge_registry::__client_accounts_open_registry_page,
+    challenge_registry::__client_accounts_delist_challenge,
+    claim_reward_with_proof::__client_accounts_claim_reward_with_proof,
+};
+#[cfg(feature = "demo")]
+pub(crate) use bootstrap_demo::__client_accounts_bootstrap_demo;
+#[cfg(feature = "cpi")]
+pub(crate) use self::{
+    initialize_challenge::__cpi_client_accounts_initialize_challenge,
+    stake::__cpi_client_accounts_stake_ctx,
+    vote::__cpi_client_accounts_submit_vote,
+    finalize::__cpi_client_accounts_finalize_challenge,
+    refund_failed_challenge::__cpi_client_accounts_refund_failed_challenge,
+    cancel_challenge::__cpi_client_accounts_cancel_challenge,
+    slash::__cpi_client_accounts_slash,
+    claim_reward::__cpi_client_accounts_claim_reward,
+    initialize_archive::__cpi_client_accounts_initialize_archive,
+    archive_challenge::__cpi_client_accounts_archive_challenge,
+    verify_archived_challenge::__cpi_client_accounts_verify_archived_challenge,
+    register_keeper::__cpi_client_accounts_register_keeper,
+    deregister_keeper::__cpi_client_accounts_deregister_keeper,
+    penalize_keeper::__cpi_client_accounts_penalize_keeper,
+    initialize_config::__cpi_client_accounts_initialize_config,
+    update_config::__cpi_client_accounts_update_config,
+    freeze_challenge::__cpi_client_accounts_set_challenge_frozen,
+    fund_tranche::__cpi_client_accounts_fund_tranche,
+    delegate_stake::__cpi_client_accounts_delegate_stake,
+    finalize_with_payouts::__cpi_client_accounts_finalize_with_payouts,
+    claim_payout::__cpi_client_accounts_claim_payout,
+    flag_collusion::__cpi_client_accounts_flag_collusion,
+    clear_collusion_flag::__cpi_client_accounts_clear_collusion_flag,
+    initialize_oracle_set::__cpi_client_accounts_initialize_oracle_set,
+    set_oracles::__cpi_client_accounts_set_oracles,
+    submit_oracle_result::__cpi_client_accounts_submit_oracle_result,
+    finalize_with_oracles::__cpi_client_accounts_finalize_with_oracles,
+    initialize_insurance_pool::__cpi_client_accounts_initialize_insurance_pool,
+    reclaim_oracle_report::__cpi_client_accounts_reclaim_oracle_report,
+    intent_escrow::__cpi_client_accounts_fund_intent_escrow,
+    intent_escrow::__cpi_client_accounts_withdraw_intent_escrow,
+    stake_with_intent::__cpi_client_accounts_stake_with_intent,
+    set_region_policy::__cpi_client_accounts_set_region_policy,
+    set_equipment_class::__cpi_client_accounts_set_equipment_class,
+    contribute::__cpi_client_accounts_contribute,
+    refund_sponsor::__cpi_client_accounts_refund_sponsor,
+    claim_escrow::__cpi_client_accounts_release_claim,
+    claim_escrow::__cpi_client_accounts_revert_claim,
+    reveal_location::__cpi_client_accounts_reveal_location,
+    claim_reward_swapped::__cpi_client_accounts_claim_reward_swapped,
+    claim_reward_with_intent::__cpi_client_accounts_claim_reward_with_intent,
+    sync_protocol_info::__cpi_client_accounts_sync_protocol_info,
+    clone_challenge::__cpi_client_accounts_clone_challenge,
+    audit_challenge::__cpi_client_accounts_audit_challenge,
+    set_vote_committee::__cpi_client_accounts_set_vote_committee,
+    encrypted_vote::__cpi_client_accounts_submit_encrypted_vote,
+    encrypted_vote::__cpi_client_accounts_submit_decryption_share,
+    encrypted_vote::__cpi_client_accounts_decrypt_and_tally,
+    register_callback::__cpi_client_accounts_register_callback,
+    initialize_namespaced_challenge::__cpi_client_accounts_initialize_namespaced_challenge,
+    campaign::__cpi_client_accounts_create_campaign,
+    campaign::__cpi_client_accounts_initialize_campaign_challenge,
+    campaign::__cpi_client_accounts_report_campaign_result,
+    campaign::__cpi_client_accounts_reconcile_campaign,
+    submit_evidence::__cpi_client_accounts_submit_evidence,
+    initialize_region_stats::__cpi_client_accounts_initialize_region_stats,
+    initialize_funded_challenge::__cpi_client_accounts_initialize_funded_challenge,
+    tier_badge::__cpi_client_accounts_initialize_tier_badge_mint,
+    tier_badge::__cpi_client_accounts_claim_tier_badge,
+    flash_challenge::__cpi_client_accounts_open_flash_challenge,
+    flash_challenge::__cpi_client_accounts_submit_flash_vote,
+    flash_challenge::__cpi_client_accounts_finalize_flash_challenge,
+    flag_reverification_due::__cpi_client_accounts_flag_reverification_due,
+    return_stake::__cpi_client_accounts_return_stake,
+    standing_stake::__cpi_client_accounts_fund_standing_stake,
+    standing_stake::__cpi_client_accounts_withdraw_standing_stake,
+    standing_stake::__cpi_client_accounts_stake_from_standing,
+    standing_stake::__cpi_client_accounts_release_standing_stake,
+    token_pool::__cpi_client_accounts_initialize_token_challenge,
+    token_pool::__cpi_client_accounts_stake_tokens,
+    token_pool::__cpi_client_accounts_claim_reward_tokens,
+    token_pool::__cpi_client_accounts_refund_token_challenge,
+    token_pool::__cpi_client_accounts_return_token_stake,
+    emergency_withdraw::__cpi_client_accounts_propose_emergency_withdraw,
+    emergency_withdraw::__cpi_client_accounts_cancel_emergency_withdraw,
+    emergency_withdraw::__cpi_client_accounts_execute_emergency_withdraw,
+    unstake::__cpi_client_accounts_unstake,
+    stake_receipt::__cpi_client_accounts_initialize_receipt_mint,
+    stake_receipt::__cpi_client_accounts_mint_stake_receipt,
+    stake_receipt::__cpi_client_accounts_redeem_stake_receipt,
+    compute_r_star::__cpi_client_accounts_compute_r_star,
+    set_fee_waivers::__cpi_client_accounts_set_fee_waivers,
+    rotate_oracle_key::__cpi_client_accounts_rotate_oracle_key,
+    prepare_vote_account::__cpi_client_accounts_prepare_vote_account,
+    extend_challenge::__cpi_client_accounts_extend_challenge,
+    sweep_unclaimed::__cpi_client_accounts_sweep_unclaimed,
+    multisig_claim::__cpi_client_accounts_set_claim_policy,
+    multisig_claim::__cpi_client_accounts_approve_claim,
+    multisig_claim::__cpi_client_accounts_execute_claim,
+    expiry_feed::__cpi_client_accounts_initialize_expiry_feed,
+    expiry_feed::__cpi_client_accounts_consume_expiry_entry,
+    close_settled::__cpi_client_accounts_close_vote,
+    close_settled::__cpi_client_accounts_close_stake,
+    claim_bond::__cpi_client_accounts_claim_bond_share,
+    challenge_report::__cpi_client_accounts_emit_challenge_report,
+    challenge_registry::__cpi_client_accounts_initialize_challenge_registry,
+    challenge_registry::__cpi_client_accounts_open_registry_page,
+    challenge_registry::__cpi_client_accounts_delist_challenge,
+    claim_reward_with_proof::__cpi_client_accounts_claim_reward_with_proof,
+};
+#[cfg(all(feature = "demo", feature = "cpi"))]
+pub(crate) use bootstrap_demo::__cpi_client_accounts_bootstrap_demo;
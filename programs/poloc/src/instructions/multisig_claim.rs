@@ -0,0 +1,207 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::instructions::claim_escrow::pay_claim;
+use crate::instructions::claim_reward::{
+    bond_top_up, fund_streak_bonus, record_claim, record_payout, reward_share,
+};
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct SetClaimPolicy<'info> {
+    #[account(seeds = [CHALLENGE_SEED, challenge_id.as_bytes()], bump = challenge.load()?.bump)]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    /// CHECK: Must stay empty; a vote copies the policy flag when it's cast.
+    #[account(
+        seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump,
+        constraint = vote_account.data_is_empty() @ PolocError::ClaimPolicyAfterVote
+    )]
+    pub vote_account: UncheckedAccount<'info>,
+
+    pub challenger: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, challenger_pubkey: Pubkey)]
+pub struct ApproveClaim<'info> {
+    #[account(seeds = [CHALLENGE_SEED, challenge_id.as_bytes()], bump = challenge.load()?.bump)]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    pub approver: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, challenger_pubkey: Pubkey)]
+pub struct ExecuteClaim<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()],
+        bump = vote.bump,
+        constraint = vote.multisig_claim @ PolocError::InvalidParameters,
+        constraint = !vote.processed @ PolocError::AlreadyClaimed
+    )]
+    pub vote: Account<'info, Vote>,
+
+    /// CHECK: The policy's recipient; receives the reward.
+    #[account(mut, address = stake_account.claim_recipient @ PolocError::Unauthorized)]
+    pub recipient: UncheckedAccount<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // Required while `config.claim_escrow_period` is set; omitted otherwise.
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + ClaimEscrow::MAX_SIZE,
+        seeds = [CLAIM_ESCROW_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()],
+        bump
+    )]
+    pub claim_escrow: Option<Account<'info, ClaimEscrow>>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + PayoutLedger::MAX_SIZE,
+        seeds = [PAYOUT_LEDGER_SEED, challenger_pubkey.as_ref()],
+        bump
+    )]
+    pub payout_ledger: Account<'info, PayoutLedger>,
+
+    // Credited with the correct vote.
+    #[account(mut, seeds = [CHALLENGER_SEED, challenger_pubkey.as_ref()], bump = challenger_profile.bump)]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    // Funds the winner's streak bonus.
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    // Anyone may execute once the approvals are in; pays for new accounts.
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Puts the stake's reward behind `threshold` of `approvers`, paid only to
+// `recipient`. Like receipts, it must be set before the vote, which carries
+// the flag from then on. No approvers and a zero threshold clear it.
+pub fn set_policy_handler(
+    ctx: Context<SetClaimPolicy>,
+    challenge_id: String,
+    approvers: Vec<Pubkey>,
+    threshold: u8,
+    recipient: Pubkey,
+) -> Result<()> {
+    require!(ctx.accounts.challenge.load()?.is_open(), PolocError::ChallengeNotActive);
+    require!(approvers.len() <= MAX_CLAIM_APPROVERS, PolocError::InvalidParameters);
+    require!(threshold as usize <= approvers.len(), PolocError::InvalidParameters);
+    require!((threshold == 0) == approvers.is_empty(), PolocError::InvalidParameters);
+    for (i, approver) in approvers.iter().enumerate() {
+        require!(
+            *approver != Pubkey::default() && !approvers[..i].contains(approver),
+            PolocError::InvalidParameters
+        );
+    }
+    require!(threshold == 0 || recipient != Pubkey::default(), PolocError::InvalidParameters);
+
+    let stake = &mut ctx.accounts.stake_account;
+    stake.claim_approvers = [Pubkey::default(); MAX_CLAIM_APPROVERS];
+    stake.claim_approvers[..approvers.len()].copy_from_slice(&approvers);
+    stake.claim_threshold = threshold;
+    stake.claim_recipient = if threshold == 0 { Pubkey::default() } else { recipient };
+    stake.claim_approvals = 0;
+
+    msg!("Claim policy for {} on challenge {}: {} of {}, paid to {}",
+         stake.challenger, challenge_id, threshold, approvers.len(), stake.claim_recipient);
+    Ok(())
+}
+
+pub fn approve_handler(ctx: Context<ApproveClaim>, challenge_id: String, challenger_pubkey: Pubkey) -> Result<()> {
+    let stake = &mut ctx.accounts.stake_account;
+    require!(stake.has_claim_policy(), PolocError::InvalidParameters);
+    let index = stake
+        .claim_approver_index(&ctx.accounts.approver.key())
+        .ok_or(PolocError::Unauthorized)?;
+    stake.claim_approvals |= 1 << index;
+
+    msg!("Claim for {} on challenge {} approved by {} ({} of {})",
+         challenger_pubkey, challenge_id, ctx.accounts.approver.key(),
+         stake.claim_approvals.count_ones(), stake.claim_threshold);
+    Ok(())
+}
+
+// Pays a policy-gated reward once enough approvers have signed off, the same
+// way `claim_reward` pays any other: share plus bond top-up and streak bonus,
+// escrowed when the config asks for it.
+pub fn execute_handler(ctx: Context<ExecuteClaim>, _challenge_id: String, challenger_pubkey: Pubkey) -> Result<()> {
+    let stake = &ctx.accounts.stake_account;
+    require!(
+        stake.claim_approvals.count_ones() >= stake.claim_threshold as u32,
+        PolocError::InsufficientClaimApprovals
+    );
+
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    let vote = &mut ctx.accounts.vote;
+    let share = reward_share(challenge, vote)?;
+    let bonus = fund_streak_bonus(
+        challenge_key,
+        &ctx.accounts.vault,
+        &ctx.accounts.insurance_pool,
+        &mut ctx.accounts.challenger_profile,
+        challenger_pubkey,
+        share,
+    )?;
+    let paid = share + bond_top_up(challenge, &ctx.accounts.config, share) + bonus;
+
+    let recipient = ctx.accounts.recipient.to_account_info();
+    pay_claim(
+        challenge_key,
+        &ctx.accounts.vault,
+        &recipient,
+        ctx.accounts.claim_escrow.as_mut(),
+        ctx.bumps.claim_escrow,
+        ctx.accounts.config.claim_escrow_period,
+        paid,
+    )?;
+    record_payout(&mut ctx.accounts.payout_ledger, ctx.bumps.payout_ledger, challenger_pubkey, challenge_key, paid)?;
+
+    msg!("Policy claim for {} paid to {}", challenger_pubkey, recipient.key());
+    record_claim(challenge, challenge_key, vote, &mut ctx.accounts.challenger_profile, challenger_pubkey, share, paid)
+}
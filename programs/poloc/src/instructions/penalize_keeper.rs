@@ -0,0 +1,70 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::program::Poloc;
+
+#[derive(Accounts)]
+pub struct PenalizeKeeper<'info> {
+    #[account(
+        mut,
+        seeds = [KEEPER_SEED, keeper.operator.as_ref()],
+        bump = keeper.bump
+    )]
+    pub keeper: Account<'info, Keeper>,
+
+    /// CHECK: Receives slashed lamports; chosen by governance.
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    pub authority: Signer<'info>,
+
+    // Penalties are a governance action gated on the program's upgrade authority.
+    #[account(constraint = program.programdata_address()? == Some(program_data.key()))]
+    pub program: Program<'info, Poloc>,
+
+    #[account(constraint = program_data.upgrade_authority_address == Some(authority.key()) @ PolocError::Unauthorized)]
+    pub program_data: Account<'info, ProgramData>,
+}
+
+pub fn handler(ctx: Context<PenalizeKeeper>, fault: KeeperFault) -> Result<()> {
+    let keeper = &mut ctx.accounts.keeper;
+    let (score_penalty, slash_bps) = fault.penalty();
+
+    let slashed = keeper.stake
+        .checked_mul(slash_bps)
+        .ok_or(PolocError::ArithmeticOverflow)?
+        / 10_000;
+
+    if slashed > 0 {
+        let keeper_info = keeper.to_account_info();
+        let destination = ctx.accounts.destination.to_account_info();
+        **keeper_info.try_borrow_mut_lamports()? = keeper_info
+            .lamports()
+            .checked_sub(slashed)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+        **destination.try_borrow_mut_lamports()? = destination
+            .lamports()
+            .checked_add(slashed)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+        keeper.stake -= slashed;
+    }
+
+    keeper.score = keeper.score.saturating_sub(score_penalty);
+    keeper.faults = keeper.faults.saturating_add(1);
+    keeper.last_active = Clock::get()?.unix_timestamp;
+
+    msg!("Keeper {} penalized: score {}, slashed {} lamports", keeper.operator, keeper.score, slashed);
+
+    emit!(KeeperPenalized {
+        schema_version: EVENT_SCHEMA_VERSION,
+        keeper: keeper.key(),
+        operator: keeper.operator,
+        fault,
+        score: keeper.score,
+        slashed,
+    });
+
+    Ok(())
+}
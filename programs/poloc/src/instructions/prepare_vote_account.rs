@@ -0,0 +1,65 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::validation::require_in_window;
+
+#[derive(Accounts)]
+pub struct PrepareVoteAccount<'info> {
+    #[account(constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen)]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        seeds = [STAKE_SEED, challenge.load()?.challenge_id().as_bytes(), challenger.key().as_ref()],
+        bump = stake_account.bump,
+        constraint = stake_account.operator == operator.key() @ PolocError::Unauthorized,
+        constraint = !stake_account.slashed @ PolocError::StakeSlashed
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + Vote::MAX_SIZE,
+        seeds = [VOTE_SEED, challenge.load()?.challenge_id().as_bytes(), challenger.key().as_ref()],
+        bump
+    )]
+    pub vote_account: Account<'info, Vote>,
+
+    /// CHECK: Stake owner; only used to derive the stake and vote PDAs.
+    pub challenger: UncheckedAccount<'info>,
+
+    // The operator that will cast the vote pays its rent now.
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Creates an empty vote account ahead of the voting window, so the vote
+// itself only writes to it. A prepared account isn't a vote: it's skipped by
+// tallies until `submit_vote` fills it in. Sealed votes are created by
+// `submit_encrypted_vote` and can't be prepared.
+pub fn handler(ctx: Context<PrepareVoteAccount>) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &ctx.accounts.challenge.load()?;
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require!(challenge.encrypted_votes == 0, PolocError::VotesEncrypted);
+    require_in_window(
+        challenge_key,
+        Clock::get()?.unix_timestamp,
+        i64::MIN,
+        challenge.voting_ends_at(),
+        PolocError::VotingClosed,
+    )?;
+
+    let vote_account = &mut ctx.accounts.vote_account;
+    vote_account.challenger = ctx.accounts.challenger.key();
+    vote_account.challenge_id = challenge.challenge_id().to_string();
+    vote_account.prepared = true;
+    vote_account.bump = ctx.bumps.vote_account;
+
+    msg!("Vote account {} prepared for {} on challenge {}",
+         vote_account.key(), vote_account.challenger, challenge.challenge_id());
+    Ok(())
+}
@@ -0,0 +1,40 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ReclaimOracleReport<'info> {
+    #[account(
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [ORACLE_REPORT_SEED, challenge_id.as_bytes(), oracle.key().as_ref()],
+        bump = oracle_report.bump,
+        has_one = oracle @ PolocError::Unauthorized,
+        // Returns rent plus whatever bond was not forfeited.
+        close = oracle
+    )]
+    pub oracle_report: Account<'info, OracleReport>,
+
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<ReclaimOracleReport>, challenge_id: String) -> Result<()> {
+    require!(
+        !ctx.accounts.challenge.load()?.is_open(),
+        PolocError::ChallengeStillActive
+    );
+
+    let report = &ctx.accounts.oracle_report;
+    msg!("Oracle {} reclaimed report for challenge {} (penalized={})",
+         report.oracle, challenge_id, report.penalized);
+    Ok(())
+}
@@ -1,18 +1,36 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::constants::*;
 use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::claim_escrow::move_lamports;
+use crate::instructions::claim_bond::require_bond_settled;
+use crate::instructions::challenge_registry::delist_settled;
 
 #[derive(Accounts)]
 #[instruction(challenge_id: String)]
 pub struct RefundFailedChallenge<'info> {
     #[account(
         mut,
-        seeds = [b"challenge", challenge_id.as_bytes()],
-        bump = challenge.bump,
-        // Closing the account automatically sends the lamports (rent + remaining reward_pool) to waldo_account.
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch,
+        constraint = challenge.load()?.campaign == Pubkey::default() || challenge.load()?.campaign_reported != 0
+            @ PolocError::CampaignResultUnreported,
+        // Closing the account sends its rent to waldo_account.
         close = waldo_account
     )]
-    pub challenge: Account<'info, Challenge>,
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, challenge_id.as_bytes()],
+        bump = vault.bump,
+        // Whatever the vault still holds (pool, bond, rent) goes to waldo_account too.
+        close = waldo_account
+    )]
+    pub vault: Account<'info, ChallengeVault>,
 
     // The original creator of the challenge who gets the refund.
     #[account(mut)]
@@ -20,10 +38,52 @@ pub struct RefundFailedChallenge<'info> {
 
     // Only the waldo (original creator) should be able to trigger the refund.
     pub authority: Signer<'info>,
+
+    // Challenges settled as overdue never passed through finalize, so their
+    // concurrency slot is freed here.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Account<'info, WaldoProfile>,
+
+    /// CHECK: Must be `challenge.funder`; required when one paid the pool.
+    #[account(mut)]
+    pub funder: Option<UncheckedAccount<'info>>,
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to mark its entry closed.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+}
+
+// Refunds apply to Finalized-but-failed, InsufficientParticipants, Expired
+// and Inconclusive challenges whose pool has not been paid out. An
+// Inconclusive pool still holds its stakes until `return_stake` sends them back.
+pub fn require_refundable(challenge: &Challenge) -> Result<()> {
+    require!(
+        challenge.status() == ChallengeStatus::Finalized
+            || challenge.status() == ChallengeStatus::InsufficientParticipants
+            || challenge.status() == ChallengeStatus::Expired
+            || challenge.status() == ChallengeStatus::Inconclusive,
+        PolocError::ChallengeNotFinalized
+    );
+    require!(challenge.open_stakes == 0, PolocError::StakesOutstanding);
+    // Pool stakes are the challengers', not the creator's; `unstake` or
+    // `return_stake` sends each back first.
+    require!(challenge.pooled_stakes == 0, PolocError::StakesOutstanding);
+
+    require!(challenge.rewards_distributed == 0, PolocError::RewardsAlreadyDistributed);
+
+    // If Finalized, ensure it actually failed.
+    if challenge.status() == ChallengeStatus::Finalized {
+        require!(!challenge.passed(), PolocError::CannotRefundSuccessfulChallenge);
+    }
+    Ok(())
 }
 
 pub fn handler(ctx: Context<RefundFailedChallenge>) -> Result<()> {
-    let challenge = &ctx.accounts.challenge;
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
     let waldo_account = &ctx.accounts.waldo_account;
     let authority = &ctx.accounts.authority;
 
@@ -32,21 +92,36 @@ pub fn handler(ctx: Context<RefundFailedChallenge>) -> Result<()> {
     // Ensure waldo_account equals recorded waldo
     require_keys_eq!(waldo_account.key(), challenge.waldo, PolocError::Unauthorized);
 
-    // Accept either Finalized (but failed) OR InsufficientParticipants status.
-    require!(
-        challenge.status == ChallengeStatus::Finalized || challenge.status == ChallengeStatus::InsufficientParticipants,
-        PolocError::ChallengeNotFinalized
-    );
+    require_refundable(challenge)?;
+    require_bond_settled(challenge, Clock::get()?.unix_timestamp)?;
+    ctx.accounts.waldo_profile.release_active(challenge);
+    delist_settled(
+        ctx.accounts.challenge_registry.as_mut(),
+        ctx.accounts.registry_page.as_mut(),
+        challenge,
+        challenge_key,
+    )?;
 
-    require!(!challenge.rewards_distributed, PolocError::RewardsAlreadyDistributed);
+    // Sponsors are paid their pro-rata share first via `refund_sponsor`;
+    // whatever is left belongs to the creator, or to the program that funded it.
+    require!(challenge.open_contributions == 0, PolocError::ContributionsOutstanding);
 
-    // If Finalized, ensure it actually failed.
-    if challenge.status == ChallengeStatus::Finalized {
-        let passed = challenge.r_star <= challenge.r_star_threshold;
-        require!(!passed, PolocError::CannotRefundSuccessfulChallenge);
+    // A funder program's pool goes back to its vault; rent and bond stay with the creator.
+    if challenge.funder != Pubkey::default() {
+        let funder = ctx.accounts.funder.as_ref().ok_or(PolocError::UnknownFunder)?;
+        require_keys_eq!(funder.key(), challenge.funder, PolocError::UnknownFunder);
+        move_lamports(&ctx.accounts.vault.to_account_info(), &funder.to_account_info(), challenge.reward_pool)?;
+        msg!("Returned {} lamports to funder {}", challenge.reward_pool, challenge.funder);
     }
 
-    // Closing the challenge account (close = waldo_account) will automatically transfer lamports.
-    msg!("Challenge failed. Refunding remaining reward pool and closing account for challenge: {}", challenge.challenge_id);
+    // Closing the challenge and its vault (close = waldo_account) will automatically transfer lamports.
+    msg!("Challenge failed. Refunding remaining reward pool and closing account for challenge: {}", challenge.challenge_id());
+
+    emit!(ChallengeRefunded {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        waldo: waldo_account.key(),
+        amount: ctx.accounts.challenge.to_account_info().lamports() + ctx.accounts.vault.to_account_info().lamports(),
+    });
     Ok(())
 }
@@ -0,0 +1,88 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::refund_failed_challenge::require_refundable;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct RefundSponsor<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(
+        mut,
+        seeds = [CONTRIBUTION_SEED, challenge_id.as_bytes(), sponsor.key().as_ref()],
+        bump = contribution.bump,
+        has_one = sponsor,
+        close = sponsor
+    )]
+    pub contribution: Account<'info, Contribution>,
+
+    #[account(mut)]
+    pub sponsor: SystemAccount<'info>,
+}
+
+// Permissionless: the refund can only go to the recorded sponsor. The share is
+// the sponsor's fraction of everything still unrefunded, counting the
+// creator's funded tranches, so each refund leaves the others' ratios intact.
+pub fn handler(ctx: Context<RefundSponsor>, challenge_id: String) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    require_refundable(challenge)?;
+
+    let amount = ctx.accounts.contribution.amount;
+    let creator_funded = challenge.tranche_amount
+        .checked_mul(challenge.active_tranches as u64)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    let outstanding = creator_funded
+        .checked_add(challenge.sponsor_total)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    let share = (challenge.reward_pool as u128)
+        .checked_mul(amount as u128)
+        .and_then(|v| v.checked_div(outstanding as u128))
+        .ok_or(PolocError::ArithmeticOverflow)? as u64;
+
+    let vault_info = ctx.accounts.vault.to_account_info();
+    let sponsor_info = ctx.accounts.sponsor.to_account_info();
+    **vault_info.try_borrow_mut_lamports()? = vault_info
+        .lamports()
+        .checked_sub(share)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    **sponsor_info.try_borrow_mut_lamports()? = sponsor_info
+        .lamports()
+        .checked_add(share)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    challenge.reward_pool = challenge.reward_pool
+        .checked_sub(share)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    challenge.sponsor_total = challenge.sponsor_total
+        .checked_sub(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    challenge.open_contributions = challenge.open_contributions
+        .checked_sub(1)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    msg!("Refunded {} lamports to sponsor {} of challenge {} ({} contribution(s) left)",
+         share, ctx.accounts.sponsor.key(), challenge_id, challenge.open_contributions);
+
+    emit!(SponsorRefunded {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        sponsor: ctx.accounts.sponsor.key(),
+        amount: share,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,146 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::invoke_signed;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+
+/// Instruction data sent to a result callback after its 8-byte discriminator,
+/// `sha256("global:on_poloc_result")[..8]`, so Anchor consumers can take it
+/// as an `on_poloc_result(result: ChallengeResult)` instruction.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ChallengeResult {
+    pub challenge: Pubkey,
+    pub status: ChallengeStatus,
+    pub r_star: u32,
+    pub r_star_threshold: u32,
+    pub passed: bool,
+    pub context: [u8; 32],
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct RegisterCallback<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(address = challenge.load()?.waldo @ PolocError::Unauthorized)]
+    pub waldo: Signer<'info>,
+}
+
+// Replaces any earlier registration; `Pubkey::default()` clears it. The
+// challenge's own finalize then can't complete without the callback succeeding,
+// so only the creator, who signs `finalize_challenge`, may set one. Nothing
+// that holds the challenge's funds may be handed to the callback.
+pub fn handler(
+    ctx: Context<RegisterCallback>,
+    challenge_id: String,
+    program: Pubkey,
+    accounts: Vec<CallbackAccount>,
+) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require!(accounts.len() <= MAX_CALLBACK_ACCOUNTS, PolocError::InvalidParameters);
+    require!(
+        program != crate::ID && (program != Pubkey::default() || accounts.is_empty()),
+        PolocError::InvalidParameters
+    );
+    let vault = Pubkey::find_program_address(&[VAULT_SEED, challenge_id.as_bytes()], &crate::ID).0;
+    let funds = [vault, challenge.reward_vault, TOKEN_PROGRAM_ID, TOKEN_2022_PROGRAM_ID];
+    let forbidden = |key: &Pubkey| *key != Pubkey::default() && funds.contains(key);
+    require!(
+        !forbidden(&program) && !accounts.iter().any(|a| forbidden(&a.pubkey)),
+        PolocError::InvalidParameters
+    );
+
+    challenge.callback_program = program;
+    challenge.callback_accounts = [CallbackAccount::default(); MAX_CALLBACK_ACCOUNTS];
+    challenge.callback_accounts[..accounts.len()].copy_from_slice(&accounts);
+    challenge.callback_account_count = accounts.len() as u8;
+
+    msg!("Challenge {} result callback set to {}", challenge_id, program);
+
+    emit!(CallbackRegistered {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        program,
+        account_count: accounts.len() as u8,
+    });
+    Ok(())
+}
+
+/// Invokes the challenge's result callback, if one is registered.
+/// `remaining_accounts` must hold the callback program, the challenge's
+/// callback PDA (`["callback", challenge_id]`) and the registered accounts,
+/// in order. The challenge is passed read-only, and must not be borrowed by
+/// the caller, so the consumer can read the settled account; the callback
+/// PDA signs, so it can check the result came from this program. The
+/// challenge itself never signs, since it is the authority of a token pool's
+/// vault.
+pub(crate) fn invoke_result_callback<'info>(
+    challenge: &AccountLoader<'info, Challenge>,
+    remaining_accounts: &[AccountInfo<'info>],
+    passed: bool,
+) -> Result<()> {
+    let settled = challenge.load()?;
+    if settled.callback_program == Pubkey::default() {
+        return Ok(());
+    }
+    let registered = &settled.callback_accounts[..settled.callback_account_count as usize];
+    let [program, authority, accounts @ ..] = remaining_accounts else {
+        return err!(PolocError::CallbackAccountsMismatch);
+    };
+    let challenge_id = settled.challenge_id().to_string();
+    let (authority_key, authority_bump) =
+        Pubkey::find_program_address(&[CALLBACK_SEED, challenge_id.as_bytes()], &crate::ID);
+    require!(
+        program.key() == settled.callback_program
+            && authority.key() == authority_key
+            && accounts.len() == registered.len()
+            && accounts.iter().zip(registered).all(|(info, r)| info.key() == r.pubkey),
+        PolocError::CallbackAccountsMismatch
+    );
+
+    let mut metas = vec![
+        AccountMeta::new_readonly(challenge.key(), false),
+        AccountMeta::new_readonly(authority_key, true),
+    ];
+    metas.extend(registered.iter().map(|r| {
+        if r.is_writable != 0 {
+            AccountMeta::new(r.pubkey, false)
+        } else {
+            AccountMeta::new_readonly(r.pubkey, false)
+        }
+    }));
+    let mut data = hash(b"global:on_poloc_result").to_bytes()[..8].to_vec();
+    ChallengeResult {
+        challenge: challenge.key(),
+        status: settled.status(),
+        r_star: settled.r_star,
+        r_star_threshold: settled.r_star_threshold,
+        passed,
+        context: settled.context,
+    }
+    .serialize(&mut data)?;
+    let callback_program = settled.callback_program;
+    drop(settled);
+
+    let mut infos = vec![challenge.to_account_info(), authority.clone()];
+    infos.extend(accounts.iter().cloned());
+    let bump = [authority_bump];
+    let signer_seeds: &[&[u8]] = &[CALLBACK_SEED, challenge_id.as_bytes(), &bump];
+    invoke_signed(
+        &Instruction { program_id: callback_program, accounts: metas, data },
+        &infos,
+        &[signer_seeds],
+    )?;
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+pub struct RegisterKeeper<'info> {
+    #[account(
+        init,
+        payer = operator,
+        space = 8 + Keeper::MAX_SIZE,
+        seeds = [KEEPER_SEED, operator.key().as_ref()],
+        bump
+    )]
+    pub keeper: Account<'info, Keeper>,
+
+    #[account(mut)]
+    pub operator: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<RegisterKeeper>, amount: u64) -> Result<()> {
+    require!(amount >= Keeper::MIN_STAKE, PolocError::InsufficientStake);
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.operator.to_account_info(),
+                to: ctx.accounts.keeper.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    let keeper = &mut ctx.accounts.keeper;
+    keeper.operator = ctx.accounts.operator.key();
+    keeper.stake = amount;
+    keeper.score = Keeper::INITIAL_SCORE;
+    keeper.successful_cranks = 0;
+    keeper.faults = 0;
+    keeper.last_active = Clock::get()?.unix_timestamp;
+    keeper.bump = ctx.bumps.keeper;
+
+    msg!("Keeper {} registered with {} lamports staked", keeper.operator, amount);
+    Ok(())
+}
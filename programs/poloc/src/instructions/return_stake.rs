@@ -0,0 +1,79 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::claim_escrow::move_lamports;
+use crate::instructions::unstake::{load_cast_vote, release_pooled_stake, require_stakes_released, settle_streak};
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, challenger_pubkey: Pubkey)]
+pub struct ReturnStake<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    // Closed to the challenger along with the stake it records.
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()],
+        bump = stake_account.bump,
+        constraint = !stake_account.standing @ PolocError::InvalidParameters,
+        constraint = !stake_account.void @ PolocError::VoidStake,
+        constraint = !stake_account.receipt_outstanding @ PolocError::ReceiptOutstanding,
+        close = challenger
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    #[account(mut, address = stake_account.challenger @ PolocError::Unauthorized)]
+    pub challenger: SystemAccount<'info>,
+
+    /// CHECK: The stake's vote PDA; may be uninitialized.
+    #[account(seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()], bump)]
+    pub vote_account: UncheckedAccount<'info>,
+
+    // Loses its streak if the stake's vote went unrewarded.
+    #[account(mut, seeds = [CHALLENGER_SEED, challenger_pubkey.as_ref()], bump = challenger_profile.bump)]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+}
+
+// Permissionless, since the stake can only go back to its challenger. Slashes
+// need a Finalized challenge, so an Inconclusive stake is always whole.
+// Stakes of a failed challenge, less any slash, come back here too, so its
+// creator can clear them ahead of `refund_failed_challenge`.
+// Standing stakes never left their account; `release_standing_stake` unlocks them.
+pub fn handler(ctx: Context<ReturnStake>, challenge_id: String, challenger_pubkey: Pubkey) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    if challenge.status() == ChallengeStatus::Inconclusive {
+        challenge.open_stakes = challenge.open_stakes
+            .checked_sub(1)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+    } else {
+        require_stakes_released(challenge, Clock::get()?.unix_timestamp)?;
+    }
+
+    let amount = ctx.accounts.stake_account.unforfeited();
+    move_lamports(&ctx.accounts.vault.to_account_info(), &ctx.accounts.challenger.to_account_info(), amount)?;
+    release_pooled_stake(challenge, &ctx.accounts.stake_account, amount)?;
+    let vote = load_cast_vote(&ctx.accounts.vote_account.to_account_info())?;
+    settle_streak(challenge, vote.as_ref(), &mut ctx.accounts.challenger_profile);
+
+    msg!("Returned stake of {} lamports to {} for challenge {} ({} left)",
+         amount, challenger_pubkey, challenge_id, challenge.pooled_stakes);
+    emit!(StakeReturned {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenger: challenger_pubkey,
+        amount,
+    });
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::validation::require_in_window;
+use crate::events::*;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct RevealLocation<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(address = challenge.load()?.waldo @ PolocError::Unauthorized)]
+    pub waldo: Signer<'info>,
+}
+
+// Ends the stake commit window of a cloaked challenge: witnesses staked
+// without knowing where it is, and from here on the set of stakes is fixed.
+// Must happen before the deadline so voters can measure against it.
+pub fn handler(
+    ctx: Context<RevealLocation>,
+    challenge_id: String,
+    claimed_lat: i32,
+    claimed_lon: i32,
+    location_salt: [u8; 32],
+) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    let clock = Clock::get()?;
+
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require_in_window(challenge_key, clock.unix_timestamp, i64::MIN, challenge.deadline, PolocError::ChallengeExpired)?;
+    require!(challenge.is_cloaked() && challenge.location_revealed == 0, PolocError::InvalidParameters);
+    require!(claimed_lat.unsigned_abs() <= 90_000_000, PolocError::InvalidParameters);
+    require!(claimed_lon.unsigned_abs() <= 180_000_000, PolocError::InvalidParameters);
+    ctx.accounts.config.check_not_excluded(claimed_lat, claimed_lon)?;
+    require!(
+        Challenge::commit_location(&challenge_id, claimed_lat, claimed_lon, &location_salt)
+            == challenge.location_commitment,
+        PolocError::LocationCommitmentMismatch
+    );
+
+    challenge.claimed_lat = claimed_lat;
+    challenge.claimed_lon = claimed_lon;
+    challenge.location_revealed = 1;
+
+    msg!("Challenge {} location revealed; {} stake(s) locked in",
+         challenge_id, challenge.participant_count);
+
+    emit!(LocationRevealed {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        claimed_lat,
+        claimed_lon,
+        participant_count: challenge.participant_count,
+    });
+    Ok(())
+}
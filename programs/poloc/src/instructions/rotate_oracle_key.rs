@@ -0,0 +1,32 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+pub struct RotateOracleKey<'info> {
+    #[account(mut, seeds = [ORACLE_SET_SEED], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+
+    pub oracle: Signer<'info>,
+}
+
+// Moves an oracle's payload signing to `new_key` for challenges that start
+// from now on. Challenges already started keep verifying against the key
+// being replaced, so only one rotation per challenge lifetime is covered.
+pub fn handler(ctx: Context<RotateOracleKey>, new_key: Pubkey) -> Result<()> {
+    require!(new_key != Pubkey::default(), PolocError::InvalidParameters);
+    let oracle_key = ctx.accounts.oracle.key();
+    let entry = ctx.accounts.oracle_set
+        .entry_mut(&oracle_key)
+        .ok_or(PolocError::UnknownOracle)?;
+    require!(new_key != entry.signing_key, PolocError::InvalidParameters);
+
+    let now = Clock::get()?.unix_timestamp;
+    entry.previous_key = entry.signing_key;
+    entry.signing_key = new_key;
+    entry.key_activated_at = now;
+
+    msg!("Oracle {} rotated its signing key to {} at {}", oracle_key, new_key, now);
+    Ok(())
+}
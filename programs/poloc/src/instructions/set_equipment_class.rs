@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+#[instruction(wallet: Pubkey)]
+pub struct SetEquipmentClass<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + ChallengerProfile::MAX_SIZE,
+        seeds = [CHALLENGER_SEED, wallet.as_ref()],
+        bump
+    )]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn handler(ctx: Context<SetEquipmentClass>, wallet: Pubkey, equipment_class: u8) -> Result<()> {
+    require!(
+        equipment_class <= ChallengerProfile::EQUIPMENT_FIXED_FIBER_PROBE,
+        PolocError::InvalidParameters
+    );
+
+    let profile = &mut ctx.accounts.challenger_profile;
+    if profile.wallet == Pubkey::default() {
+        profile.wallet = wallet;
+        profile.bump = ctx.bumps.challenger_profile;
+    }
+    profile.equipment_class = equipment_class;
+
+    msg!("Witness {} equipment class set to {}", wallet, equipment_class);
+    Ok(())
+}
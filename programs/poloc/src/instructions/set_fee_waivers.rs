@@ -0,0 +1,53 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+pub struct SetFeeWaivers<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + FeeWaiverList::MAX_SIZE,
+        seeds = [FEE_WAIVER_SEED],
+        bump
+    )]
+    pub fee_waivers: Account<'info, FeeWaiverList>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Replaces the whole list. A default `credential` turns the credential gate
+// off; an empty list with no credential waives nothing.
+pub fn handler(
+    ctx: Context<SetFeeWaivers>,
+    waldos: Vec<Pubkey>,
+    credential: Pubkey,
+    schema: Pubkey,
+) -> Result<()> {
+    require!(waldos.len() <= FeeWaiverList::MAX_WALDOS, PolocError::InvalidParameters);
+    for (i, waldo) in waldos.iter().enumerate() {
+        require!(!waldos[..i].contains(waldo), PolocError::InvalidParameters);
+    }
+    if credential != Pubkey::default() {
+        require!(
+            ctx.accounts.config.attestation_program != Pubkey::default() && schema != Pubkey::default(),
+            PolocError::InvalidParameters
+        );
+    }
+
+    let list = &mut ctx.accounts.fee_waivers;
+    list.waldos = waldos;
+    list.credential = credential;
+    list.schema = schema;
+    list.bump = ctx.bumps.fee_waivers;
+
+    msg!("Fee waivers set: {} creator(s), credential-gated: {}", list.waldos.len(), list.is_credential_gated());
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::instructions::initialize_oracle_set::apply_oracles;
+
+#[derive(Accounts)]
+pub struct SetOracles<'info> {
+    #[account(mut, seeds = [ORACLE_SET_SEED], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<SetOracles>, oracles: Vec<Pubkey>) -> Result<()> {
+    let oracle_set = &mut ctx.accounts.oracle_set;
+    apply_oracles(oracle_set, oracles)?;
+
+    msg!("Oracle set updated: {} oracle(s)", oracle_set.oracles.len());
+    Ok(())
+}
+
+// Once set, `finalize_with_oracles` waits for `quorum` reports and the
+// creator-signed `finalize_challenge` is closed. 0 lifts both.
+pub fn quorum_handler(ctx: Context<SetOracles>, quorum: u8) -> Result<()> {
+    let oracle_set = &mut ctx.accounts.oracle_set;
+    require!(quorum as usize <= oracle_set.oracles.len(), PolocError::OracleQuorumUnreachable);
+    oracle_set.quorum = quorum;
+
+    msg!("Oracle quorum set to {} of {}", quorum, oracle_set.oracles.len());
+    Ok(())
+}
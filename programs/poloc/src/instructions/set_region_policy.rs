@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+#[instruction(cell_lat: i16, cell_lon: i16)]
+pub struct SetRegionPolicy<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + RegionPolicy::MAX_SIZE,
+        seeds = [REGION_SEED, &cell_lat.to_le_bytes(), &cell_lon.to_le_bytes()],
+        bump
+    )]
+    pub region_policy: Account<'info, RegionPolicy>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Zero for either limit falls back to the global Config value.
+pub fn handler(
+    ctx: Context<SetRegionPolicy>,
+    cell_lat: i16,
+    cell_lon: i16,
+    min_stake: u64,
+    min_participants: u32,
+) -> Result<()> {
+    require!((-90..90).contains(&cell_lat), PolocError::InvalidParameters);
+    require!((-180..180).contains(&cell_lon), PolocError::InvalidParameters);
+
+    let policy = &mut ctx.accounts.region_policy;
+    policy.cell_lat = cell_lat;
+    policy.cell_lon = cell_lon;
+    policy.min_stake = min_stake;
+    policy.min_participants = min_participants;
+    policy.bump = ctx.bumps.region_policy;
+
+    msg!("Region ({}, {}) policy: min_stake={}, min_participants={}",
+         cell_lat, cell_lon, min_stake, min_participants);
+    Ok(())
+}
@@ -0,0 +1,52 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+#[derive(Accounts)]
+pub struct SetVoteCommittee<'info> {
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + VoteCommittee::MAX_SIZE,
+        seeds = [VOTE_COMMITTEE_SEED],
+        bump
+    )]
+    pub vote_committee: Account<'info, VoteCommittee>,
+
+    #[account(mut, seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Shares are tracked by member index, so rotate the committee only while no
+// encrypted challenge is waiting on a tally.
+pub fn handler(
+    ctx: Context<SetVoteCommittee>,
+    encryption_key: [u8; 32],
+    members: Vec<Pubkey>,
+    threshold: u8,
+) -> Result<()> {
+    require!(encryption_key != [0u8; 32], PolocError::InvalidParameters);
+    require!(
+        !members.is_empty() && members.len() <= VoteCommittee::MAX_MEMBERS,
+        PolocError::InvalidParameters
+    );
+    require!(threshold >= 1 && threshold as usize <= members.len(), PolocError::InvalidParameters);
+    for (i, member) in members.iter().enumerate() {
+        require!(!members[..i].contains(member), PolocError::InvalidParameters);
+    }
+
+    let committee = &mut ctx.accounts.vote_committee;
+    committee.members = members;
+    committee.threshold = threshold;
+    committee.bump = ctx.bumps.vote_committee;
+    ctx.accounts.config.vote_committee_key = encryption_key;
+
+    msg!("Vote committee set: {}-of-{}", threshold, committee.members.len());
+    Ok(())
+}
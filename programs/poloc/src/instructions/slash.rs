@@ -1,29 +1,68 @@
 use anchor_lang::prelude::*;
 use crate::state::*;
+use crate::constants::*;
 use crate::errors::PolocError;
+use crate::events::*;
+use poloc_core::slashing::{rebase_penalty, slash_amount};
 
 #[derive(Accounts)]
 #[instruction(challenge_id: String, challenger_pubkey: Pubkey)]
 pub struct Slash<'info> {
     #[account(
         mut, // The challenge account must be mutable to receive accounting updates.
-        seeds = [b"challenge", challenge_id.as_bytes()],
-        bump = challenge.bump
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
     )]
-    pub challenge: Account<'info, Challenge>,
+    pub challenge: AccountLoader<'info, Challenge>,
 
     #[account(
         mut,
         // Use the same stake PDA derivation as in `stake.rs`.
-        seeds = [b"stake", challenge_id.as_bytes(), challenger_pubkey.as_ref()],
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()],
         bump = stake_account.bump,
-        // Do not close here: stake lamports are stored in the challenge PDA.
+        // Do not close here: stake lamports are stored in the challenge vault.
     )]
     pub stake_account: Account<'info, Stake>,
 
-    // This authority MUST be the original creator of the challenge ('waldo').
-    #[account(address = challenge.waldo @ PolocError::Unauthorized)]
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + ChallengerProfile::MAX_SIZE,
+        seeds = [CHALLENGER_SEED, challenger_pubkey.as_ref()],
+        bump
+    )]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    // Verified grounds for this slash, recorded by `submit_evidence`.
+    #[account(
+        mut,
+        seeds = [EVIDENCE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()],
+        bump = evidence.bump,
+        constraint = !evidence.consumed @ PolocError::EvidenceConsumed
+    )]
+    pub evidence: Account<'info, Evidence>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    // This authority MUST be the challenge's arbiter (the creator unless one was appointed).
+    #[account(mut, address = challenge.load()?.arbiter @ PolocError::Unauthorized)]
     pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Marks `forfeited` of the stake as slashed. The forfeited share of a pool
+// stake joins the reward split; the rest still goes back through `unstake`.
+pub fn forfeit_stake(challenge: &mut Challenge, stake: &mut Stake, forfeited: u64) {
+    stake.slashed = true;
+    stake.slashed_amount = forfeited;
+    challenge.slashed_total = challenge.slashed_total.saturating_add(forfeited);
+    challenge.slash_count = challenge.slash_count.saturating_add(1);
+    if stake.pooled {
+        challenge.stake_principal = challenge.stake_principal.saturating_sub(forfeited);
+    }
 }
 
 pub fn handler(
@@ -31,27 +70,53 @@ pub fn handler(
     _challenge_id: String,
     challenger_pubkey: Pubkey,
 ) -> Result<()> {
-    let challenge = &mut ctx.accounts.challenge;
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
     let stake_account = &mut ctx.accounts.stake_account;
 
     // 1. Ensure the challenge is in a state where slashing is allowed.
-    require!(challenge.status == ChallengeStatus::Finalized, PolocError::ChallengeNotFinalized);
+    require!(challenge.status() == ChallengeStatus::Finalized, PolocError::ChallengeNotFinalized);
 
     // Prevent double-slash
     require!(!stake_account.slashed, PolocError::AlreadySlashed);
 
-    // Mark the stake as slashed. The actual lamports were moved into the challenge PDA during `stake`.
-    stake_account.slashed = true;
+    // Mark the stake as slashed. The actual lamports were moved into the challenge vault during `stake`.
+    // A wrong vote forfeits a share scaled by its claimed confidence; other grounds forfeit it all.
+    // A stake that snapshotted a USD rate loses the same dollars whatever the price did since.
+    let forfeited = rebase_penalty(
+        slash_amount(stake_account.amount, ctx.accounts.evidence.confidence),
+        stake_account.usd_rate,
+        ctx.accounts.config.usd_rate,
+        stake_account.amount,
+    );
+    forfeit_stake(challenge, stake_account, forfeited);
+    ctx.accounts.evidence.consumed = true;
 
-    // If you did not already add stake to reward_pool at stake time, you would add it here.
-    // Our flow adds it during stake(), so we do not add it here to avoid double-counting.
+    // Start (or extend) the wallet's cooldown on new stakes.
+    let now = Clock::get()?.unix_timestamp;
+    let profile = &mut ctx.accounts.challenger_profile;
+    if profile.wallet == Pubkey::default() {
+        profile.wallet = challenger_pubkey;
+        profile.bump = ctx.bumps.challenger_profile;
+    }
+    profile.record_slash();
+    profile.cooldown_until = profile.cooldown_until.max(now + ctx.accounts.config.slash_cooldown);
 
     msg!(
-        "Challenger {} slashed. Stake of {} lamports forfeited (account marked slashed).",
+        "Challenger {} slashed for {:?}. {} of {} staked lamports forfeited (account marked slashed).",
         challenger_pubkey,
+        ctx.accounts.evidence.reason,
+        stake_account.slashed_amount,
         stake_account.amount
     );
     msg!("Reward pool currently: {}", challenge.reward_pool);
 
+    emit!(StakeSlashed {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenger: challenger_pubkey,
+        amount: stake_account.slashed_amount,
+    });
+
     Ok(())
 }
@@ -1,78 +1,287 @@
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
 use crate::state::*;
+use crate::constants::*;
 use crate::errors::*;
+use crate::events::*;
+use crate::instructions::finalize::{settle_if_overdue, SettlementAccounts};
+use crate::instructions::challenge_registry::track_tvl;
+use crate::validation::{check_credential, require_in_window, resolve_region_limits};
 
 #[derive(Accounts)]
-#[instruction(challenge_id: String)]
 pub struct StakeCtx<'info> {
+    // Any challenge account of this program; on the hot path its PDA isn't
+    // re-derived, and the other seeds come from its stored id.
     #[account(
         mut,
-        seeds = [b"challenge", challenge_id.as_bytes()],
-        bump = challenge.bump
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
     )]
-    pub challenge: Account<'info, Challenge>,
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge.load()?.challenge_id().as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
     
     #[account(
         init,
         payer = challenger,
         space = 8 + Stake::MAX_SIZE,
-        seeds = [b"stake", challenge_id.as_bytes(), challenger.key().as_ref()],
+        seeds = [STAKE_SEED, challenge.load()?.challenge_id().as_bytes(), challenger.key().as_ref()],
         bump
     )]
     pub stake_account: Account<'info, Stake>,
     
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: RegionPolicy PDA for the challenge's cell; may be uninitialized.
+    /// Validated by `resolve_region_limits`.
+    pub region_policy: UncheckedAccount<'info>,
+
+    /// CHECK: RegionStats PDA for the same cell; may be uninitialized.
+    /// Validated by `record_region_stats` when an overdue challenge settles here.
+    #[account(mut)]
+    pub region_stats: UncheckedAccount<'info>,
+
+    /// CHECK: Must stay empty; an initialized account here means the
+    /// challenger is flagged for collusion.
+    #[account(
+        seeds = [COLLUSION_SEED, challenger.key().as_ref()],
+        bump,
+        constraint = collusion_flag.data_is_empty() @ PolocError::WalletFlagged
+    )]
+    pub collusion_flag: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = challenger,
+        space = 8 + ChallengerProfile::MAX_SIZE,
+        seeds = [CHALLENGER_SEED, challenger.key().as_ref()],
+        bump
+    )]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    /// CHECK: Challenger's attestation, required when the challenge sets a
+    /// credential. Validated by `check_credential`.
+    pub credential: Option<UncheckedAccount<'info>>,
+
     #[account(mut)]
     pub challenger: Signer<'info>,
     
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to keep its TVL current, or close its entry if an overdue challenge
+    // settles here.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+
     pub system_program: Program<'info, System>,
+
+    // Optional: the creator's profile, to free the slot of an overdue
+    // challenge settled here; without it such a challenge is refused.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Option<Account<'info, WaldoProfile>>,
 }
 
 pub fn handler(
     ctx: Context<StakeCtx>,
-    challenge_id: String,
     amount: u64,
 ) -> Result<()> {
     let stake_account = &mut ctx.accounts.stake_account;
     let clock = Clock::get()?;
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    let challenge_id = challenge.challenge_id().to_string();
 
     // Validate challenge is active
-    require!(ctx.accounts.challenge.status == ChallengeStatus::Active, PolocError::ChallengeNotActive);
-    require!(clock.unix_timestamp <= ctx.accounts.challenge.deadline, PolocError::ChallengeExpired);
-
-    // Validate stake amount (minimum 0.001 SOL)
-    require!(amount >= 1_000_000, PolocError::InsufficientStake);
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    let (min_stake, min_participants) = resolve_region_limits(
+        &ctx.accounts.region_policy.to_account_info(),
+        challenge,
+        &ctx.accounts.config,
+    )?;
+    trace!("Stake {} (bump {}) on {}: min stake {}, min participants {}, cap {}",
+           stake_account.key(), ctx.bumps.stake_account, challenge_id,
+           min_stake, min_participants, ctx.accounts.config.max_participants);
+    let region_stats = ctx.accounts.region_stats.to_account_info();
+    let settlement = SettlementAccounts {
+        waldo_profile: ctx.accounts.waldo_profile.as_mut(),
+        challenge_registry: ctx.accounts.challenge_registry.as_mut(),
+        registry_page: ctx.accounts.registry_page.as_mut(),
+    };
+    let limits = Some((min_participants, &region_stats));
+    if settle_if_overdue(challenge, challenge_key, clock.unix_timestamp, limits, settlement)? {
+        // The stake account was already created; leave it as a void record,
+        // which holds nothing but rent for `close_stake` to refund.
+        stake_account.challenger = ctx.accounts.challenger.key();
+        stake_account.challenge_id = challenge_id;
+        stake_account.operator = ctx.accounts.challenger.key();
+        stake_account.bump = ctx.bumps.stake_account;
+        stake_account.void = true;
+        return Ok(());
+    }
 
-    // Check maximum participants (20 max)
-    require!(ctx.accounts.challenge.participant_count < 20, PolocError::MaxParticipantsReached);
+    let profile = &mut ctx.accounts.challenger_profile;
+    if profile.wallet == Pubkey::default() {
+        profile.wallet = ctx.accounts.challenger.key();
+        profile.bump = ctx.bumps.challenger_profile;
+    }
+    check_stake_allowed(
+        challenge,
+        challenge_key,
+        profile,
+        amount,
+        min_stake,
+        &ctx.accounts.config,
+        clock.unix_timestamp,
+    )?;
+    check_credential(
+        ctx.accounts.credential.as_ref().map(|c| c.as_ref()),
+        challenge,
+        &ctx.accounts.config,
+        &ctx.accounts.challenger.key(),
+        clock.unix_timestamp,
+    )?;
+    profile.record_participation();
 
-    // Transfer stake amount to challenge PDA
+    // Transfer stake amount to the challenge vault
     system_program::transfer(
         CpiContext::new(
             ctx.accounts.system_program.to_account_info(),
             system_program::Transfer {
                 from: ctx.accounts.challenger.to_account_info(),
-                to: ctx.accounts.challenge.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
             }
         ),
         amount,
     )?;
 
-    // Now take a mutable reference after the transfer
-    let challenge = &mut ctx.accounts.challenge;
+    record_stake(
+        challenge,
+        challenge_key,
+        stake_account,
+        ctx.accounts.challenger.key(),
+        challenge_id,
+        amount,
+        clock.unix_timestamp,
+        ctx.bumps.stake_account,
+    )?;
+    stake_account.usd_rate = ctx.accounts.config.stake_usd_rate(challenge);
+    track_tvl(ctx.accounts.challenge_registry.as_mut(), ctx.accounts.registry_page.as_mut(), challenge, challenge_key)?;
+    Ok(())
+}
+
+// Checks shared by every path that opens a stake.
+pub(crate) fn check_stake_allowed(
+    challenge: &Challenge,
+    challenge_key: Pubkey,
+    profile: &ChallengerProfile,
+    amount: u64,
+    min_stake: u64,
+    config: &Config,
+    now: i64,
+) -> Result<()> {
+    require_in_window(challenge_key, now, i64::MIN, challenge.deadline, PolocError::ChallengeExpired)?;
+    require_in_window(challenge_key, now, i64::MIN, challenge.staking_ends_at(), PolocError::StakeWindowClosed)?;
 
-    // --- Ensure on-chain accounting matches actual lamports in the PDA ---
+    // Cloaked challenges only take stakes while the location is still hidden
+    require!(challenge.location_revealed == 0, PolocError::StakeWindowClosed);
+
+    require!(
+        profile.equipment_class >= challenge.min_equipment_class,
+        PolocError::EquipmentClassTooLow
+    );
+
+    // Recently slashed wallets sit out until their cooldown ends, and repeat
+    // offenders until correct votes elsewhere earn their reputation back
+    require!(now >= profile.cooldown_until, PolocError::SlashCooldownActive);
+    require!(profile.score >= challenge.min_reputation, PolocError::ReputationTooLow);
+
+    // Validate stake amount against the resolved (regional or global) minimum,
+    // and lamport stakes against the USD floor at the current rate
+    let usd_floor = if challenge.is_token_pool() { 0 } else { config.usd_min_stake() };
+    require!(amount >= min_stake.max(usd_floor), PolocError::InsufficientStake);
+
+    // Check maximum participants
+    require!(challenge.participant_count < config.max_participants, PolocError::MaxParticipantsReached);
+    // Token stakes are counted in the mint's units, which the cap doesn't cover.
+    if !challenge.is_token_pool() {
+        config.check_pool_cap(challenge.committed_tvl().saturating_add(amount))?;
+    }
+    Ok(())
+}
+
+// Bookkeeping once `amount` lamports, or tokens of a token pool, have landed
+// in the challenge's vault.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn record_stake(
+    challenge: &mut Challenge,
+    challenge_key: Pubkey,
+    stake_account: &mut Account<Stake>,
+    challenger: Pubkey,
+    challenge_id: String,
+    amount: u64,
+    now: i64,
+    bump: u8,
+) -> Result<()> {
+    open_stake(challenge, stake_account, challenger, challenge_id, amount, now, bump)?;
+    pool_stake(challenge, stake_account, amount)?;
+
+    emit!(Staked {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenger,
+        amount,
+    });
+
+    Ok(())
+}
+
+// Adds a stake held in the challenge's vault to its pool, and counts it
+// among the stakes `require_refundable` waits for.
+pub fn pool_stake(challenge: &mut Challenge, stake_account: &mut Stake, amount: u64) -> Result<()> {
+    // --- Ensure on-chain accounting matches actual lamports in the vault ---
     challenge.reward_pool = challenge.reward_pool
         .checked_add(amount)
         .ok_or(PolocError::ArithmeticOverflow)?;
+    challenge.pooled_stakes = challenge.pooled_stakes
+        .checked_add(1)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    challenge.stake_principal = challenge.stake_principal
+        .checked_add(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    stake_account.pooled = true;
+    Ok(())
+}
+
+// Records a stake of `amount` and counts it towards the challenge, wherever
+// its lamports are held.
+pub(crate) fn open_stake(
+    challenge: &mut Challenge,
+    stake_account: &mut Account<Stake>,
+    challenger: Pubkey,
+    challenge_id: String,
+    amount: u64,
+    now: i64,
+    bump: u8,
+) -> Result<()> {
+    challenge.activate_if_started(now);
 
     // Initialize stake account
-    stake_account.challenger = ctx.accounts.challenger.key();
+    stake_account.challenger = challenger;
     stake_account.challenge_id = challenge_id.clone();
     stake_account.amount = amount;
-    stake_account.timestamp = clock.unix_timestamp;
+    stake_account.timestamp = now;
     stake_account.slashed = false;
-    stake_account.bump = ctx.bumps.stake_account;
+    stake_account.slashed_amount = 0;
+    stake_account.bump = bump;
+    stake_account.operator = challenger;
+
+    challenge.total_staked = challenge.total_staked
+        .checked_add(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
 
     // Update challenge participant count
     challenge.participant_count = challenge.participant_count
@@ -80,7 +289,7 @@ pub fn handler(
         .ok_or(PolocError::ArithmeticOverflow)?;
 
     msg!("Challenger {} staked {} lamports for challenge {}", 
-         ctx.accounts.challenger.key(), amount, challenge_id);
+         challenger, amount, challenge_id);
 
     Ok(())
 }
@@ -0,0 +1,243 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+
+// SPL Token instruction discriminants.
+const MINT_TO: u8 = 7;
+const BURN: u8 = 8;
+const INITIALIZE_MINT_2: u8 = 20;
+
+const MINT_LEN: usize = 82;
+// Receipts are minted 1:1 with staked lamports.
+const RECEIPT_DECIMALS: u8 = 9;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct InitializeReceiptMint<'info> {
+    #[account(
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    /// CHECK: Created here as an SPL Token mint.
+    #[account(mut, seeds = [RECEIPT_MINT_SEED, challenge_id.as_bytes()], bump)]
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    #[account(mut, address = challenge.load()?.waldo @ PolocError::Unauthorized)]
+    pub waldo: Signer<'info>,
+
+    /// CHECK: Address-constrained to SPL Token.
+    #[account(address = TOKEN_PROGRAM_ID)]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct MintStakeReceipt<'info> {
+    #[account(
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    // Standing stakes never entered the pool, so there is no position to represent.
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump = stake_account.bump,
+        constraint = !stake_account.standing @ PolocError::InvalidParameters,
+        constraint = !stake_account.slashed @ PolocError::StakeSlashed,
+        constraint = !stake_account.receipt_outstanding @ PolocError::ReceiptOutstanding
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    /// CHECK: Must stay empty; a vote copies the receipt flag when it's cast.
+    #[account(
+        seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump,
+        constraint = vote_account.data_is_empty() @ PolocError::ReceiptAfterVote
+    )]
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: The challenge's receipt mint; signs `MintTo` as its own authority.
+    #[account(mut, seeds = [RECEIPT_MINT_SEED, challenge_id.as_bytes()], bump, owner = TOKEN_PROGRAM_ID)]
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Any SPL Token account for the receipt mint; parsed in the handler.
+    #[account(mut, owner = TOKEN_PROGRAM_ID @ PolocError::InvalidTokenAccount)]
+    pub receipt_account: UncheckedAccount<'info>,
+
+    pub challenger: Signer<'info>,
+
+    /// CHECK: Address-constrained to SPL Token.
+    #[account(address = TOKEN_PROGRAM_ID)]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, challenger_pubkey: Pubkey)]
+pub struct RedeemStakeReceipt<'info> {
+    #[account(seeds = [CHALLENGE_SEED, challenge_id.as_bytes()], bump = challenge.load()?.bump)]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()],
+        bump = stake_account.bump,
+        constraint = stake_account.receipt_outstanding @ PolocError::NoReceiptOutstanding
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    /// CHECK: The stake's vote PDA; may be uninitialized. Its receipt flag is
+    /// cleared in the handler when it exists.
+    #[account(mut, seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()], bump)]
+    pub vote_account: UncheckedAccount<'info>,
+
+    /// CHECK: The challenge's receipt mint.
+    #[account(mut, seeds = [RECEIPT_MINT_SEED, challenge_id.as_bytes()], bump, owner = TOKEN_PROGRAM_ID)]
+    pub receipt_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Burned from by `holder`; SPL Token checks mint and authority.
+    #[account(mut, owner = TOKEN_PROGRAM_ID @ PolocError::InvalidTokenAccount)]
+    pub receipt_account: UncheckedAccount<'info>,
+
+    pub holder: Signer<'info>,
+
+    /// CHECK: Address-constrained to SPL Token.
+    #[account(address = TOKEN_PROGRAM_ID)]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+// Opts a challenge into stake receipts. The mint is a PDA of the challenge
+// id and its own mint authority, so only `mint_stake_receipt` can issue them.
+pub fn initialize_mint_handler(ctx: Context<InitializeReceiptMint>, challenge_id: String) -> Result<()> {
+    require!(ctx.accounts.challenge.load()?.is_open(), PolocError::ChallengeNotActive);
+    let mint = ctx.accounts.receipt_mint.to_account_info();
+    let token_program = ctx.accounts.token_program.key();
+    let seeds: &[&[u8]] = &[RECEIPT_MINT_SEED, challenge_id.as_bytes(), &[ctx.bumps.receipt_mint]];
+
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::CreateAccount { from: ctx.accounts.waldo.to_account_info(), to: mint.clone() },
+            &[seeds],
+        ),
+        Rent::get()?.minimum_balance(MINT_LEN),
+        MINT_LEN as u64,
+        &token_program,
+    )?;
+
+    let mut data = vec![INITIALIZE_MINT_2, RECEIPT_DECIMALS];
+    data.extend_from_slice(mint.key().as_ref());
+    data.push(0); // no freeze authority
+    invoke(
+        &Instruction { program_id: token_program, accounts: vec![AccountMeta::new(mint.key(), false)], data },
+        std::slice::from_ref(&mint),
+    )?;
+
+    msg!("Stake receipt mint {} initialized for challenge {}", mint.key(), challenge_id);
+    Ok(())
+}
+
+// Mints the stake's amount in receipts, to any account the challenger names.
+// Until they are burned again the stake can't be withdrawn and its vote's
+// reward can't be claimed, so whoever holds them holds the position.
+pub fn mint_handler(ctx: Context<MintStakeReceipt>, challenge_id: String) -> Result<()> {
+    require!(ctx.accounts.challenge.load()?.is_open(), PolocError::ChallengeNotActive);
+    let mint = ctx.accounts.receipt_mint.to_account_info();
+    let receipt_account = ctx.accounts.receipt_account.to_account_info();
+    {
+        let data = receipt_account.try_borrow_data()?;
+        require!(data.len() >= 32, PolocError::InvalidTokenAccount);
+        require!(data[0..32] == mint.key().to_bytes(), PolocError::InvalidTokenAccount);
+    }
+
+    let stake_account = &mut ctx.accounts.stake_account;
+    let amount = stake_account.amount;
+    let mut data = vec![MINT_TO];
+    data.extend_from_slice(&amount.to_le_bytes());
+    invoke_signed(
+        &Instruction {
+            program_id: ctx.accounts.token_program.key(),
+            accounts: vec![
+                AccountMeta::new(mint.key(), false),
+                AccountMeta::new(receipt_account.key(), false),
+                AccountMeta::new_readonly(mint.key(), true),
+            ],
+            data,
+        },
+        &[mint.clone(), receipt_account.clone()],
+        &[&[RECEIPT_MINT_SEED, challenge_id.as_bytes(), &[ctx.bumps.receipt_mint]]],
+    )?;
+    stake_account.receipt_outstanding = true;
+
+    msg!("Minted {} receipt tokens for {}'s stake in challenge {}",
+         amount, stake_account.challenger, challenge_id);
+    emit!(StakeReceiptMinted {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: ctx.accounts.challenge.key(),
+        challenger: stake_account.challenger,
+        receipt_account: receipt_account.key(),
+        amount,
+    });
+    Ok(())
+}
+
+// Burns a stake's receipts from the holder's account and unlocks the stake
+// and its vote. Payouts still go to the challenger; the holder only gives up
+// the receipts.
+pub fn redeem_handler(
+    ctx: Context<RedeemStakeReceipt>,
+    challenge_id: String,
+    challenger_pubkey: Pubkey,
+) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+    let amount = stake_account.amount;
+    let receipt_account = ctx.accounts.receipt_account.to_account_info();
+    let mint = ctx.accounts.receipt_mint.to_account_info();
+    let holder = ctx.accounts.holder.to_account_info();
+
+    let mut data = vec![BURN];
+    data.extend_from_slice(&amount.to_le_bytes());
+    invoke(
+        &Instruction {
+            program_id: ctx.accounts.token_program.key(),
+            accounts: vec![
+                AccountMeta::new(receipt_account.key(), false),
+                AccountMeta::new(mint.key(), false),
+                AccountMeta::new_readonly(holder.key(), true),
+            ],
+            data,
+        },
+        &[receipt_account, mint, holder],
+    )?;
+    stake_account.receipt_outstanding = false;
+
+    let vote_info = ctx.accounts.vote_account.to_account_info();
+    if vote_info.owner == &crate::ID && !vote_info.data_is_empty() {
+        let mut vote = Vote::try_deserialize(&mut &vote_info.try_borrow_data()?[..])?;
+        vote.receipt_outstanding = false;
+        vote.try_serialize(&mut &mut vote_info.try_borrow_mut_data()?[..])?;
+    }
+
+    msg!("Redeemed {} receipt tokens for {}'s stake in challenge {}",
+         amount, challenger_pubkey, challenge_id);
+    emit!(StakeReceiptRedeemed {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: ctx.accounts.challenge.key(),
+        challenger: challenger_pubkey,
+        holder: ctx.accounts.holder.key(),
+        amount,
+    });
+    Ok(())
+}
@@ -0,0 +1,156 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::ed25519::verify_preceding_signature;
+use crate::instructions::stake::{check_stake_allowed, record_stake};
+use crate::validation::{check_credential, resolve_region_limits};
+
+const STAKE_INTENT_DOMAIN: &[u8] = b"poloc:stake-intent:v1";
+
+/// Bytes a witness signs to pre-approve a stake. Binding the program and
+/// challenge addresses keeps the approval from being used anywhere else.
+pub fn stake_intent_message(challenge: &Pubkey, amount: u64, expiry: i64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(STAKE_INTENT_DOMAIN.len() + 32 + 32 + 8 + 8);
+    message.extend_from_slice(STAKE_INTENT_DOMAIN);
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(challenge.as_ref());
+    message.extend_from_slice(&amount.to_le_bytes());
+    message.extend_from_slice(&expiry.to_le_bytes());
+    message
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct StakeWithIntent<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    // The stake PDA can only be created once, so an intent is single-use.
+    #[account(
+        init,
+        payer = relayer,
+        space = 8 + Stake::MAX_SIZE,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: RegionPolicy PDA for the challenge's cell; may be uninitialized.
+    /// Validated by `resolve_region_limits`.
+    pub region_policy: UncheckedAccount<'info>,
+
+    /// CHECK: Must stay empty; see `StakeCtx::collusion_flag`.
+    #[account(
+        seeds = [COLLUSION_SEED, challenger.key().as_ref()],
+        bump,
+        constraint = collusion_flag.data_is_empty() @ PolocError::WalletFlagged
+    )]
+    pub collusion_flag: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = relayer,
+        space = 8 + ChallengerProfile::MAX_SIZE,
+        seeds = [CHALLENGER_SEED, challenger.key().as_ref()],
+        bump
+    )]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    #[account(
+        mut,
+        seeds = [INTENT_ESCROW_SEED, challenger.key().as_ref()],
+        bump = intent_escrow.bump
+    )]
+    pub intent_escrow: Account<'info, IntentEscrow>,
+
+    /// CHECK: See `StakeCtx::credential`.
+    pub credential: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Witness whose signed intent is verified via the Ed25519 instruction.
+    pub challenger: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub relayer: Signer<'info>,
+
+    /// CHECK: Address-constrained to the instructions sysvar.
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Must directly follow an Ed25519 program instruction over
+// `stake_intent_message(challenge, amount, expiry)` signed by `challenger`.
+pub fn handler(ctx: Context<StakeWithIntent>, challenge_id: String, amount: u64, expiry: i64) -> Result<()> {
+    let clock = Clock::get()?;
+    let challenger = ctx.accounts.challenger.key();
+    let challenge_key = ctx.accounts.challenge.key();
+
+    require!(clock.unix_timestamp <= expiry, PolocError::IntentExpired);
+    let message = stake_intent_message(&challenge_key, amount, expiry);
+    verify_preceding_signature(&ctx.accounts.instructions.to_account_info(), &challenger, &message)?;
+
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    let profile = &mut ctx.accounts.challenger_profile;
+    if profile.wallet == Pubkey::default() {
+        profile.wallet = challenger;
+        profile.bump = ctx.bumps.challenger_profile;
+    }
+    let (min_stake, _) = resolve_region_limits(
+        &ctx.accounts.region_policy.to_account_info(),
+        challenge,
+        &ctx.accounts.config,
+    )?;
+    check_stake_allowed(
+        challenge,
+        challenge_key,
+        profile,
+        amount,
+        min_stake,
+        &ctx.accounts.config,
+        clock.unix_timestamp,
+    )?;
+    check_credential(
+        ctx.accounts.credential.as_ref().map(|c| c.as_ref()),
+        challenge,
+        &ctx.accounts.config,
+        &ctx.accounts.challenger.key(),
+        clock.unix_timestamp,
+    )?;
+    profile.record_participation();
+
+    // Both accounts are program-owned, so lamports move directly.
+    let escrow_info = ctx.accounts.intent_escrow.to_account_info();
+    let rent = Rent::get()?.minimum_balance(escrow_info.data_len());
+    require!(escrow_info.lamports().saturating_sub(rent) >= amount, PolocError::InsufficientEscrow);
+    **escrow_info.try_borrow_mut_lamports()? -= amount;
+    **ctx.accounts.vault.to_account_info().try_borrow_mut_lamports()? += amount;
+
+    record_stake(
+        challenge,
+        challenge_key,
+        &mut ctx.accounts.stake_account,
+        challenger,
+        challenge_id,
+        amount,
+        clock.unix_timestamp,
+        ctx.bumps.stake_account,
+    )?;
+    ctx.accounts.stake_account.usd_rate = ctx.accounts.config.stake_usd_rate(challenge);
+    Ok(())
+}
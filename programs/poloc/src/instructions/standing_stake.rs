@@ -0,0 +1,318 @@
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::claim_escrow::move_lamports;
+use crate::instructions::finalize::{settle_if_overdue, SettlementAccounts};
+use crate::instructions::stake::{check_stake_allowed, open_stake};
+use crate::instructions::unstake::{load_cast_vote, settle_streak};
+use crate::validation::{check_credential, resolve_region_limits};
+
+#[derive(Accounts)]
+pub struct FundStandingStake<'info> {
+    #[account(
+        init_if_needed,
+        payer = witness,
+        space = 8 + StandingStake::MAX_SIZE,
+        seeds = [STANDING_STAKE_SEED, witness.key().as_ref()],
+        bump
+    )]
+    pub standing_stake: Account<'info, StandingStake>,
+
+    #[account(mut)]
+    pub witness: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct WithdrawStandingStake<'info> {
+    #[account(
+        mut,
+        seeds = [STANDING_STAKE_SEED, witness.key().as_ref()],
+        bump = standing_stake.bump,
+        has_one = witness @ PolocError::Unauthorized
+    )]
+    pub standing_stake: Account<'info, StandingStake>,
+
+    #[account(mut)]
+    pub witness: Signer<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct StakeFromStanding<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    // Still one record per challenge, so votes and slashes work unchanged;
+    // its rent comes back when the stake is released.
+    #[account(
+        init,
+        payer = challenger,
+        space = 8 + Stake::MAX_SIZE,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    #[account(
+        mut,
+        seeds = [STANDING_STAKE_SEED, challenger.key().as_ref()],
+        bump = standing_stake.bump
+    )]
+    pub standing_stake: Account<'info, StandingStake>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: RegionPolicy PDA for the challenge's cell; may be uninitialized.
+    /// Validated by `resolve_region_limits`.
+    pub region_policy: UncheckedAccount<'info>,
+
+    /// CHECK: Must stay empty; see `StakeCtx::collusion_flag`.
+    #[account(
+        seeds = [COLLUSION_SEED, challenger.key().as_ref()],
+        bump,
+        constraint = collusion_flag.data_is_empty() @ PolocError::WalletFlagged
+    )]
+    pub collusion_flag: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = challenger,
+        space = 8 + ChallengerProfile::MAX_SIZE,
+        seeds = [CHALLENGER_SEED, challenger.key().as_ref()],
+        bump
+    )]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    /// CHECK: See `StakeCtx::credential`.
+    pub credential: Option<UncheckedAccount<'info>>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, challenger_pubkey: Pubkey)]
+pub struct ReleaseStandingStake<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()],
+        bump = stake_account.bump,
+        constraint = stake_account.standing @ PolocError::InvalidParameters,
+        close = challenger
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    #[account(
+        mut,
+        seeds = [STANDING_STAKE_SEED, challenger_pubkey.as_ref()],
+        bump = standing_stake.bump
+    )]
+    pub standing_stake: Account<'info, StandingStake>,
+
+    #[account(mut, address = stake_account.challenger @ PolocError::Unauthorized)]
+    pub challenger: SystemAccount<'info>,
+
+    /// CHECK: The stake's vote PDA; may be uninitialized.
+    #[account(seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()], bump)]
+    pub vote_account: UncheckedAccount<'info>,
+
+    // Loses its streak if the stake's vote went unrewarded.
+    #[account(mut, seeds = [CHALLENGER_SEED, challenger_pubkey.as_ref()], bump = challenger_profile.bump)]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    // Optional: the creator's profile, to free the slot of an overdue
+    // challenge settled here; without it such a challenge is refused.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Option<Account<'info, WaldoProfile>>,
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to mark its entry closed if an overdue challenge settles here.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+}
+
+pub fn fund_handler(ctx: Context<FundStandingStake>, amount: u64) -> Result<()> {
+    let standing = &mut ctx.accounts.standing_stake;
+    standing.witness = ctx.accounts.witness.key();
+    standing.bump = ctx.bumps.standing_stake;
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.witness.to_account_info(),
+                to: ctx.accounts.standing_stake.to_account_info(),
+            },
+        ),
+        amount,
+    )?;
+
+    msg!("Standing stake of {} topped up with {} lamports", ctx.accounts.witness.key(), amount);
+    Ok(())
+}
+
+pub fn withdraw_handler(ctx: Context<WithdrawStandingStake>, amount: u64) -> Result<()> {
+    let available = unlocked_balance(&ctx.accounts.standing_stake)?;
+    require!(amount <= available, PolocError::InsufficientStandingStake);
+
+    move_lamports(
+        &ctx.accounts.standing_stake.to_account_info(),
+        &ctx.accounts.witness.to_account_info(),
+        amount,
+    )?;
+
+    msg!("Withdrew {} lamports from standing stake of {}", amount, ctx.accounts.witness.key());
+    Ok(())
+}
+
+// Same checks as `stake`, but nothing is deposited: `amount` of the standing
+// balance is locked instead and stays out of the reward pool.
+pub fn stake_handler(ctx: Context<StakeFromStanding>, challenge_id: String, amount: u64) -> Result<()> {
+    let clock = Clock::get()?;
+    let challenger = ctx.accounts.challenger.key();
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    let (min_stake, _) = resolve_region_limits(
+        &ctx.accounts.region_policy.to_account_info(),
+        challenge,
+        &ctx.accounts.config,
+    )?;
+    let profile = &mut ctx.accounts.challenger_profile;
+    if profile.wallet == Pubkey::default() {
+        profile.wallet = challenger;
+        profile.bump = ctx.bumps.challenger_profile;
+    }
+    check_stake_allowed(
+        challenge,
+        challenge_key,
+        profile,
+        amount,
+        min_stake,
+        &ctx.accounts.config,
+        clock.unix_timestamp,
+    )?;
+    check_credential(
+        ctx.accounts.credential.as_ref().map(|c| c.as_ref()),
+        challenge,
+        &ctx.accounts.config,
+        &challenger,
+        clock.unix_timestamp,
+    )?;
+    profile.record_participation();
+
+    let standing = &mut ctx.accounts.standing_stake;
+    require!(amount <= unlocked_balance(standing)?, PolocError::InsufficientStandingStake);
+    standing.locked = standing.locked
+        .checked_add(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    open_stake(
+        challenge,
+        &mut ctx.accounts.stake_account,
+        challenger,
+        challenge_id,
+        amount,
+        clock.unix_timestamp,
+        ctx.bumps.stake_account,
+    )?;
+    ctx.accounts.stake_account.standing = true;
+    ctx.accounts.stake_account.usd_rate = ctx.accounts.config.stake_usd_rate(challenge);
+    challenge.open_stakes = challenge.open_stakes
+        .checked_add(1)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    emit!(StandingStakeLocked {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenger,
+        amount,
+    });
+    Ok(())
+}
+
+// Permissionless once the challenge is settled. A Finalized challenge keeps
+// the lock until STANDING_RELEASE_DELAY past its last possible finalize, so a
+// slash can still land; whatever it forfeited moves into the pool here.
+pub fn release_handler(
+    ctx: Context<ReleaseStandingStake>,
+    challenge_id: String,
+    challenger_pubkey: Pubkey,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    settle_if_overdue(challenge, challenge_key, now, None, SettlementAccounts {
+        waldo_profile: ctx.accounts.waldo_profile.as_mut(),
+        challenge_registry: ctx.accounts.challenge_registry.as_mut(),
+        registry_page: ctx.accounts.registry_page.as_mut(),
+    })?;
+    require!(!challenge.is_open(), PolocError::ChallengeStillActive);
+    if challenge.status() == ChallengeStatus::Finalized {
+        require!(now >= challenge.slash_window_ends_at(), PolocError::StandingStakeLocked);
+    }
+
+    let stake = &ctx.accounts.stake_account;
+    let forfeited = stake.slashed_amount.min(stake.amount);
+    let standing = &mut ctx.accounts.standing_stake;
+    standing.locked = standing.locked
+        .checked_sub(stake.amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    if forfeited > 0 {
+        move_lamports(&standing.to_account_info(), &ctx.accounts.vault.to_account_info(), forfeited)?;
+        challenge.reward_pool = challenge.reward_pool
+            .checked_add(forfeited)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+    }
+    challenge.open_stakes = challenge.open_stakes
+        .checked_sub(1)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    let vote = load_cast_vote(&ctx.accounts.vote_account.to_account_info())?;
+    settle_streak(challenge, vote.as_ref(), &mut ctx.accounts.challenger_profile);
+
+    msg!("Released standing stake of {} for challenge {}: {} lamports unlocked, {} forfeited",
+         challenger_pubkey, challenge_id, stake.amount - forfeited, forfeited);
+    emit!(StandingStakeReleased {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenger: challenger_pubkey,
+        released: stake.amount - forfeited,
+        forfeited,
+    });
+    Ok(())
+}
+
+// Lamports above rent that no open stake has locked.
+fn unlocked_balance(standing: &Account<StandingStake>) -> Result<u64> {
+    let info = standing.to_account_info();
+    let rent = Rent::get()?.minimum_balance(info.data_len());
+    Ok(info.lamports().saturating_sub(rent).saturating_sub(standing.locked))
+}
@@ -0,0 +1,121 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use poloc_core::slashing::MAX_CONFIDENCE_BPS;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, challenger_pubkey: Pubkey)]
+pub struct SubmitEvidence<'info> {
+    #[account(
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    // Only staked witnesses can be slashed.
+    #[account(
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()],
+        bump = stake_account.bump
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + Evidence::MAX_SIZE,
+        seeds = [EVIDENCE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()],
+        bump
+    )]
+    pub evidence: Account<'info, Evidence>,
+
+    /// CHECK: The witness's vote PDA; empty if they never voted. Read by the
+    /// NoShow and ContradictoryMeasurement checks.
+    #[account(seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()], bump)]
+    pub vote: UncheckedAccount<'info>,
+
+    /// CHECK: The witness's collusion flag PDA; read by the CollusionFlagged check.
+    #[account(seeds = [COLLUSION_SEED, challenger_pubkey.as_ref()], bump)]
+    pub collusion_flag: UncheckedAccount<'info>,
+
+    // The challenge's arbiter, same as `slash`.
+    #[account(mut, address = challenge.load()?.arbiter @ PolocError::Unauthorized)]
+    pub authority: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Checks the reason against on-chain state before recording it, so every
+// slash can be traced to a verified ground. Vote-based reasons need the vote
+// accounts, which are gone once closed at settlement.
+pub fn handler(
+    ctx: Context<SubmitEvidence>,
+    challenge_id: String,
+    challenger_pubkey: Pubkey,
+    reason: SlashReason,
+    hashes: Vec<[u8; 32]>,
+) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &ctx.accounts.challenge.load()?;
+    require!(challenge.status() == ChallengeStatus::Finalized, PolocError::ChallengeNotFinalized);
+    require!(hashes.len() <= Evidence::MAX_HASHES, PolocError::InvalidParameters);
+
+    let vote_info = ctx.accounts.vote.to_account_info();
+    // A prepared vote account that was never cast is still a no-show.
+    let vote = if vote_info.owner == &crate::ID && !vote_info.data_is_empty() {
+        Some(Vote::try_deserialize(&mut &vote_info.try_borrow_data()?[..])?).filter(|vote| !vote.prepared)
+    } else {
+        None
+    };
+    // Only a wrong vote is scaled by the confidence it claimed.
+    let mut confidence = MAX_CONFIDENCE_BPS;
+    match reason {
+        SlashReason::NoShow => {
+            // A closed vote account would look like one never cast.
+            require!(
+                challenge.votes_closed == 0 && challenge.closed_votes == 0 && vote.is_none(),
+                PolocError::InvalidEvidence
+            );
+        }
+        SlashReason::ContradictoryMeasurement => {
+            require!(challenge.votes_closed == 0, PolocError::InvalidEvidence);
+            let vote = vote.ok_or(PolocError::InvalidEvidence)?;
+            require!(!vote.sealed && vote.is_valid != challenge.passed(), PolocError::InvalidEvidence);
+            require!(
+                hashes.first() == Some(&Evidence::measurement_hash(&challenge_key, &vote)),
+                PolocError::InvalidEvidence
+            );
+            confidence = vote.confidence;
+        }
+        SlashReason::CollusionFlagged => {
+            let flag = ctx.accounts.collusion_flag.to_account_info();
+            require!(flag.owner == &crate::ID && !flag.data_is_empty(), PolocError::InvalidEvidence);
+        }
+    }
+
+    let evidence = &mut ctx.accounts.evidence;
+    evidence.challenge = challenge_key;
+    evidence.challenger = challenger_pubkey;
+    evidence.submitter = ctx.accounts.authority.key();
+    evidence.reason = reason;
+    evidence.hashes = [[0u8; 32]; Evidence::MAX_HASHES];
+    evidence.hashes[..hashes.len()].copy_from_slice(&hashes);
+    evidence.hash_count = hashes.len() as u8;
+    evidence.submitted_at = Clock::get()?.unix_timestamp;
+    evidence.consumed = false;
+    evidence.bump = ctx.bumps.evidence;
+    evidence.confidence = confidence;
+
+    msg!("Evidence {:?} against {} recorded for challenge {}", reason, challenger_pubkey, challenge_id);
+
+    emit!(EvidenceSubmitted {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenger: challenger_pubkey,
+        evidence: evidence.key(),
+        reason,
+    });
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::keccak;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::validation::require_in_window;
+use crate::ed25519::verify_preceding_signature;
+use crate::instructions::finalize::require_settleable;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct SubmitOracleResult<'info> {
+    #[account(
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [ORACLE_SET_SEED], bump = oracle_set.bump)]
+    pub oracle_set: Account<'info, OracleSet>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = oracle,
+        space = 8 + OracleReport::MAX_SIZE,
+        seeds = [ORACLE_REPORT_SEED, challenge_id.as_bytes(), oracle.key().as_ref()],
+        bump
+    )]
+    pub oracle_report: Account<'info, OracleReport>,
+
+    #[account(mut)]
+    pub oracle: Signer<'info>,
+
+    /// CHECK: Address-constrained to the instructions sysvar.
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Hash an oracle signs off-chain for a result. Binding the challenge address
+/// and start time keeps a result from applying to a re-opened challenge that
+/// reuses the same ID.
+pub fn oracle_payload_hash(challenge: &Pubkey, start_time: i64, r_star: u32, nonce: u64) -> [u8; 32] {
+    keccak::hashv(&[
+        challenge.as_ref(),
+        &start_time.to_le_bytes(),
+        &r_star.to_le_bytes(),
+        &nonce.to_le_bytes(),
+    ])
+    .to_bytes()
+}
+
+pub fn handler(
+    ctx: Context<SubmitOracleResult>,
+    challenge_id: String,
+    r_star: u32,
+    nonce: u64,
+    payload_hash: [u8; 32],
+) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &ctx.accounts.challenge.load()?;
+    let clock = Clock::get()?;
+
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require_in_window(
+        challenge_key,
+        clock.unix_timestamp,
+        challenge.voting_ends_at() + 1,
+        i64::MAX,
+        PolocError::VotingNotOpen,
+    )?;
+    require_settleable(challenge, challenge_key, &ctx.accounts.config, clock.unix_timestamp)?;
+
+    let expected = oracle_payload_hash(&challenge_key, challenge.start_time, r_star, nonce);
+    require!(payload_hash == expected, PolocError::OraclePayloadMismatch);
+
+    let oracle_key = ctx.accounts.oracle.key();
+    let entry = ctx.accounts.oracle_set
+        .entry_mut(&oracle_key)
+        .ok_or(PolocError::UnknownOracle)?;
+    require!(nonce > entry.nonce, PolocError::StaleOracleNonce);
+    // An oracle whose signing key is its own authority has already signed the
+    // transaction; a separate key signs the payload in a preceding Ed25519
+    // instruction.
+    let signing_key = entry.signing_key_for(challenge.start_time);
+    if signing_key != oracle_key {
+        verify_preceding_signature(&ctx.accounts.instructions.to_account_info(), &signing_key, &payload_hash)?;
+    }
+    entry.nonce = nonce;
+
+    // The bond sits in the report account and is forfeited if the result
+    // strays too far from the median at finalization.
+    let bond = ctx.accounts.config.oracle_report_bond;
+    if bond > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                system_program::Transfer {
+                    from: ctx.accounts.oracle.to_account_info(),
+                    to: ctx.accounts.oracle_report.to_account_info(),
+                },
+            ),
+            bond,
+        )?;
+    }
+
+    let report = &mut ctx.accounts.oracle_report;
+    report.oracle = oracle_key;
+    report.challenge = challenge_key;
+    report.r_star = r_star;
+    report.nonce = nonce;
+    report.payload_hash = payload_hash;
+    report.bump = ctx.bumps.oracle_report;
+    report.bond = bond;
+    report.penalized = false;
+
+    msg!("Oracle {} reported R*={}m for challenge {} (nonce {})", oracle_key, r_star, challenge_id, nonce);
+    Ok(())
+}
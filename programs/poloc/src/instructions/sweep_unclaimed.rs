@@ -0,0 +1,86 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::claim_escrow::move_lamports;
+use crate::instructions::unstake::require_stakes_returned;
+use crate::validation::require_in_window;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct SweepUnclaimed<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch,
+        constraint = challenge.load()?.campaign == Pubkey::default() || challenge.load()?.campaign_reported != 0
+            @ PolocError::CampaignResultUnreported,
+        close = waldo
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, challenge_id.as_bytes()],
+        bump = vault.bump,
+        // Rounding dust and the bond go back to the creator with the rent.
+        close = waldo
+    )]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(mut, address = challenge.load()?.waldo @ PolocError::Unauthorized)]
+    pub waldo: SystemAccount<'info>,
+
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+}
+
+// Permissionless once the claim period of a passed challenge is over: what
+// winners left unclaimed goes to the insurance pool and the challenge closes.
+// The creator only gets back what `archive_challenge` would have returned.
+pub fn handler(ctx: Context<SweepUnclaimed>, challenge_id: String) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    require!(
+        challenge.status() == ChallengeStatus::Finalized && challenge.passed(),
+        PolocError::ChallengeNotSettled
+    );
+    require!(challenge.claim_deadline > 0, PolocError::ClaimPeriodOpen);
+    require_in_window(
+        challenge_key,
+        Clock::get()?.unix_timestamp,
+        challenge.claim_deadline + 1,
+        i64::MAX,
+        PolocError::ClaimPeriodOpen,
+    )?;
+    // The pool still holds the stakes nobody has withdrawn; `return_stake`
+    // sends them back first.
+    require_stakes_returned(challenge)?;
+
+    let amount = challenge.reward_pool;
+    if amount > 0 {
+        move_lamports(
+            &ctx.accounts.vault.to_account_info(),
+            &ctx.accounts.insurance_pool.to_account_info(),
+            amount,
+        )?;
+    }
+    let pool = &mut ctx.accounts.insurance_pool;
+    pool.total_received = pool.total_received
+        .checked_add(amount)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+
+    challenge.reward_pool = 0;
+    challenge.rewards_distributed = 1;
+
+    msg!("Swept {} unclaimed lamports from challenge {} to the insurance pool", amount, challenge_id);
+    emit!(UnclaimedRewardsSwept {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        amount,
+    });
+    Ok(())
+}
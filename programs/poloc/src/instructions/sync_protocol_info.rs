@@ -0,0 +1,44 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::events::EVENT_SCHEMA_VERSION;
+
+#[derive(Accounts)]
+pub struct SyncProtocolInfo<'info> {
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + ProtocolInfo::MAX_SIZE,
+        seeds = [PROTOCOL_INFO_SEED],
+        bump
+    )]
+    pub protocol_info: Account<'info, ProtocolInfo>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Anyone may call this after an upgrade or a config change; it only copies
+// compiled-in values and the config's participant cap.
+pub fn handler(ctx: Context<SyncProtocolInfo>) -> Result<()> {
+    const _: () = assert!(PROTOCOL_VERSION.len() <= ProtocolInfo::MAX_VERSION_LEN);
+
+    let info = &mut ctx.accounts.protocol_info;
+    info.version = PROTOCOL_VERSION.to_string();
+    info.event_schema_version = EVENT_SCHEMA_VERSION;
+    info.voting_window = VOTING_WINDOW;
+    info.finalize_grace = FINALIZE_GRACE;
+    info.max_challenge_duration = MAX_CHALLENGE_DURATION;
+    info.max_schedule_ahead = MAX_SCHEDULE_AHEAD;
+    info.max_participants = ctx.accounts.config.max_participants;
+    info.max_tranches = MAX_TRANCHES;
+    info.bump = ctx.bumps.protocol_info;
+
+    msg!("Protocol info synced: version {}", info.version);
+    Ok(())
+}
@@ -0,0 +1,151 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::system_program;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+
+// Token-2022 instruction discriminants.
+const MINT_TO: u8 = 7;
+const INITIALIZE_MINT_2: u8 = 20;
+const INITIALIZE_NON_TRANSFERABLE_MINT: u8 = 32;
+
+// Base mint padded to a token account's 165 bytes, the account type byte,
+// and the empty NonTransferable TLV entry (2-byte type, 2-byte length).
+const BADGE_MINT_LEN: usize = 165 + 1 + 4;
+
+#[derive(Accounts)]
+pub struct InitializeTierBadgeMint<'info> {
+    #[account(seeds = [CONFIG_SEED], bump = config.bump, has_one = admin @ PolocError::Unauthorized)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Created here as a non-transferable Token-2022 mint.
+    #[account(mut, seeds = [TIER_BADGE_SEED], bump)]
+    pub badge_mint: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub admin: Signer<'info>,
+
+    /// CHECK: Address-constrained to Token-2022.
+    #[account(address = TOKEN_2022_PROGRAM_ID)]
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimTierBadge<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGER_SEED, wallet.key().as_ref()],
+        bump = challenger_profile.bump
+    )]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    // Every reward claim lands here, so it counts the wallet's honest votes.
+    #[account(seeds = [PAYOUT_LEDGER_SEED, wallet.key().as_ref()], bump = payout_ledger.bump)]
+    pub payout_ledger: Account<'info, PayoutLedger>,
+
+    /// CHECK: The badge mint PDA; signs `MintTo` as its own authority.
+    #[account(mut, seeds = [TIER_BADGE_SEED], bump, owner = TOKEN_2022_PROGRAM_ID)]
+    pub badge_mint: UncheckedAccount<'info>,
+
+    /// CHECK: The wallet's Token-2022 account for the badge mint; parsed in the handler.
+    #[account(mut, owner = TOKEN_2022_PROGRAM_ID @ PolocError::InvalidTokenAccount)]
+    pub badge_account: UncheckedAccount<'info>,
+
+    pub wallet: Signer<'info>,
+
+    /// CHECK: Address-constrained to Token-2022.
+    #[account(address = TOKEN_2022_PROGRAM_ID)]
+    pub token_program: UncheckedAccount<'info>,
+}
+
+// One program-wide badge mint: holders can't transfer it, and a wallet's
+// balance is the highest tier it has claimed.
+pub fn initialize_mint_handler(ctx: Context<InitializeTierBadgeMint>) -> Result<()> {
+    let mint = ctx.accounts.badge_mint.to_account_info();
+    let token_program = ctx.accounts.token_program.key();
+    let seeds: &[&[u8]] = &[TIER_BADGE_SEED, &[ctx.bumps.badge_mint]];
+
+    system_program::create_account(
+        CpiContext::new_with_signer(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::CreateAccount { from: ctx.accounts.admin.to_account_info(), to: mint.clone() },
+            &[seeds],
+        ),
+        Rent::get()?.minimum_balance(BADGE_MINT_LEN),
+        BADGE_MINT_LEN as u64,
+        &token_program,
+    )?;
+
+    // The extension must be initialized before the mint itself.
+    let mint_meta = vec![AccountMeta::new(mint.key(), false)];
+    invoke(
+        &Instruction {
+            program_id: token_program,
+            accounts: mint_meta.clone(),
+            data: vec![INITIALIZE_NON_TRANSFERABLE_MINT],
+        },
+        std::slice::from_ref(&mint),
+    )?;
+    let mut data = vec![INITIALIZE_MINT_2, 0];
+    data.extend_from_slice(mint.key().as_ref());
+    data.push(0); // no freeze authority
+    invoke(&Instruction { program_id: token_program, accounts: mint_meta, data }, std::slice::from_ref(&mint))?;
+
+    msg!("Tier badge mint {} initialized", mint.key());
+    Ok(())
+}
+
+// Permissionless for the wallet itself: mints up to the tier its rewarded
+// votes have earned. Wallets still cooling down from a slash must wait.
+pub fn claim_handler(ctx: Context<ClaimTierBadge>) -> Result<()> {
+    let wallet = ctx.accounts.wallet.key();
+    let profile = &mut ctx.accounts.challenger_profile;
+    require!(
+        Clock::get()?.unix_timestamp >= profile.cooldown_until,
+        PolocError::SlashCooldownActive
+    );
+
+    let honest_votes = ctx.accounts.payout_ledger.total_entries;
+    let tier = ChallengerProfile::tier_for(honest_votes);
+    require!(tier > profile.badge_tier, PolocError::NoTierUpgrade);
+
+    let badge_account = ctx.accounts.badge_account.to_account_info();
+    {
+        let data = badge_account.try_borrow_data()?;
+        require!(data.len() >= 64, PolocError::InvalidTokenAccount);
+        require!(data[0..32] == ctx.accounts.badge_mint.key().to_bytes(), PolocError::InvalidTokenAccount);
+        require!(data[32..64] == wallet.to_bytes(), PolocError::InvalidTokenAccount);
+    }
+
+    let mint = ctx.accounts.badge_mint.to_account_info();
+    let mut data = vec![MINT_TO];
+    data.extend_from_slice(&((tier - profile.badge_tier) as u64).to_le_bytes());
+    invoke_signed(
+        &Instruction {
+            program_id: ctx.accounts.token_program.key(),
+            accounts: vec![
+                AccountMeta::new(mint.key(), false),
+                AccountMeta::new(badge_account.key(), false),
+                AccountMeta::new_readonly(mint.key(), true),
+            ],
+            data,
+        },
+        &[mint.clone(), badge_account],
+        &[&[TIER_BADGE_SEED, &[ctx.bumps.badge_mint]]],
+    )?;
+    profile.badge_tier = tier;
+
+    msg!("Witness {} claimed tier {} badge ({} honest votes)", wallet, tier, honest_votes);
+    emit!(TierBadgeClaimed {
+        schema_version: EVENT_SCHEMA_VERSION,
+        wallet,
+        tier,
+        honest_votes,
+    });
+    Ok(())
+}
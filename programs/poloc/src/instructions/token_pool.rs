@@ -0,0 +1,727 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
+use anchor_lang::solana_program::program::{invoke, invoke_signed};
+use anchor_lang::solana_program::sysvar::slot_hashes as slot_hashes_sysvar;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::claim_reward::{claimable_reward, record_claim};
+use crate::instructions::claim_reward_swapped::token_account_fields;
+use crate::instructions::finalize::{settle_if_overdue, SettlementAccounts};
+use crate::instructions::initialize_challenge::{create_challenge, InitializeChallengeArgs, NewChallengeAccounts};
+use crate::instructions::refund_failed_challenge::require_refundable;
+use crate::instructions::claim_bond::require_bond_settled;
+use crate::instructions::stake::{check_stake_allowed, record_stake};
+use crate::instructions::unstake::{load_cast_vote, release_pooled_stake, require_stakes_released, settle_streak};
+use crate::validation::{check_credential, resolve_region_limits};
+
+// SPL Token instruction discriminants, shared by Token-2022.
+const CLOSE_ACCOUNT: u8 = 9;
+const TRANSFER_CHECKED: u8 = 12;
+// Associated token account program `CreateIdempotent`.
+const CREATE_IDEMPOTENT: u8 = 1;
+// Offset of `decimals` in the SPL mint layout.
+const MINT_DECIMALS_OFFSET: usize = 44;
+
+// A challenge whose pool and stakes are in an SPL token rather than lamports.
+// The pool sits in the challenge PDA's associated token account for the mint,
+// created here. Fees, bonds and rent stay in lamports, but a token pool has no
+// lamport value to size a fee on, so it pays none; tranches and underwriting
+// are lamport-only.
+#[derive(Accounts)]
+#[instruction(args: InitializeChallengeArgs)]
+pub struct InitializeTokenChallenge<'info> {
+    #[account(
+        init,
+        payer = waldo,
+        space = Challenge::MAX_SIZE,
+        seeds = [CHALLENGE_SEED, args.challenge_id.as_bytes()],
+        bump
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        init,
+        payer = waldo,
+        space = 8 + ChallengeVault::MAX_SIZE,
+        seeds = [VAULT_SEED, args.challenge_id.as_bytes()],
+        bump
+    )]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init_if_needed,
+        payer = waldo,
+        space = 8 + WaldoProfile::MAX_SIZE,
+        seeds = [WALDO_SEED, waldo.key().as_ref()],
+        bump
+    )]
+    pub waldo_profile: Account<'info, WaldoProfile>,
+
+    #[account(
+        init_if_needed,
+        payer = waldo,
+        space = 8 + DeviceLock::MAX_SIZE,
+        seeds = [DEVICE_SEED, args.device_hash.as_ref()],
+        bump
+    )]
+    pub device_lock: Account<'info, DeviceLock>,
+
+    #[account(mut, seeds = [INSURANCE_SEED], bump = insurance_pool.bump)]
+    pub insurance_pool: Account<'info, InsurancePool>,
+
+    /// CHECK: The pool's mint; its decimals are read for `TransferChecked`.
+    #[account(owner = token_program.key() @ PolocError::InvalidTokenAccount)]
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: The challenge's associated token account for `reward_mint`;
+    /// address checked in the handler, created idempotently.
+    #[account(mut)]
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: The waldo's token account the pool is paid from; the token
+    /// program checks its mint and authority.
+    #[account(mut, owner = token_program.key() @ PolocError::InvalidTokenAccount)]
+    pub waldo_token_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub waldo: Signer<'info>,
+
+    /// CHECK: SPL Token or Token-2022.
+    #[account(
+        constraint = token_program.key() == TOKEN_PROGRAM_ID || token_program.key() == TOKEN_2022_PROGRAM_ID
+            @ PolocError::InvalidTokenAccount
+    )]
+    pub token_program: UncheckedAccount<'info>,
+
+    /// CHECK: Address-constrained to the associated token account program.
+    #[account(address = ASSOCIATED_TOKEN_PROGRAM_ID)]
+    pub associated_token_program: UncheckedAccount<'info>,
+
+    /// CHECK: Address-constrained to the SlotHashes sysvar; seeds the measurement nonce.
+    #[account(address = slot_hashes_sysvar::ID)]
+    pub slot_hashes: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct StakeTokens<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        init,
+        payer = challenger,
+        space = 8 + Stake::MAX_SIZE,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: RegionPolicy PDA for the challenge's cell; may be uninitialized.
+    /// Validated by `resolve_region_limits`.
+    pub region_policy: UncheckedAccount<'info>,
+
+    /// CHECK: RegionStats PDA for the same cell; may be uninitialized.
+    /// Validated by `record_region_stats` when an overdue challenge settles here.
+    #[account(mut)]
+    pub region_stats: UncheckedAccount<'info>,
+
+    /// CHECK: Must stay empty; see `StakeCtx::collusion_flag`.
+    #[account(
+        seeds = [COLLUSION_SEED, challenger.key().as_ref()],
+        bump,
+        constraint = collusion_flag.data_is_empty() @ PolocError::WalletFlagged
+    )]
+    pub collusion_flag: UncheckedAccount<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = challenger,
+        space = 8 + ChallengerProfile::MAX_SIZE,
+        seeds = [CHALLENGER_SEED, challenger.key().as_ref()],
+        bump
+    )]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    /// CHECK: See `StakeCtx::credential`.
+    pub credential: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Address-constrained to the challenge's reward mint.
+    #[account(address = challenge.load()?.reward_mint, owner = token_program.key() @ PolocError::InvalidTokenAccount)]
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Address-constrained to the challenge's vault.
+    #[account(mut, address = challenge.load()?.reward_vault)]
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: The challenger's token account the stake is paid from.
+    #[account(mut)]
+    pub challenger_token_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    /// CHECK: Must own `reward_mint`.
+    pub token_program: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+
+    // Optional: the creator's profile, to free the slot of an overdue
+    // challenge settled here; without it such a challenge is refused.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Option<Account<'info, WaldoProfile>>,
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to mark its entry closed if an overdue challenge settles here.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct ClaimRewardTokens<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [VOTE_SEED, challenge_id.as_bytes(), winner.key().as_ref()],
+        bump = vote.bump,
+        constraint = vote.challenger == winner.key() @ PolocError::Unauthorized,
+        constraint = !vote.processed @ PolocError::AlreadyClaimed,
+    )]
+    pub vote: Account<'info, Vote>,
+
+    pub winner: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump = config.bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Address-constrained to the challenge's reward mint.
+    #[account(address = challenge.load()?.reward_mint, owner = token_program.key() @ PolocError::InvalidTokenAccount)]
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Address-constrained to the challenge's vault.
+    #[account(mut, address = challenge.load()?.reward_vault)]
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Where the winner wants the tokens; the token program checks its mint.
+    #[account(mut)]
+    pub destination: UncheckedAccount<'info>,
+
+    /// CHECK: Must own `reward_mint`.
+    pub token_program: UncheckedAccount<'info>,
+
+    // Credited with the correct vote.
+    #[account(mut, seeds = [CHALLENGER_SEED, winner.key().as_ref()], bump = challenger_profile.bump)]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    // Optional: the creator's profile, to free the slot of an overdue
+    // challenge settled here; without it such a challenge is refused.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Option<Account<'info, WaldoProfile>>,
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to mark its entry closed if an overdue challenge settles here.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct RefundTokenChallenge<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch,
+        close = waldo
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [VAULT_SEED, challenge_id.as_bytes()],
+        bump = vault.bump,
+        close = waldo
+    )]
+    pub vault: Account<'info, ChallengeVault>,
+
+    #[account(mut, address = challenge.load()?.waldo @ PolocError::Unauthorized)]
+    pub waldo: Signer<'info>,
+
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Account<'info, WaldoProfile>,
+
+    /// CHECK: Address-constrained to the challenge's reward mint.
+    #[account(address = challenge.load()?.reward_mint, owner = token_program.key() @ PolocError::InvalidTokenAccount)]
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Address-constrained to the challenge's vault; closed to the waldo.
+    #[account(mut, address = challenge.load()?.reward_vault)]
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Where the waldo wants the pool back.
+    #[account(mut)]
+    pub waldo_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Must own `reward_mint`.
+    pub token_program: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String, challenger_pubkey: Pubkey)]
+pub struct ReturnTokenStake<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()],
+        bump = stake_account.bump,
+        constraint = !stake_account.void @ PolocError::VoidStake,
+        constraint = !stake_account.receipt_outstanding @ PolocError::ReceiptOutstanding,
+        close = challenger
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    #[account(mut, address = stake_account.challenger @ PolocError::Unauthorized)]
+    pub challenger: SystemAccount<'info>,
+
+    /// CHECK: The stake's vote PDA; may be uninitialized.
+    #[account(seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger_pubkey.as_ref()], bump)]
+    pub vote_account: UncheckedAccount<'info>,
+
+    // Loses its streak if the stake's vote went unrewarded.
+    #[account(mut, seeds = [CHALLENGER_SEED, challenger_pubkey.as_ref()], bump = challenger_profile.bump)]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    /// CHECK: Address-constrained to the challenge's reward mint.
+    #[account(address = challenge.load()?.reward_mint, owner = token_program.key() @ PolocError::InvalidTokenAccount)]
+    pub reward_mint: UncheckedAccount<'info>,
+
+    /// CHECK: Address-constrained to the challenge's vault.
+    #[account(mut, address = challenge.load()?.reward_vault)]
+    pub reward_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Must be a token account of `challenger` for `reward_mint`.
+    #[account(mut)]
+    pub challenger_token_account: UncheckedAccount<'info>,
+
+    /// CHECK: Must own `reward_mint`.
+    pub token_program: UncheckedAccount<'info>,
+
+    // Optional: the creator's profile, to free the slot of an overdue
+    // challenge settled here; without it such a challenge is refused.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Option<Account<'info, WaldoProfile>>,
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to mark its entry closed if an overdue challenge settles here.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+}
+
+pub fn initialize_handler(ctx: Context<InitializeTokenChallenge>, args: InitializeChallengeArgs) -> Result<()> {
+    let accounts = ctx.accounts;
+    let mint = accounts.reward_mint.key();
+    let token_program = accounts.token_program.key();
+    let vault = Pubkey::find_program_address(
+        &[accounts.challenge.key().as_ref(), token_program.as_ref(), mint.as_ref()],
+        &ASSOCIATED_TOKEN_PROGRAM_ID,
+    ).0;
+    require_keys_eq!(vault, accounts.reward_vault.key(), PolocError::InvalidTokenAccount);
+
+    let reward_pool = args.reward_pool;
+    create_challenge(
+        NewChallengeAccounts {
+            challenge: &accounts.challenge,
+            vault: &mut accounts.vault,
+            config: &accounts.config,
+            waldo_profile: &mut accounts.waldo_profile,
+            device_lock: &mut accounts.device_lock,
+            insurance_pool: &mut accounts.insurance_pool,
+            waldo: &accounts.waldo,
+            system_program: &accounts.system_program,
+            slot_hashes: &accounts.slot_hashes,
+            prior_result: None,
+            fee_waivers: None,
+            waiver_attestation: None,
+            expiry_feed: None,
+            registry: None,
+            funder: None,
+            token_pool: Some((mint, vault)),
+            challenge_bump: ctx.bumps.challenge,
+            vault_bump: ctx.bumps.vault,
+            waldo_profile_bump: ctx.bumps.waldo_profile,
+            device_lock_bump: ctx.bumps.device_lock,
+        },
+        args,
+        accounts.config.r_star_threshold,
+        0,
+        None,
+    )?;
+
+    invoke(
+        &Instruction {
+            program_id: ASSOCIATED_TOKEN_PROGRAM_ID,
+            accounts: vec![
+                AccountMeta::new(accounts.waldo.key(), true),
+                AccountMeta::new(vault, false),
+                AccountMeta::new_readonly(accounts.challenge.key(), false),
+                AccountMeta::new_readonly(mint, false),
+                AccountMeta::new_readonly(accounts.system_program.key(), false),
+                AccountMeta::new_readonly(token_program, false),
+            ],
+            data: vec![CREATE_IDEMPOTENT],
+        },
+        &[
+            accounts.waldo.to_account_info(),
+            accounts.reward_vault.to_account_info(),
+            accounts.challenge.to_account_info(),
+            accounts.reward_mint.to_account_info(),
+            accounts.system_program.to_account_info(),
+            accounts.token_program.to_account_info(),
+        ],
+    )?;
+    deposit_tokens(
+        &accounts.token_program,
+        &accounts.waldo_token_account,
+        &accounts.reward_mint,
+        &accounts.reward_vault,
+        &accounts.waldo,
+        reward_pool,
+    )?;
+
+    msg!("Token pool of {} (mint {}) held in {}", reward_pool, mint, vault);
+    Ok(())
+}
+
+// `stake` with the deposit in the challenge's reward mint.
+pub fn stake_handler(ctx: Context<StakeTokens>, challenge_id: String, amount: u64) -> Result<()> {
+    let stake_account = &mut ctx.accounts.stake_account;
+    let clock = Clock::get()?;
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    let (min_stake, min_participants) = resolve_region_limits(
+        &ctx.accounts.region_policy.to_account_info(),
+        challenge,
+        &ctx.accounts.config,
+    )?;
+    let region_stats = ctx.accounts.region_stats.to_account_info();
+    let settlement = SettlementAccounts {
+        waldo_profile: ctx.accounts.waldo_profile.as_mut(),
+        challenge_registry: ctx.accounts.challenge_registry.as_mut(),
+        registry_page: ctx.accounts.registry_page.as_mut(),
+    };
+    let limits = Some((min_participants, &region_stats));
+    if settle_if_overdue(challenge, challenge_key, clock.unix_timestamp, limits, settlement)? {
+        // The stake account was already created; leave it as a void record,
+        // which holds nothing but rent for `close_stake` to refund.
+        stake_account.challenger = ctx.accounts.challenger.key();
+        stake_account.challenge_id = challenge_id;
+        stake_account.operator = ctx.accounts.challenger.key();
+        stake_account.bump = ctx.bumps.stake_account;
+        stake_account.void = true;
+        return Ok(());
+    }
+
+    let profile = &mut ctx.accounts.challenger_profile;
+    if profile.wallet == Pubkey::default() {
+        profile.wallet = ctx.accounts.challenger.key();
+        profile.bump = ctx.bumps.challenger_profile;
+    }
+    check_stake_allowed(
+        challenge,
+        challenge_key,
+        profile,
+        amount,
+        min_stake,
+        &ctx.accounts.config,
+        clock.unix_timestamp,
+    )?;
+    check_credential(
+        ctx.accounts.credential.as_ref().map(|c| c.as_ref()),
+        challenge,
+        &ctx.accounts.config,
+        &ctx.accounts.challenger.key(),
+        clock.unix_timestamp,
+    )?;
+    profile.record_participation();
+
+    deposit_tokens(
+        &ctx.accounts.token_program,
+        &ctx.accounts.challenger_token_account,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.challenger,
+        amount,
+    )?;
+
+    record_stake(
+        challenge,
+        challenge_key,
+        stake_account,
+        ctx.accounts.challenger.key(),
+        challenge_id,
+        amount,
+        clock.unix_timestamp,
+        ctx.bumps.stake_account,
+    )
+}
+
+// `claim_reward` paid from the vault. Bond top-ups are in lamports and the
+// payout ledger counts lamports, so neither applies; escrowed claims are
+// lamport-only too.
+pub fn claim_handler(ctx: Context<ClaimRewardTokens>, _challenge_id: String) -> Result<()> {
+    require!(ctx.accounts.config.claim_escrow_period == 0, PolocError::ClaimEscrowRequired);
+
+    let challenge_key = ctx.accounts.challenge.key();
+    let mut challenge = ctx.accounts.challenge.load_mut()?;
+    if settle_if_overdue(&mut challenge, challenge_key, Clock::get()?.unix_timestamp, None, SettlementAccounts {
+        waldo_profile: ctx.accounts.waldo_profile.as_mut(),
+        challenge_registry: ctx.accounts.challenge_registry.as_mut(),
+        registry_page: ctx.accounts.registry_page.as_mut(),
+    })? {
+        return Ok(());
+    }
+
+    let share = claimable_reward(&challenge, &ctx.accounts.vote)?;
+    record_claim(
+        &mut challenge,
+        challenge_key,
+        &mut ctx.accounts.vote,
+        &mut ctx.accounts.challenger_profile,
+        ctx.accounts.winner.key(),
+        share,
+        share,
+    )?;
+    drop(challenge);
+    withdraw_tokens(
+        &ctx.accounts.challenge,
+        &ctx.accounts.token_program,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.destination,
+        share,
+    )
+}
+
+// `refund_failed_challenge` for a token pool: the pool goes back to the
+// waldo once every stake has been returned, and the vault and challenge
+// close to them. Tokens sent to the vault outside the pool's books aren't the
+// waldo's; they keep the vault open instead.
+pub fn refund_handler(ctx: Context<RefundTokenChallenge>, challenge_id: String) -> Result<()> {
+    let challenge_key = ctx.accounts.challenge.key();
+    let mut challenge = ctx.accounts.challenge.load_mut()?;
+    require_refundable(&challenge)?;
+    require_bond_settled(&challenge, Clock::get()?.unix_timestamp)?;
+    ctx.accounts.waldo_profile.release_active(&mut challenge);
+    // The transfers below borrow the challenge as their signer.
+    drop(challenge);
+
+    let challenge = ctx.accounts.challenge.load()?;
+    let (_, _, held) = token_account_fields(&ctx.accounts.reward_vault)?;
+    let amount = challenge.reward_pool;
+    require!(held >= amount, PolocError::RewardAssetMismatch);
+    withdraw_tokens(
+        &ctx.accounts.challenge,
+        &ctx.accounts.token_program,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.waldo_token_account,
+        amount,
+    )?;
+    if held > amount {
+        msg!("Leaving {} untracked tokens in vault {}", held - amount, ctx.accounts.reward_vault.key());
+    } else {
+        let seeds: &[&[u8]] = &[CHALLENGE_SEED, challenge.challenge_id().as_bytes(), &[challenge.bump]];
+        invoke_signed(
+            &Instruction {
+                program_id: ctx.accounts.token_program.key(),
+                accounts: vec![
+                    AccountMeta::new(ctx.accounts.reward_vault.key(), false),
+                    AccountMeta::new(ctx.accounts.waldo.key(), false),
+                    AccountMeta::new_readonly(challenge_key, true),
+                ],
+                data: vec![CLOSE_ACCOUNT],
+            },
+            &[
+                ctx.accounts.reward_vault.to_account_info(),
+                ctx.accounts.waldo.to_account_info(),
+                ctx.accounts.challenge.to_account_info(),
+            ],
+            &[seeds],
+        )?;
+    }
+
+    msg!("Refunded {} tokens and closed token challenge {}", amount, challenge_id);
+    emit!(ChallengeRefunded {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        waldo: challenge.waldo,
+        amount,
+    });
+    Ok(())
+}
+
+// `return_stake` for a token pool, which also covers `unstake`: standing
+// stakes can't back a token challenge, so every stake here sits in the vault.
+// Permissionless, since the tokens can only go to the challenger.
+pub fn return_stake_handler(
+    ctx: Context<ReturnTokenStake>,
+    challenge_id: String,
+    challenger_pubkey: Pubkey,
+) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let challenge_key = ctx.accounts.challenge.key();
+    let mut challenge = ctx.accounts.challenge.load_mut()?;
+    settle_if_overdue(&mut challenge, challenge_key, now, None, SettlementAccounts {
+        waldo_profile: ctx.accounts.waldo_profile.as_mut(),
+        challenge_registry: ctx.accounts.challenge_registry.as_mut(),
+        registry_page: ctx.accounts.registry_page.as_mut(),
+    })?;
+    let (_, owner, _) = token_account_fields(&ctx.accounts.challenger_token_account)?;
+    require_keys_eq!(owner, challenger_pubkey, PolocError::InvalidTokenAccount);
+
+    let vote = load_cast_vote(&ctx.accounts.vote_account.to_account_info())?;
+    let amount = returnable_stake(&mut challenge, &ctx.accounts.stake_account, now)?;
+    release_pooled_stake(&mut challenge, &ctx.accounts.stake_account, amount)?;
+    settle_streak(&challenge, vote.as_ref(), &mut ctx.accounts.challenger_profile);
+    let pooled_stakes = challenge.pooled_stakes;
+    drop(challenge);
+    withdraw_tokens(
+        &ctx.accounts.challenge,
+        &ctx.accounts.token_program,
+        &ctx.accounts.reward_vault,
+        &ctx.accounts.reward_mint,
+        &ctx.accounts.challenger_token_account,
+        amount,
+    )?;
+
+    msg!("Returned stake of {} tokens to {} for challenge {} ({} left)",
+         amount, challenger_pubkey, challenge_id, pooled_stakes);
+    emit!(StakeReturned {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenger: challenger_pubkey,
+        amount,
+    });
+    Ok(())
+}
+
+/// Tokens owed back for `stake` as it leaves an ended challenge, under the
+/// rules of the lamport `unstake` and `return_stake`: an Inconclusive stake
+/// comes back whole, any other ended challenge first lets go of its stakes,
+/// and a slashed stake comes back less what the slash forfeited.
+pub fn returnable_stake(challenge: &mut Challenge, stake: &Stake, now: i64) -> Result<u64> {
+    if challenge.status() == ChallengeStatus::Inconclusive {
+        challenge.open_stakes = challenge.open_stakes
+            .checked_sub(1)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+        return Ok(stake.amount);
+    }
+    require_stakes_released(challenge, now)?;
+    Ok(stake.unforfeited())
+}
+
+// Moves `amount` into the vault and checks all of it arrived: a mint that
+// withholds a transfer fee would leave the pool short of its accounting.
+fn deposit_tokens<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let (_, _, before) = token_account_fields(vault)?;
+    transfer_checked(token_program, from, mint, vault, authority, amount, &[])?;
+    let (_, _, after) = token_account_fields(vault)?;
+    require!(after.saturating_sub(before) == amount, PolocError::RewardAssetMismatch);
+    Ok(())
+}
+
+// Pays `amount` out of the vault, signed by the challenge PDA that owns it.
+// The transfer borrows the challenge, so the caller must not hold it mutably.
+fn withdraw_tokens<'info>(
+    challenge: &AccountLoader<'info, Challenge>,
+    token_program: &AccountInfo<'info>,
+    vault: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let authority = challenge.load()?;
+    let seeds: &[&[u8]] = &[CHALLENGE_SEED, authority.challenge_id().as_bytes(), &[authority.bump]];
+    transfer_checked(token_program, vault, mint, to, &challenge.to_account_info(), amount, &[seeds])
+}
+
+fn transfer_checked<'info>(
+    token_program: &AccountInfo<'info>,
+    from: &AccountInfo<'info>,
+    mint: &AccountInfo<'info>,
+    to: &AccountInfo<'info>,
+    authority: &AccountInfo<'info>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let decimals = *mint.try_borrow_data()?
+        .get(MINT_DECIMALS_OFFSET)
+        .ok_or(PolocError::InvalidTokenAccount)?;
+    let mut data = vec![TRANSFER_CHECKED];
+    data.extend_from_slice(&amount.to_le_bytes());
+    data.push(decimals);
+    invoke_signed(
+        &Instruction {
+            program_id: token_program.key(),
+            accounts: vec![
+                AccountMeta::new(from.key(), false),
+                AccountMeta::new_readonly(mint.key(), false),
+                AccountMeta::new(to.key(), false),
+                AccountMeta::new_readonly(authority.key(), true),
+            ],
+            data,
+        },
+        &[from.clone(), mint.clone(), to.clone(), authority.clone()],
+        signer_seeds,
+    )?;
+    Ok(())
+}
@@ -0,0 +1,157 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+use crate::instructions::claim_escrow::move_lamports;
+use crate::instructions::finalize::{settle_if_overdue, SettlementAccounts};
+use crate::instructions::challenge_registry::track_tvl;
+use crate::instructions::claim_reward::reward_share;
+
+#[derive(Accounts)]
+#[instruction(challenge_id: String)]
+pub struct Unstake<'info> {
+    #[account(
+        mut,
+        seeds = [CHALLENGE_SEED, challenge_id.as_bytes()],
+        bump = challenge.load()?.bump,
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen,
+        constraint = !challenge.load()?.is_token_pool() @ PolocError::RewardAssetMismatch
+    )]
+    pub challenge: AccountLoader<'info, Challenge>,
+
+    #[account(mut, seeds = [VAULT_SEED, challenge_id.as_bytes()], bump = vault.bump)]
+    pub vault: Account<'info, ChallengeVault>,
+
+    // Standing stakes never left their account; `release_standing_stake` unlocks them.
+    #[account(
+        mut,
+        seeds = [STAKE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()],
+        bump = stake_account.bump,
+        constraint = !stake_account.void @ PolocError::VoidStake,
+        constraint = !stake_account.standing @ PolocError::InvalidParameters,
+        constraint = !stake_account.receipt_outstanding @ PolocError::ReceiptOutstanding,
+        close = challenger
+    )]
+    pub stake_account: Account<'info, Stake>,
+
+    /// CHECK: The stake's vote PDA; may be uninitialized. Parsed in the
+    /// handler when it exists.
+    #[account(seeds = [VOTE_SEED, challenge_id.as_bytes(), challenger.key().as_ref()], bump)]
+    pub vote_account: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub challenger: Signer<'info>,
+
+    // Loses its streak if the stake's vote went unrewarded.
+    #[account(mut, seeds = [CHALLENGER_SEED, challenger.key().as_ref()], bump = challenger_profile.bump)]
+    pub challenger_profile: Account<'info, ChallengerProfile>,
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to keep its TVL current, or close its entry if an overdue challenge
+    // settles here.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
+
+    // Optional: the creator's profile, to free the slot of an overdue
+    // challenge settled here; without it such a challenge is refused.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Option<Account<'info, WaldoProfile>>,
+}
+
+// Whether an ended challenge has let go of its unslashed pool stakes:
+// Expired and InsufficientParticipants at once, Finalized once its slash
+// window closes.
+pub(crate) fn require_stakes_released(challenge: &Challenge, now: i64) -> Result<()> {
+    require!(
+        matches!(
+            challenge.status(),
+            ChallengeStatus::Finalized | ChallengeStatus::Expired | ChallengeStatus::InsufficientParticipants
+        ),
+        PolocError::StakeStillLocked
+    );
+    if challenge.status() == ChallengeStatus::Finalized {
+        require!(now >= challenge.slash_window_ends_at(), PolocError::StakeStillLocked);
+    }
+    Ok(())
+}
+
+// Whether every stake has left the challenge, so its vault can be emptied:
+// standing and Inconclusive stakes released, pool stakes unstaked or returned.
+pub fn require_stakes_returned(challenge: &Challenge) -> Result<()> {
+    require!(challenge.open_stakes == 0, PolocError::StakesOutstanding);
+    require!(challenge.pooled_stakes == 0, PolocError::StakesOutstanding);
+    Ok(())
+}
+
+// The vote at `info`, if the challenger cast one.
+pub(crate) fn load_cast_vote(info: &AccountInfo) -> Result<Option<Vote>> {
+    if info.owner != &crate::ID || info.data_is_empty() {
+        return Ok(None);
+    }
+    let vote = Vote::try_deserialize(&mut &info.try_borrow_data()?[..])?;
+    Ok((!vote.prepared && !vote.void).then_some(vote))
+}
+
+// Called as a stake comes back: a vote cast with it that earned no reward,
+// for whatever reason the challenge settled that way, ends the streak.
+// Slashed stakes already reset it in `record_slash`.
+pub(crate) fn settle_streak(challenge: &Challenge, vote: Option<&Vote>, profile: &mut ChallengerProfile) {
+    if vote.is_some_and(|vote| reward_share(challenge, vote).is_err()) {
+        profile.record_unrewarded_vote();
+    }
+}
+
+// Takes a returned pool stake, `amount` of it left after any slash, out of
+// the challenge's books. Only a stake `record_stake` counted comes off
+// `pooled_stakes` and `stake_principal`.
+pub fn release_pooled_stake(challenge: &mut Challenge, stake: &Stake, amount: u64) -> Result<()> {
+    challenge.reward_pool = challenge.reward_pool
+        .checked_sub(amount)
+        .ok_or(PolocError::RewardsAlreadyDistributed)?;
+    if stake.pooled {
+        challenge.pooled_stakes = challenge.pooled_stakes
+            .checked_sub(1)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+        challenge.stake_principal = challenge.stake_principal
+            .checked_sub(amount)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+    }
+    Ok(())
+}
+
+// Gives a stake back, less any slash, once its challenge has ended. A
+// Finalized challenge holds stakes until its slash window closes, like
+// standing stakes. Winners get their stake back here too: only
+// `distributable_pool` is split.
+pub fn handler(ctx: Context<Unstake>, challenge_id: String) -> Result<()> {
+    let now = Clock::get()?.unix_timestamp;
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    settle_if_overdue(challenge, challenge_key, now, None, SettlementAccounts {
+        waldo_profile: ctx.accounts.waldo_profile.as_mut(),
+        challenge_registry: ctx.accounts.challenge_registry.as_mut(),
+        registry_page: ctx.accounts.registry_page.as_mut(),
+    })?;
+    require_stakes_released(challenge, now)?;
+
+    let vote = load_cast_vote(&ctx.accounts.vote_account.to_account_info())?;
+    let amount = ctx.accounts.stake_account.unforfeited();
+    settle_streak(challenge, vote.as_ref(), &mut ctx.accounts.challenger_profile);
+
+    move_lamports(&ctx.accounts.vault.to_account_info(), &ctx.accounts.challenger.to_account_info(), amount)?;
+    release_pooled_stake(challenge, &ctx.accounts.stake_account, amount)?;
+    track_tvl(ctx.accounts.challenge_registry.as_mut(), ctx.accounts.registry_page.as_mut(), challenge, challenge_key)?;
+
+    msg!("Challenger {} unstaked {} lamports from challenge {}",
+         ctx.accounts.challenger.key(), amount, challenge_id);
+    emit!(Unstaked {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenger: ctx.accounts.challenger.key(),
+        amount,
+    });
+    Ok(())
+}
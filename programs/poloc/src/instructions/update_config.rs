@@ -0,0 +1,237 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+
+/// Arguments for `update_config`. Each field left as `None` keeps its current
+/// value; new fields are appended at the end.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct UpdateConfigArgs {
+    pub admin: Option<Pubkey>,
+    pub max_challenges_per_window: Option<u32>,
+    pub rate_limit_window: Option<i64>,
+    pub slash_cooldown: Option<i64>,
+    pub oracle_report_bond: Option<u64>,
+    pub oracle_deviation_band_bps: Option<u16>,
+    pub min_stake: Option<u64>,
+    pub min_participants: Option<u32>,
+    pub claim_escrow_period: Option<i64>,
+    pub vote_quorum_bps: Option<u16>,
+    pub stake_quorum_bps: Option<u16>,
+    pub relayer_claim_fee: Option<u64>,
+    pub creation_fee_bps: Option<u16>,
+    pub waldo_bond: Option<u64>,
+    pub retry_band_bps: Option<u16>,
+    pub retry_discount_bps: Option<u16>,
+    pub guardian: Option<Pubkey>,
+    pub duration_tiers: Option<[DurationTier; DURATION_TIERS]>,
+    pub vote_gas_rebate: Option<u64>,
+    pub min_witness_compensation: Option<u64>,
+    pub attestation_program: Option<Pubkey>,
+    pub integrators: Option<[IntegratorNamespace; MAX_INTEGRATORS]>,
+    pub max_active_challenges: Option<u32>,
+    pub prior_validity_window: Option<i64>,
+    pub prior_quorum_relief_bps: Option<u16>,
+    pub funders: Option<[Pubkey; MAX_FUNDERS]>,
+    pub result_ttl: Option<i64>,
+    pub voting_window: Option<i64>,
+    pub max_participants: Option<u32>,
+    pub r_star_threshold: Option<u32>,
+    pub max_pool_lamports: Option<u64>,
+    pub measurement_max_age: Option<i64>,
+    pub min_r_star_threshold: Option<u32>,
+    pub max_r_star_threshold: Option<u32>,
+    pub claim_period: Option<i64>,
+    pub exclusion_zones: Option<[ExclusionZone; MAX_EXCLUSION_ZONES]>,
+    pub min_settlement_buffer: Option<i64>,
+    pub usd_rate: Option<u64>,
+    pub min_stake_usd: Option<u64>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateConfig<'info> {
+    #[account(
+        mut,
+        seeds = [CONFIG_SEED],
+        bump = config.bump,
+        has_one = admin @ PolocError::Unauthorized
+    )]
+    pub config: Account<'info, Config>,
+
+    pub admin: Signer<'info>,
+}
+
+pub fn handler(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()> {
+    let config = &mut ctx.accounts.config;
+
+    if let Some(admin) = args.admin {
+        config.admin = admin;
+    }
+    if let Some(max) = args.max_challenges_per_window {
+        require!(max > 0, PolocError::InvalidParameters);
+        config.max_challenges_per_window = max;
+    }
+    if let Some(window) = args.rate_limit_window {
+        require!(window > 0, PolocError::InvalidParameters);
+        config.rate_limit_window = window;
+    }
+    if let Some(cooldown) = args.slash_cooldown {
+        require!(cooldown >= 0, PolocError::InvalidParameters);
+        config.slash_cooldown = cooldown;
+    }
+    if let Some(bond) = args.oracle_report_bond {
+        config.oracle_report_bond = bond;
+    }
+    if let Some(band) = args.oracle_deviation_band_bps {
+        require!(band <= 10_000, PolocError::InvalidParameters);
+        config.oracle_deviation_band_bps = band;
+    }
+    if let Some(min_stake) = args.min_stake {
+        require!(min_stake > 0, PolocError::InvalidParameters);
+        config.min_stake = min_stake;
+    }
+    if let Some(min_participants) = args.min_participants {
+        require!(min_participants > 0, PolocError::InvalidParameters);
+        config.min_participants = min_participants;
+    }
+    if let Some(period) = args.claim_escrow_period {
+        require!(period >= 0, PolocError::InvalidParameters);
+        config.claim_escrow_period = period;
+    }
+    if let Some(bps) = args.vote_quorum_bps {
+        require!(bps <= 10_000, PolocError::InvalidParameters);
+        config.vote_quorum_bps = bps;
+    }
+    if let Some(bps) = args.stake_quorum_bps {
+        require!(bps <= 10_000, PolocError::InvalidParameters);
+        config.stake_quorum_bps = bps;
+    }
+    if let Some(fee) = args.relayer_claim_fee {
+        config.relayer_claim_fee = fee;
+    }
+    if let Some(bps) = args.creation_fee_bps {
+        require!(bps <= 10_000, PolocError::InvalidParameters);
+        config.creation_fee_bps = bps;
+    }
+    if let Some(bond) = args.waldo_bond {
+        config.waldo_bond = bond;
+    }
+    if let Some(bps) = args.retry_band_bps {
+        config.retry_band_bps = bps;
+    }
+    if let Some(bps) = args.retry_discount_bps {
+        require!(bps <= 10_000, PolocError::InvalidParameters);
+        config.retry_discount_bps = bps;
+    }
+    if let Some(guardian) = args.guardian {
+        config.guardian = guardian;
+    }
+    if let Some(tiers) = args.duration_tiers {
+        let mut floor = 0;
+        for tier in &tiers {
+            require!(
+                tier.max_duration > floor && tier.max_duration <= MAX_CHALLENGE_DURATION,
+                PolocError::InvalidParameters
+            );
+            floor = tier.max_duration;
+        }
+        config.duration_tiers = tiers;
+    }
+    if let Some(rebate) = args.vote_gas_rebate {
+        config.vote_gas_rebate = rebate;
+    }
+    if let Some(floor) = args.min_witness_compensation {
+        config.min_witness_compensation = floor;
+    }
+    if let Some(program) = args.attestation_program {
+        config.attestation_program = program;
+    }
+    if let Some(integrators) = args.integrators {
+        let mut taken = 0u32;
+        for entry in integrators.iter().filter(|e| e.program != Pubkey::default()) {
+            require!((0x01..0x20).contains(&entry.prefix), PolocError::InvalidParameters);
+            require!(taken & (1 << entry.prefix) == 0, PolocError::InvalidParameters);
+            taken |= 1 << entry.prefix;
+        }
+        config.integrators = integrators;
+    }
+    if let Some(max) = args.max_active_challenges {
+        config.max_active_challenges = max;
+    }
+    if let Some(window) = args.prior_validity_window {
+        require!(window >= 0, PolocError::InvalidParameters);
+        config.prior_validity_window = window;
+    }
+    if let Some(bps) = args.prior_quorum_relief_bps {
+        require!(bps <= 10_000, PolocError::InvalidParameters);
+        config.prior_quorum_relief_bps = bps;
+    }
+    if let Some(funders) = args.funders {
+        config.funders = funders;
+    }
+    if let Some(ttl) = args.result_ttl {
+        require!(ttl >= 0, PolocError::InvalidParameters);
+        config.result_ttl = ttl;
+    }
+    // Running challenges keep the window they were created with.
+    if let Some(window) = args.voting_window {
+        require!(window > 0 && window <= FINALIZE_GRACE, PolocError::InvalidParameters);
+        config.voting_window = window;
+    }
+    // Every vote must fit the challenge's roster, or a full challenge could
+    // never be finalized; `ROSTER_CAPACITY` follows the ceiling.
+    if let Some(max) = args.max_participants {
+        require!(max > 0 && max <= PARTICIPANT_CAP_CEILING, PolocError::InvalidParameters);
+        config.max_participants = max;
+    }
+    if let Some(threshold) = args.r_star_threshold {
+        require!(threshold > 0, PolocError::InvalidParameters);
+        config.r_star_threshold = threshold;
+    }
+    // Only checked as value comes in; challenges already above a lowered cap
+    // keep what they hold.
+    if let Some(cap) = args.max_pool_lamports {
+        config.max_pool_lamports = cap;
+    }
+    if let Some(age) = args.measurement_max_age {
+        require!(age >= 0, PolocError::InvalidParameters);
+        config.measurement_max_age = age;
+    }
+    // A sweep must not open before the last slash can land and the stakes
+    // it leaves are released.
+    if let Some(period) = args.claim_period {
+        require!(period == 0 || period >= FINALIZE_GRACE + STANDING_RELEASE_DELAY, PolocError::InvalidParameters);
+        config.claim_period = period;
+    }
+    // Applies to new challenges and reveals; existing ones keep their location.
+    if let Some(zones) = args.exclusion_zones {
+        require!(zones.iter().all(ExclusionZone::is_well_formed), PolocError::InvalidParameters);
+        config.exclusion_zones = zones;
+    }
+    // Must leave finalize some room before overdue challenges expire.
+    if let Some(buffer) = args.min_settlement_buffer {
+        require!((0..FINALIZE_GRACE).contains(&buffer), PolocError::InvalidParameters);
+        config.min_settlement_buffer = buffer;
+    }
+    // A USD floor needs a rate to be converted at.
+    if let Some(rate) = args.usd_rate {
+        config.usd_rate = rate;
+    }
+    if let Some(min) = args.min_stake_usd {
+        config.min_stake_usd = min;
+    }
+    require!(config.min_stake_usd == 0 || config.usd_rate > 0, PolocError::InvalidParameters);
+    if let Some(min) = args.min_r_star_threshold {
+        config.min_r_star_threshold = min;
+    }
+    if let Some(max) = args.max_r_star_threshold {
+        config.max_r_star_threshold = max;
+    }
+    require!(
+        config.min_r_star_threshold > 0 && config.min_r_star_threshold <= config.max_r_star_threshold,
+        PolocError::InvalidParameters
+    );
+
+    msg!("Config updated by {}", ctx.accounts.admin.key());
+    Ok(())
+}
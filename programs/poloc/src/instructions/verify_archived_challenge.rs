@@ -0,0 +1,90 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::compression::{self, SPL_ACCOUNT_COMPRESSION_ID};
+use crate::instructions::archive_challenge::archive_leaf;
+
+#[derive(Accounts)]
+pub struct VerifyArchivedChallenge<'info> {
+    #[account(
+        seeds = [ARCHIVE_SEED],
+        bump = archive.bump,
+        has_one = merkle_tree @ PolocError::InvalidArchiveTree
+    )]
+    pub archive: Account<'info, Archive>,
+
+    /// CHECK: Matched against `archive.merkle_tree`; read by the compression program.
+    pub merkle_tree: UncheckedAccount<'info>,
+
+    /// CHECK: Address-constrained to spl-account-compression.
+    #[account(address = SPL_ACCOUNT_COMPRESSION_ID)]
+    pub compression_program: UncheckedAccount<'info>,
+}
+
+/// Verified summary of an archived challenge, written to return data so
+/// calling programs can consume it via CPI.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct ArchivedChallengeResult {
+    pub challenge_id: String,
+    pub waldo: Pubkey,
+    pub claimed_lat: i32,
+    pub claimed_lon: i32,
+    pub start_time: i64,
+    pub deadline: i64,
+    pub status: ChallengeStatus,
+    pub r_star: u32,
+    pub r_star_threshold: u32,
+    pub passed: bool,
+    pub vote_count: u32,
+    pub valid_vote_count: u32,
+    pub leaf: [u8; 32],
+    pub context: [u8; 32],
+}
+
+// `challenge_data`/`vote_data` are the raw bytes from the `ChallengeArchived`
+// event; `remaining_accounts` carries the proof nodes, leaf to root.
+pub fn handler<'info>(
+    ctx: Context<'_, '_, 'info, 'info, VerifyArchivedChallenge<'info>>,
+    challenge_data: Vec<u8>,
+    vote_data: Vec<Vec<u8>>,
+    root: [u8; 32],
+    leaf_index: u32,
+) -> Result<ArchivedChallengeResult> {
+    // Reject snapshots that are not a Challenge account before paying for the CPI.
+    let challenge = Challenge::from_snapshot(&challenge_data)?;
+    let archived_votes = challenge.archived_vote_count();
+    require!(vote_data.len() == archived_votes, PolocError::InvalidParameters);
+
+    let leaf = archive_leaf(&challenge_data, &vote_data);
+
+    compression::verify_leaf(
+        &ctx.accounts.compression_program.to_account_info(),
+        &ctx.accounts.merkle_tree.to_account_info(),
+        ctx.remaining_accounts,
+        root,
+        leaf,
+        leaf_index,
+    )?;
+
+    let passed = challenge.status() == ChallengeStatus::Finalized && challenge.passed();
+
+    msg!("Archived challenge {} verified at leaf {}", challenge.challenge_id(), leaf_index);
+
+    Ok(ArchivedChallengeResult {
+        challenge_id: challenge.challenge_id().to_string(),
+        waldo: challenge.waldo,
+        claimed_lat: challenge.claimed_lat,
+        claimed_lon: challenge.claimed_lon,
+        start_time: challenge.start_time,
+        deadline: challenge.deadline,
+        status: challenge.status(),
+        r_star: challenge.r_star,
+        r_star_threshold: challenge.r_star_threshold,
+        passed,
+        vote_count: challenge.vote_count,
+        valid_vote_count: challenge.valid_vote_count,
+        leaf,
+        context: challenge.context,
+    })
+}
@@ -1,71 +1,212 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::instructions as instructions_sysvar;
 use crate::state::*;
+use crate::constants::*;
 use crate::errors::*;
+use crate::validation::require_in_window;
+use crate::events::*;
+use crate::ed25519::{verify_preceding_signature, verify_signature_before};
+use crate::instructions::finalize::{settle_if_overdue, SettlementAccounts};
+use poloc_core::slashing::MAX_CONFIDENCE_BPS;
+
+const BEACON_DOMAIN: &[u8] = b"poloc:beacon:v1";
+const RTT_DOMAIN: &[u8] = b"poloc:rtt:v1";
+
+/// Bytes the prover's beacon signs when answering a witness's probe. The
+/// challenge nonce ties the echo, and the RTT timed on it, to this challenge:
+/// one recorded for an earlier challenge at the same site won't verify.
+pub fn beacon_message(challenge: &Pubkey, nonce: &[u8; 32], challenger: &Pubkey) -> Vec<u8> {
+    let mut message = Vec::with_capacity(BEACON_DOMAIN.len() + 32 + 32 + 32 + 32);
+    message.extend_from_slice(BEACON_DOMAIN);
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(challenge.as_ref());
+    message.extend_from_slice(nonce);
+    message.extend_from_slice(challenger.as_ref());
+    message
+}
+
+/// Bytes the measuring witness signs over its timing of the beacon echo, for
+/// challenges created with `rtt_attested`. Together with the prover's signed
+/// echo it is a portable record of who measured what, and when.
+pub fn rtt_message(challenge: &Pubkey, nonce: &[u8; 32], witness: &Pubkey, min_rtt: u32, measured_at: i64) -> Vec<u8> {
+    let mut message = Vec::with_capacity(RTT_DOMAIN.len() + 32 + 32 + 32 + 32 + 4 + 8);
+    message.extend_from_slice(RTT_DOMAIN);
+    message.extend_from_slice(crate::ID.as_ref());
+    message.extend_from_slice(challenge.as_ref());
+    message.extend_from_slice(nonce);
+    message.extend_from_slice(witness.as_ref());
+    message.extend_from_slice(&min_rtt.to_le_bytes());
+    message.extend_from_slice(&measured_at.to_le_bytes());
+    message
+}
+
+/// Arguments for `submit_vote`. New optional fields should be appended at the
+/// end so existing clients keep serializing a valid prefix.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SubmitVoteArgs {
+    pub challenge_id: String,
+    pub challenger_id: String,
+    pub is_valid: bool,
+    pub uncertainty: u32,      // Uncertainty in meters
+    pub min_rtt: u32,          // Minimum RTT in microseconds
+    pub candidate: u8,         // Candidate location measured; 0 unless the challenge is any-of
+    pub confidence: u16,       // Self-reported confidence in basis points; a wrong vote is slashed more the higher it is
+    pub measured_at: i64,      // Unix time the RTT was measured; see `check_measurement_fresh`
+}
+
+/// Arguments for `submit_vote_by_key`: `SubmitVoteArgs` without its strings.
+/// The challenge id is read from the challenge account and no challenger id
+/// is recorded.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct SubmitVoteByKeyArgs {
+    pub is_valid: bool,
+    pub uncertainty: u32,
+    pub min_rtt: u32,
+    pub candidate: u8,
+    pub confidence: u16,
+    pub measured_at: i64,
+}
+
+impl From<SubmitVoteByKeyArgs> for SubmitVoteArgs {
+    fn from(args: SubmitVoteByKeyArgs) -> Self {
+        SubmitVoteArgs {
+            challenge_id: String::new(),
+            challenger_id: String::new(),
+            is_valid: args.is_valid,
+            uncertainty: args.uncertainty,
+            min_rtt: args.min_rtt,
+            candidate: args.candidate,
+            confidence: args.confidence,
+            measured_at: args.measured_at,
+        }
+    }
+}
 
 #[derive(Accounts)]
-#[instruction(challenge_id: String, challenger_id: String)]
 pub struct SubmitVote<'info> {
+    // See `StakeCtx::challenge`.
     #[account(
         mut,
-        seeds = [b"challenge", challenge_id.as_bytes()],
-        bump = challenge.bump
+        constraint = challenge.load()?.frozen == 0 @ PolocError::ChallengeFrozen
     )]
-    pub challenge: Account<'info, Challenge>,
+    pub challenge: AccountLoader<'info, Challenge>,
     
     #[account(
-        seeds = [b"stake", challenge_id.as_bytes(), challenger.key().as_ref()],
-        bump = stake_account.bump
+        seeds = [STAKE_SEED, challenge.load()?.challenge_id().as_bytes(), challenger.key().as_ref()],
+        bump = stake_account.bump,
+        constraint = stake_account.operator == operator.key() @ PolocError::Unauthorized
     )]
     pub stake_account: Account<'info, Stake>,
     
+    // Usually created here; already exists if `prepare_vote_account` ran.
     #[account(
-        init,
-        payer = challenger,
+        init_if_needed,
+        payer = operator,
         space = 8 + Vote::MAX_SIZE,
-        seeds = [b"vote", challenge_id.as_bytes(), challenger.key().as_ref()],
+        seeds = [VOTE_SEED, challenge.load()?.challenge_id().as_bytes(), challenger.key().as_ref()],
         bump
     )]
     pub vote_account: Account<'info, Vote>,
     
+    /// CHECK: Stake owner; only used to derive the stake and vote PDAs.
+    pub challenger: UncheckedAccount<'info>,
+
+    // Signs and pays for the vote on the owner's behalf.
     #[account(mut)]
-    pub challenger: Signer<'info>,
-    
+    pub operator: Signer<'info>,
+
+    /// CHECK: Address-constrained to the instructions sysvar.
+    #[account(address = instructions_sysvar::ID)]
+    pub instructions: UncheckedAccount<'info>,
+
     pub system_program: Program<'info, System>,
+
+    // Optional: the creator's profile, to free the slot of an overdue
+    // challenge settled here; without it such a challenge is refused.
+    #[account(mut, seeds = [WALDO_SEED, challenge.load()?.waldo.as_ref()], bump = waldo_profile.bump)]
+    pub waldo_profile: Option<Account<'info, WaldoProfile>>,
+
+    // Optional: the challenge registry and the page listing this challenge,
+    // to mark its entry closed if an overdue challenge settles here.
+    #[account(mut, seeds = [REGISTRY_SEED], bump = challenge_registry.bump)]
+    pub challenge_registry: Option<Account<'info, ChallengeRegistry>>,
+    #[account(mut, seeds = [REGISTRY_PAGE_SEED, &registry_page.page.to_le_bytes()], bump = registry_page.bump)]
+    pub registry_page: Option<Account<'info, RegistryPage>>,
 }
 
-pub fn handler(
-    ctx: Context<SubmitVote>,
-    challenge_id: String,
-    challenger_id: String,
-    is_valid: bool,
-    uncertainty: u32,
-    min_rtt: u32,
-) -> Result<()> {
-    let challenge = &mut ctx.accounts.challenge;
+pub fn handler(ctx: Context<SubmitVote>, args: SubmitVoteArgs) -> Result<()> {
+    // `args.challenge_id` is only carried for older clients; the accounts
+    // were derived from the stored id.
+    let SubmitVoteArgs {
+        challenge_id: _,
+        challenger_id,
+        is_valid,
+        uncertainty,
+        min_rtt,
+        candidate,
+        confidence,
+        measured_at,
+    } = args;
+    let challenge_key = ctx.accounts.challenge.key();
+    let challenge = &mut ctx.accounts.challenge.load_mut()?;
+    let challenge_id = challenge.challenge_id().to_string();
     let vote_account = &mut ctx.accounts.vote_account;
     let stake_account = &ctx.accounts.stake_account;
     let clock = Clock::get()?;
-
-    // Challenge must be active
+    trace!("Vote {} (bump {}) on {} for stake {}",
+           vote_account.key(), ctx.bumps.vote_account, challenge_id, stake_account.key());
+    // A fresh account is all zeroes; anything else must be an uncast preparation.
     require!(
-        challenge.status == ChallengeStatus::Active,
-        PolocError::ChallengeNotActive
+        vote_account.challenger == Pubkey::default() || vote_account.prepared,
+        PolocError::AlreadyVoted
     );
 
-    // Voting window: must be after deadline, but within 5 minutes
-    if clock.unix_timestamp <= challenge.deadline {
-        return err!(PolocError::VotingNotOpen);
-    }
-    if clock.unix_timestamp > challenge.deadline + 300 {
-        return err!(PolocError::VotingClosed);
+    // Challenge must be active
+    challenge.activate_if_started(clock.unix_timestamp);
+    require!(challenge.is_open(), PolocError::ChallengeNotActive);
+    require!(challenge.location_known(), PolocError::LocationNotRevealed);
+
+    if settle_if_overdue(challenge, challenge_key, clock.unix_timestamp, None, SettlementAccounts {
+        waldo_profile: ctx.accounts.waldo_profile.as_mut(),
+        challenge_registry: ctx.accounts.challenge_registry.as_mut(),
+        registry_page: ctx.accounts.registry_page.as_mut(),
+    })? {
+        // The vote account was already created; leave it as a void record,
+        // which `close_vote` refunds at once.
+        vote_account.challenger = ctx.accounts.challenger.key();
+        vote_account.challenge_id = challenge_id;
+        vote_account.bump = ctx.bumps.vote_account;
+        vote_account.void = true;
+        return Ok(());
     }
 
+    check_voting_window(challenge, challenge_key, clock.unix_timestamp)?;
+    require!(challenge.encrypted_votes == 0, PolocError::VotesEncrypted);
+
     // Challenger must have an active stake and not be slashed
     require!(!stake_account.slashed, PolocError::StakeSlashed);
 
     // Validate parameters
-    require!(uncertainty <= 50_000, PolocError::InvalidParameters); // Max 50 km
-    require!(min_rtt > 0 && min_rtt <= 1_000_000, PolocError::InvalidParameters); // ≤ 1s RTT
+    require!(vote_in_range(uncertainty, min_rtt), PolocError::InvalidParameters);
+    require!(candidate < challenge.candidate_total(), PolocError::InvalidCandidate);
+    require!(confidence <= MAX_CONFIDENCE_BPS, PolocError::InvalidParameters);
+    check_measurement_fresh(challenge, measured_at, clock.unix_timestamp)?;
+    // A valid vote must directly follow an Ed25519 instruction over the
+    // prover's signed beacon echo for this witness, and on `rtt_attested`
+    // challenges one over the witness's timing after that.
+    if is_valid {
+        let instructions = ctx.accounts.instructions.to_account_info();
+        let message = beacon_message(&challenge_key, &challenge.measurement_nonce, &ctx.accounts.challenger.key());
+        if challenge.rtt_attested != 0 {
+            // The operator took the measurement; its timing follows the echo.
+            let operator = ctx.accounts.operator.key();
+            let timing = rtt_message(&challenge_key, &challenge.measurement_nonce, &operator, min_rtt, measured_at);
+            verify_signature_before(&instructions, 1, &operator, &timing)?;
+            verify_signature_before(&instructions, 2, &challenge.waldo, &message)?;
+        } else {
+            verify_preceding_signature(&instructions, &challenge.waldo, &message)?;
+        }
+    }
 
     // Initialize vote account
     vote_account.challenger = ctx.accounts.challenger.key();
@@ -74,9 +215,19 @@ pub fn handler(
     vote_account.is_valid = is_valid;
     vote_account.uncertainty = uncertainty;
     vote_account.min_rtt = min_rtt;
+    vote_account.candidate = candidate;
+    vote_account.confidence = confidence;
+    vote_account.receipt_outstanding = stake_account.receipt_outstanding;
+    vote_account.stake = stake_account.amount;
+    vote_account.multisig_claim = stake_account.has_claim_policy();
+    vote_account.measured_at = measured_at;
     vote_account.timestamp = clock.unix_timestamp;
     vote_account.processed = false;
+    vote_account.prepared = false;
     vote_account.bump = ctx.bumps.vote_account;
+    let vote_key = vote_account.key();
+    challenge.chain_vote(&vote_key, vote_account)?;
+    challenge.push_roster(vote_account)?;
 
     // Update challenge vote counts
     challenge.vote_count = challenge.vote_count
@@ -86,6 +237,22 @@ pub fn handler(
         challenge.valid_vote_count = challenge.valid_vote_count
             .checked_add(1)
             .ok_or(PolocError::ArithmeticOverflow)?;
+        challenge.valid_stake = challenge.valid_stake
+            .checked_add(stake_account.amount)
+            .ok_or(PolocError::ArithmeticOverflow)?;
+        if challenge.candidate_count > 0 {
+            let slot = &mut challenge.candidates[candidate as usize];
+            slot.valid_votes = slot.valid_votes
+                .checked_add(1)
+                .ok_or(PolocError::ArithmeticOverflow)?;
+            slot.valid_stake = slot.valid_stake
+                .checked_add(stake_account.amount)
+                .ok_or(PolocError::ArithmeticOverflow)?;
+        }
+    } else {
+        challenge.invalid_stake = challenge.invalid_stake
+            .checked_add(stake_account.amount.max(1))
+            .ok_or(PolocError::ArithmeticOverflow)?;
     }
 
     msg!(
@@ -97,5 +264,58 @@ pub fn handler(
         min_rtt
     );
 
+    emit!(VoteSubmitted {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        challenger: ctx.accounts.challenger.key(),
+        is_valid,
+        uncertainty,
+        min_rtt,
+    });
+    emit!(VoteChained {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: challenge_key,
+        vote: vote_key,
+        chain_index: vote_account.chain_index,
+        chain_hash: vote_account.chain_hash,
+    });
+
     Ok(())
 }
+
+// Voting window: must be after deadline, but within the challenge's window. A
+// scheduled challenge instead takes votes from its start, but only while one
+// of its rounds is open; its stakes close as the first round opens.
+pub(crate) fn check_voting_window(challenge: &Challenge, key: Pubkey, now: i64) -> Result<()> {
+    let ends_at = challenge.voting_ends_at();
+    let schedule = &challenge.vote_schedule;
+    let opens_at = if schedule.is_set() { challenge.start_time } else { challenge.deadline + 1 };
+    let error = if now > ends_at { PolocError::VotingClosed } else { PolocError::VotingNotOpen };
+    require_in_window(key, now, opens_at, ends_at, error)?;
+    if !schedule.is_open_at(now) {
+        let opens_at = schedule.next_round_at(now);
+        let closes_at = (opens_at + schedule.length as i64 - 1).min(ends_at);
+        return require_in_window(key, now, opens_at, closes_at, PolocError::OutsideVotingRound);
+    }
+    Ok(())
+}
+
+// A measurement must be taken while the challenge runs, never in the future,
+// and within the challenge's freshness bound of the vote, so RTTs recorded
+// beforehand can't be replayed.
+pub(crate) fn check_measurement_fresh(challenge: &Challenge, measured_at: i64, now: i64) -> Result<()> {
+    require!(
+        measured_at >= challenge.start_time && measured_at <= now,
+        PolocError::StaleMeasurement
+    );
+    require!(
+        challenge.measurement_max_age == 0 || now - measured_at <= challenge.measurement_max_age,
+        PolocError::StaleMeasurement
+    );
+    Ok(())
+}
+
+// Max 50 km uncertainty, RTT up to 1s
+pub(crate) fn vote_in_range(uncertainty: u32, min_rtt: u32) -> bool {
+    uncertainty <= 50_000 && min_rtt > 0 && min_rtt <= 1_000_000
+}
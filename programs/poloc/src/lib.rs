@@ -1,65 +1,80 @@
+// `#[program]` generates its IDL instructions at the crate root, and they
+// call the deprecated `AccountInfo::realloc`. No narrower attribute reaches
+// them, so deprecations are allowed at the root only; every module below
+// warns again.
+#![allow(deprecated)]
 use anchor_lang::prelude::*;
+#[macro_use]
+#[warn(deprecated)]
+mod trace;
+#[warn(deprecated)]
 pub mod instructions;
+#[warn(deprecated)]
+pub mod constants;
+#[warn(deprecated)]
 pub mod state;
+#[warn(deprecated)]
 pub mod errors;
+#[warn(deprecated)]
+pub mod events;
+#[warn(deprecated)]
+pub mod validation;
+#[warn(deprecated)]
+pub mod compression;
+#[warn(deprecated)]
+pub mod utils;
+#[warn(deprecated)]
+pub mod merkle;
+#[warn(deprecated)]
+pub mod ed25519;
 use instructions::*;
+use state::{CallbackAccount, KeeperFault, SlashReason};
+
+// Re-exported so off-chain clients can build instruction arguments directly.
+pub use instructions::{
+    CloneChallengeArgs, InitializeChallengeArgs, OpenFlashChallengeArgs, SubmitVoteArgs, SubmitVoteByKeyArgs, UpdateConfigArgs,
+};
 
 declare_id!("DD4EFbG6h1HNNGm51wS4HWBvsquEhPZbC2qcCnYBRmQ");
 
-#[program]  
+#[program]
+#[warn(deprecated)]
 pub mod poloc {
     use super::*;
      pub fn initialize_challenge(
         ctx: Context<InitializeChallenge>,
-        challenge_id: String,
-        claimed_lat: i32,      // Latitude in micro-degrees (lat * 1e6)
-        claimed_lon: i32,      // Longitude in micro-degrees (lon * 1e6)
-        duration: u64,         // Duration in seconds
-        reward_pool: u64,      // Reward pool in lamports
+        args: InitializeChallengeArgs,
     ) -> Result<()> {
-        instructions::initialize_challenge::handler(
-            ctx,
-            challenge_id,
-            claimed_lat,
-            claimed_lon,
-            duration,
-            reward_pool,
-        )
+        instructions::initialize_challenge::handler(ctx, args)
+    }
+
+    /// Opens a new challenge with a settled challenge's parameters, optionally tweaked
+    pub fn clone_challenge(ctx: Context<CloneChallenge>, args: CloneChallengeArgs) -> Result<()> {
+        instructions::clone_challenge::handler(ctx, args)
     }
 
     /// Stake tokens to participate in a challenge
     pub fn stake(
         ctx: Context<StakeCtx>,
-        challenge_id: String,
+        _challenge_id: String,
         amount: u64,
     ) -> Result<()> {
-        instructions::stake::handler(ctx, challenge_id, amount)
+        instructions::stake::handler(ctx, amount)
     }
 
     /// Submit vote with delay estimates and validity assessment
     pub fn submit_vote(
         ctx: Context<SubmitVote>,
-        challenge_id: String,
-        challenger_id: String,
-        is_valid: bool,
-        uncertainty: u32,      // Uncertainty in meters
-        min_rtt: u32,         // Minimum RTT in microseconds
+        args: SubmitVoteArgs,
     ) -> Result<()> {
-        instructions::vote::handler(
-            ctx,
-            challenge_id,
-            challenger_id,
-            is_valid,
-            uncertainty,
-            min_rtt,
-        )
+        instructions::vote::handler(ctx, args)
     }
 
     /// Finalize challenge and compute results
-    pub fn finalize_challenge(
-        ctx: Context<FinalizeChallenge>,
+    pub fn finalize_challenge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeChallenge<'info>>,
         challenge_id: String,
-        r_star: u32,           // Final uncertainty in meters
+        r_star: u32,           // Must be 0; R* comes from the vote roster
     ) -> Result<()> {
         instructions::finalize::handler(ctx, challenge_id, r_star)
     }
@@ -67,9 +82,60 @@ pub mod poloc {
     /// Distribute rewards to honest participants
   pub fn claim_reward(
         ctx: Context<ClaimReward>,
+        _challenge_id: String,
+    ) -> Result<()> {
+        instructions::claim_reward::handler(ctx)
+    }
+
+    /// Pays an escrowed claim out to its beneficiary once the dispute period ends
+    pub fn release_claim(ctx: Context<ReleaseClaim>, challenge_id: String) -> Result<()> {
+        instructions::claim_escrow::release_handler(ctx, challenge_id)
+    }
+
+    /// Reverts an escrowed claim into the insurance pool during the dispute period (config admin only)
+    pub fn revert_claim(ctx: Context<RevertClaim>, challenge_id: String) -> Result<()> {
+        instructions::claim_escrow::revert_handler(ctx, challenge_id)
+    }
+
+    /// Claims a reward swapped into an SPL token through a Jupiter route
+    pub fn claim_reward_swapped(
+        ctx: Context<ClaimRewardSwapped>,
         challenge_id: String,
+        route_data: Vec<u8>,
+        min_amount_out: u64,
     ) -> Result<()> {
-        instructions::claim_reward::handler(ctx, challenge_id)
+        instructions::claim_reward_swapped::handler(ctx, challenge_id, route_data, min_amount_out)
+    }
+
+    /// Blocks every handler on a challenge (config guardian only)
+    pub fn freeze_challenge(ctx: Context<SetChallengeFrozen>, challenge_id: String) -> Result<()> {
+        instructions::freeze_challenge::freeze_handler(ctx, challenge_id)
+    }
+
+    /// Lifts a guardian freeze (config guardian only)
+    pub fn unfreeze_challenge(ctx: Context<SetChallengeFrozen>, challenge_id: String) -> Result<()> {
+        instructions::freeze_challenge::unfreeze_handler(ctx, challenge_id)
+    }
+
+    /// Reveals a cloaked challenge's coordinates, closing its stake window
+    pub fn reveal_location(
+        ctx: Context<RevealLocation>,
+        challenge_id: String,
+        claimed_lat: i32,
+        claimed_lon: i32,
+        location_salt: [u8; 32],
+    ) -> Result<()> {
+        instructions::reveal_location::handler(ctx, challenge_id, claimed_lat, claimed_lon, location_salt)
+    }
+
+    /// Relays a winner's signed claim; the relayer fee comes out of the reward
+    pub fn claim_reward_with_intent(ctx: Context<ClaimRewardWithIntent>, challenge_id: String) -> Result<()> {
+        instructions::claim_reward_with_intent::handler(ctx, challenge_id)
+    }
+
+    /// Publishes this build's protocol constants in the ProtocolInfo account (permissionless)
+    pub fn sync_protocol_info(ctx: Context<SyncProtocolInfo>) -> Result<()> {
+        instructions::sync_protocol_info::handler(ctx)
     }
 
     /// Refunds the reward pool to the creator if a challenge failed.
@@ -80,7 +146,32 @@ pub mod poloc {
         instructions::refund_failed_challenge::handler(ctx,)
     }
 
-    /// Slash dishonest challengers
+    pub fn cancel_challenge(ctx: Context<CancelChallenge>, challenge_id: String) -> Result<()> {
+        instructions::cancel_challenge::handler(ctx, challenge_id)
+    }
+
+    /// Adds a sponsor's lamports to an active challenge's reward pool
+    pub fn contribute(ctx: Context<Contribute>, challenge_id: String, amount: u64) -> Result<()> {
+        instructions::contribute::handler(ctx, challenge_id, amount)
+    }
+
+    /// Returns a sponsor's pro-rata share of a failed challenge's pool
+    pub fn refund_sponsor(ctx: Context<RefundSponsor>, challenge_id: String) -> Result<()> {
+        instructions::refund_sponsor::handler(ctx, challenge_id)
+    }
+
+    /// Records verified grounds for slashing a challenger (challenge arbiter only)
+    pub fn submit_evidence(
+        ctx: Context<SubmitEvidence>,
+        challenge_id: String,
+        challenger_pubkey: Pubkey,
+        reason: SlashReason,
+        hashes: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::submit_evidence::handler(ctx, challenge_id, challenger_pubkey, reason, hashes)
+    }
+
+    /// Slash dishonest challengers (challenge arbiter only)
     pub fn slash(
         ctx: Context<Slash>,
         challenge_id: String,
@@ -88,6 +179,557 @@ pub mod poloc {
     ) -> Result<()> {
         instructions::slash::handler(ctx, challenge_id, challenger_pubkey)
     }
+
+    /// Registers the compressed Merkle tree used for challenge archival (upgrade authority only)
+    pub fn initialize_archive(
+        ctx: Context<InitializeArchive>,
+        max_depth: u32,
+        max_buffer_size: u32,
+    ) -> Result<()> {
+        instructions::initialize_archive::handler(ctx, max_depth, max_buffer_size)
+    }
+
+    /// Hashes a settled challenge into the archive tree and closes its accounts
+    pub fn archive_challenge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ArchiveChallenge<'info>>,
+        challenge_id: String,
+    ) -> Result<()> {
+        instructions::archive_challenge::handler(ctx, challenge_id)
+    }
+
+    /// Verifies an archived challenge snapshot against the archive tree and returns its result
+    pub fn verify_archived_challenge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, VerifyArchivedChallenge<'info>>,
+        challenge_data: Vec<u8>,
+        vote_data: Vec<Vec<u8>>,
+        root: [u8; 32],
+        leaf_index: u32,
+    ) -> Result<ArchivedChallengeResult> {
+        instructions::verify_archived_challenge::handler(ctx, challenge_data, vote_data, root, leaf_index)
+    }
+
+    /// Registers an automation operator with staked lamports
+    pub fn register_keeper(ctx: Context<RegisterKeeper>, amount: u64) -> Result<()> {
+        instructions::register_keeper::handler(ctx, amount)
+    }
+
+    /// Returns a keeper's stake once the unbonding period has passed
+    pub fn deregister_keeper(ctx: Context<DeregisterKeeper>) -> Result<()> {
+        instructions::deregister_keeper::handler(ctx)
+    }
+
+    /// Lowers a keeper's score and slashes stake for a reported fault (upgrade authority only)
+    pub fn penalize_keeper(ctx: Context<PenalizeKeeper>, fault: KeeperFault) -> Result<()> {
+        instructions::penalize_keeper::handler(ctx, fault)
+    }
+
+    /// Creates the program config with default limits (upgrade authority only)
+    pub fn initialize_config(ctx: Context<InitializeConfig>) -> Result<()> {
+        instructions::initialize_config::handler(ctx)
+    }
+
+    /// Adjusts program-wide limits (config admin only)
+    pub fn update_config(ctx: Context<UpdateConfig>, args: UpdateConfigArgs) -> Result<()> {
+        instructions::update_config::handler(ctx, args)
+    }
+
+    /// Adds the next tranche of a challenge's scheduled reward pool
+    pub fn fund_tranche(ctx: Context<FundTranche>, challenge_id: String) -> Result<()> {
+        instructions::fund_tranche::handler(ctx, challenge_id)
+    }
+
+    /// Authorizes an operator wallet to vote for the caller's stake
+    pub fn delegate_stake(ctx: Context<DelegateStake>, challenge_id: String, operator: Pubkey) -> Result<()> {
+        instructions::delegate_stake::handler(ctx, challenge_id, operator)
+    }
+
+    /// Finalizes by posting a payout Merkle root built from the vote roster, closing the votes
+    pub fn finalize_with_payouts<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeWithPayouts<'info>>,
+        challenge_id: String,
+        r_star: u32,           // Must be 0; R* comes from the vote roster
+    ) -> Result<()> {
+        instructions::finalize_with_payouts::handler(ctx, challenge_id, r_star)
+    }
+
+    /// Claims one leaf of a posted payout root with its Merkle proof
+    pub fn claim_payout(
+        ctx: Context<ClaimPayout>,
+        challenge_id: String,
+        index: u32,
+        proof: Vec<[u8; 32]>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::claim_payout::handler(ctx, challenge_id, index, proof, amount)
+    }
+
+    /// Flags a wallet whose votes match `partner`'s across several challenges (config admin only)
+    pub fn flag_collusion<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FlagCollusion<'info>>,
+        wallet: Pubkey,
+        partner: Pubkey,
+    ) -> Result<()> {
+        instructions::flag_collusion::handler(ctx, wallet, partner)
+    }
+
+    /// Lifts a collusion flag (config admin only)
+    pub fn clear_collusion_flag(ctx: Context<ClearCollusionFlag>) -> Result<()> {
+        instructions::clear_collusion_flag::handler(ctx)
+    }
+
+    /// Creates the registered oracle set (config admin only)
+    pub fn initialize_oracle_set(ctx: Context<InitializeOracleSet>, oracles: Vec<Pubkey>) -> Result<()> {
+        instructions::initialize_oracle_set::handler(ctx, oracles)
+    }
+
+    /// Replaces the registered oracles, preserving nonces (config admin only)
+    pub fn set_oracles(ctx: Context<SetOracles>, oracles: Vec<Pubkey>) -> Result<()> {
+        instructions::set_oracles::handler(ctx, oracles)
+    }
+
+    /// Sets how many oracle reports a multi-oracle finalize needs (config admin only)
+    pub fn set_oracle_quorum(ctx: Context<SetOracles>, quorum: u8) -> Result<()> {
+        instructions::set_oracles::quorum_handler(ctx, quorum)
+    }
+
+    /// Records one oracle's R* for a challenge, replay-protected by nonce and payload hash
+    pub fn submit_oracle_result(
+        ctx: Context<SubmitOracleResult>,
+        challenge_id: String,
+        r_star: u32,
+        nonce: u64,
+        payload_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::submit_oracle_result::handler(ctx, challenge_id, r_star, nonce, payload_hash)
+    }
+
+    /// Finalizes with the median of the submitted oracle reports
+    pub fn finalize_with_oracles<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeWithOracles<'info>>,
+        challenge_id: String,
+    ) -> Result<()> {
+        instructions::finalize_with_oracles::handler(ctx, challenge_id)
+    }
+
+    /// Creates the insurance pool that receives penalties (config admin only)
+    pub fn initialize_insurance_pool(ctx: Context<InitializeInsurancePool>) -> Result<()> {
+        instructions::initialize_insurance_pool::handler(ctx)
+    }
+
+    /// Closes an oracle's report after settlement, returning rent and any unforfeited bond
+    pub fn reclaim_oracle_report(ctx: Context<ReclaimOracleReport>, challenge_id: String) -> Result<()> {
+        instructions::reclaim_oracle_report::handler(ctx, challenge_id)
+    }
+
+    /// Deposits lamports a relayer may later stake on the witness's behalf
+    pub fn fund_intent_escrow(ctx: Context<FundIntentEscrow>, amount: u64) -> Result<()> {
+        instructions::intent_escrow::fund_handler(ctx, amount)
+    }
+
+    /// Withdraws unused lamports from the witness's intent escrow
+    pub fn withdraw_intent_escrow(ctx: Context<WithdrawIntentEscrow>, amount: u64) -> Result<()> {
+        instructions::intent_escrow::withdraw_handler(ctx, amount)
+    }
+
+    /// Opens a stake from a witness's pre-signed intent, submitted by a relayer
+    pub fn stake_with_intent(ctx: Context<StakeWithIntent>, challenge_id: String, amount: u64, expiry: i64) -> Result<()> {
+        instructions::stake_with_intent::handler(ctx, challenge_id, amount, expiry)
+    }
+
+    /// Sets per-cell overrides for minimum stake and participants (config admin only)
+    pub fn set_region_policy(
+        ctx: Context<SetRegionPolicy>,
+        cell_lat: i16,
+        cell_lon: i16,
+        min_stake: u64,
+        min_participants: u32,
+    ) -> Result<()> {
+        instructions::set_region_policy::handler(ctx, cell_lat, cell_lon, min_stake, min_participants)
+    }
+
+    /// Attests a witness's equipment class (config admin only)
+    pub fn set_equipment_class(ctx: Context<SetEquipmentClass>, wallet: Pubkey, equipment_class: u8) -> Result<()> {
+        instructions::set_equipment_class::handler(ctx, wallet, equipment_class)
+    }
+
+    /// Checks a challenge PDA's lamports against its books, emitting any discrepancy
+    pub fn audit_challenge(ctx: Context<AuditChallenge>, challenge_id: String) -> Result<()> {
+        instructions::audit_challenge::handler(ctx, challenge_id)
+    }
+
+    /// Sets the committee that decrypts sealed votes and its joint key (config admin only)
+    pub fn set_vote_committee(
+        ctx: Context<SetVoteCommittee>,
+        encryption_key: [u8; 32],
+        members: Vec<Pubkey>,
+        threshold: u8,
+    ) -> Result<()> {
+        instructions::set_vote_committee::handler(ctx, encryption_key, members, threshold)
+    }
+
+    /// Submits a vote sealed to the committee key on an encrypted-vote challenge
+    pub fn submit_encrypted_vote(
+        ctx: Context<SubmitEncryptedVote>,
+        challenge_id: String,
+        challenger_id: String,
+        commitment: [u8; 32],
+        ciphertext: Vec<u8>,
+    ) -> Result<()> {
+        instructions::encrypted_vote::submit_handler(ctx, challenge_id, challenger_id, commitment, ciphertext)
+    }
+
+    /// Posts a committee member's decryption share once voting has closed
+    pub fn submit_decryption_share(ctx: Context<SubmitDecryptionShare>, challenge_id: String, share: Vec<u8>) -> Result<()> {
+        instructions::encrypted_vote::share_handler(ctx, challenge_id, share)
+    }
+
+    /// Reveals and counts sealed votes once enough decryption shares are in
+    pub fn decrypt_and_tally<'info>(
+        ctx: Context<'_, '_, 'info, 'info, DecryptAndTally<'info>>,
+        challenge_id: String,
+        votes: Vec<Option<DecryptedVote>>,
+    ) -> Result<()> {
+        instructions::encrypted_vote::tally_handler(ctx, challenge_id, votes)
+    }
+
+    /// Registers a program to be invoked with the result at finalize (creator only)
+    pub fn register_callback(
+        ctx: Context<RegisterCallback>,
+        challenge_id: String,
+        program: Pubkey,
+        accounts: Vec<CallbackAccount>,
+    ) -> Result<()> {
+        instructions::register_callback::handler(ctx, challenge_id, program, accounts)
+    }
+
+    /// Creates a challenge in an allowlisted integrator's id namespace (CPI from the integrator)
+    pub fn initialize_namespaced_challenge(
+        ctx: Context<InitializeNamespacedChallenge>,
+        args: InitializeChallengeArgs,
+    ) -> Result<()> {
+        instructions::initialize_namespaced_challenge::handler(ctx, args)
+    }
+
+    /// Opens a campaign holding a shared reward budget
+    pub fn create_campaign(
+        ctx: Context<CreateCampaign>,
+        campaign_id: String,
+        budget: u64,
+        max_pool: u64,
+    ) -> Result<()> {
+        instructions::campaign::create_handler(ctx, campaign_id, budget, max_pool)
+    }
+
+    /// Creates a challenge whose reward pool is drawn from a campaign budget (campaign authority only)
+    pub fn initialize_campaign_challenge(
+        ctx: Context<InitializeCampaignChallenge>,
+        campaign_id: String,
+        args: InitializeChallengeArgs,
+    ) -> Result<()> {
+        instructions::campaign::create_challenge_handler(ctx, campaign_id, args)
+    }
+
+    /// Counts a settled campaign challenge's result into the campaign totals
+    pub fn report_campaign_result(
+        ctx: Context<ReportCampaignResult>,
+        campaign_id: String,
+        challenge_id: String,
+    ) -> Result<()> {
+        instructions::campaign::report_handler(ctx, campaign_id, challenge_id)
+    }
+
+    /// Closes a campaign once every challenge has reported, returning unallocated budget
+    pub fn reconcile_campaign(ctx: Context<ReconcileCampaign>, campaign_id: String) -> Result<()> {
+        instructions::campaign::reconcile_handler(ctx, campaign_id)
+    }
+
+    /// Starts tracking settlement stats for a 1x1 degree cell (permissionless)
+    pub fn initialize_region_stats(ctx: Context<InitializeRegionStats>, cell_lat: i16, cell_lon: i16) -> Result<()> {
+        instructions::initialize_region_stats::handler(ctx, cell_lat, cell_lon)
+    }
+
+    /// Creates a challenge whose pool is paid by an allowlisted funder program's PDA (CPI from the funder)
+    pub fn initialize_funded_challenge(
+        ctx: Context<InitializeFundedChallenge>,
+        args: InitializeChallengeArgs,
+        funder_seeds: Vec<Vec<u8>>,
+    ) -> Result<()> {
+        instructions::initialize_funded_challenge::handler(ctx, args, funder_seeds)
+    }
+
+    /// Creates the non-transferable Token-2022 tier badge mint (admin only)
+    pub fn initialize_tier_badge_mint(ctx: Context<InitializeTierBadgeMint>) -> Result<()> {
+        instructions::tier_badge::initialize_mint_handler(ctx)
+    }
+
+    /// Mints a witness's tier badge up to the tier its rewarded votes have earned
+    pub fn claim_tier_badge(ctx: Context<ClaimTierBadge>) -> Result<()> {
+        instructions::tier_badge::claim_handler(ctx)
+    }
+
+    /// Opens a flash challenge: a few minutes to measure and vote, for point-of-sale checks
+    pub fn open_flash_challenge(ctx: Context<OpenFlashChallenge>, args: OpenFlashChallengeArgs) -> Result<()> {
+        instructions::flash_challenge::open_handler(ctx, args)
+    }
+
+    /// Records a witness's beacon-verified measurement of a flash challenge
+    pub fn submit_flash_vote(ctx: Context<SubmitFlashVote>, uncertainty: u32, min_rtt: u32) -> Result<()> {
+        instructions::flash_challenge::vote_handler(ctx, uncertainty, min_rtt)
+    }
+
+    /// Aggregates a flash challenge's votes, pays its witnesses and closes it (permissionless)
+    pub fn finalize_flash_challenge<'info>(
+        ctx: Context<'_, '_, 'info, 'info, FinalizeFlashChallenge<'info>>,
+    ) -> Result<()> {
+        instructions::flash_challenge::finalize_handler(ctx)
+    }
+
+    /// Announces that a passed result's TTL has run out (permissionless keeper crank)
+    pub fn flag_reverification_due(ctx: Context<FlagReverificationDue>, challenge_id: String) -> Result<()> {
+        instructions::flag_reverification_due::handler(ctx, challenge_id)
+    }
+
+    /// Returns a stake from an inconclusive challenge to its challenger (permissionless)
+    pub fn return_stake(ctx: Context<ReturnStake>, challenge_id: String, challenger_pubkey: Pubkey) -> Result<()> {
+        instructions::return_stake::handler(ctx, challenge_id, challenger_pubkey)
+    }
+
+    /// Deposits lamports into the witness's standing stake
+    pub fn fund_standing_stake(ctx: Context<FundStandingStake>, amount: u64) -> Result<()> {
+        instructions::standing_stake::fund_handler(ctx, amount)
+    }
+
+    /// Withdraws unlocked lamports from the witness's standing stake
+    pub fn withdraw_standing_stake(ctx: Context<WithdrawStandingStake>, amount: u64) -> Result<()> {
+        instructions::standing_stake::withdraw_handler(ctx, amount)
+    }
+
+    /// Stakes for a challenge by locking part of the witness's standing stake
+    pub fn stake_from_standing(ctx: Context<StakeFromStanding>, challenge_id: String, amount: u64) -> Result<()> {
+        instructions::standing_stake::stake_handler(ctx, challenge_id, amount)
+    }
+
+    /// Unlocks a standing stake once its challenge settles, forfeiting any slash (permissionless)
+    pub fn release_standing_stake(
+        ctx: Context<ReleaseStandingStake>,
+        challenge_id: String,
+        challenger_pubkey: Pubkey,
+    ) -> Result<()> {
+        instructions::standing_stake::release_handler(ctx, challenge_id, challenger_pubkey)
+    }
+
+    /// Creates a challenge whose reward pool and stakes are in an SPL token
+    pub fn initialize_token_challenge(ctx: Context<InitializeTokenChallenge>, args: InitializeChallengeArgs) -> Result<()> {
+        instructions::token_pool::initialize_handler(ctx, args)
+    }
+
+    /// Stakes on a token challenge in its reward mint
+    pub fn stake_tokens(ctx: Context<StakeTokens>, challenge_id: String, amount: u64) -> Result<()> {
+        instructions::token_pool::stake_handler(ctx, challenge_id, amount)
+    }
+
+    /// Claims a winner's share of a token challenge's pool
+    pub fn claim_reward_tokens(ctx: Context<ClaimRewardTokens>, challenge_id: String) -> Result<()> {
+        instructions::token_pool::claim_handler(ctx, challenge_id)
+    }
+
+    /// Refunds a failed token challenge's pool to its waldo once its stakes are returned, and closes it
+    pub fn refund_token_challenge(ctx: Context<RefundTokenChallenge>, challenge_id: String) -> Result<()> {
+        instructions::token_pool::refund_handler(ctx, challenge_id)
+    }
+
+    /// Returns a stake from an ended token challenge to its challenger (permissionless)
+    pub fn return_token_stake(
+        ctx: Context<ReturnTokenStake>,
+        challenge_id: String,
+        challenger_pubkey: Pubkey,
+    ) -> Result<()> {
+        instructions::token_pool::return_stake_handler(ctx, challenge_id, challenger_pubkey)
+    }
+
+    /// Queues a timelocked withdrawal from a frozen challenge (upgrade authority only)
+    pub fn propose_emergency_withdraw(
+        ctx: Context<ProposeEmergencyWithdraw>,
+        challenge_id: String,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::emergency_withdraw::propose_handler(ctx, challenge_id, amount)
+    }
+
+    /// Drops a pending emergency withdrawal (upgrade authority only)
+    pub fn cancel_emergency_withdraw(ctx: Context<CancelEmergencyWithdraw>, challenge_id: String) -> Result<()> {
+        instructions::emergency_withdraw::cancel_handler(ctx, challenge_id)
+    }
+
+    /// Executes an emergency withdrawal once its timelock has passed (upgrade authority only)
+    pub fn emergency_withdraw(ctx: Context<ExecuteEmergencyWithdraw>, challenge_id: String) -> Result<()> {
+        instructions::emergency_withdraw::execute_handler(ctx, challenge_id)
+    }
+
+    /// Returns an unslashed stake to its challenger once the challenge has ended
+    pub fn unstake(ctx: Context<Unstake>, challenge_id: String) -> Result<()> {
+        instructions::unstake::handler(ctx, challenge_id)
+    }
+
+    /// `stake` without the challenge id argument; seeds come from the challenge account
+    pub fn stake_by_key(ctx: Context<StakeCtx>, amount: u64) -> Result<()> {
+        instructions::stake::handler(ctx, amount)
+    }
+
+    /// `submit_vote` without string arguments; seeds come from the challenge account
+    pub fn submit_vote_by_key(ctx: Context<SubmitVote>, args: SubmitVoteByKeyArgs) -> Result<()> {
+        instructions::vote::handler(ctx, args.into())
+    }
+
+    /// `claim_reward` without the challenge id argument; seeds come from the challenge account
+    pub fn claim_reward_by_key(ctx: Context<ClaimReward>) -> Result<()> {
+        instructions::claim_reward::handler(ctx)
+    }
+
+    /// Creates the challenge's stake receipt mint (creator only, lamport pools)
+    pub fn initialize_receipt_mint(ctx: Context<InitializeReceiptMint>, challenge_id: String) -> Result<()> {
+        instructions::stake_receipt::initialize_mint_handler(ctx, challenge_id)
+    }
+
+    /// Mints receipt tokens for the caller's stake; it stays locked until they're burned
+    pub fn mint_stake_receipt(ctx: Context<MintStakeReceipt>, challenge_id: String) -> Result<()> {
+        instructions::stake_receipt::mint_handler(ctx, challenge_id)
+    }
+
+    /// Burns a stake's receipt tokens, unlocking withdrawal and claims
+    pub fn redeem_stake_receipt(
+        ctx: Context<RedeemStakeReceipt>,
+        challenge_id: String,
+        challenger_pubkey: Pubkey,
+    ) -> Result<()> {
+        instructions::stake_receipt::redeem_handler(ctx, challenge_id, challenger_pubkey)
+    }
+
+    /// Computes R* from the vote roster of a closed challenge, ahead of finalizing it
+    pub fn compute_r_star(ctx: Context<ComputeRStar>, challenge_id: String) -> Result<()> {
+        instructions::compute_r_star::handler(ctx, challenge_id)
+    }
+
+    /// Sets the creators, or the credential, whose challenges skip fees and bonds (config admin only)
+    pub fn set_fee_waivers(
+        ctx: Context<SetFeeWaivers>,
+        waldos: Vec<Pubkey>,
+        credential: Pubkey,
+        schema: Pubkey,
+    ) -> Result<()> {
+        instructions::set_fee_waivers::handler(ctx, waldos, credential, schema)
+    }
+
+    /// Rotates the calling oracle's result signing key for newly started challenges
+    pub fn rotate_oracle_key(ctx: Context<RotateOracleKey>, new_key: Pubkey) -> Result<()> {
+        instructions::rotate_oracle_key::handler(ctx, new_key)
+    }
+
+    /// Creates a witness's empty vote account ahead of the voting window
+    pub fn prepare_vote_account(ctx: Context<PrepareVoteAccount>) -> Result<()> {
+        instructions::prepare_vote_account::handler(ctx)
+    }
+
+    /// Moves out the deadline of a challenge short of participants and tops up its pool (creator only)
+    pub fn extend_challenge(
+        ctx: Context<ExtendChallenge>,
+        challenge_id: String,
+        additional_duration: u64,
+        additional_reward: u64,
+    ) -> Result<()> {
+        instructions::extend_challenge::handler(ctx, challenge_id, additional_duration, additional_reward)
+    }
+
+    /// Sends a passed challenge's unclaimed rewards to the insurance pool after its claim period and closes it
+    pub fn sweep_unclaimed(ctx: Context<SweepUnclaimed>, challenge_id: String) -> Result<()> {
+        instructions::sweep_unclaimed::handler(ctx, challenge_id)
+    }
+
+    /// Puts the caller's stake reward behind an M-of-N approval policy (before voting)
+    pub fn set_claim_policy(
+        ctx: Context<SetClaimPolicy>,
+        challenge_id: String,
+        approvers: Vec<Pubkey>,
+        threshold: u8,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::multisig_claim::set_policy_handler(ctx, challenge_id, approvers, threshold, recipient)
+    }
+
+    /// Records one approver's sign-off on a policy-gated reward claim
+    pub fn approve_claim(ctx: Context<ApproveClaim>, challenge_id: String, challenger_pubkey: Pubkey) -> Result<()> {
+        instructions::multisig_claim::approve_handler(ctx, challenge_id, challenger_pubkey)
+    }
+
+    /// Pays a policy-gated reward to its recipient once enough approvals are in (permissionless)
+    pub fn execute_claim(ctx: Context<ExecuteClaim>, challenge_id: String, challenger_pubkey: Pubkey) -> Result<()> {
+        instructions::multisig_claim::execute_handler(ctx, challenge_id, challenger_pubkey)
+    }
+
+    /// Creates the feed of challenge deadlines keepers poll for due work (config admin only)
+    pub fn initialize_expiry_feed(ctx: Context<InitializeExpiryFeed>) -> Result<()> {
+        instructions::expiry_feed::initialize_handler(ctx)
+    }
+
+    /// Clears an expiry feed entry whose challenge is no longer open
+    pub fn consume_expiry_entry(ctx: Context<ConsumeExpiryEntry>, index: u8) -> Result<()> {
+        instructions::expiry_feed::consume_handler(ctx, index)
+    }
+
+    /// Closes a settled vote, refunding its rent to the challenger
+    pub fn close_vote(ctx: Context<CloseVote>, challenge_id: String) -> Result<()> {
+        instructions::close_settled::close_vote_handler(ctx, challenge_id)
+    }
+
+    /// Closes a slashed or orphaned stake, refunding its rent to the challenger
+    pub fn close_stake(ctx: Context<CloseStake>, challenge_id: String) -> Result<()> {
+        instructions::close_settled::close_stake_handler(ctx, challenge_id)
+    }
+
+    /// Claims an invalid voter's share of a failed challenge's waldo bond
+    pub fn claim_bond_share(ctx: Context<ClaimBondShare>, challenge_id: String) -> Result<()> {
+        instructions::claim_bond::handler(ctx, challenge_id)
+    }
+
+    /// Logs a summary of a settled challenge's timings, votes, payouts and slashes
+    pub fn emit_challenge_report(ctx: Context<EmitChallengeReport>, challenge_id: String) -> Result<()> {
+        instructions::challenge_report::handler(ctx, challenge_id)
+    }
+
+    /// Creates the challenge registry and its first page (config admin only)
+    pub fn initialize_challenge_registry(ctx: Context<InitializeChallengeRegistry>) -> Result<()> {
+        instructions::challenge_registry::initialize_handler(ctx)
+    }
+
+    /// Opens the next registry page once the current one is full
+    pub fn open_registry_page(ctx: Context<OpenRegistryPage>) -> Result<()> {
+        instructions::challenge_registry::open_page_handler(ctx)
+    }
+
+    /// Marks a settled or closed challenge's registry entry closed
+    pub fn delist_challenge(ctx: Context<DelistChallenge>, page: u32, slot: u8) -> Result<()> {
+        instructions::challenge_registry::delist_handler(ctx, page, slot)
+    }
+
+    /// Claims a vote's reward to a wallet other than the one that cast it, on
+    /// a signed proof from the original key or as the stake's operator
+    pub fn claim_reward_with_proof(
+        ctx: Context<ClaimRewardWithProof>,
+        challenge_id: String,
+        challenger: Pubkey,
+    ) -> Result<()> {
+        instructions::claim_reward_with_proof::handler(ctx, challenge_id, challenger)
+    }
+
+    /// Creates a config, witness profiles and a sample challenge in one go (`demo` feature only)
+    #[cfg(feature = "demo")]
+    pub fn bootstrap_demo(
+        ctx: Context<BootstrapDemo>,
+        challenge_id: String,
+        witnesses: [Pubkey; bootstrap_demo::DEMO_WITNESSES],
+    ) -> Result<()> {
+        instructions::bootstrap_demo::handler(ctx, challenge_id, witnesses)
+    }
     
 
   
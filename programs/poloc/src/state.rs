@@ -1,47 +1,1432 @@
+use std::cell::{Ref, RefMut};
 use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use crate::constants::*;
 
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+// Stored in `Challenge::status` as its discriminant; see `Challenge::status()`.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
 pub enum ChallengeStatus {
+    #[default]
     Active,
     Finalized,
     Expired,
     InsufficientParticipants,
+    Scheduled, // staking open; runs from `start_time`, then becomes Active
+    Inconclusive, // R* just above the threshold; stakes go back, nobody is paid or slashed
 }
 
-impl Default for ChallengeStatus {
-    fn default() -> Self {
-        ChallengeStatus::Active
+impl ChallengeStatus {
+    fn from_u8(value: u8) -> Self {
+        match value {
+            1 => Self::Finalized,
+            2 => Self::Expired,
+            3 => Self::InsufficientParticipants,
+            4 => Self::Scheduled,
+            5 => Self::Inconclusive,
+            _ => Self::Active,
+        }
     }
 }
 
-#[account]
+// Zero-copy, so every participant's vote summary lives in the challenge
+// itself: finalize and payouts read them from `roster` instead of trusting
+// remaining_accounts or an off-chain tally. `repr(C)` allows no implicit
+// padding, so fields are grouped by alignment, flags are u8 and the id is a
+// fixed buffer; use the `challenge_id()` and `status()` accessors.
+#[account(zero_copy)]
 pub struct Challenge {
-    // NOTE: Strings are stored with a 4-byte length prefix + N bytes of content.
-    // We cap strings at 32 bytes of content here for predictability.
-    pub challenge_id: String,           // 4 + 32 = 36 bytes
-    pub waldo: Pubkey,                  // 32 bytes
-    pub claimed_lat: i32,               // 4 bytes - latitude in micro-degrees
-    pub claimed_lon: i32,               // 4 bytes - longitude in micro-degrees
+    // 8-byte aligned
     pub start_time: i64,                // 8 bytes
     pub deadline: i64,                  // 8 bytes
     pub reward_pool: u64,               // 8 bytes
-    pub status: ChallengeStatus,        // 1 byte (enum discriminant)
+    pub tranche_amount: u64,            // 8 bytes - lamports per funding tranche
+    pub sponsor_total: u64,             // 8 bytes - contributed lamports not yet refunded
+    pub total_staked: u64,              // 8 bytes - lamports staked by all participants
+    pub valid_stake: u64,               // 8 bytes - stake behind valid votes
+    pub waldo_bond: u64,                // 8 bytes - held on top of the pool; forfeited to invalid voters if R* misses the threshold
+    pub expires_at: i64,                // 8 bytes - result stops being current at this time; 0 = never
+    pub voting_window: i64,             // 8 bytes - config's voting window at creation; 0 = VOTING_WINDOW
+    pub claimed_stake: u64,             // 8 bytes - valid stake whose reward has been claimed
+    pub measurement_max_age: i64,       // 8 bytes - config's measurement freshness bound at creation; 0 = unbounded
+    pub claim_deadline: i64,            // 8 bytes - unclaimed rewards may be swept after this; 0 = never
+    pub invalid_stake: u64,             // 8 bytes - stake behind invalid votes; splits a forfeited bond
+    pub bond_claimed_stake: u64,        // 8 bytes - invalid stake whose bond share has been paid
+    pub rewards_claimed: u64,           // 8 bytes - pool lamports (or tokens) paid out by reward claims
+    pub slashed_total: u64,             // 8 bytes - stake forfeited by slashes
+    pub voting_closed_at: i64,          // 8 bytes - `voting_ends_at()` as of finalize; 0 until then
+    pub settleable_at: i64,             // 8 bytes - earliest finalize allowed, after the settlement buffer
+    pub finalized_at: i64,              // 8 bytes
+    pub stake_principal: u64,           // 8 bytes - the part of `reward_pool` owed back to `pooled_stakes`; never split
+    pub candidates: [CandidateLocation; MAX_CANDIDATE_LOCATIONS], // 4 * 24 = 96 bytes - any-of set, index 0 = as created
+    pub roster: [RosterEntry; ROSTER_CAPACITY], // 20 * 56 = 1120 bytes - every vote in casting order; see `roster_entries`
+    // 4-byte aligned
+    pub claimed_lat: i32,               // 4 bytes - latitude in micro-degrees
+    pub claimed_lon: i32,               // 4 bytes - longitude in micro-degrees
     pub participant_count: u32,         // 4 bytes
     pub vote_count: u32,                // 4 bytes
     pub valid_vote_count: u32,          // 4 bytes
     pub r_star: u32,                    // 4 bytes - final uncertainty in meters
     pub r_star_threshold: u32,          // 4 bytes - threshold for acceptance
-    pub rewards_distributed: bool,      // 1 byte
+    pub open_contributions: u32,        // 4 bytes - Contribution accounts not yet settled
+    pub sealed_votes: u32,              // 4 bytes - sealed votes not yet tallied
+    pub open_stakes: u32,               // 4 bytes - standing stakes not yet released, plus pool stakes once inconclusive
+    pub min_reputation: i32,            // 4 bytes - lowest ChallengerProfile::score allowed to stake
+    pub closed_votes: u32,              // 4 bytes - vote accounts closed one by one by `close_vote`
+    pub reward_claims: u32,             // 4 bytes - reward claims made
+    pub slash_count: u32,               // 4 bytes - stakes slashed
+    pub registry_page: u32,             // 4 bytes
+    pub chained_votes: u32,             // 4 bytes - votes linked into `vote_chain`
+    pub pooled_stakes: u32,             // 4 bytes - stakes in the pool (lamports or tokens) not yet unstaked, returned or slashed
+    pub roster_len: u32,                // 4 bytes - `roster` entries used
+    pub vote_schedule: VoteSchedule,    // 12 bytes - repeating voting rounds; unset = after the deadline only
+    // 2-byte aligned
+    pub quorum_relief_bps: u16,         // 2 bytes - share of both quorums waived thanks to `prior_result`
+    // 1-byte aligned
+    pub waldo: Pubkey,                  // 32 bytes
+    pub required_credential: Pubkey,    // 32 bytes - attestation issuer stakers must hold; default = open
+    pub required_schema: Pubkey,        // 32 bytes - attestation schema under `required_credential`
+    pub callback_program: Pubkey,       // 32 bytes - invoked with the result at finalize; default = none
+    pub campaign: Pubkey,               // 32 bytes - funding campaign; default = none
+    pub arbiter: Pubkey,                // 32 bytes - holds slashing authority; the waldo unless appointed
+    pub prior_result: Pubkey,           // 32 bytes - recent passed result the creator offered as a prior; default = none
+    pub funder: Pubkey,                 // 32 bytes - program PDA that paid the pool and gets it back on failure; default = the waldo
+    pub reward_mint: Pubkey,            // 32 bytes - SPL mint the pool and stakes are denominated in; default = lamports
+    pub reward_vault: Pubkey,           // 32 bytes - the challenge PDA's associated token account for `reward_mint`
+    pub challenge_id: [u8; MAX_CHALLENGE_ID_LEN], // 32 bytes - first `challenge_id_len` used; see `challenge_id()`
+    pub location_commitment: [u8; 32], // 32 bytes - set for cloaked challenges, zero otherwise
+    pub measurement_nonce: [u8; 32],    // 32 bytes - from slot hashes at init; beacons must echo it
+    pub context: [u8; 32],              // 32 bytes - integrator's business object (e.g. order ID hash); zero = none
+    pub vote_chain: [u8; 32],           // 32 bytes - head of the vote hash chain; see `Challenge::chain_vote`
+    pub callback_accounts: [CallbackAccount; MAX_CALLBACK_ACCOUNTS], // 4 * 33 = 132 bytes
+    pub challenge_id_len: u8,           // 1 byte
+    pub status: u8,                     // 1 byte - ChallengeStatus; see `status()`
+    pub rewards_distributed: u8,        // 1 byte - bool
     pub bump: u8,                       // 1 byte
-    // Total payload size (without Anchor discriminator): 123 bytes
+    pub total_tranches: u8,             // 1 byte - tranches in the funding schedule
+    pub active_tranches: u8,            // 1 byte - tranches funded so far
+    pub votes_closed: u8,               // 1 byte - bool; vote accounts closed at settlement
+    pub private: u8,                    // 1 byte - bool; kept out of registries and coordinate-bearing events
+    pub min_equipment_class: u8,        // 1 byte - lowest ChallengerProfile::equipment_class allowed to stake
+    pub travel_inconsistent: u8,        // 1 byte - bool; set by the finalize consistency guard
+    pub location_revealed: u8,          // 1 byte - bool; cloaked coordinates revealed; stakes locked
+    pub quorum_met: u8,                 // 1 byte - bool; count and stake quorums held at finalize
+    pub frozen: u8,                     // 1 byte - bool; guardian kill-switch; blocks every handler
+    pub underwrites_witnesses: u8,      // 1 byte - bool; bond tops up winners to the configured floor
+    pub encrypted_votes: u8,            // 1 byte - bool; votes are sealed to the committee until tallied
+    pub decryption_shares: u8,          // 1 byte - bitmap of committee members who posted a share
+    pub candidate_count: u8,            // 1 byte - 0 for a single-location challenge
+    pub selected_candidate: u8,         // 1 byte - best-supported candidate, chosen at finalize
+    pub callback_account_count: u8,     // 1 byte
+    pub campaign_reported: u8,          // 1 byte - bool; result counted in the campaign totals
+    pub holds_active_slot: u8,          // 1 byte - bool; counted in the creator's `active_challenges`
+    pub reverification_flagged: u8,     // 1 byte - bool; a keeper has announced the expiry
+    pub r_star_computed: u8,            // 1 byte - bool; `r_star` was taken from the votes by `compute_r_star`
+    pub rtt_attested: u8,               // 1 byte - bool; valid votes carry the witness's signed timing; see `rtt_message`
+    pub listed: u8,                     // 1 byte - bool; open in the challenge registry at `registry_page`/`registry_slot`
+    pub registry_slot: u8,              // 1 byte
+    pub _padding: [u8; 4],              // 4 bytes - to the 8-byte alignment
+    // Total payload size (without Anchor discriminator): 2112 bytes
     // We'll include the 8-byte Anchor discriminator in MAX_SIZE below for direct use in init(space = Challenge::MAX_SIZE)
 }
 
 impl Challenge {
-    // 8 bytes discriminator + 123 payload = 131 bytes
-    pub const MAX_SIZE: usize = 8 + 123;
+    // 8 bytes discriminator + 2112 payload = 2120 bytes
+    pub const MAX_SIZE: usize = 8 + 2112;
+
+    /// The id the challenge PDA is derived from.
+    pub fn challenge_id(&self) -> &str {
+        core::str::from_utf8(&self.challenge_id[..self.challenge_id_len as usize]).unwrap_or_default()
+    }
+
+    /// Ids are a PDA seed, so never longer than the buffer.
+    pub fn set_challenge_id(&mut self, id: &str) {
+        self.challenge_id = [0u8; MAX_CHALLENGE_ID_LEN];
+        self.challenge_id[..id.len()].copy_from_slice(id.as_bytes());
+        self.challenge_id_len = id.len() as u8;
+    }
+
+    pub fn status(&self) -> ChallengeStatus {
+        ChallengeStatus::from_u8(self.status)
+    }
+
+    pub fn set_status(&mut self, status: ChallengeStatus) {
+        self.status = status as u8;
+    }
+
+    /// Borrows the challenge held by `info`, or None if it has been closed.
+    /// For challenges passed unchecked because they may already be gone.
+    pub fn load_existing<'a>(info: &'a AccountInfo) -> Result<Option<Ref<'a, Challenge>>> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+        let data = info.try_borrow_data()?;
+        Self::check_data(&data)?;
+        Ok(Some(Ref::map(data, |data| bytemuck::from_bytes(&data[8..Self::MAX_SIZE]))))
+    }
+
+    /// Same as `load_existing`, for writing.
+    pub fn load_existing_mut<'a>(info: &'a AccountInfo) -> Result<Option<RefMut<'a, Challenge>>> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(None);
+        }
+        let data = info.try_borrow_mut_data()?;
+        Self::check_data(&data)?;
+        Ok(Some(RefMut::map(data, |data| bytemuck::from_bytes_mut(&mut data[8..Self::MAX_SIZE]))))
+    }
+
+    /// Copies a challenge out of a raw account snapshot, such as the one
+    /// `ChallengeArchived` carries, which needn't be aligned.
+    pub fn from_snapshot(data: &[u8]) -> Result<Challenge> {
+        Self::check_data(data)?;
+        Ok(bytemuck::pod_read_unaligned(&data[8..Self::MAX_SIZE]))
+    }
+
+    fn check_data(data: &[u8]) -> Result<()> {
+        require!(data.len() >= Self::MAX_SIZE, ErrorCode::AccountDidNotDeserialize);
+        require!(data[..8] == *Self::DISCRIMINATOR, ErrorCode::AccountDiscriminatorMismatch);
+        Ok(())
+    }
+
+    /// Every vote cast so far, sealed ones included, in casting order.
+    pub fn roster_entries(&self) -> &[RosterEntry] {
+        &self.roster[..self.roster_len as usize]
+    }
+
+    /// Adds a newly cast vote to the roster.
+    pub fn push_roster(&mut self, vote: &Vote) -> Result<()> {
+        let index = self.roster_len as usize;
+        require!(index < ROSTER_CAPACITY, crate::errors::PolocError::RosterFull);
+        self.roster[index] = RosterEntry::from(vote);
+        self.roster_len += 1;
+        Ok(())
+    }
+
+    /// Rewrites the entry for `vote`'s challenger, once a sealed vote is tallied.
+    pub fn update_roster(&mut self, vote: &Vote) -> Result<()> {
+        let count = self.roster_len as usize;
+        let entry = self.roster[..count]
+            .iter_mut()
+            .find(|e| e.challenger == vote.challenger)
+            .ok_or(crate::errors::PolocError::NotInRoster)?;
+        *entry = RosterEntry::from(vote);
+        Ok(())
+    }
+
+    /// The part of `reward_pool` the winners split: pool stakes go back to
+    /// their stakers, winners included, whatever the outcome.
+    pub fn distributable_pool(&self) -> u64 {
+        self.reward_pool.saturating_sub(self.stake_principal)
+    }
+
+    /// Active, or Scheduled and not yet settled. Scheduled challenges accept
+    /// stakes, and their votes are timed off the scheduled start.
+    pub fn is_open(&self) -> bool {
+        matches!(self.status(), ChallengeStatus::Active | ChallengeStatus::Scheduled)
+    }
+
+    /// Links a newly cast vote into the challenge's vote chain. Each vote
+    /// records its position and the chain head after it, so anyone holding
+    /// the votes can replay the chain and compare it with `vote_chain`: a
+    /// dropped, reordered or altered vote changes every later head.
+    pub fn chain_vote(&mut self, vote_key: &Pubkey, vote: &mut Vote) -> Result<()> {
+        vote.chain_index = self.chained_votes;
+        vote.chain_hash = vote.chain_link(&self.vote_chain, vote_key);
+        self.vote_chain = vote.chain_hash;
+        self.chained_votes = self.chained_votes
+            .checked_add(1)
+            .ok_or(crate::errors::PolocError::ArithmeticOverflow)?;
+        Ok(())
+    }
+
+    /// keccak(challenge_id, lat, lon, salt), the commitment used both for
+    /// private event payloads and for cloaked challenges.
+    pub fn commit_location(challenge_id: &str, lat: i32, lon: i32, salt: &[u8; 32]) -> [u8; 32] {
+        anchor_lang::solana_program::keccak::hashv(&[
+            challenge_id.as_bytes(),
+            &lat.to_le_bytes(),
+            &lon.to_le_bytes(),
+            salt,
+        ])
+        .to_bytes()
+    }
+
+    /// Token pools hold the reward and every stake in `reward_vault`, in
+    /// `reward_mint` base units; lamport-only instructions reject them.
+    pub fn is_token_pool(&self) -> bool {
+        self.reward_mint != Pubkey::default()
+    }
+
+    /// Cloaked challenges keep their coordinates off-chain until revealed.
+    pub fn is_cloaked(&self) -> bool {
+        self.location_commitment != [0u8; 32]
+    }
+
+    /// Whether stakers must present an attestation from `required_credential`.
+    pub fn requires_credential(&self) -> bool {
+        self.required_credential != Pubkey::default()
+    }
+
+    /// Locations votes may reference: the any-of set, or just the claimed one.
+    pub fn candidate_total(&self) -> u8 {
+        self.candidate_count.max(1)
+    }
+
+    /// Whether `claimed_lat` / `claimed_lon` hold the real location.
+    pub fn location_known(&self) -> bool {
+        !self.is_cloaked() || self.location_revealed != 0
+    }
+
+    /// Result of a finalized challenge: R* within threshold and both quorums met.
+    pub fn passed(&self) -> bool {
+        self.r_star <= self.r_star_threshold && self.quorum_met != 0
+    }
+
+    /// A passed result that hasn't reached `expires_at`. Consumers should
+    /// treat anything else as stale and ask for a new proof.
+    pub fn is_current(&self, now: i64) -> bool {
+        self.status() == ChallengeStatus::Finalized
+            && self.passed()
+            && (self.expires_at == 0 || now < self.expires_at)
+    }
+
+    /// Lamports the challenge's vault holds for it: the reward pool, with
+    /// stakes and contributions, and the creator's bond. A token pool only
+    /// holds its bond in lamports.
+    pub fn tvl(&self) -> u64 {
+        let pool = if self.is_token_pool() { 0 } else { self.reward_pool };
+        pool.saturating_add(self.waldo_bond)
+    }
+
+    /// `tvl` plus the tranches the creator has committed but not funded yet.
+    pub fn committed_tvl(&self) -> u64 {
+        let unfunded = self.total_tranches.saturating_sub(self.active_tranches) as u64;
+        let pending = if self.is_token_pool() { 0 } else { unfunded.saturating_mul(self.tranche_amount) };
+        self.tvl().saturating_add(pending)
+    }
+
+    /// When voting closes. Challenges created before the window moved into
+    /// the config carry 0 and keep the compiled-in VOTING_WINDOW.
+    pub fn voting_ends_at(&self) -> i64 {
+        let window = if self.voting_window > 0 { self.voting_window } else { VOTING_WINDOW };
+        self.deadline + window
+    }
+
+    /// Last moment a stake is taken: the deadline, or for a scheduled
+    /// challenge the moment before its first round opens, so nobody joins
+    /// after votes have been cast and seen.
+    pub fn staking_ends_at(&self) -> i64 {
+        let schedule = &self.vote_schedule;
+        if !schedule.is_set() {
+            return self.deadline;
+        }
+        let first_round = if schedule.is_open_at(self.start_time) {
+            self.start_time
+        } else {
+            schedule.next_round_at(self.start_time)
+        };
+        (first_round - 1).min(self.deadline)
+    }
+
+    /// Earliest time a result may be settled: once voting has closed and
+    /// `buffer` more seconds have passed.
+    pub fn settleable_at(&self, buffer: i64) -> i64 {
+        self.voting_ends_at() + 1 + buffer
+    }
+
+    /// The votes put the claimed location outside the threshold. Only this
+    /// forfeits the bond; a claim that failed on quorum alone refunds it.
+    pub fn forfeits_bond(&self) -> bool {
+        self.status() == ChallengeStatus::Finalized && self.r_star > self.r_star_threshold
+    }
+
+    /// A forfeited bond goes to the invalid voters, who haven't all been
+    /// paid their share yet.
+    pub fn owes_bond(&self) -> bool {
+        self.forfeits_bond()
+            && self.waldo_bond > 0
+            && self.bond_claimed_stake < self.invalid_stake
+    }
+
+    /// Vote accounts still open, which `archive_challenge` takes in full.
+    pub fn archived_vote_count(&self) -> usize {
+        if self.votes_closed != 0 {
+            0
+        } else {
+            self.vote_count.saturating_sub(self.closed_votes) as usize
+        }
+    }
+
+    /// STANDING_RELEASE_DELAY past the last possible finalize. Until then the
+    /// arbiter may still slash, so stakes stay put.
+    pub fn slash_window_ends_at(&self) -> i64 {
+        self.voting_ends_at() + FINALIZE_GRACE + STANDING_RELEASE_DELAY
+    }
+
+    /// R* above the threshold by no more than `band_bps` of it.
+    pub fn in_inconclusive_band(&self, band_bps: u16) -> bool {
+        let band = self.r_star_threshold as u64 * band_bps as u64 / 10_000;
+        self.r_star > self.r_star_threshold
+            && self.r_star as u64 <= self.r_star_threshold as u64 + band
+    }
+
+    /// Lazily promotes a Scheduled challenge once its start time has passed.
+    pub fn activate_if_started(&mut self, now: i64) {
+        if self.status() == ChallengeStatus::Scheduled && now >= self.start_time {
+            self.set_status(ChallengeStatus::Active);
+        }
+    }
 }
 
+// Merkle-airdrop settlement for one challenge. Seeds: ["payout", challenge_id].
+// Leaf `i` pays the i-th winning voter their stake-weighted share (see
+// `crate::merkle` and `poloc_core::rewards::payout_shares`).
+#[account]
+pub struct PayoutDistribution {
+    pub challenge: Pubkey,              // 32 bytes
+    pub root: [u8; 32],                 // 32 bytes
+    pub amount: u64,                    // 8 bytes - lamports across all leaves
+    pub winner_count: u32,              // 4 bytes
+    pub claimed_bitmap: u32,            // 4 bytes - bit i set once leaf i is claimed
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 81 bytes
+}
+
+impl PayoutDistribution {
+    // NOTE: used as `space = 8 + PayoutDistribution::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 81;
+}
+
+// Governance flag on a wallet caught voting in lockstep with `partner`.
+// Seeds: ["collusion", wallet]. While it exists the wallet cannot stake;
+// clearing the flag closes the account.
+#[account]
+pub struct CollusionFlag {
+    pub wallet: Pubkey,                 // 32 bytes
+    pub partner: Pubkey,                // 32 bytes
+    pub evidence_count: u8,             // 1 byte - challenges with identical votes
+    pub flagged_at: i64,                // 8 bytes
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 74 bytes
+}
+
+impl CollusionFlag {
+    // NOTE: used as `space = 8 + CollusionFlag::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 74;
+
+    pub const MIN_EVIDENCE_CHALLENGES: usize = 3;
+}
+
+// Global registry of the compressed archive tree. Seeds: ["archive"].
+// The PDA itself is the tree authority for spl-account-compression.
+#[account]
+pub struct Archive {
+    pub admin: Pubkey,                  // 32 bytes
+    pub merkle_tree: Pubkey,            // 32 bytes
+    pub leaf_count: u64,                // 8 bytes
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 73 bytes
+}
+
+impl Archive {
+    // NOTE: used as `space = 8 + Archive::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 73;
+}
+
+// Program-wide tunables. Seeds: ["config"].
+#[account]
+pub struct Config {
+    pub admin: Pubkey,                  // 32 bytes
+    pub max_challenges_per_window: u32, // 4 bytes
+    pub rate_limit_window: i64,         // 8 bytes - seconds
+    pub bump: u8,                       // 1 byte
+    pub slash_cooldown: i64,            // 8 bytes - seconds a slashed wallet may not stake
+    pub oracle_report_bond: u64,        // 8 bytes - lamports escrowed with each oracle report
+    pub oracle_deviation_band_bps: u16, // 2 bytes - allowed distance from the median R*
+    pub min_stake: u64,                 // 8 bytes - lamports, unless a RegionPolicy overrides it
+    pub min_participants: u32,          // 4 bytes - unless a RegionPolicy overrides it
+    pub claim_escrow_period: i64,       // 8 bytes - seconds claims sit in escrow; 0 pays out directly
+    pub vote_quorum_bps: u16,           // 2 bytes - valid votes needed, share of all votes
+    pub stake_quorum_bps: u16,          // 2 bytes - stake behind valid votes, share of all stake
+    pub relayer_claim_fee: u64,         // 8 bytes - lamports a relayed claim pays its relayer
+    pub creation_fee_bps: u16,          // 2 bytes - share of the reward pool paid to the insurance pool
+    pub waldo_bond: u64,                // 8 bytes - lamports a creator locks in each challenge
+    pub retry_band_bps: u16,            // 2 bytes - R* above threshold by at most this much is inconclusive
+    pub retry_discount_bps: u16,        // 2 bytes - fee and bond discount when cloning an inconclusive challenge
+    pub guardian: Pubkey,               // 32 bytes - may freeze and unfreeze individual challenges
+    pub duration_tiers: [DurationTier; DURATION_TIERS], // 3 * 10 = 30 bytes - short, standard, extended
+    pub vote_gas_rebate: u64,           // 8 bytes - per-vote rebate underwritten challenges guarantee
+    pub min_witness_compensation: u64,  // 8 bytes - floor underwritten challenges guarantee each winner
+    pub vote_committee_key: [u8; 32],   // 32 bytes - joint key sealed votes are encrypted to; zero = unset
+    pub attestation_program: Pubkey,    // 32 bytes - owner of credential attestations; default = disabled
+    pub integrators: [IntegratorNamespace; MAX_INTEGRATORS], // 4 * 33 = 132 bytes - namespace allowlist
+    pub max_active_challenges: u32,     // 4 bytes - open challenges a creator may hold at once; 0 = uncapped
+    pub prior_validity_window: i64,     // 8 bytes - seconds after its deadline a passed result still counts as a prior
+    pub prior_quorum_relief_bps: u16,   // 2 bytes - share of both quorums waived for a challenge with a prior
+    pub funders: [Pubkey; MAX_FUNDERS], // 4 * 32 = 128 bytes - programs whose PDAs may fund challenges
+    pub result_ttl: i64,                // 8 bytes - seconds a finalized result stays current; 0 = forever
+    pub voting_window: i64,             // 8 bytes - seconds votes are taken after a deadline; new challenges only
+    pub max_participants: u32,          // 4 bytes - stakes a challenge accepts
+    pub r_star_threshold: u32,          // 4 bytes - meters; acceptance threshold for new challenges
+    pub max_pool_lamports: u64,         // 8 bytes - cap on a challenge's committed TVL; 0 = uncapped
+    pub measurement_max_age: i64,       // 8 bytes - seconds a vote's measurement stays usable; new challenges only, 0 = unbounded
+    pub min_r_star_threshold: u32,      // 4 bytes - meters; tightest threshold a creator may request
+    pub max_r_star_threshold: u32,      // 4 bytes - meters; loosest threshold a creator may request
+    pub claim_period: i64,              // 8 bytes - seconds winners have to claim after finalize; 0 = forever
+    pub exclusion_zones: [ExclusionZone; MAX_EXCLUSION_ZONES], // 8 * 16 = 128 bytes - areas challenges may not claim
+    pub min_settlement_buffer: i64,     // 8 bytes - seconds after voting closes before any finalize
+    pub usd_rate: u64,                  // 8 bytes - micro-USD per SOL, as the admin last set it; 0 = unset
+    pub min_stake_usd: u64,             // 8 bytes - micro-USD floor on lamport stakes at `usd_rate`; 0 = none
+    // Total payload size: 733 bytes
+}
+
+impl Config {
+    // NOTE: used as `space = 8 + Config::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 733;
+
+    pub const DEFAULT_MAX_CHALLENGES_PER_WINDOW: u32 = 10;
+    pub const DEFAULT_RATE_LIMIT_WINDOW: i64 = 3_600;
+    pub const DEFAULT_SLASH_COOLDOWN: i64 = 7 * 86_400;
+    pub const DEFAULT_ORACLE_REPORT_BOND: u64 = 50_000_000; // 0.05 SOL
+    pub const DEFAULT_ORACLE_DEVIATION_BAND_BPS: u16 = 2_000; // 20%
+    pub const DEFAULT_MIN_STAKE: u64 = 1_000_000;             // 0.001 SOL
+    pub const DEFAULT_MIN_PARTICIPANTS: u32 = 3;
+    pub const DEFAULT_VOTE_QUORUM_BPS: u16 = 5_000;
+    pub const DEFAULT_STAKE_QUORUM_BPS: u16 = 5_000;
+    pub const DEFAULT_RELAYER_CLAIM_FEE: u64 = 10_000;
+    pub const DEFAULT_RETRY_BAND_BPS: u16 = 1_000;            // 10% above threshold
+    pub const DEFAULT_RETRY_DISCOUNT_BPS: u16 = 5_000;        // half off
+    pub const DEFAULT_DURATION_TIERS: [DurationTier; DURATION_TIERS] = [
+        DurationTier { max_duration: 3_600, fee_multiplier_bps: 10_000 },       // short: 1h at 1x
+        DurationTier { max_duration: 86_400, fee_multiplier_bps: 10_000 },      // standard: 24h at 1x
+        DurationTier { max_duration: 7 * 86_400, fee_multiplier_bps: 30_000 },  // extended: 7d at 3x
+    ];
+    pub const DEFAULT_VOTE_GAS_REBATE: u64 = 5_000;           // one signature fee
+    pub const DEFAULT_MIN_WITNESS_COMPENSATION: u64 = 0;
+    pub const DEFAULT_MAX_ACTIVE_CHALLENGES: u32 = 5;
+    pub const DEFAULT_PRIOR_VALIDITY_WINDOW: i64 = 7 * 86_400;
+    pub const DEFAULT_PRIOR_QUORUM_RELIEF_BPS: u16 = 5_000;   // half the usual quorum
+    pub const DEFAULT_RESULT_TTL: i64 = 30 * 86_400;
+    pub const DEFAULT_VOTING_WINDOW: i64 = VOTING_WINDOW;
+    pub const DEFAULT_MAX_PARTICIPANTS: u32 = MAX_PARTICIPANTS;
+    pub const DEFAULT_R_STAR_THRESHOLD: u32 = DEFAULT_R_STAR_THRESHOLD;
+    pub const DEFAULT_MAX_POOL_LAMPORTS: u64 = 100_000_000_000; // 100 SOL
+    pub const DEFAULT_MEASUREMENT_MAX_AGE: i64 = 3_600;
+    pub const DEFAULT_MIN_R_STAR_THRESHOLD: u32 = 10;         // building level
+    pub const DEFAULT_MAX_R_STAR_THRESHOLD: u32 = 50_000;     // city level; the largest uncertainty a vote may report
+    pub const DEFAULT_CLAIM_PERIOD: i64 = 90 * 86_400;
+    pub const DEFAULT_MIN_SETTLEMENT_BUFFER: i64 = SETTLEMENT_BUFFER;
+
+    /// Namespace prefix allowlisted for `program`, if any.
+    pub fn integrator_prefix(&self, program: &Pubkey) -> Option<u8> {
+        self.integrators
+            .iter()
+            .find(|entry| entry.program == *program && *program != Pubkey::default())
+            .map(|entry| entry.prefix)
+    }
+
+    /// Fails when `(lat, lon)` lies in one of the exclusion zones.
+    pub fn check_not_excluded(&self, lat: i32, lon: i32) -> Result<()> {
+        require!(
+            !self.exclusion_zones.iter().any(|zone| zone.contains(lat, lon)),
+            crate::errors::PolocError::LocationExcluded
+        );
+        Ok(())
+    }
+
+    /// Whether `program` is allowlisted to fund challenges from its PDAs.
+    pub fn is_funder(&self, program: &Pubkey) -> bool {
+        *program != Pubkey::default() && self.funders.contains(program)
+    }
+
+    /// Lamports `min_stake_usd` comes to at `usd_rate`, rounded up; 0 while
+    /// either is unset.
+    pub fn usd_min_stake(&self) -> u64 {
+        if self.usd_rate == 0 || self.min_stake_usd == 0 {
+            return 0;
+        }
+        let lamports = (self.min_stake_usd as u128 * 1_000_000_000).div_ceil(self.usd_rate as u128);
+        lamports.min(u64::MAX as u128) as u64
+    }
+
+    /// Rate a new stake on `challenge` snapshots for `rebase_penalty`. Token
+    /// stakes aren't priced.
+    pub fn stake_usd_rate(&self, challenge: &Challenge) -> u64 {
+        if challenge.is_token_pool() { 0 } else { self.usd_rate }
+    }
+
+    /// Fails when `committed_tvl` lamports would exceed the per-challenge cap.
+    pub fn check_pool_cap(&self, committed_tvl: u64) -> Result<()> {
+        require!(
+            self.max_pool_lamports == 0 || committed_tvl <= self.max_pool_lamports,
+            crate::errors::PolocError::PoolCapExceeded
+        );
+        Ok(())
+    }
+
+    /// Threshold for a new challenge: the creator's request when it's within
+    /// the configured bounds, or `default` when they asked for none (0).
+    pub fn resolve_r_star_threshold(&self, requested: u32, default: u32) -> Result<u32> {
+        if requested == 0 {
+            return Ok(default);
+        }
+        require!(
+            (self.min_r_star_threshold..=self.max_r_star_threshold).contains(&requested),
+            crate::errors::PolocError::ThresholdOutOfBounds
+        );
+        Ok(requested)
+    }
+
+    /// Shortest tier that covers `duration`, if any.
+    pub fn duration_tier(&self, duration: u64) -> Option<&DurationTier> {
+        self.duration_tiers.iter().find(|tier| duration <= tier.max_duration)
+    }
+}
+
+// An account passed to a challenge's result callback. Callbacks can't ask for
+// signers; the challenge's callback PDA signs as proof of origin.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+#[derive(bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct CallbackAccount {
+    pub pubkey: Pubkey,                 // 32 bytes
+    pub is_writable: u8,                // 1 byte - bool
+}
+
+// One location of an any-of challenge and the valid support it drew.
+#[zero_copy]
+#[derive(PartialEq, Eq, Default)]
+pub struct CandidateLocation {
+    pub lat: i32,                       // 4 bytes - micro-degrees
+    pub lon: i32,                       // 4 bytes - micro-degrees
+    pub valid_votes: u32,               // 4 bytes
+    pub _padding: [u8; 4],              // 4 bytes
+    pub valid_stake: u64,               // 8 bytes
+}
+
+// Repeating voting rounds, so witnesses measure in sync: a round opens every
+// `period` seconds, `offset` seconds past a multiple of `period` on the unix
+// clock, and stays open for `length` seconds. Hourly on the hour for five
+// minutes is { period: 3600, offset: 0, length: 300 }.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default, Debug)]
+#[derive(bytemuck::Pod, bytemuck::Zeroable)]
+#[repr(C)]
+pub struct VoteSchedule {
+    pub period: u32,                    // 4 bytes - seconds between round openings; 0 = no schedule
+    pub offset: u32,                    // 4 bytes - seconds past each multiple of `period`
+    pub length: u32,                    // 4 bytes - seconds each round stays open
+}
+
+impl VoteSchedule {
+    pub fn is_set(&self) -> bool {
+        self.period > 0
+    }
+
+    /// Unset, or a round that opens inside its period and closes before the next.
+    pub fn is_well_formed(&self) -> bool {
+        !self.is_set() || (self.length > 0 && self.length <= self.period && self.offset < self.period)
+    }
+
+    /// Whether a round is open at `now`. Always true when unset.
+    pub fn is_open_at(&self, now: i64) -> bool {
+        !self.is_set() || (now - self.offset as i64).rem_euclid(self.period as i64) < self.length as i64
+    }
+
+    /// When the first round after `now` opens. Only meaningful when set.
+    pub fn next_round_at(&self, now: i64) -> i64 {
+        let period = self.period as i64;
+        now - (now - self.offset as i64).rem_euclid(period) + period
+    }
+}
+
+// An integrator program allowed to create challenges whose ids start with
+// `prefix`, a byte users can't start an id with.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct IntegratorNamespace {
+    pub program: Pubkey,                // 32 bytes - default = empty slot
+    pub prefix: u8,                     // 1 byte - 0x01..=0x1f
+}
+
+impl IntegratorNamespace {
+    /// Namespace prefix of a challenge id: a leading control byte, which
+    /// user-created ids may not have. Every PDA derived from the id (the
+    /// challenge, its stakes and votes) inherits the namespace.
+    pub fn prefix_of(challenge_id: &str) -> Option<u8> {
+        challenge_id.as_bytes().first().copied().filter(|b| (0x01..0x20).contains(b))
+    }
+}
+
+// A latitude/longitude box, in micro-degrees and inclusive at both ends,
+// that challenges may not claim a location in. Zones crossing the
+// antimeridian take two entries.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExclusionZone {
+    pub min_lat: i32,                   // 4 bytes
+    pub min_lon: i32,                   // 4 bytes
+    pub max_lat: i32,                   // 4 bytes
+    pub max_lon: i32,                   // 4 bytes
+}
+
+impl ExclusionZone {
+    /// An all-zero zone is an empty slot.
+    pub fn is_set(&self) -> bool {
+        *self != Self::default()
+    }
+
+    pub fn is_well_formed(&self) -> bool {
+        !self.is_set()
+            || (-90_000_000 <= self.min_lat
+                && self.min_lat <= self.max_lat
+                && self.max_lat <= 90_000_000
+                && -180_000_000 <= self.min_lon
+                && self.min_lon <= self.max_lon
+                && self.max_lon <= 180_000_000)
+    }
+
+    pub fn contains(&self, lat: i32, lon: i32) -> bool {
+        self.is_set()
+            && (self.min_lat..=self.max_lat).contains(&lat)
+            && (self.min_lon..=self.max_lon).contains(&lon)
+    }
+}
+
+// A challenge length band and the creation-fee multiplier it costs. Tiers are
+// kept in ascending `max_duration` order.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct DurationTier {
+    pub max_duration: u64,              // 8 bytes - seconds
+    pub fee_multiplier_bps: u16,        // 2 bytes - applied to the creation fee
+}
+
+// Compiled-in protocol constants of the running deployment, and the config's
+// live participant cap, refreshed by the permissionless `sync_protocol_info`.
+// Seeds: ["protocol_info"].
+#[account]
+pub struct ProtocolInfo {
+    pub version: String,                // 4 + 16 bytes - PROTOCOL_VERSION
+    pub event_schema_version: u8,       // 1 byte
+    pub voting_window: i64,             // 8 bytes
+    pub finalize_grace: i64,            // 8 bytes
+    pub max_challenge_duration: u64,    // 8 bytes
+    pub max_schedule_ahead: i64,        // 8 bytes
+    pub max_participants: u32,          // 4 bytes - Config::max_participants as of the last sync
+    pub max_tranches: u8,               // 1 byte
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 59 bytes
+}
+
+impl ProtocolInfo {
+    // NOTE: used as `space = 8 + ProtocolInfo::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 59;
+    pub const MAX_VERSION_LEN: usize = 16;
+}
+
+// Per-cell overrides of the global staking limits. Seeds:
+// ["region", cell_lat (i16 LE), cell_lon (i16 LE)]; cells are 1x1 degree.
+// A zero field means "use the Config value".
+#[account]
+pub struct RegionPolicy {
+    pub cell_lat: i16,                  // 2 bytes
+    pub cell_lon: i16,                  // 2 bytes
+    pub min_stake: u64,                 // 8 bytes
+    pub min_participants: u32,          // 4 bytes
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 17 bytes
+}
+
+impl RegionPolicy {
+    // NOTE: used as `space = 8 + RegionPolicy::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 17;
+
+    pub const CELL_SIZE: i32 = 1_000_000; // micro-degrees
+
+    pub fn cell(lat: i32, lon: i32) -> (i16, i16) {
+        (
+            lat.div_euclid(Self::CELL_SIZE) as i16,
+            lon.div_euclid(Self::CELL_SIZE) as i16,
+        )
+    }
+
+    pub fn address(cell_lat: i16, cell_lon: i16) -> Pubkey {
+        Pubkey::find_program_address(
+            &[REGION_SEED, &cell_lat.to_le_bytes(), &cell_lon.to_le_bytes()],
+            &crate::ID,
+        )
+        .0
+    }
+}
+
+// Settlement outcomes per 1x1 degree cell, for coverage analytics. Seeds:
+// ["region_stats", cell_lat (i16 LE), cell_lon (i16 LE)]. Created by anyone
+// via `initialize_region_stats`; public challenges are counted at finalize
+// once it exists.
+#[account]
+pub struct RegionStats {
+    pub cell_lat: i16,                  // 2 bytes
+    pub cell_lon: i16,                  // 2 bytes
+    pub finalized: u32,                 // 4 bytes - challenges that reached a result
+    pub passed: u32,                    // 4 bytes - of those, how many passed
+    pub insufficient: u32,              // 4 bytes - closed for lack of witnesses
+    pub r_star_sum: u64,                // 8 bytes - over `finalized`, for the average
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 25 bytes
+}
+
+impl RegionStats {
+    // NOTE: used as `space = 8 + RegionStats::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 25;
+
+    pub fn address(cell_lat: i16, cell_lon: i16) -> Pubkey {
+        Pubkey::find_program_address(
+            &[REGION_STATS_SEED, &cell_lat.to_le_bytes(), &cell_lon.to_le_bytes()],
+            &crate::ID,
+        )
+        .0
+    }
+
+    /// Counts a settled challenge. Only Finalized results carry an R*.
+    pub fn record(&mut self, challenge: &Challenge) {
+        match challenge.status() {
+            ChallengeStatus::Finalized => {
+                self.finalized = self.finalized.saturating_add(1);
+                self.passed = self.passed.saturating_add(challenge.passed() as u32);
+                self.r_star_sum = self.r_star_sum.saturating_add(challenge.r_star as u64);
+            }
+            ChallengeStatus::InsufficientParticipants => {
+                self.insufficient = self.insufficient.saturating_add(1);
+            }
+            _ => {}
+        }
+    }
+
+    /// Share of finalized challenges that passed, in basis points.
+    pub fn pass_rate_bps(&self) -> u16 {
+        (self.passed as u64 * 10_000).checked_div(self.finalized as u64).unwrap_or(0) as u16
+    }
+
+    /// Mean R* over finalized challenges, in meters.
+    pub fn average_r_star(&self) -> u32 {
+        self.r_star_sum.checked_div(self.finalized as u64).unwrap_or(0) as u32
+    }
+}
+
+// Receives penalties (e.g. forfeited oracle bonds). Seeds: ["insurance"].
+// Lamports above rent are the pool balance.
+#[account]
+pub struct InsurancePool {
+    pub total_received: u64,            // 8 bytes
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 9 bytes
+}
+
+impl InsurancePool {
+    // NOTE: used as `space = 8 + InsurancePool::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 9;
+}
+
+// Per-creator bookkeeping. Seeds: ["waldo", waldo].
+#[account]
+pub struct WaldoProfile {
+    pub waldo: Pubkey,                  // 32 bytes
+    pub window_start: i64,              // 8 bytes
+    pub current_window_count: u32,      // 4 bytes
+    pub previous_window_count: u32,     // 4 bytes
+    pub total_challenges: u64,          // 8 bytes
+    pub bump: u8,                       // 1 byte
+    pub active_challenges: u32,         // 4 bytes - created and not yet settled
+    // Total payload size: 61 bytes
+}
+
+impl WaldoProfile {
+    // NOTE: used as `space = 8 + WaldoProfile::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 61;
+
+    /// Frees the slot `challenge` took at creation. Runs on every settlement
+    /// path that sees the profile; the flag keeps it to once per challenge.
+    pub fn release_active(&mut self, challenge: &mut Challenge) {
+        if challenge.holds_active_slot != 0 {
+            challenge.holds_active_slot = 0;
+            self.active_challenges = self.active_challenges.saturating_sub(1);
+        }
+    }
+
+    // Sliding-window counter: the previous window's count is weighted by how
+    // much of it still overlaps the trailing `window` seconds. Returns false
+    // (without recording) when one more challenge would exceed `max`.
+    pub fn try_record_challenge(&mut self, now: i64, window: i64, max: u32) -> bool {
+        let window = window.max(1);
+        let elapsed = now.saturating_sub(self.window_start);
+        if elapsed >= 2 * window {
+            self.previous_window_count = 0;
+            self.current_window_count = 0;
+            self.window_start = now;
+        } else if elapsed >= window {
+            self.previous_window_count = self.current_window_count;
+            self.current_window_count = 0;
+            self.window_start += window;
+        }
+
+        let into_window = now.saturating_sub(self.window_start).clamp(0, window);
+        let carried = (self.previous_window_count as i64) * (window - into_window) / window;
+        if carried + self.current_window_count as i64 >= max as i64 {
+            return false;
+        }
+
+        self.current_window_count += 1;
+        self.total_challenges = self.total_challenges.saturating_add(1);
+        true
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct OracleEntry {
+    pub authority: Pubkey,              // 32 bytes
+    pub nonce: u64,                     // 8 bytes - highest nonce accepted so far
+    pub score: u32,                     // 4 bytes - lowered when a report strays from the median
+    pub signing_key: Pubkey,            // 32 bytes - signs result payloads; starts as `authority`
+    pub previous_key: Pubkey,           // 32 bytes - signing key before the last rotation; default = none
+    pub key_activated_at: i64,          // 8 bytes - when `signing_key` took over from `previous_key`
+}
+
+impl OracleEntry {
+    pub fn new(authority: Pubkey) -> Self {
+        Self {
+            authority,
+            nonce: 0,
+            score: OracleSet::INITIAL_SCORE,
+            signing_key: authority,
+            previous_key: Pubkey::default(),
+            key_activated_at: 0,
+        }
+    }
+
+    /// Key that signs results for a challenge started at `start_time`.
+    /// Challenges already running at a rotation stay on the previous key, so
+    /// rotating doesn't strand results that are in flight.
+    pub fn signing_key_for(&self, start_time: i64) -> Pubkey {
+        if start_time < self.key_activated_at && self.previous_key != Pubkey::default() {
+            self.previous_key
+        } else {
+            self.signing_key
+        }
+    }
+}
+
+// Registered result oracles for the multi-oracle path. Seeds: ["oracle_set"].
+#[account]
+pub struct OracleSet {
+    pub oracles: Vec<OracleEntry>,      // 4 + 7 * 116 = 816 bytes
+    pub bump: u8,                       // 1 byte
+    pub quorum: u8,                     // 1 byte - reports a multi-oracle finalize needs; 0 = no set quorum
+    // Total payload size: 818 bytes
+}
+
+impl OracleSet {
+    // NOTE: used as `space = 8 + OracleSet::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 818;
+
+    pub const MAX_ORACLES: usize = 7;
+    pub const INITIAL_SCORE: u32 = 1_000;
+    pub const DEVIATION_SCORE_PENALTY: u32 = 100;
+
+    pub fn entry_mut(&mut self, authority: &Pubkey) -> Option<&mut OracleEntry> {
+        self.oracles.iter_mut().find(|o| o.authority == *authority)
+    }
+
+    /// Reports `finalize_with_oracles` needs before it settles anything.
+    pub fn required_reports(&self) -> usize {
+        (self.quorum as usize).max(1)
+    }
+}
+
+// One oracle's result for one challenge. Seeds: ["oracle_report", challenge_id, oracle].
+#[account]
+pub struct OracleReport {
+    pub oracle: Pubkey,                 // 32 bytes
+    pub challenge: Pubkey,              // 32 bytes
+    pub r_star: u32,                    // 4 bytes
+    pub nonce: u64,                     // 8 bytes
+    pub payload_hash: [u8; 32],         // 32 bytes
+    pub bump: u8,                       // 1 byte
+    pub bond: u64,                      // 8 bytes - escrowed in this account until reclaimed
+    pub penalized: bool,                // 1 byte
+    // Total payload size: 118 bytes
+}
+
+impl OracleReport {
+    // NOTE: used as `space = 8 + OracleReport::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 118;
+}
+
+// Lamports a witness sets aside for relayed stake intents. Seeds:
+// ["intent_escrow", witness]. The balance is everything above rent.
+#[account]
+pub struct IntentEscrow {
+    pub witness: Pubkey,                // 32 bytes
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 33 bytes
+}
+
+impl IntentEscrow {
+    // NOTE: used as `space = 8 + IntentEscrow::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 33;
+}
+
+// Collateral a witness posts once and stakes from across challenges. Seeds:
+// ["standing_stake", witness]. Stakes drawn from it stay here, locked, instead
+// of joining a challenge's pool; the balance is everything above rent.
+#[account]
+pub struct StandingStake {
+    pub witness: Pubkey,                // 32 bytes
+    pub locked: u64,                    // 8 bytes - lamports backing unreleased stakes
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 41 bytes
+}
+
+impl StandingStake {
+    // NOTE: used as `space = 8 + StandingStake::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 41;
+}
+
+// Holds a challenge's lamports: its reward pool, stakes and the creator's
+// bond. Seeds: ["vault", challenge_id]. Created and closed with the
+// challenge, which keeps only its own rent.
+#[account]
+pub struct ChallengeVault {
+    pub challenge: Pubkey,              // 32 bytes
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 33 bytes
+}
+
+impl ChallengeVault {
+    // NOTE: used as `space = 8 + ChallengeVault::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 33;
+}
+
+// A pending governance withdrawal from a frozen challenge's vault. Seeds:
+// ["emergency_withdrawal", challenge]. At most one per challenge; it can run
+// from `executable_at` on, if the challenge is still frozen.
+#[account]
+pub struct EmergencyWithdrawal {
+    pub challenge: Pubkey,              // 32 bytes
+    pub destination: Pubkey,            // 32 bytes
+    pub amount: u64,                    // 8 bytes - lamports
+    pub proposed_at: i64,               // 8 bytes
+    pub executable_at: i64,             // 8 bytes - proposed_at + EMERGENCY_WITHDRAW_DELAY
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 89 bytes
+}
+
+impl EmergencyWithdrawal {
+    // NOTE: used as `space = 8 + EmergencyWithdrawal::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 89;
+}
+
+// A witness's most recent claims, oldest overwritten first, so operators have
+// an income statement without an indexer. Seeds: ["payout_ledger", wallet].
+#[account]
+pub struct PayoutLedger {
+    pub wallet: Pubkey,                 // 32 bytes
+    pub entries: [PayoutEntry; PAYOUT_LEDGER_CAPACITY], // 16 * 48 = 768 bytes
+    pub next: u8,                       // 1 byte - slot the next entry is written to
+    pub total_entries: u64,             // 8 bytes - claims recorded, including overwritten ones
+    pub total_earned: u64,              // 8 bytes - lamports, including overwritten entries
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 818 bytes
+}
+
+impl PayoutLedger {
+    // NOTE: used as `space = 8 + PayoutLedger::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 818;
+
+    pub fn record(&mut self, challenge: Pubkey, amount: u64, timestamp: i64) {
+        self.entries[self.next as usize] = PayoutEntry { challenge, amount, timestamp };
+        self.next = ((self.next as usize + 1) % PAYOUT_LEDGER_CAPACITY) as u8;
+        self.total_entries = self.total_entries.saturating_add(1);
+        self.total_earned = self.total_earned.saturating_add(amount);
+    }
+
+    /// Recorded entries, oldest first.
+    pub fn recent(&self) -> impl Iterator<Item = &PayoutEntry> {
+        let filled = (self.total_entries as usize).min(PAYOUT_LEDGER_CAPACITY);
+        let start = if filled < PAYOUT_LEDGER_CAPACITY { 0 } else { self.next as usize };
+        (0..filled).map(move |i| &self.entries[(start + i) % PAYOUT_LEDGER_CAPACITY])
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct PayoutEntry {
+    pub challenge: Pubkey,              // 32 bytes
+    pub amount: u64,                    // 8 bytes - lamports paid or escrowed, after relayer fees
+    pub timestamp: i64,                 // 8 bytes
+}
+
+// Recently created challenges and their deadlines, oldest overwritten first,
+// so keepers can find due work by polling one account instead of scanning
+// every challenge. Seeds: ["expiry_feed"].
+#[account]
+pub struct ExpiryFeed {
+    pub entries: [ExpiryEntry; EXPIRY_FEED_CAPACITY], // 32 * 40 = 1280 bytes
+    pub next: u8,                       // 1 byte - slot the next entry is written to
+    pub total_entries: u64,             // 8 bytes - challenges recorded, including overwritten ones
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 1290 bytes
+}
+
+impl ExpiryFeed {
+    // NOTE: used as `space = 8 + ExpiryFeed::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 1290;
+
+    pub fn record(&mut self, challenge: Pubkey, deadline: i64) {
+        self.entries[self.next as usize] = ExpiryEntry { challenge, deadline };
+        self.next = ((self.next as usize + 1) % EXPIRY_FEED_CAPACITY) as u8;
+        self.total_entries = self.total_entries.saturating_add(1);
+    }
+
+    /// Entries not yet consumed whose deadline has passed, oldest first.
+    pub fn due(&self, now: i64) -> impl Iterator<Item = &ExpiryEntry> {
+        let filled = (self.total_entries as usize).min(EXPIRY_FEED_CAPACITY);
+        let start = if filled < EXPIRY_FEED_CAPACITY { 0 } else { self.next as usize };
+        (0..filled)
+            .map(move |i| &self.entries[(start + i) % EXPIRY_FEED_CAPACITY])
+            .filter(move |entry| !entry.is_consumed() && entry.deadline <= now)
+    }
+}
+
+// Where challenges are listed for enumeration: a run of fixed-size pages,
+// filled in order. Seeds: ["registry"].
+#[account]
+pub struct ChallengeRegistry {
+    pub current_page: u32,              // 4 bytes - page new challenges are appended to
+    pub total_listed: u64,              // 8 bytes - challenges ever listed
+    pub open_count: u64,                // 8 bytes - listed challenges not yet delisted
+    pub bump: u8,                       // 1 byte
+    pub tvl: u64,                       // 8 bytes - sum of the open entries' `tvl`
+    // Total payload size: 29 bytes
+}
+
+impl ChallengeRegistry {
+    // NOTE: used as `space = 8 + ChallengeRegistry::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 29;
+
+    /// Moves `entry`'s share of the total to `tvl`.
+    pub fn record_tvl(&mut self, entry: &mut RegistryEntry, tvl: u64) {
+        self.tvl = self.tvl.saturating_sub(entry.tvl).saturating_add(tvl);
+        entry.tvl = tvl;
+    }
+}
+
+// One page of the challenge registry. Seeds: ["registry_page", page (u32 LE)].
+// Entries stay in place once delisted, so a challenge's slot never moves.
+#[account]
+pub struct RegistryPage {
+    pub page: u32,                      // 4 bytes
+    pub entries: [RegistryEntry; REGISTRY_PAGE_CAPACITY], // 64 * 41 = 2624 bytes
+    pub count: u8,                      // 1 byte - slots used
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 2630 bytes
+}
+
+impl RegistryPage {
+    // NOTE: used as `space = 8 + RegistryPage::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 2630;
+
+    pub fn is_full(&self) -> bool {
+        self.count as usize >= REGISTRY_PAGE_CAPACITY
+    }
+
+    /// Challenges on this page that are still open.
+    pub fn open(&self) -> impl Iterator<Item = &Pubkey> {
+        self.entries[..self.count as usize].iter().filter(|e| e.open).map(|e| &e.challenge)
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct RegistryEntry {
+    pub challenge: Pubkey,              // 32 bytes
+    pub open: bool,                     // 1 byte - cleared when the challenge settles or closes
+    pub tvl: u64,                       // 8 bytes - `Challenge::tvl()` as of its last registry update
+}
+
+// One vote in `Challenge::roster`.
+#[zero_copy]
+pub struct RosterEntry {
+    pub challenger: Pubkey,             // 32 bytes
+    pub stake: u64,                     // 8 bytes - as recorded on the vote
+    pub uncertainty: u32,               // 4 bytes
+    pub min_rtt: u32,                   // 4 bytes
+    pub confidence: u16,                // 2 bytes
+    pub candidate: u8,                  // 1 byte
+    pub is_valid: u8,                   // 1 byte - bool
+    pub sealed: u8,                     // 1 byte - bool; contents zero until tallied
+    pub _padding: [u8; 3],              // 3 bytes
+}
+
+impl From<&Vote> for RosterEntry {
+    fn from(vote: &Vote) -> Self {
+        Self {
+            challenger: vote.challenger,
+            stake: vote.stake,
+            uncertainty: vote.uncertainty,
+            min_rtt: vote.min_rtt,
+            confidence: vote.confidence,
+            candidate: vote.candidate,
+            is_valid: vote.is_valid as u8,
+            sealed: vote.sealed as u8,
+            _padding: [0; 3],
+        }
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct ExpiryEntry {
+    pub challenge: Pubkey,              // 32 bytes - default once a keeper consumes it
+    pub deadline: i64,                  // 8 bytes - as of creation; extensions aren't tracked
+}
+
+impl ExpiryEntry {
+    pub fn is_consumed(&self) -> bool {
+        self.challenge == Pubkey::default()
+    }
+}
+
+// A claimed reward held for the dispute period when `claim_escrow_period` is
+// set. Seeds: ["claim_escrow", challenge_id, beneficiary]. The escrowed
+// amount sits on top of rent.
+#[account]
+pub struct ClaimEscrow {
+    pub challenge: Pubkey,              // 32 bytes
+    pub beneficiary: Pubkey,            // 32 bytes
+    pub amount: u64,                    // 8 bytes
+    pub release_at: i64,                // 8 bytes - `release_claim` allowed from here on
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 81 bytes
+}
+
+impl ClaimEscrow {
+    // NOTE: used as `space = 8 + ClaimEscrow::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 81;
+}
+
+// A sponsor's share of a challenge's reward pool. Seeds:
+// ["contribution", challenge_id, sponsor].
+#[account]
+pub struct Contribution {
+    pub challenge: Pubkey,              // 32 bytes
+    pub sponsor: Pubkey,                // 32 bytes
+    pub amount: u64,                    // 8 bytes - lamports contributed
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 73 bytes
+}
+
+impl Contribution {
+    // NOTE: used as `space = 8 + Contribution::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 73;
+}
+
+// Binds a prover device to its current challenge so it cannot claim two
+// places at once. Seeds: ["device", device_hash].
+#[account]
+pub struct DeviceLock {
+    pub device_hash: [u8; 32],          // 32 bytes - hash of the prover's device identifier
+    pub challenge: Pubkey,              // 32 bytes - latest challenge claimed from this device
+    pub claimed_lat: i32,               // 4 bytes
+    pub claimed_lon: i32,               // 4 bytes
+    pub locked_until: i64,              // 8 bytes - deadline of `challenge`
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 81 bytes
+}
+
+impl DeviceLock {
+    // NOTE: used as `space = 8 + DeviceLock::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 81;
+}
+
+// Per-witness bookkeeping. Seeds: ["challenger", wallet].
+#[account]
+pub struct ChallengerProfile {
+    pub wallet: Pubkey,                 // 32 bytes
+    pub slash_count: u32,               // 4 bytes
+    pub cooldown_until: i64,            // 8 bytes - no new stakes before this time
+    pub bump: u8,                       // 1 byte
+    pub equipment_class: u8,            // 1 byte - attested by the config admin, see EQUIPMENT_*
+    pub badge_tier: u8,                 // 1 byte - tier badge tokens minted so far, see `tier_for`
+    pub challenges_participated: u32,   // 4 bytes - stakes opened
+    pub correct_votes: u32,             // 4 bytes - votes whose reward was claimed
+    pub score: i32,                     // 4 bytes - reputation; see `record_correct_vote` and `record_slash`
+    pub streak: u32,                    // 4 bytes - rewarded votes in a row; see `fund_streak_bonus`
+    // Total payload size: 63 bytes
+}
+
+impl ChallengerProfile {
+    // NOTE: used as `space = 8 + ChallengerProfile::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 63;
+
+    // A slash costs as much reputation as this many correct votes earn.
+    pub const CORRECT_VOTE_SCORE: i32 = 1;
+    pub const SLASH_SCORE_PENALTY: i32 = 10;
+
+    pub fn record_participation(&mut self) {
+        self.challenges_participated = self.challenges_participated.saturating_add(1);
+    }
+
+    pub fn record_correct_vote(&mut self) {
+        self.correct_votes = self.correct_votes.saturating_add(1);
+        self.score = self.score.saturating_add(Self::CORRECT_VOTE_SCORE);
+    }
+
+    /// A cast vote that settled without a reward breaks the streak.
+    pub fn record_unrewarded_vote(&mut self) {
+        self.streak = 0;
+    }
+
+    pub fn record_slash(&mut self) {
+        self.slash_count = self.slash_count.saturating_add(1);
+        self.score = self.score.saturating_sub(Self::SLASH_SCORE_PENALTY);
+        self.streak = 0;
+    }
+
+    // Rewarded votes needed for each badge tier, lowest first.
+    pub const TIER_THRESHOLDS: [u64; 3] = [10, 100, 1_000];
+
+    /// Badge tier earned by `honest_votes` rewarded votes; 0 below the first threshold.
+    pub fn tier_for(honest_votes: u64) -> u8 {
+        Self::TIER_THRESHOLDS.iter().filter(|&&needed| honest_votes >= needed).count() as u8
+    }
+
+    // Equipment classes, ordered by measurement quality so challenges can
+    // require a minimum.
+    pub const EQUIPMENT_UNATTESTED: u8 = 0;
+    pub const EQUIPMENT_SMARTPHONE: u8 = 1;
+    pub const EQUIPMENT_LORA_GATEWAY: u8 = 2;
+    pub const EQUIPMENT_GNSS_RTK: u8 = 3;
+    pub const EQUIPMENT_FIXED_FIBER_PROBE: u8 = 4;
+}
+
+// Grounds for slashing a witness's stake. Seeds: ["evidence", challenge_id,
+// challenger]. The typed reason is checked on-chain when the evidence is
+// submitted; `hashes` carry the supporting material for auditors.
+#[account]
+pub struct Evidence {
+    pub challenge: Pubkey,              // 32 bytes
+    pub challenger: Pubkey,             // 32 bytes
+    pub submitter: Pubkey,              // 32 bytes
+    pub reason: SlashReason,            // 1 byte
+    pub hashes: [[u8; 32]; Evidence::MAX_HASHES], // 4 * 32 = 128 bytes
+    pub hash_count: u8,                 // 1 byte
+    pub submitted_at: i64,              // 8 bytes
+    pub consumed: bool,                 // 1 byte - used by a slash
+    pub bump: u8,                       // 1 byte
+    pub confidence: u16,                // 2 bytes - the vote's confidence; full for non-vote reasons
+    // Total payload size: 238 bytes
+}
+
+impl Evidence {
+    // NOTE: used as `space = 8 + Evidence::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 238;
+    pub const MAX_HASHES: usize = 4;
+
+    /// Hash a ContradictoryMeasurement claim must lead with: the vote as
+    /// recorded, so the evidence can't describe a different measurement.
+    pub fn measurement_hash(challenge: &Pubkey, vote: &Vote) -> [u8; 32] {
+        anchor_lang::solana_program::keccak::hashv(&[
+            challenge.as_ref(),
+            vote.challenger.as_ref(),
+            &[vote.is_valid as u8],
+            &vote.uncertainty.to_le_bytes(),
+            &vote.min_rtt.to_le_bytes(),
+            &vote.timestamp.to_le_bytes(),
+        ])
+        .to_bytes()
+    }
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum SlashReason {
+    /// Staked but never voted.
+    NoShow,
+    /// Voted against the finalized outcome.
+    ContradictoryMeasurement,
+    /// Wallet carries a collusion flag.
+    CollusionFlagged,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
+pub enum KeeperFault {
+    MissedCrank,
+    PrematureFinalize,
+    MaliciousCrank,
+}
+
+impl KeeperFault {
+    // (score penalty, share of stake slashed in basis points)
+    pub fn penalty(&self) -> (u32, u64) {
+        match self {
+            KeeperFault::MissedCrank => (50, 0),
+            KeeperFault::PrematureFinalize => (200, 1_000),
+            KeeperFault::MaliciousCrank => (500, 5_000),
+        }
+    }
+}
+
+// Shared reward budget for a group of challenges. Budget lamports are held in
+// the PDA itself. Seeds: ["campaign", campaign_id].
+#[account]
+pub struct Campaign {
+    pub authority: Pubkey,              // 32 bytes - creates the campaign's challenges
+    pub campaign_id: String,            // 4 + 32 = 36 bytes
+    pub budget: u64,                    // 8 bytes - lamports deposited
+    pub allocated: u64,                 // 8 bytes - drawn into challenge pools
+    pub max_pool: u64,                  // 8 bytes - per-challenge pool cap; 0 = none
+    pub challenge_count: u32,           // 4 bytes
+    pub open_challenges: u32,           // 4 bytes - created but not yet reported
+    pub passed_count: u32,              // 4 bytes
+    pub failed_count: u32,              // 4 bytes
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 109 bytes
+}
+
+impl Campaign {
+    // NOTE: used as `space = 8 + Campaign::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 109;
+
+    /// Budget not yet drawn into a challenge.
+    pub fn remaining(&self) -> u64 {
+        self.budget.saturating_sub(self.allocated)
+    }
+}
+
+// Automation operator eligible for crank tips. Seeds: ["keeper", operator].
+// Staked lamports are held in the PDA itself.
+#[account]
+pub struct Keeper {
+    pub operator: Pubkey,               // 32 bytes
+    pub stake: u64,                     // 8 bytes
+    pub score: u32,                     // 4 bytes
+    pub successful_cranks: u64,         // 8 bytes
+    pub faults: u32,                    // 4 bytes
+    pub last_active: i64,               // 8 bytes
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 65 bytes
+}
+
+impl Keeper {
+    // NOTE: used as `space = 8 + Keeper::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 65;
+
+    pub const MIN_STAKE: u64 = 100_000_000;       // 0.1 SOL
+    pub const INITIAL_SCORE: u32 = 1_000;
+    pub const MAX_SCORE: u32 = 10_000;
+    pub const MIN_TIP_SCORE: u32 = 500;           // below this, cranks are not tipped
+    pub const CRANK_TIP: u64 = 5_000;             // lamports per successful crank
+    pub const UNBONDING_PERIOD: i64 = 86_400;     // 24h after last activity
+
+    pub fn is_eligible(&self) -> bool {
+        self.stake >= Self::MIN_STAKE && self.score >= Self::MIN_TIP_SCORE
+    }
+}
+
+// `challenger` owns the stake and its reward rights; `operator` is the wallet
+// allowed to vote for it (the owner unless delegated via `delegate_stake`).
 #[account]
 pub struct Stake {
     pub challenger: Pubkey,             // 32 bytes
@@ -50,12 +1435,38 @@ pub struct Stake {
     pub timestamp: i64,                 // 8 bytes
     pub slashed: bool,                  // 1 byte
     pub bump: u8,                       // 1 byte
-    // Total payload size: 86 bytes
+    pub operator: Pubkey,               // 32 bytes
+    pub slashed_amount: u64,            // 8 bytes - share of `amount` forfeited by the slash
+    pub standing: bool,                 // 1 byte - locked in the witness's StandingStake instead of the pool
+    pub receipt_outstanding: bool,      // 1 byte - receipt tokens minted and not yet burned
+    pub claim_approvers: [Pubkey; MAX_CLAIM_APPROVERS], // 3 * 32 = 96 bytes - M-of-N claim policy signers
+    pub claim_threshold: u8,            // 1 byte - approvals `execute_claim` needs; 0 = no policy
+    pub claim_recipient: Pubkey,        // 32 bytes - where a policy-gated reward is paid
+    pub claim_approvals: u8,            // 1 byte - bitmap over `claim_approvers`
+    pub pooled: bool,                   // 1 byte - counted in the challenge's `pooled_stakes`
+    pub void: bool,                     // 1 byte - left by a `stake` that settled an overdue challenge instead
+    pub usd_rate: u64,                  // 8 bytes - config's `usd_rate` when staked; 0 = not snapshotted
+    // Total payload size: 268 bytes
 }
 
 impl Stake {
     // NOTE: used as `space = 8 + Stake::MAX_SIZE` where the `8 +` is the Anchor discriminator
-    pub const MAX_SIZE: usize = 86;
+    pub const MAX_SIZE: usize = 268;
+
+    pub fn has_claim_policy(&self) -> bool {
+        self.claim_threshold > 0
+    }
+
+    /// What goes back to the challenger: the stake less any slash.
+    pub fn unforfeited(&self) -> u64 {
+        self.amount - self.slashed_amount.min(self.amount)
+    }
+
+    pub fn claim_approver_index(&self, approver: &Pubkey) -> Option<usize> {
+        self.claim_approvers
+            .iter()
+            .position(|a| a == approver && *a != Pubkey::default())
+    }
 }
 
 #[account]
@@ -69,10 +1480,124 @@ pub struct Vote {
     pub timestamp: i64,                 // 8 bytes
     pub processed: bool,                // 1 byte
     pub bump: u8,                       // 1 byte
-    // Total payload size: 123 bytes
+    pub commitment: [u8; 32],           // 32 bytes - sealed votes only, see `sealed_vote_commitment`
+    pub sealed: bool,                   // 1 byte - contents hidden until `decrypt_and_tally`
+    pub candidate: u8,                  // 1 byte - index of the candidate location measured
+    pub confidence: u16,                // 2 bytes - self-reported, basis points; scales a slash
+    pub receipt_outstanding: bool,      // 1 byte - copied from the stake; blocks claims until burned
+    pub stake: u64,                     // 8 bytes - the stake's amount when cast; weighs R* and the reward split
+    pub measured_at: i64,               // 8 bytes - when the witness took the RTT measurement; 0 for sealed votes
+    pub prepared: bool,                 // 1 byte - created by `prepare_vote_account` and not yet cast
+    pub multisig_claim: bool,           // 1 byte - copied from the stake; reward only via `execute_claim`
+    pub chain_index: u32,               // 4 bytes - position in the challenge's vote chain
+    pub chain_hash: [u8; 32],           // 32 bytes - chain head once this vote was linked
+    pub void: bool,                     // 1 byte - left by a vote that settled an overdue challenge instead
+    // Total payload size: 215 bytes
 }
 
 impl Vote {
     // NOTE: used as `space = 8 + Vote::MAX_SIZE` in `init`
-    pub const MAX_SIZE: usize = 123;
+    pub const MAX_SIZE: usize = 215;
+
+    const CHAIN_DOMAIN: &'static [u8] = b"poloc:vote-chain:v1";
+
+    /// Next vote chain head after `previous`: the vote's address, its voter,
+    /// and what it said as of casting. Sealed votes are linked by their
+    /// commitment, since their contents are zero until tallied.
+    pub fn chain_link(&self, previous: &[u8; 32], vote_key: &Pubkey) -> [u8; 32] {
+        anchor_lang::solana_program::keccak::hashv(&[
+            Self::CHAIN_DOMAIN,
+            previous,
+            vote_key.as_ref(),
+            self.challenger.as_ref(),
+            &[self.is_valid as u8, self.sealed as u8, self.candidate],
+            &self.uncertainty.to_le_bytes(),
+            &self.min_rtt.to_le_bytes(),
+            &self.confidence.to_le_bytes(),
+            &self.measured_at.to_le_bytes(),
+            &self.timestamp.to_le_bytes(),
+            &self.commitment,
+        ])
+        .to_bytes()
+    }
+}
+
+// Committee that threshold-decrypts sealed votes. Its joint encryption key is
+// `Config::vote_committee_key`. Seeds: ["vote_committee"].
+#[account]
+pub struct VoteCommittee {
+    pub members: Vec<Pubkey>,           // 4 + 8 * 32 = 260 bytes
+    pub threshold: u8,                  // 1 byte - shares needed before tallying
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 262 bytes
+}
+
+impl VoteCommittee {
+    // NOTE: used as `space = 8 + VoteCommittee::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 262;
+
+    // Fits the `Challenge::decryption_shares` bitmap
+    pub const MAX_MEMBERS: usize = 8;
+
+    pub fn member_index(&self, member: &Pubkey) -> Option<usize> {
+        self.members.iter().position(|m| m == member)
+    }
+}
+
+// Creators whose challenges skip the creation fee and waldo bond, e.g. for
+// academic or disaster-response deployments. Either listed by key, or holding
+// an attestation from `credential` under `schema`. Seeds: ["fee_waivers"].
+#[account]
+pub struct FeeWaiverList {
+    pub waldos: Vec<Pubkey>,            // 4 + 16 * 32 = 516 bytes
+    pub credential: Pubkey,             // 32 bytes - attestation issuer that also qualifies; default = none
+    pub schema: Pubkey,                 // 32 bytes - ignored unless `credential` is set
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 581 bytes
+}
+
+impl FeeWaiverList {
+    // NOTE: used as `space = 8 + FeeWaiverList::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 581;
+
+    pub const MAX_WALDOS: usize = 16;
+
+    pub fn is_credential_gated(&self) -> bool {
+        self.credential != Pubkey::default()
+    }
+}
+
+// Point-of-sale presence check: opened, measured and settled within minutes.
+// Votes live inline instead of in Stake/Vote PDAs, and the account closes at
+// finalize. Seeds: ["flash", waldo, flash_id (u64 LE)].
+#[account]
+pub struct FlashChallenge {
+    pub waldo: Pubkey,                  // 32 bytes
+    pub flash_id: u64,                  // 8 bytes - chosen by the waldo, unique among its open checks
+    pub claimed_lat: i32,               // 4 bytes - micro-degrees
+    pub claimed_lon: i32,               // 4 bytes - micro-degrees
+    pub deadline: i64,                  // 8 bytes - measuring and voting close together
+    pub r_star_threshold: u32,          // 4 bytes - meters
+    pub reward_per_witness: u64,        // 8 bytes - lamports paid to each voter at finalize
+    pub measurement_nonce: [u8; 32],    // 32 bytes - from slot hashes at open; beacons must echo it
+    pub vote_count: u8,                 // 1 byte
+    pub votes: [FlashVote; MAX_FLASH_WITNESSES], // 8 * 36 = 288 bytes
+    pub bump: u8,                       // 1 byte
+    // Total payload size: 390 bytes
+}
+
+impl FlashChallenge {
+    // NOTE: used as `space = 8 + FlashChallenge::MAX_SIZE` in `init`
+    pub const MAX_SIZE: usize = 390;
+
+    pub fn votes(&self) -> &[FlashVote] {
+        &self.votes[..self.vote_count as usize]
+    }
+}
+
+// A witness's beacon-verified measurement of a flash challenge.
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Default)]
+pub struct FlashVote {
+    pub witness: Pubkey,                // 32 bytes
+    pub uncertainty: u32,               // 4 bytes - meters
 }
@@ -0,0 +1,12 @@
+//! Verbose logging for debugging builds.
+
+/// `msg!` that only runs with the `trace` feature. Without it the call is
+/// compiled out, but its arguments still type-check, so variables used only
+/// for tracing don't trip unused warnings.
+macro_rules! trace {
+    ($($arg:tt)*) => {
+        if cfg!(feature = "trace") {
+            anchor_lang::prelude::msg!($($arg)*);
+        }
+    };
+}
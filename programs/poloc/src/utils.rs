@@ -0,0 +1,35 @@
+use anchor_lang::prelude::*;
+use crate::errors::PolocError;
+
+const EARTH_RADIUS_M: f64 = 6_371_000.0;
+
+/// Great-circle distance in meters between two micro-degree coordinates
+/// (haversine).
+pub fn distance_m(lat_a: i32, lon_a: i32, lat_b: i32, lon_b: i32) -> u64 {
+    let to_rad = |micro: i32| (micro as f64 / 1e6).to_radians();
+    let (phi_a, phi_b) = (to_rad(lat_a), to_rad(lat_b));
+    let d_phi = phi_b - phi_a;
+    let d_lambda = to_rad(lon_b) - to_rad(lon_a);
+
+    let h = (d_phi / 2.0).sin().powi(2)
+        + phi_a.cos() * phi_b.cos() * (d_lambda / 2.0).sin().powi(2);
+    (2.0 * EARTH_RADIUS_M * h.sqrt().min(1.0).asin()) as u64
+}
+
+/// Closes a program-owned account that is not wrapped in `Account<T>` (e.g.
+/// one passed through `remaining_accounts`), sending its lamports to
+/// `destination`. Mirrors what Anchor's `close = ...` constraint does.
+pub fn close_program_account(info: &AccountInfo, destination: &AccountInfo) -> Result<()> {
+    require_keys_eq!(*info.owner, crate::ID, PolocError::InvalidRemainingAccountOwner);
+
+    let lamports = info.lamports();
+    **destination.try_borrow_mut_lamports()? = destination
+        .lamports()
+        .checked_add(lamports)
+        .ok_or(PolocError::ArithmeticOverflow)?;
+    **info.try_borrow_mut_lamports()? = 0;
+
+    info.assign(&anchor_lang::system_program::ID);
+    info.resize(0)?;
+    Ok(())
+}
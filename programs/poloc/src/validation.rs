@@ -0,0 +1,340 @@
+use anchor_lang::prelude::*;
+use crate::state::*;
+use crate::constants::*;
+use crate::errors::PolocError;
+use crate::events::*;
+
+// Upper bound on accounts a batch instruction may pass via `remaining_accounts`:
+// one vote per participant at the highest cap `update_config` allows.
+pub const MAX_REMAINING_ACCOUNTS: usize = PARTICIPANT_CAP_CEILING as usize;
+
+/// Validates the shape of a `remaining_accounts` slice of `Vote` PDAs for
+/// `challenge_id` and deserializes them.
+///
+/// All checks run up front, before the calling handler mutates anything:
+/// 1. the count matches `expected_count` (when given) and the hard cap,
+/// 2. every account is owned by this program,
+/// 3. every address is the canonical vote PDA for its challenger,
+/// 4. accounts are strictly ascending by challenger pubkey (no duplicates),
+/// 5. no account is a prepared vote that was never cast.
+pub fn load_vote_accounts(
+    remaining_accounts: &[AccountInfo],
+    challenge_id: &str,
+    expected_count: Option<usize>,
+) -> Result<Vec<Vote>> {
+    let votes: Vec<Vote> = load_ordered_accounts(
+        remaining_accounts,
+        VOTE_SEED,
+        challenge_id,
+        expected_count,
+        |vote: &Vote| vote.challenger,
+        PolocError::MismatchedVoteAccountOrder,
+    )?;
+    // Prepared accounts aren't counted in `vote_count`, so they never belong here.
+    require!(votes.iter().all(|vote| !vote.prepared), PolocError::VoteNotCast);
+    Ok(votes)
+}
+
+/// Same as `load_vote_accounts`, for `Stake` PDAs.
+pub fn load_stake_accounts(
+    remaining_accounts: &[AccountInfo],
+    challenge_id: &str,
+    expected_count: Option<usize>,
+) -> Result<Vec<Stake>> {
+    load_ordered_accounts(
+        remaining_accounts,
+        STAKE_SEED,
+        challenge_id,
+        expected_count,
+        |stake: &Stake| stake.challenger,
+        PolocError::MismatchedStakeAccountOrder,
+    )
+}
+
+/// Fails with `error` unless `opens_at <= now <= closes_at`, logging a
+/// `TimingWindowMissed` with the boundaries first. The transaction still
+/// reverts, but its logs keep the event, so a client can tell how early or
+/// late it was and reschedule.
+pub fn require_in_window(
+    subject: Pubkey,
+    now: i64,
+    opens_at: i64,
+    closes_at: i64,
+    error: PolocError,
+) -> Result<()> {
+    if (opens_at..=closes_at).contains(&now) {
+        return Ok(());
+    }
+    emit!(TimingWindowMissed {
+        schema_version: EVENT_SCHEMA_VERSION,
+        challenge: subject,
+        error_code: error.into(),
+        now,
+        opens_at,
+        closes_at,
+    });
+    Err(error.into())
+}
+
+/// Effective `(min_stake, min_participants)` for `challenge`: the
+/// `RegionPolicy` for its cell when that account exists, else the `Config`
+/// values. `region_policy` must be the cell's PDA even when uninitialized, so
+/// callers cannot dodge an override by passing some other account.
+/// Unrevealed cloaked challenges have no cell yet and use the `Config` values.
+pub fn resolve_region_limits(
+    region_policy: &AccountInfo,
+    challenge: &Challenge,
+    config: &Config,
+) -> Result<(u64, u32)> {
+    if !challenge.location_known() {
+        return Ok((config.min_stake, config.min_participants));
+    }
+    let (cell_lat, cell_lon) = RegionPolicy::cell(challenge.claimed_lat, challenge.claimed_lon);
+    require_keys_eq!(
+        region_policy.key(),
+        RegionPolicy::address(cell_lat, cell_lon),
+        PolocError::InvalidRegionPolicy
+    );
+
+    let mut limits = (config.min_stake, config.min_participants);
+    if region_policy.owner == &crate::ID && !region_policy.data_is_empty() {
+        let policy = RegionPolicy::try_deserialize(&mut &region_policy.try_borrow_data()?[..])?;
+        if policy.min_stake > 0 {
+            limits.0 = policy.min_stake;
+        }
+        if policy.min_participants > 0 {
+            limits.1 = policy.min_participants;
+        }
+    }
+    Ok(limits)
+}
+
+/// Adds a settled `challenge` to `region_stats` when that account exists.
+/// Like `resolve_region_limits`, it must be the PDA of `cell` (the cell the
+/// challenge was in when finalize started) even when uninitialized, so callers
+/// cannot leave a result out of its cell's stats. Private challenges are kept
+/// out of the stats.
+pub fn record_region_stats(region_stats: &AccountInfo, cell: (i16, i16), challenge: &Challenge) -> Result<()> {
+    require_keys_eq!(
+        region_stats.key(),
+        RegionStats::address(cell.0, cell.1),
+        PolocError::InvalidRegionStats
+    );
+    if challenge.private != 0 || region_stats.owner != &crate::ID || region_stats.data_is_empty() {
+        return Ok(());
+    }
+    let mut stats = RegionStats::try_deserialize(&mut &region_stats.try_borrow_data()?[..])?;
+    stats.record(challenge);
+    stats.try_serialize(&mut &mut region_stats.try_borrow_mut_data()?[..])
+}
+
+// Seed prefix of attestation accounts under `Config::attestation_program`.
+const ATTESTATION_SEED: &[u8] = b"attestation";
+
+/// Checks `attestation` satisfies `challenge`'s credential requirement for
+/// `holder`. See `check_attestation`.
+pub fn check_credential(
+    attestation: Option<&AccountInfo>,
+    challenge: &Challenge,
+    config: &Config,
+    holder: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    if !challenge.requires_credential() {
+        return Ok(());
+    }
+    let attestation = attestation.ok_or(PolocError::CredentialRequired)?;
+    check_attestation(
+        attestation,
+        &challenge.required_credential,
+        &challenge.required_schema,
+        config,
+        holder,
+        now,
+    )
+}
+
+/// Whether `waldo`'s new challenge skips the creation fee and bond: listed by
+/// key, or holding the list's credential. An attestation that is passed but
+/// doesn't check out is an error rather than a silent charge.
+pub fn fee_waived(
+    fee_waivers: Option<&FeeWaiverList>,
+    attestation: Option<&AccountInfo>,
+    config: &Config,
+    waldo: &Pubkey,
+    now: i64,
+) -> Result<bool> {
+    let Some(list) = fee_waivers else {
+        return Ok(false);
+    };
+    if list.waldos.contains(waldo) {
+        return Ok(true);
+    }
+    match attestation {
+        Some(attestation) if list.is_credential_gated() => {
+            check_attestation(attestation, &list.credential, &list.schema, config, waldo, now)?;
+            Ok(true)
+        }
+        _ => Ok(false),
+    }
+}
+
+/// `attestation` must be the attestation program's
+/// `["attestation", credential, schema, holder]` PDA, so the address alone
+/// binds issuer, schema and subject; the data only has to be live and
+/// unexpired. Layout: discriminator (1), nonce, credential, schema (32 each),
+/// data (u32 length + bytes), signer (32), expiry (i64, 0 = never).
+fn check_attestation(
+    attestation: &AccountInfo,
+    credential: &Pubkey,
+    schema: &Pubkey,
+    config: &Config,
+    holder: &Pubkey,
+    now: i64,
+) -> Result<()> {
+    let (expected, _) = Pubkey::find_program_address(
+        &[ATTESTATION_SEED, credential.as_ref(), schema.as_ref(), holder.as_ref()],
+        &config.attestation_program,
+    );
+    require_keys_eq!(attestation.key(), expected, PolocError::InvalidCredential);
+    require_keys_eq!(*attestation.owner, config.attestation_program, PolocError::InvalidCredential);
+
+    let data = attestation.try_borrow_data()?;
+    let len_at = 1 + 32 * 3;
+    let data_len = data.get(len_at..len_at + 4)
+        .map(|b| u32::from_le_bytes(b.try_into().unwrap()) as usize)
+        .ok_or(PolocError::InvalidCredential)?;
+    let expiry_at = len_at + 4 + data_len + 32;
+    let expiry = data.get(expiry_at..expiry_at + 8)
+        .map(|b| i64::from_le_bytes(b.try_into().unwrap()))
+        .ok_or(PolocError::InvalidCredential)?;
+    require!(expiry == 0 || now < expiry, PolocError::CredentialExpired);
+    Ok(())
+}
+
+/// Per-challenge nonce: the newest entry of the SlotHashes sysvar mixed with
+/// the challenge address. Nobody knows it before the creating transaction
+/// lands, so a measurement carrying it was taken for this challenge.
+/// `slot_hashes` must already be address-checked by the caller.
+pub fn challenge_nonce(slot_hashes: &AccountInfo, challenge: &Pubkey) -> Result<[u8; 32]> {
+    let data = slot_hashes.try_borrow_data()?;
+    // u64 entry count, then (slot: u64, hash: [u8; 32]) newest first.
+    require!(data.get(..8).is_some_and(|count| count != [0u8; 8]), PolocError::InvalidParameters);
+    let newest = data.get(8..48).ok_or(PolocError::InvalidParameters)?;
+    Ok(anchor_lang::solana_program::keccak::hashv(&[newest, challenge.as_ref()]).to_bytes())
+}
+
+/// Same as `load_vote_accounts`, for `OracleReport` PDAs (ascending by oracle).
+pub fn load_oracle_reports(
+    remaining_accounts: &[AccountInfo],
+    challenge_id: &str,
+) -> Result<Vec<OracleReport>> {
+    load_ordered_accounts(
+        remaining_accounts,
+        ORACLE_REPORT_SEED,
+        challenge_id,
+        None,
+        |report: &OracleReport| report.oracle,
+        PolocError::MismatchedOracleReportOrder,
+    )
+}
+
+/// Loads `[wallet_vote, partner_vote]` pairs from `remaining_accounts` and
+/// checks they show `wallet` and `partner` casting identical votes in at least
+/// `min_challenges` distinct challenges. Returns the number of challenges.
+pub fn check_collusion_evidence(
+    remaining_accounts: &[AccountInfo],
+    wallet: &Pubkey,
+    partner: &Pubkey,
+    min_challenges: usize,
+) -> Result<usize> {
+    require!(remaining_accounts.len().is_multiple_of(2), PolocError::RemainingAccountsCountMismatch);
+    require!(
+        remaining_accounts.len() <= MAX_REMAINING_ACCOUNTS,
+        PolocError::TooManyRemainingAccounts
+    );
+    require_keys_neq!(*wallet, *partner, PolocError::InvalidParameters);
+
+    let mut seen: Vec<String> = Vec::with_capacity(remaining_accounts.len() / 2);
+    for pair in remaining_accounts.chunks(2) {
+        let mut votes = Vec::with_capacity(2);
+        for info in pair {
+            require_keys_eq!(*info.owner, crate::ID, PolocError::InvalidRemainingAccountOwner);
+            votes.push(Vote::try_deserialize(&mut &info.try_borrow_data()?[..])?);
+        }
+        let (ours, theirs) = (&votes[0], &votes[1]);
+
+        require_keys_eq!(ours.challenger, *wallet, PolocError::InsufficientCollusionEvidence);
+        require_keys_eq!(theirs.challenger, *partner, PolocError::InsufficientCollusionEvidence);
+        require!(
+            ours.challenge_id == theirs.challenge_id
+                && ours.is_valid == theirs.is_valid
+                && ours.uncertainty == theirs.uncertainty
+                && ours.min_rtt == theirs.min_rtt,
+            PolocError::InsufficientCollusionEvidence
+        );
+        require!(!seen.contains(&ours.challenge_id), PolocError::InsufficientCollusionEvidence);
+        seen.push(ours.challenge_id.clone());
+    }
+
+    require!(seen.len() >= min_challenges, PolocError::InsufficientCollusionEvidence);
+    Ok(seen.len())
+}
+
+fn load_ordered_accounts<T, F>(
+    remaining_accounts: &[AccountInfo],
+    seed_prefix: &[u8],
+    challenge_id: &str,
+    expected_count: Option<usize>,
+    challenger_of: F,
+    order_error: PolocError,
+) -> Result<Vec<T>>
+where
+    T: AccountDeserialize,
+    F: Fn(&T) -> Pubkey,
+{
+    if let Some(expected) = expected_count {
+        require!(
+            remaining_accounts.len() == expected,
+            PolocError::RemainingAccountsCountMismatch
+        );
+    }
+    require!(
+        remaining_accounts.len() <= MAX_REMAINING_ACCOUNTS,
+        PolocError::TooManyRemainingAccounts
+    );
+
+    let mut loaded = Vec::with_capacity(remaining_accounts.len());
+    let mut previous: Option<Pubkey> = None;
+
+    for (index, info) in remaining_accounts.iter().enumerate() {
+        if info.owner != &crate::ID {
+            msg!("remaining_accounts[{}] has wrong owner {}", index, info.owner);
+            return err!(PolocError::InvalidRemainingAccountOwner);
+        }
+
+        let data = info.try_borrow_data()?;
+        let account = T::try_deserialize(&mut &data[..])?;
+        let challenger = challenger_of(&account);
+
+        let (expected_key, _) = Pubkey::find_program_address(
+            &[seed_prefix, challenge_id.as_bytes(), challenger.as_ref()],
+            &crate::ID,
+        );
+        if info.key() != expected_key {
+            msg!("remaining_accounts[{}] is not the PDA for {}", index, challenger);
+            return err!(PolocError::InvalidRemainingAccountAddress);
+        }
+
+        if let Some(prev) = previous {
+            if challenger <= prev {
+                msg!("remaining_accounts[{}] out of order ({} after {})", index, challenger, prev);
+                return Err(order_error.into());
+            }
+        }
+        previous = Some(challenger);
+        loaded.push(account);
+    }
+
+    Ok(loaded)
+}
@@ -0,0 +1,9 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+// A default account, built by deserializing zeroed account data.
+pub fn blank<T: AccountDeserialize + Discriminator>(size: usize) -> T {
+    let mut data = vec![0u8; size];
+    data[..8].copy_from_slice(T::DISCRIMINATOR);
+    T::try_deserialize(&mut data.as_slice()).unwrap()
+}
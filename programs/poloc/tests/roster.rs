@@ -0,0 +1,40 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::blank;
+use poloc::constants::PARTICIPANT_CAP_CEILING;
+use poloc::errors::PolocError;
+use poloc::state::{Challenge, Vote};
+
+#[test]
+fn roster_holds_a_vote_per_participant_at_the_cap() {
+    assert_eq!(8 + std::mem::size_of::<Challenge>(), Challenge::MAX_SIZE);
+
+    let mut challenge: Challenge = bytemuck::Zeroable::zeroed();
+    let mut vote: Vote = blank(8 + Vote::MAX_SIZE);
+    for _ in 0..PARTICIPANT_CAP_CEILING {
+        vote.challenger = Pubkey::new_unique();
+        challenge.push_roster(&vote).unwrap();
+    }
+    assert_eq!(challenge.roster_entries().len(), PARTICIPANT_CAP_CEILING as usize);
+    assert_eq!(challenge.push_roster(&vote).unwrap_err(), PolocError::RosterFull.into());
+}
+
+#[test]
+fn tallied_votes_replace_their_sealed_entry() {
+    let mut challenge: Challenge = bytemuck::Zeroable::zeroed();
+    let mut vote: Vote = blank(8 + Vote::MAX_SIZE);
+    vote.challenger = Pubkey::new_unique();
+    vote.sealed = true;
+    challenge.push_roster(&vote).unwrap();
+
+    vote.sealed = false;
+    vote.is_valid = true;
+    vote.uncertainty = 40;
+    challenge.update_roster(&vote).unwrap();
+    let entry = challenge.roster_entries()[0];
+    assert_eq!((entry.sealed, entry.is_valid, entry.uncertainty), (0, 1, 40));
+
+    vote.challenger = Pubkey::new_unique();
+    assert_eq!(challenge.update_roster(&vote).unwrap_err(), PolocError::NotInRoster.into());
+}
@@ -0,0 +1,202 @@
+mod common;
+
+use anchor_lang::prelude::*;
+use common::blank;
+use poloc::errors::PolocError;
+use poloc::instructions::claim_reward::reward_share;
+use poloc::instructions::close_settled::require_stake_settled;
+use poloc::instructions::refund_failed_challenge::require_refundable;
+use poloc::instructions::slash::forfeit_stake;
+use poloc::instructions::stake::pool_stake;
+use poloc::instructions::token_pool::returnable_stake;
+use poloc::instructions::unstake::{release_pooled_stake, require_stakes_returned};
+use poloc::state::{Challenge, ChallengeRegistry, ChallengeStatus, Config, RegistryEntry, Stake, Vote};
+
+const STAKE: u64 = 5_000_000;
+const NOW: i64 = 1_700_000_000;
+
+fn expired_with_one_stake() -> (Challenge, Stake) {
+    let mut challenge: Challenge = bytemuck::Zeroable::zeroed();
+    challenge.set_status(ChallengeStatus::Expired);
+    challenge.reward_pool = 1_000_000_000 + STAKE;
+    challenge.participant_count = 1;
+    challenge.pooled_stakes = 1;
+    challenge.stake_principal = STAKE;
+
+    let mut stake: Stake = blank(8 + Stake::MAX_SIZE);
+    stake.challenger = Pubkey::new_unique();
+    stake.amount = STAKE;
+    stake.timestamp = NOW;
+    stake.pooled = true;
+    (challenge, stake)
+}
+
+#[test]
+fn refund_waits_for_every_pooled_stake() {
+    let (mut challenge, stake) = expired_with_one_stake();
+    assert_eq!(require_refundable(&challenge).unwrap_err(), PolocError::StakesOutstanding.into());
+
+    release_pooled_stake(&mut challenge, &stake, stake.amount).unwrap();
+    assert_eq!(challenge.pooled_stakes, 0);
+    assert_eq!(challenge.reward_pool, 1_000_000_000);
+    require_refundable(&challenge).unwrap();
+}
+
+#[test]
+fn uncounted_record_leaves_the_refund_blocked() {
+    let (mut challenge, _) = expired_with_one_stake();
+    // What `stake` leaves behind when it only settled an overdue challenge.
+    let mut empty: Stake = blank(8 + Stake::MAX_SIZE);
+    empty.challenger = Pubkey::new_unique();
+    empty.void = true;
+
+    release_pooled_stake(&mut challenge, &empty, empty.amount).unwrap();
+    assert_eq!(challenge.pooled_stakes, 1);
+    assert_eq!(challenge.reward_pool, 1_000_000_000 + STAKE);
+    assert_eq!(require_refundable(&challenge).unwrap_err(), PolocError::StakesOutstanding.into());
+    // Its rent comes back as soon as the challenge is settled.
+    require_stake_settled(Some(&challenge), &empty, NOW).unwrap();
+    // A live stake still has to come back through `unstake`.
+    let stake = blank::<Stake>(8 + Stake::MAX_SIZE);
+    assert_eq!(
+        require_stake_settled(Some(&challenge), &stake, NOW).unwrap_err(),
+        PolocError::InvalidParameters.into()
+    );
+}
+
+#[test]
+fn loser_unstakes_after_every_winner_claimed() {
+    let mut challenge: Challenge = bytemuck::Zeroable::zeroed();
+    challenge.reward_pool = 1_000_000_000;
+    let mut winner: Stake = blank(8 + Stake::MAX_SIZE);
+    winner.amount = STAKE;
+    pool_stake(&mut challenge, &mut winner, STAKE).unwrap();
+    let mut loser: Stake = blank(8 + Stake::MAX_SIZE);
+    loser.amount = 3 * STAKE;
+    pool_stake(&mut challenge, &mut loser, 3 * STAKE).unwrap();
+
+    // Passed, with the winner's the only valid vote.
+    challenge.set_status(ChallengeStatus::Finalized);
+    challenge.quorum_met = 1;
+    challenge.r_star_threshold = 100;
+    challenge.r_star = 50;
+    challenge.valid_vote_count = 1;
+    challenge.valid_stake = STAKE;
+    let mut vote: Vote = blank(8 + Vote::MAX_SIZE);
+    vote.is_valid = true;
+    vote.stake = STAKE;
+
+    // The winner splits the pool alone, without either stake in it.
+    let share = reward_share(&challenge, &vote).unwrap();
+    assert_eq!(share, 1_000_000_000);
+    challenge.reward_pool -= share;
+    challenge.claimed_stake += vote.stake;
+
+    release_pooled_stake(&mut challenge, &loser, loser.amount).unwrap();
+    release_pooled_stake(&mut challenge, &winner, winner.amount).unwrap();
+    assert_eq!(challenge.reward_pool, 0);
+    assert_eq!(challenge.stake_principal, 0);
+    assert_eq!(challenge.pooled_stakes, 0);
+}
+
+#[test]
+fn sweep_waits_for_stakes_left_in_the_pool() {
+    let (mut challenge, stake) = expired_with_one_stake();
+    assert_eq!(require_stakes_returned(&challenge).unwrap_err(), PolocError::StakesOutstanding.into());
+
+    release_pooled_stake(&mut challenge, &stake, stake.amount).unwrap();
+    require_stakes_returned(&challenge).unwrap();
+    // What the sweep sends to insurance: the pool, without the stake.
+    assert_eq!(challenge.reward_pool, 1_000_000_000);
+}
+
+#[test]
+fn slashed_stake_returns_what_the_slash_left() {
+    let mut challenge: Challenge = bytemuck::Zeroable::zeroed();
+    challenge.reward_pool = 1_000_000_000;
+    let mut stake: Stake = blank(8 + Stake::MAX_SIZE);
+    stake.amount = STAKE;
+    pool_stake(&mut challenge, &mut stake, STAKE).unwrap();
+
+    // Failed, with the slash window over.
+    challenge.set_status(ChallengeStatus::Finalized);
+    challenge.deadline = NOW - 30 * 86_400;
+    forfeit_stake(&mut challenge, &mut stake, STAKE / 4);
+    assert_eq!(challenge.distributable_pool(), 1_000_000_000 + STAKE / 4);
+
+    let amount = returnable_stake(&mut challenge, &stake, NOW).unwrap();
+    assert_eq!(amount, STAKE - STAKE / 4);
+    release_pooled_stake(&mut challenge, &stake, amount).unwrap();
+    assert_eq!(challenge.pooled_stakes, 0);
+    assert_eq!(challenge.stake_principal, 0);
+    assert_eq!(challenge.reward_pool, 1_000_000_000 + STAKE / 4);
+}
+
+#[test]
+fn failed_token_challenge_returns_the_stake_before_the_pool() {
+    let mut challenge: Challenge = bytemuck::Zeroable::zeroed();
+    challenge.reward_mint = Pubkey::new_unique();
+    challenge.reward_pool = 1_000_000;
+    let mut stake: Stake = blank(8 + Stake::MAX_SIZE);
+    stake.amount = STAKE;
+    stake.timestamp = NOW;
+    pool_stake(&mut challenge, &mut stake, STAKE).unwrap();
+    assert_eq!(challenge.pooled_stakes, 1);
+
+    // Failed on R*, and long enough ago that the slash window is over.
+    challenge.set_status(ChallengeStatus::Finalized);
+    challenge.deadline = NOW - 30 * 86_400;
+    challenge.r_star_threshold = 100;
+    challenge.r_star = 5_000;
+    assert_eq!(require_refundable(&challenge).unwrap_err(), PolocError::StakesOutstanding.into());
+
+    let amount = returnable_stake(&mut challenge, &stake, NOW).unwrap();
+    assert_eq!(amount, STAKE);
+    release_pooled_stake(&mut challenge, &stake, amount).unwrap();
+    require_refundable(&challenge).unwrap();
+    // What the refund pays the waldo: the pool, without the stake.
+    assert_eq!(challenge.reward_pool, 1_000_000);
+}
+
+#[test]
+fn registry_tvl_follows_a_stake_and_an_unstake() {
+    let mut registry: ChallengeRegistry = blank(8 + ChallengeRegistry::MAX_SIZE);
+    let mut challenge: Challenge = bytemuck::Zeroable::zeroed();
+    challenge.reward_pool = 1_000_000_000;
+    challenge.waldo_bond = 10_000_000;
+    let mut entry = RegistryEntry { challenge: Pubkey::new_unique(), open: true, tvl: 0 };
+    registry.record_tvl(&mut entry, challenge.tvl());
+    // Another listed challenge's share stays put throughout.
+    let mut other = RegistryEntry { challenge: Pubkey::new_unique(), open: true, tvl: 0 };
+    registry.record_tvl(&mut other, 7);
+    assert_eq!(registry.tvl, 1_010_000_007);
+
+    let mut stake: Stake = blank(8 + Stake::MAX_SIZE);
+    stake.amount = STAKE;
+    pool_stake(&mut challenge, &mut stake, STAKE).unwrap();
+    registry.record_tvl(&mut entry, challenge.tvl());
+    assert_eq!(registry.tvl, 1_010_000_007 + STAKE);
+
+    release_pooled_stake(&mut challenge, &stake, STAKE).unwrap();
+    registry.record_tvl(&mut entry, challenge.tvl());
+    assert_eq!(registry.tvl, 1_010_000_007);
+
+    // Closing the entry takes its whole share out.
+    registry.record_tvl(&mut entry, 0);
+    assert_eq!(registry.tvl, 7);
+}
+
+#[test]
+fn usd_floor_converts_at_the_config_rate() {
+    let mut config: Config = blank(8 + Config::MAX_SIZE);
+    config.min_stake_usd = 1_000_000; // $1
+    assert_eq!(config.usd_min_stake(), 0);
+
+    config.usd_rate = 150_000_000; // $150 per SOL
+    assert_eq!(config.usd_min_stake(), 6_666_667);
+
+    let mut challenge: Challenge = bytemuck::Zeroable::zeroed();
+    assert_eq!(config.stake_usd_rate(&challenge), 150_000_000);
+    challenge.reward_mint = Pubkey::new_unique();
+    assert_eq!(config.stake_usd_rate(&challenge), 0);
+}
@@ -0,0 +1,111 @@
+//! Read-only access to poloc accounts for downstream programs.
+//!
+//! Consumers that gate their own instructions on a verified location should
+//! not copy the `Challenge` layout: it is zero-copy, but fields move as it
+//! changes, and the account data a consumer is handed needn't be aligned.
+//! `ChallengeView` reads the fields it exposes straight out of the account
+//! data at their offsets in `Challenge`, and `assert_location_verified`
+//! bundles the checks a consumer needs into one call:
+//!
+//! ```ignore
+//! let location = poloc_reader::assert_location_verified(
+//!     &ctx.accounts.poloc_challenge,
+//!     24 * 60 * 60, // settled in the last day
+//!     100,          // to within 100 m
+//! )?;
+//! require_keys_eq!(location.waldo, ctx.accounts.user.key());
+//! require!(location.context == order.id_hash, MyError::WrongOrder);
+//! ```
+
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use poloc::state::Challenge;
+
+mod view;
+
+pub use view::ChallengeView;
+
+#[error_code(offset = 9000)]
+pub enum ReaderError {
+    #[msg("Account is not a poloc challenge")]
+    NotAChallenge,
+    #[msg("Challenge account data is truncated")]
+    Truncated,
+    #[msg("Challenge is not finalized")]
+    NotFinalized,
+    #[msg("Challenge did not pass")]
+    NotPassed,
+    #[msg("Challenge location was never revealed")]
+    LocationHidden,
+    #[msg("Challenge failed the travel consistency check")]
+    TravelInconsistent,
+    #[msg("Challenge is frozen")]
+    Frozen,
+    #[msg("Challenge result is older than the allowed age")]
+    ResultTooOld,
+    #[msg("Challenge R* is wider than the allowed radius")]
+    RadiusTooWide,
+    #[msg("Challenge result is past its TTL")]
+    ResultExpired,
+}
+
+/// What a passing challenge established: `waldo` was within `r_star` meters
+/// of (`lat`, `lon`) at `measured_at`, for the business object `context`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VerifiedLocation {
+    pub challenge: Pubkey,
+    pub waldo: Pubkey,
+    pub lat: i32,      // micro-degrees
+    pub lon: i32,      // micro-degrees
+    pub r_star: u32,   // meters
+    pub measured_at: i64,
+    pub context: [u8; 32],
+}
+
+/// Checks that `account` is a poloc challenge which passed, is still within
+/// the program's result TTL, settled no more than `max_age` seconds ago and
+/// placed its prover within `radius` meters, and returns what it verified.
+pub fn assert_location_verified(
+    account: &AccountInfo,
+    max_age: i64,
+    radius: u32,
+) -> Result<VerifiedLocation> {
+    require_keys_eq!(*account.owner, poloc::ID, ReaderError::NotAChallenge);
+    let data = account.try_borrow_data()?;
+    let view = ChallengeView::new(&data)?;
+    view.verified_location(account.key(), Clock::get()?.unix_timestamp, max_age, radius)
+}
+
+impl ChallengeView<'_> {
+    /// The checks behind `assert_location_verified`, against an explicit
+    /// `now` so they can run off-chain.
+    pub fn verified_location(
+        &self,
+        challenge: Pubkey,
+        now: i64,
+        max_age: i64,
+        radius: u32,
+    ) -> Result<VerifiedLocation> {
+        require!(self.is_finalized(), ReaderError::NotFinalized);
+        require!(self.passed(), ReaderError::NotPassed);
+        require!(self.location_known(), ReaderError::LocationHidden);
+        require!(!self.travel_inconsistent(), ReaderError::TravelInconsistent);
+        require!(!self.frozen(), ReaderError::Frozen);
+        require!(now.saturating_sub(self.deadline()) <= max_age, ReaderError::ResultTooOld);
+        require!(self.r_star() <= radius, ReaderError::RadiusTooWide);
+        require!(self.is_current(now), ReaderError::ResultExpired);
+        Ok(VerifiedLocation {
+            challenge,
+            waldo: self.waldo(),
+            lat: self.claimed_lat(),
+            lon: self.claimed_lon(),
+            r_star: self.r_star(),
+            measured_at: self.deadline(),
+            context: self.context(),
+        })
+    }
+}
+
+fn is_challenge(data: &[u8]) -> bool {
+    data.starts_with(Challenge::DISCRIMINATOR)
+}
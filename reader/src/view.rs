@@ -0,0 +1,141 @@
+use core::mem::offset_of;
+use anchor_lang::prelude::*;
+use poloc::constants::MAX_CHALLENGE_ID_LEN;
+use poloc::state::{Challenge, ChallengeStatus};
+
+use crate::{is_challenge, ReaderError};
+
+// `Challenge` is zero-copy, so each field sits at a fixed offset after the
+// 8-byte discriminator. The account data needn't be aligned for this view.
+const ID: usize = offset_of!(Challenge, challenge_id);
+const ID_LEN: usize = offset_of!(Challenge, challenge_id_len);
+const WALDO: usize = offset_of!(Challenge, waldo);
+const CLAIMED_LAT: usize = offset_of!(Challenge, claimed_lat);
+const CLAIMED_LON: usize = offset_of!(Challenge, claimed_lon);
+const START_TIME: usize = offset_of!(Challenge, start_time);
+const DEADLINE: usize = offset_of!(Challenge, deadline);
+const STATUS: usize = offset_of!(Challenge, status);
+const VALID_VOTE_COUNT: usize = offset_of!(Challenge, valid_vote_count);
+const R_STAR: usize = offset_of!(Challenge, r_star);
+const R_STAR_THRESHOLD: usize = offset_of!(Challenge, r_star_threshold);
+const PRIVATE: usize = offset_of!(Challenge, private);
+const TRAVEL_INCONSISTENT: usize = offset_of!(Challenge, travel_inconsistent);
+const LOCATION_COMMITMENT: usize = offset_of!(Challenge, location_commitment);
+const LOCATION_REVEALED: usize = offset_of!(Challenge, location_revealed);
+const QUORUM_MET: usize = offset_of!(Challenge, quorum_met);
+const FROZEN: usize = offset_of!(Challenge, frozen);
+const EXPIRES_AT: usize = offset_of!(Challenge, expires_at);
+const CONTEXT: usize = offset_of!(Challenge, context);
+
+// `Challenge::status` holds the status's discriminant.
+const STATUS_FINALIZED: u8 = ChallengeStatus::Finalized as u8;
+
+/// Borrowed, zero-copy view of a `Challenge` account's data.
+#[derive(Clone, Copy)]
+pub struct ChallengeView<'a> {
+    fields: &'a [u8],
+}
+
+impl<'a> ChallengeView<'a> {
+    /// Checks the discriminator and length; fields are read on access.
+    pub fn new(data: &'a [u8]) -> Result<Self> {
+        require!(is_challenge(data), ReaderError::NotAChallenge);
+        require!(data.len() >= Challenge::MAX_SIZE, ReaderError::Truncated);
+        let fields = &data[8..Challenge::MAX_SIZE];
+        require!(fields[ID_LEN] as usize <= MAX_CHALLENGE_ID_LEN, ReaderError::NotAChallenge);
+        Ok(Self { fields })
+    }
+
+    fn bytes<const N: usize>(&self, at: usize) -> [u8; N] {
+        self.fields[at..at + N].try_into().unwrap()
+    }
+
+    fn flag(&self, at: usize) -> bool {
+        self.fields[at] != 0
+    }
+
+    /// The challenge id, or `None` if it isn't UTF-8.
+    pub fn challenge_id(&self) -> Option<&'a str> {
+        core::str::from_utf8(&self.fields[ID..ID + self.fields[ID_LEN] as usize]).ok()
+    }
+
+    pub fn waldo(&self) -> Pubkey {
+        Pubkey::new_from_array(self.bytes(WALDO))
+    }
+
+    /// Latitude in micro-degrees; the selected candidate once finalized.
+    pub fn claimed_lat(&self) -> i32 {
+        i32::from_le_bytes(self.bytes(CLAIMED_LAT))
+    }
+
+    /// Longitude in micro-degrees; the selected candidate once finalized.
+    pub fn claimed_lon(&self) -> i32 {
+        i32::from_le_bytes(self.bytes(CLAIMED_LON))
+    }
+
+    pub fn start_time(&self) -> i64 {
+        i64::from_le_bytes(self.bytes(START_TIME))
+    }
+
+    pub fn deadline(&self) -> i64 {
+        i64::from_le_bytes(self.bytes(DEADLINE))
+    }
+
+    /// When the result stops being current; 0 = never.
+    pub fn expires_at(&self) -> i64 {
+        i64::from_le_bytes(self.bytes(EXPIRES_AT))
+    }
+
+    pub fn is_finalized(&self) -> bool {
+        self.fields[STATUS] == STATUS_FINALIZED
+    }
+
+    pub fn valid_vote_count(&self) -> u32 {
+        u32::from_le_bytes(self.bytes(VALID_VOTE_COUNT))
+    }
+
+    /// Final uncertainty in meters; 0 until finalized.
+    pub fn r_star(&self) -> u32 {
+        u32::from_le_bytes(self.bytes(R_STAR))
+    }
+
+    pub fn r_star_threshold(&self) -> u32 {
+        u32::from_le_bytes(self.bytes(R_STAR_THRESHOLD))
+    }
+
+    pub fn private(&self) -> bool {
+        self.flag(PRIVATE)
+    }
+
+    pub fn travel_inconsistent(&self) -> bool {
+        self.flag(TRAVEL_INCONSISTENT)
+    }
+
+    pub fn frozen(&self) -> bool {
+        self.flag(FROZEN)
+    }
+
+    pub fn quorum_met(&self) -> bool {
+        self.flag(QUORUM_MET)
+    }
+
+    /// Same as `Challenge::location_known`.
+    pub fn location_known(&self) -> bool {
+        self.bytes::<32>(LOCATION_COMMITMENT) == [0u8; 32] || self.flag(LOCATION_REVEALED)
+    }
+
+    /// Same as `Challenge::passed`; only meaningful once finalized.
+    pub fn passed(&self) -> bool {
+        self.r_star() <= self.r_star_threshold() && self.quorum_met()
+    }
+
+    /// The integrator's business object the proof is bound to; zero = none.
+    pub fn context(&self) -> [u8; 32] {
+        self.bytes(CONTEXT)
+    }
+
+    /// Same as `Challenge::is_current`.
+    pub fn is_current(&self, now: i64) -> bool {
+        self.is_finalized() && self.passed() && (self.expires_at() == 0 || now < self.expires_at())
+    }
+}
@@ -0,0 +1,120 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+use poloc::state::{Challenge, ChallengeStatus};
+use poloc_reader::{ChallengeView, ReaderError};
+
+const NOW: i64 = 1_700_000_000;
+
+fn passed() -> Challenge {
+    let mut challenge: Challenge = bytemuck::Zeroable::zeroed();
+    challenge.set_challenge_id("reader-layout");
+    challenge.waldo = Pubkey::new_unique();
+    challenge.claimed_lat = 37_774_900;
+    challenge.claimed_lon = -122_419_400;
+    challenge.start_time = NOW - 3_600;
+    challenge.deadline = NOW - 600;
+    challenge.set_status(ChallengeStatus::Finalized);
+    challenge.valid_vote_count = 7;
+    challenge.r_star = 80;
+    challenge.r_star_threshold = 100;
+    challenge.quorum_met = 1;
+    challenge.expires_at = NOW + 86_400;
+    challenge.context = [9u8; 32];
+    challenge
+}
+
+fn encode(challenge: &Challenge) -> Vec<u8> {
+    let mut data = Challenge::DISCRIMINATOR.to_vec();
+    data.extend_from_slice(bytemuck::bytes_of(challenge));
+    data
+}
+
+fn rejection(challenge: &Challenge, max_age: i64, radius: u32) -> Error {
+    let data = encode(challenge);
+    ChallengeView::new(&data)
+        .unwrap()
+        .verified_location(Pubkey::default(), NOW, max_age, radius)
+        .unwrap_err()
+}
+
+#[test]
+fn view_matches_challenge_layout() {
+    let mut challenge = passed();
+    challenge.private = 1;
+    challenge.travel_inconsistent = 1;
+    challenge.frozen = 1;
+    challenge.location_commitment = [7u8; 32];
+    let data = encode(&challenge);
+    let view = ChallengeView::new(&data).unwrap();
+
+    assert_eq!(view.challenge_id(), Some("reader-layout"));
+    assert_eq!(view.waldo(), challenge.waldo);
+    assert_eq!(view.claimed_lat(), challenge.claimed_lat);
+    assert_eq!(view.claimed_lon(), challenge.claimed_lon);
+    assert_eq!(view.start_time(), challenge.start_time);
+    assert_eq!(view.deadline(), challenge.deadline);
+    assert!(view.is_finalized());
+    assert_eq!(view.valid_vote_count(), 7);
+    assert_eq!(view.r_star(), 80);
+    assert_eq!(view.r_star_threshold(), 100);
+    assert!(view.quorum_met() && view.passed());
+    assert!(view.private() && view.travel_inconsistent() && view.frozen());
+    assert!(!view.location_known());
+    assert_eq!(view.expires_at(), challenge.expires_at);
+    assert_eq!(view.context(), challenge.context);
+
+    challenge.location_revealed = 1;
+    assert!(ChallengeView::new(&encode(&challenge)).unwrap().location_known());
+}
+
+#[test]
+fn recent_tight_pass_is_verified() {
+    let challenge = passed();
+    let data = encode(&challenge);
+    let key = Pubkey::new_unique();
+    let location = ChallengeView::new(&data)
+        .unwrap()
+        .verified_location(key, NOW, 3_600, 100)
+        .unwrap();
+    assert_eq!(location.challenge, key);
+    assert_eq!(location.waldo, challenge.waldo);
+    assert_eq!((location.lat, location.lon), (challenge.claimed_lat, challenge.claimed_lon));
+    assert_eq!(location.r_star, 80);
+    assert_eq!(location.measured_at, challenge.deadline);
+    assert_eq!(location.context, challenge.context);
+}
+
+#[test]
+fn stale_or_loose_results_are_rejected() {
+    let challenge = passed();
+    assert_eq!(rejection(&challenge, 599, 100), ReaderError::ResultTooOld.into());
+    assert_eq!(rejection(&challenge, 3_600, 79), ReaderError::RadiusTooWide.into());
+
+    let mut expired = passed();
+    expired.expires_at = NOW;
+    assert_eq!(rejection(&expired, 3_600, 100), ReaderError::ResultExpired.into());
+    expired.expires_at = 0;
+    assert!(ChallengeView::new(&encode(&expired)).unwrap().is_current(NOW));
+}
+
+#[test]
+fn unsettled_or_failed_challenges_are_rejected() {
+    let mut active = passed();
+    active.set_status(ChallengeStatus::Active);
+    assert_eq!(rejection(&active, 3_600, 100), ReaderError::NotFinalized.into());
+
+    let mut no_quorum = passed();
+    no_quorum.quorum_met = 0;
+    assert_eq!(rejection(&no_quorum, 3_600, 100), ReaderError::NotPassed.into());
+
+    let mut inconsistent = passed();
+    inconsistent.travel_inconsistent = 1;
+    assert_eq!(rejection(&inconsistent, 3_600, 100), ReaderError::TravelInconsistent.into());
+}
+
+#[test]
+fn other_accounts_and_short_data_are_rejected() {
+    assert!(ChallengeView::new(&[0u8; 64]).is_err());
+    let data = encode(&passed());
+    assert!(ChallengeView::new(&data[..40]).is_err());
+}
@@ -5,8 +5,12 @@ import {
   LAMPORTS_PER_SOL,
   PublicKey,
   SystemProgram,
+  Ed25519Program,
+  SYSVAR_INSTRUCTIONS_PUBKEY,
+  SYSVAR_SLOT_HASHES_PUBKEY,
 } from "@solana/web3.js";
 import { assert } from "chai";
+import { createHash } from "crypto";
 import { Poloc } from "../target/types/poloc"; // Ensure this type name matches your IDL file name
 
 describe("poloc", () => {
@@ -24,10 +28,37 @@ describe("poloc", () => {
   // FIX: These helpers now use the challengeId STRING, which matches the IDL perfectly.
   const getChallengePda = (challengeId: string) =>
     findPda([Buffer.from("challenge"), Buffer.from(challengeId)]);
+  const getVaultPda = (challengeId: string) =>
+    findPda([Buffer.from("vault"), Buffer.from(challengeId)]);
   const getStakePda = (challengeId: string, user: PublicKey) =>
     findPda([Buffer.from("stake"), Buffer.from(challengeId), user.toBuffer()]);
   const getVotePda = (challengeId: string, user: PublicKey) =>
     findPda([Buffer.from("vote"), Buffer.from(challengeId), user.toBuffer()]);
+  const configPda = findPda([Buffer.from("config")]);
+  const insurancePoolPda = findPda([Buffer.from("insurance")]);
+  const oracleSetPda = findPda([Buffer.from("oracle_set")]);
+  const getChallengerProfilePda = (user: PublicKey) =>
+    findPda([Buffer.from("challenger"), user.toBuffer()]);
+  const getCollusionFlagPda = (user: PublicKey) =>
+    findPda([Buffer.from("collusion"), user.toBuffer()]);
+  const getPayoutLedgerPda = (user: PublicKey) =>
+    findPda([Buffer.from("payout_ledger"), user.toBuffer()]);
+  const getWaldoProfilePda = (user: PublicKey) =>
+    findPda([Buffer.from("waldo"), user.toBuffer()]);
+  const getDeviceLockPda = (deviceHash: Buffer) =>
+    findPda([Buffer.from("device"), deviceHash]);
+  // Cells are 1x1 degree: floor(micro-degrees / 1e6), as i16 LE.
+  const getCellPda = (seed: string, lat: number, lon: number) => {
+    const cellLat = Buffer.alloc(2);
+    const cellLon = Buffer.alloc(2);
+    cellLat.writeInt16LE(Math.floor(lat / 1e6));
+    cellLon.writeInt16LE(Math.floor(lon / 1e6));
+    return findPda([Buffer.from(seed), cellLat, cellLon]);
+  };
+  const getRegionPolicyPda = (lat: number, lon: number) =>
+    getCellPda("region", lat, lon);
+  const getRegionStatsPda = (lat: number, lon: number) =>
+    getCellPda("region_stats", lat, lon);
 
   before(async () => {
     await Promise.all([
@@ -38,6 +69,31 @@ describe("poloc", () => {
         .requestAirdrop(challenger1.publicKey, 2 * LAMPORTS_PER_SOL)
         .then((sig) => provider.connection.confirmTransaction(sig)),
     ]);
+
+    // The local validator deploys with the provider wallet as upgrade authority.
+    const [programData] = PublicKey.findProgramAddressSync(
+      [program.programId.toBuffer()],
+      new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
+    );
+    await program.methods
+      .initializeConfig()
+      .accounts({
+        config: configPda,
+        admin: provider.wallet.publicKey,
+        program: program.programId,
+        programData,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
+    await program.methods
+      .initializeInsurancePool()
+      .accounts({
+        insurancePool: insurancePoolPda,
+        config: configPda,
+        admin: provider.wallet.publicKey,
+        systemProgram: SystemProgram.programId,
+      })
+      .rpc();
   });
 
   const sleep = (ms: number) =>
@@ -49,26 +105,63 @@ describe("poloc", () => {
     const rewardPool = new anchor.BN(1 * LAMPORTS_PER_SOL);
     const stakeAmount = new anchor.BN(0.5 * LAMPORTS_PER_SOL);
     const testDuration = new anchor.BN(2);
+    const claimedLat = 40712800;
+    const claimedLon = -74006000;
+    const regionPolicyPda = getRegionPolicyPda(claimedLat, claimedLon);
+    const regionStatsPda = getRegionStatsPda(claimedLat, claimedLon);
+    const deviceHash = createHash("sha256").update("test-device").digest();
     const testVotingWindow = 3; // Must match the #[cfg(test)] value in your Rust code
+    const testSettlementBuffer = 1; // Same, for SETTLEMENT_BUFFER
 
     it("Initializes a new challenge", async () => {
       await program.methods
-        .initializeChallenge(
+        .initializeChallenge({
           challengeId,
-          40712800,
-          -74006000,
-          testDuration,
-          rewardPool
-        )
+          claimedLat,
+          claimedLon,
+          duration: testDuration,
+          rewardPool,
+          tranches: 1,
+          private: false,
+          locationSalt: new Array(32).fill(0),
+          minEquipmentClass: 0,
+          deviceHash: [...deviceHash],
+          startTime: new anchor.BN(0),
+          locationCommitment: new Array(32).fill(0),
+          underwriteWitnesses: false,
+          encryptedVotes: false,
+          requiredCredential: PublicKey.default,
+          requiredSchema: PublicKey.default,
+          alternateLocations: [],
+          arbiter: PublicKey.default,
+          voteSchedule: { period: 0, offset: 0, length: 0 },
+          context: new Array(32).fill(0),
+          minReputation: 0,
+          thresholdMeters: 0,
+          rttAttested: false,
+        })
         .accounts({
           challenge: challengePda,
+          vault: getVaultPda(challengeId),
+          config: configPda,
+          waldoProfile: getWaldoProfilePda(waldo.publicKey),
+          deviceLock: getDeviceLockPda(deviceHash),
+          insurancePool: insurancePoolPda,
           waldo: waldo.publicKey,
+          slotHashes: SYSVAR_SLOT_HASHES_PUBKEY,
+          priorResult: null,
+          feeWaivers: null,
+          waiverAttestation: null,
+          expiryFeed: null,
+          challengeRegistry: null,
+          registryPage: null,
           systemProgram: SystemProgram.programId,
         })
         .signers([waldo])
         .rpc();
       const acc = await program.account.challenge.fetch(challengePda);
-      assert.strictEqual(Object.keys(acc.status)[0], "active");
+      assert.strictEqual(acc.status, 0); // ChallengeStatus::Active
+      assert.notDeepEqual(acc.measurementNonce, new Array(32).fill(0));
     });
 
     it("Allows challengers to stake", async () => {
@@ -77,8 +170,18 @@ describe("poloc", () => {
         .stake(challengeId, stakeAmount)
         .accounts({
           challenge: challengePda,
+          vault: getVaultPda(challengeId),
+          config: configPda,
+          regionPolicy: regionPolicyPda,
+          regionStats: regionStatsPda,
           stakeAccount: stakePda,
+          collusionFlag: getCollusionFlagPda(challenger1.publicKey),
+          challengerProfile: getChallengerProfilePda(challenger1.publicKey),
+          credential: null,
           challenger: challenger1.publicKey,
+          challengeRegistry: null,
+          registryPage: null,
+          waldoProfile: getWaldoProfilePda(waldo.publicKey),
           systemProgram: SystemProgram.programId,
         })
         .signers([challenger1])
@@ -92,44 +195,104 @@ describe("poloc", () => {
       await sleep(testDuration.toNumber() * 1000 + 500);
       const stakePda = getStakePda(challengeId, challenger1.publicKey);
       const votePda = getVotePda(challengeId, challenger1.publicKey);
+      // The prover's beacon signs its echo to the witness's probe.
+      const { measurementNonce, deadline } = await program.account.challenge.fetch(challengePda);
+      const beaconEcho = Ed25519Program.createInstructionWithPrivateKey({
+        privateKey: waldo.secretKey,
+        message: Buffer.concat([
+          Buffer.from("poloc:beacon:v1"),
+          program.programId.toBuffer(),
+          challengePda.toBuffer(),
+          Buffer.from(measurementNonce),
+          challenger1.publicKey.toBuffer(),
+        ]),
+      });
       await program.methods
-        .submitVote(
+        .submitVote({
           challengeId,
-          challenger1.publicKey.toString(),
-          true,
-          800,
-          60000
-        )
+          challengerId: challenger1.publicKey.toString(),
+          isValid: true,
+          uncertainty: 800,
+          minRtt: 60000,
+          candidate: 0,
+          confidence: 9000,
+          // Measured as the challenge closed, well within the freshness bound.
+          measuredAt: deadline,
+        })
         .accounts({
           challenge: challengePda,
           stakeAccount: stakePda,
           voteAccount: votePda,
           challenger: challenger1.publicKey,
+          operator: challenger1.publicKey,
+          instructions: SYSVAR_INSTRUCTIONS_PUBKEY,
+          waldoProfile: getWaldoProfilePda(waldo.publicKey),
+          challengeRegistry: null,
+          registryPage: null,
           systemProgram: SystemProgram.programId,
         })
+        .preInstructions([beaconEcho])
         .signers([challenger1])
         .rpc();
       const acc = await program.account.challenge.fetch(challengePda);
       assert.strictEqual(acc.voteCount, 1);
     });
 
+    it("Starts tracking stats for the challenge's cell", async () => {
+      await program.methods
+        .initializeRegionStats(
+          Math.floor(claimedLat / 1e6),
+          Math.floor(claimedLon / 1e6)
+        )
+        .accounts({
+          regionStats: regionStatsPda,
+          payer: provider.wallet.publicKey,
+          systemProgram: SystemProgram.programId,
+        })
+        .rpc();
+    });
+
     it("Finalizes a successful challenge", async () => {
       console.log(
-        `     Waiting for test voting window (${testVotingWindow}s) to pass...`
+        `     Waiting for test voting window (${testVotingWindow}s) and settlement buffer (${testSettlementBuffer}s) to pass...`
       );
-      await sleep(testVotingWindow * 1000 + 500);
+      await sleep((testVotingWindow + testSettlementBuffer) * 1000 + 500);
+
+      // R* is the median uncertainty of the valid votes; there is one, at 800 m.
+      const rStarSuccess = 800;
+      await program.methods
+        .computeRStar(challengeId)
+        .accounts({ challenge: challengePda })
+        .rpc();
 
-      const rStarSuccess = 500;
       await program.methods
-        .finalizeChallenge(challengeId, rStarSuccess)
-        .accounts({ challenge: challengePda, authority: waldo.publicKey })
+        .finalizeChallenge(challengeId, 0)
+        .accounts({
+          challenge: challengePda,
+          config: configPda,
+          regionPolicy: regionPolicyPda,
+          regionStats: regionStatsPda,
+          waldoProfile: getWaldoProfilePda(waldo.publicKey),
+          authority: waldo.publicKey,
+          oracleSet: oracleSetPda,
+          previousChallenge: null,
+          challengeRegistry: null,
+          registryPage: null,
+        })
         .signers([waldo])
         .rpc();
 
       const challengeAccount = await program.account.challenge.fetch(
         challengePda
       );
-      assert.strictEqual(Object.keys(challengeAccount.status)[0], "finalized");
+      assert.strictEqual(challengeAccount.status, 1); // ChallengeStatus::Finalized
+      const stats = await program.account.regionStats.fetch(regionStatsPda);
+      assert.strictEqual(stats.finalized, 1);
+      assert.strictEqual(stats.rStarSum.toNumber(), rStarSuccess);
+      const profile = await program.account.waldoProfile.fetch(
+        getWaldoProfilePda(waldo.publicKey)
+      );
+      assert.strictEqual(profile.activeChallenges, 0);
     });
 
     it("Allows an honest voter to claim their reward", async () => {
@@ -142,8 +305,17 @@ describe("poloc", () => {
         .claimReward(challengeId)
         .accounts({
           challenge: challengePda,
+          vault: getVaultPda(challengeId),
           vote: votePda,
           winner: challenger1.publicKey,
+          config: configPda,
+          claimEscrow: null,
+          payoutLedger: getPayoutLedgerPda(challenger1.publicKey),
+          challengerProfile: getChallengerProfilePda(challenger1.publicKey),
+          insurancePool: insurancePoolPda,
+          challengeRegistry: null,
+          registryPage: null,
+          waldoProfile: getWaldoProfilePda(waldo.publicKey),
           systemProgram: SystemProgram.programId,
         })
         .signers([challenger1])